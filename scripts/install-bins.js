@@ -3,7 +3,7 @@ import path from "node:path";
 
 const platformName = process.platform === "darwin" ? "macos" : process.platform === "win32" ? "windows" : "linux";
 const BIN_DIR = path.resolve("src-tauri/bin", platformName);
-const baseNames = ["ffmpeg", "ffprobe", "aria2c"];
+const baseNames = ["ffmpeg", "ffprobe"];
 const targetNames = process.platform === "win32" ? baseNames.map((name) => `${name}.exe`) : baseNames;
 const defaultSourceDir = path.resolve(process.cwd(), "bin", platformName);
 const sourceDir = process.env.BIN_SOURCE_DIR || defaultSourceDir;
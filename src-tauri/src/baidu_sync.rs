@@ -2,16 +2,24 @@ use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 use tokio::time::{sleep, Duration};
 
+use crate::bandwidth::{class_limit_kbps, record_usage, TransferClass};
 use crate::commands::settings::DEFAULT_BAIDU_MAX_PARALLEL;
 use crate::config::resolve_baidu_pcs_path;
 use crate::db::Db;
+use crate::remote_storage::{open_remote_storage, RemoteDir, RemoteFileMeta, RemoteStorage, BACKEND_BAIDU};
 use crate::utils::{append_log, now_rfc3339, sanitize_filename};
+use crate::webhook::{emit_webhook_event, EVENT_BAIDU_SYNC_FAILED};
+
+static BAIDU_RATE_LIMIT_LOCK: Mutex<()> = Mutex::new(());
+static BAIDU_ACTIVE_UPLOADS: AtomicUsize = AtomicUsize::new(0);
+static BAIDU_ACTIVE_DOWNLOADS: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -60,17 +68,14 @@ pub struct BaiduSyncTaskRecord {
   pub error: Option<String>,
   pub retry_count: i64,
   pub policy: Option<String>,
+  pub verify_status: Option<String>,
+  pub remote_size: Option<i64>,
+  pub remote_md5: Option<String>,
+  pub verified_at: Option<String>,
   pub created_at: String,
   pub updated_at: String,
 }
 
-#[derive(Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct BaiduRemoteDir {
-  pub name: String,
-  pub path: String,
-}
-
 #[derive(Clone)]
 pub struct BaiduSyncContext {
   pub db: Arc<Db>,
@@ -210,12 +215,12 @@ pub fn list_baidu_sync_tasks(
   db.with_conn(|conn| {
     let mut stmt = if status_filter.is_some() {
       conn.prepare(
-        "SELECT id, source_type, source_id, source_title, local_path, remote_dir, remote_name, status, progress, error, retry_count, policy, created_at, updated_at \
+        "SELECT id, source_type, source_id, source_title, local_path, remote_dir, remote_name, status, progress, error, retry_count, policy, verify_status, remote_size, remote_md5, verified_at, created_at, updated_at \
          FROM baidu_sync_task WHERE status = ?1 ORDER BY created_at DESC LIMIT ?2 OFFSET ?3",
       )?
     } else {
       conn.prepare(
-        "SELECT id, source_type, source_id, source_title, local_path, remote_dir, remote_name, status, progress, error, retry_count, policy, created_at, updated_at \
+        "SELECT id, source_type, source_id, source_title, local_path, remote_dir, remote_name, status, progress, error, retry_count, policy, verify_status, remote_size, remote_md5, verified_at, created_at, updated_at \
          FROM baidu_sync_task ORDER BY created_at DESC LIMIT ?1 OFFSET ?2",
       )?
     };
@@ -231,44 +236,16 @@ pub fn list_baidu_sync_tasks(
   .map_err(|err| err.to_string())
 }
 
-pub fn list_baidu_remote_dirs(
-  db: &Db,
-  path: &str,
-) -> Result<Vec<BaiduRemoteDir>, String> {
-  let settings = load_baidu_sync_settings(db)?;
-  let exec_path = resolve_baidu_exec_path(&settings.exec_path);
-  let target_path = normalize_baidu_path(path);
-  let output = run_baidu_pcs_command(&exec_path, &["ls".to_string(), target_path.clone()])?;
-  let content = if output.stdout.trim().is_empty() {
-    output.stderr
-  } else {
-    output.stdout
-  };
-  Ok(parse_baidu_ls_dirs(&content, &target_path))
+pub fn list_remote_dirs(db: &Db, path: &str) -> Result<Vec<RemoteDir>, String> {
+  open_remote_storage(db)?.list_dirs(&normalize_baidu_path(path))
 }
 
-pub fn check_baidu_remote_file_exists(db: &Db, remote_path: &str) -> Result<bool, String> {
-  let settings = load_baidu_sync_settings(db)?;
-  let exec_path = resolve_baidu_exec_path(&settings.exec_path);
-  let target_path = normalize_baidu_path(remote_path);
-  match run_baidu_pcs_command(&exec_path, &["meta".to_string(), target_path]) {
-    Ok(_) => Ok(true),
-    Err(err) => {
-      if is_baidu_not_found_error(&err) {
-        Ok(false)
-      } else {
-        Err(err)
-      }
-    }
-  }
+pub fn check_remote_file_exists(db: &Db, remote_path: &str) -> Result<bool, String> {
+  open_remote_storage(db)?.exists(&normalize_baidu_path(remote_path))
 }
 
-pub fn fetch_baidu_remote_file_size(db: &Db, remote_path: &str) -> Result<u64, String> {
-  let settings = load_baidu_sync_settings(db)?;
-  let exec_path = resolve_baidu_exec_path(&settings.exec_path);
-  let target_path = normalize_baidu_path(remote_path);
-  let output = run_baidu_pcs_command(&exec_path, &["meta".to_string(), target_path])?;
-  Ok(parse_meta_size(&output.stdout).unwrap_or(0))
+pub fn fetch_remote_file_size(db: &Db, remote_path: &str) -> Result<u64, String> {
+  open_remote_storage(db)?.size(&normalize_baidu_path(remote_path))
 }
 
 fn load_baidu_download_max_parallel(db: &Db) -> i64 {
@@ -290,8 +267,55 @@ fn load_baidu_download_max_parallel(db: &Db) -> i64 {
   .unwrap_or(DEFAULT_BAIDU_MAX_PARALLEL)
 }
 
-fn apply_baidu_download_max_parallel(db: &Db, exec_path: &Path) -> Result<(), String> {
-  let max_parallel = load_baidu_download_max_parallel(db);
+struct BaiduTransferSlot {
+  counter: &'static AtomicUsize,
+}
+
+impl Drop for BaiduTransferSlot {
+  fn drop(&mut self) {
+    self.counter.fetch_sub(1, Ordering::SeqCst);
+  }
+}
+
+fn spawn_baidu_transfer(
+  exec_path: &Path,
+  command: &mut Command,
+  class: TransferClass,
+) -> Result<(Child, BaiduTransferSlot), String> {
+  let (flag, counter) = match class {
+    TransferClass::Sync => ("-max_upload_rate", &BAIDU_ACTIVE_UPLOADS),
+    _ => ("-max_download_rate", &BAIDU_ACTIVE_DOWNLOADS),
+  };
+  let _guard = BAIDU_RATE_LIMIT_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+  let active = counter.fetch_add(1, Ordering::SeqCst) + 1;
+  let slot = BaiduTransferSlot { counter };
+  apply_baidu_rate_limit(exec_path, flag, class, active)?;
+  let child = command
+    .spawn()
+    .map_err(|err| format!("BaiduPCS-Go 执行失败: {}", err))?;
+  Ok((child, slot))
+}
+
+fn apply_baidu_rate_limit(
+  exec_path: &Path,
+  flag: &str,
+  class: TransferClass,
+  active: usize,
+) -> Result<(), String> {
+  let limit_kbps = class_limit_kbps(class);
+  let value = if limit_kbps > 0 {
+    format!("{}KB", (limit_kbps / active.max(1) as i64).max(1))
+  } else {
+    "0".to_string()
+  };
+  run_baidu_pcs_command(
+    exec_path,
+    &["config".to_string(), "set".to_string(), flag.to_string(), value],
+  )?;
+  Ok(())
+}
+
+fn apply_baidu_download_max_parallel(exec_path: &Path, max_parallel: i64) -> Result<(), String> {
   run_baidu_pcs_command(
     exec_path,
     &[
@@ -304,15 +328,7 @@ fn apply_baidu_download_max_parallel(db: &Db, exec_path: &Path) -> Result<(), St
   Ok(())
 }
 
-pub fn download_baidu_file(
-  db: &Db,
-  remote_path: &str,
-  local_path: &Path,
-) -> Result<PathBuf, String> {
-  download_baidu_file_with_hook(db, remote_path, local_path, |_| {})
-}
-
-pub fn download_baidu_file_with_hook<F>(
+pub fn download_remote_file_with_hook<F>(
   db: &Db,
   remote_path: &str,
   local_path: &Path,
@@ -321,82 +337,204 @@ pub fn download_baidu_file_with_hook<F>(
 where
   F: FnOnce(Arc<Mutex<Child>>),
 {
-  let settings = load_baidu_sync_settings(db)?;
-  let exec_path = resolve_baidu_exec_path(&settings.exec_path);
-  let target_path = normalize_baidu_path(remote_path);
-  let local_dir = match local_path.parent() {
-    Some(value) => value,
-    None => return Err("下载目标目录无效".to_string()),
-  };
-  if let Err(err) = fs::create_dir_all(local_dir) {
-    return Err(format!("创建下载目录失败: {}", err));
-  }
-  apply_baidu_download_max_parallel(db, &exec_path)?;
-  let remote_name = target_path
-    .rsplit('/')
-    .find(|value| !value.is_empty())
-    .unwrap_or("")
-    .to_string();
-  if remote_name.is_empty() {
-    return Err("网盘文件名为空".to_string());
+  let mut on_spawn = Some(on_spawn);
+  open_remote_storage(db)?.download(
+    &normalize_baidu_path(remote_path),
+    local_path,
+    &mut |child| {
+      if let Some(hook) = on_spawn.take() {
+        hook(child);
+      }
+    },
+  )
+}
+
+pub struct BaiduPcsStorage {
+  exec_path: PathBuf,
+  max_parallel: i64,
+}
+
+impl BaiduPcsStorage {
+  pub fn from_db(db: &Db) -> Result<Self, String> {
+    let settings = load_baidu_sync_settings(db)?;
+    Ok(Self {
+      exec_path: resolve_baidu_exec_path(&settings.exec_path),
+      max_parallel: load_baidu_download_max_parallel(db),
+    })
   }
-  let _ = run_baidu_pcs_download_with_hook(&exec_path, &target_path, local_dir, on_spawn)?;
-  if local_path.exists() {
-    return Ok(local_path.to_path_buf());
+}
+
+impl RemoteStorage for BaiduPcsStorage {
+  fn kind(&self) -> &'static str {
+    BACKEND_BAIDU
+  }
+
+  fn upload(
+    &self,
+    local_path: &Path,
+    remote_dir: &str,
+    remote_name: &str,
+    policy: &str,
+    on_progress: &mut dyn FnMut(f64),
+  ) -> Result<(), String> {
+    record_usage(TransferClass::Sync, 0);
+    run_baidu_pcs_upload(
+      &self.exec_path,
+      &[
+        "upload".to_string(),
+        format!("-policy={}", policy),
+        local_path.to_string_lossy().to_string(),
+        remote_dir.to_string(),
+      ],
+      |progress| {
+        record_usage(TransferClass::Sync, 0);
+        on_progress(progress);
+      },
+    )?;
+    let local_name = local_path
+      .file_name()
+      .and_then(|value| value.to_str())
+      .unwrap_or("")
+      .to_string();
+    if remote_name != local_name {
+      let _ = run_baidu_pcs_command(
+        &self.exec_path,
+        &[
+          "mv".to_string(),
+          join_baidu_path(remote_dir, &local_name),
+          join_baidu_path(remote_dir, remote_name),
+        ],
+      );
+    }
+    Ok(())
   }
-  let direct_path = local_dir.join(&remote_name);
-  if direct_path.exists() {
-    if direct_path == local_path {
-      return Ok(direct_path);
+
+  fn download(
+    &self,
+    remote_path: &str,
+    local_path: &Path,
+    on_spawn: &mut dyn FnMut(Arc<Mutex<Child>>),
+  ) -> Result<PathBuf, String> {
+    let local_dir = match local_path.parent() {
+      Some(value) => value,
+      None => return Err("下载目标目录无效".to_string()),
+    };
+    if let Err(err) = fs::create_dir_all(local_dir) {
+      return Err(format!("创建下载目录失败: {}", err));
     }
+    apply_baidu_download_max_parallel(&self.exec_path, self.max_parallel)?;
+    record_usage(TransferClass::Download, 0);
+    let remote_name = remote_path
+      .rsplit('/')
+      .find(|value| !value.is_empty())
+      .unwrap_or("")
+      .to_string();
+    if remote_name.is_empty() {
+      return Err("网盘文件名为空".to_string());
+    }
+    let _ = run_baidu_pcs_download_with_hook(&self.exec_path, remote_path, local_dir, on_spawn)?;
     if local_path.exists() {
       return Ok(local_path.to_path_buf());
     }
-    fs::rename(&direct_path, local_path)
-      .map_err(|err| format!("重命名下载文件失败: {}", err))?;
-    return Ok(local_path.to_path_buf());
-  }
-  if let Some(found) = find_file_by_name(local_dir, &remote_name) {
-    if found == local_path {
-      return Ok(found);
+    let direct_path = local_dir.join(&remote_name);
+    if direct_path.exists() {
+      if direct_path == local_path {
+        return Ok(direct_path);
+      }
+      if local_path.exists() {
+        return Ok(local_path.to_path_buf());
+      }
+      fs::rename(&direct_path, local_path)
+        .map_err(|err| format!("重命名下载文件失败: {}", err))?;
+      return Ok(local_path.to_path_buf());
     }
-    if local_path.exists() {
+    if let Some(found) = find_file_by_name(local_dir, &remote_name) {
+      if found == local_path {
+        return Ok(found);
+      }
+      if local_path.exists() {
+        return Ok(local_path.to_path_buf());
+      }
+      fs::rename(&found, local_path)
+        .map_err(|err| format!("重命名下载文件失败: {}", err))?;
       return Ok(local_path.to_path_buf());
     }
-    fs::rename(&found, local_path)
-      .map_err(|err| format!("重命名下载文件失败: {}", err))?;
-    return Ok(local_path.to_path_buf());
+    Err("网盘文件下载完成但未找到本地文件".to_string())
+  }
+
+  fn exists(&self, remote_path: &str) -> Result<bool, String> {
+    match run_baidu_pcs_command(&self.exec_path, &["meta".to_string(), remote_path.to_string()]) {
+      Ok(_) => Ok(true),
+      Err(err) => {
+        if is_baidu_not_found_error(&err) {
+          Ok(false)
+        } else {
+          Err(err)
+        }
+      }
+    }
+  }
+
+  fn meta(&self, remote_path: &str) -> Result<RemoteFileMeta, String> {
+    let output =
+      run_baidu_pcs_command(&self.exec_path, &["meta".to_string(), remote_path.to_string()])?;
+    Ok(RemoteFileMeta {
+      size: parse_meta_size(&output.stdout).unwrap_or(0),
+      md5: parse_meta_md5(&output.stdout),
+    })
+  }
+
+  fn fix_md5(&self, remote_path: &str) -> Result<bool, String> {
+    run_baidu_pcs_command(&self.exec_path, &["fixmd5".to_string(), remote_path.to_string()])
+      .map(|_| true)
+  }
+
+  fn list_dirs(&self, path: &str) -> Result<Vec<RemoteDir>, String> {
+    let output = run_baidu_pcs_command(&self.exec_path, &["ls".to_string(), path.to_string()])?;
+    let content = if output.stdout.trim().is_empty() {
+      output.stderr
+    } else {
+      output.stdout
+    };
+    Ok(parse_baidu_ls_dirs(&content, path))
+  }
+
+  fn mkdir(&self, path: &str) -> Result<(), String> {
+    run_baidu_pcs_command(&self.exec_path, &["mkdir".to_string(), path.to_string()]).map(|_| ())
+  }
+
+  fn rename(&self, from_path: &str, to_path: &str) -> Result<(), String> {
+    run_baidu_pcs_command(
+      &self.exec_path,
+      &["mv".to_string(), from_path.to_string(), to_path.to_string()],
+    )
+    .map(|_| ())
   }
-  Err("网盘文件下载完成但未找到本地文件".to_string())
 }
 
-pub fn create_baidu_remote_dir(
+pub fn create_remote_dir(
   db: &Db,
   parent_path: &str,
   name: &str,
-) -> Result<BaiduRemoteDir, String> {
-  let settings = load_baidu_sync_settings(db)?;
-  let exec_path = resolve_baidu_exec_path(&settings.exec_path);
+) -> Result<RemoteDir, String> {
   let safe_name = sanitize_filename(name.trim());
   if safe_name.is_empty() {
     return Err("目录名称不能为空".to_string());
   }
   let base_path = normalize_baidu_path(parent_path);
   let full_path = join_baidu_path(&base_path, &safe_name);
-  run_baidu_pcs_command(&exec_path, &["mkdir".to_string(), full_path.clone()])?;
-  Ok(BaiduRemoteDir {
+  open_remote_storage(db)?.mkdir(&full_path)?;
+  Ok(RemoteDir {
     name: safe_name,
     path: full_path,
   })
 }
 
-pub fn rename_baidu_remote_dir(
+pub fn rename_remote_dir(
   db: &Db,
   from_path: &str,
   name: &str,
-) -> Result<BaiduRemoteDir, String> {
-  let settings = load_baidu_sync_settings(db)?;
-  let exec_path = resolve_baidu_exec_path(&settings.exec_path);
+) -> Result<RemoteDir, String> {
   let normalized_from = normalize_baidu_path(from_path);
   if normalized_from == "/" {
     return Err("无法重命名根目录".to_string());
@@ -416,13 +554,10 @@ pub fn rename_baidu_remote_dir(
   };
   let target_path = join_baidu_path(&parent_path, &safe_name);
   if target_path != normalized_from {
-    run_baidu_pcs_command(
-      &exec_path,
-      &["mv".to_string(), normalized_from.clone(), target_path.clone()],
-    )?;
+    open_remote_storage(db)?.rename(&normalized_from, &target_path)?;
     update_submission_sync_paths(db, &normalized_from, &target_path)?;
   }
-  Ok(BaiduRemoteDir {
+  Ok(RemoteDir {
     name: safe_name,
     path: target_path,
   })
@@ -1121,7 +1256,6 @@ async fn run_baidu_sync_task(
   task: BaiduSyncTask,
 ) -> Result<(), String> {
   update_baidu_sync_status(context.db.as_ref(), task.id, "UPLOADING", 0.0, None)?;
-  let exec_path = resolve_baidu_exec_path(&settings.exec_path);
   let policy = normalize_baidu_upload_policy(task.policy.as_deref().or(Some(&settings.policy)))
     .unwrap_or_else(|| "overwrite".to_string());
   append_log(
@@ -1131,57 +1265,104 @@ async fn run_baidu_sync_task(
       task.id, task.local_path, task.remote_dir, task.remote_name, policy
     ),
   );
-  let upload_result = run_baidu_pcs_upload(
-    &exec_path,
-    &[
-      "upload".to_string(),
-      format!("-policy={}", policy),
-      task.local_path.clone(),
-      task.remote_dir.clone(),
-    ],
-    |progress| {
-      let _ = update_baidu_sync_progress(context.db.as_ref(), task.id, progress);
-    },
-  );
+  let db = context.db.clone();
+  let local_path = task.local_path.clone();
+  let remote_dir = task.remote_dir.clone();
+  let remote_name = task.remote_name.clone();
+  let task_id = task.id;
+  let upload_result = tauri::async_runtime::spawn_blocking(move || {
+    let storage = open_remote_storage(db.as_ref())?;
+    storage.upload(
+      Path::new(&local_path),
+      &remote_dir,
+      &remote_name,
+      &policy,
+      &mut |progress| {
+        let _ = update_baidu_sync_progress(db.as_ref(), task_id, progress);
+      },
+    )?;
+    Ok::<_, String>(storage.kind())
+  })
+  .await
+  .unwrap_or_else(|_| Err("同步任务执行失败".to_string()));
   match upload_result {
-    Ok(output) => {
-      let local_name = Path::new(&task.local_path)
-        .file_name()
-        .and_then(|value| value.to_str())
-        .unwrap_or("")
-        .to_string();
-      let mut remote_path = join_baidu_path(&task.remote_dir, &local_name);
-      if task.remote_name != local_name {
-        let target_path = join_baidu_path(&task.remote_dir, &task.remote_name);
-        let _ = run_baidu_pcs_command(
-          &exec_path,
-          &["mv".to_string(), remote_path.clone(), target_path.clone()],
-        );
-        remote_path = target_path;
-      }
+    Ok(backend) => {
+      let remote_path = join_baidu_path(&task.remote_dir, &task.remote_name);
       append_log(
         context.app_log_path.as_ref(),
-        &format!("baidu_sync_task_uploaded id={} remote={}", task.id, remote_path),
+        &format!(
+          "baidu_sync_task_uploaded id={} backend={} remote={}",
+          task.id, backend, remote_path
+        ),
       );
-      let meta = run_baidu_pcs_command(&exec_path, &["meta".to_string(), remote_path.clone()]);
-      match meta {
-        Ok(meta_output) => {
-          let size = parse_meta_size(&meta_output.stdout).unwrap_or(0);
-          if size == 0 {
-            let err = "上传后文件大小为0";
-            append_log(
-              context.app_log_path.as_ref(),
-              &format!("baidu_sync_task_error id={} err={}", task.id, err),
-            );
-            return handle_baidu_sync_failure(context.db.as_ref(), task, settings.retry, err);
+      let db = context.db.clone();
+      let local_path = task.local_path.clone();
+      let verify_path = remote_path.clone();
+      let verify_result = tauri::async_runtime::spawn_blocking(move || {
+        let storage = open_remote_storage(db.as_ref())?;
+        let verify_path = normalize_baidu_path(&verify_path);
+        let mut meta = storage.meta(&verify_path)?;
+        let mut check = verify_uploaded_file(&local_path, &meta);
+        if let Ok(UploadCheck::Md5Mismatch(local_md5)) = &check {
+          if storage.fix_md5(&verify_path).unwrap_or(false) {
+            meta = storage.meta(&verify_path)?;
+            if meta
+              .md5
+              .as_deref()
+              .map(|remote_md5| remote_md5.eq_ignore_ascii_case(local_md5))
+              .unwrap_or(false)
+            {
+              check = Ok(UploadCheck::Verified);
+            }
           }
         }
+        Ok::<_, String>((meta, check))
+      })
+      .await
+      .unwrap_or_else(|_| Err("同步校验执行失败".to_string()));
+      let (meta, check) = match verify_result {
+        Ok(value) => value,
         Err(err) => {
           append_log(
             context.app_log_path.as_ref(),
             &format!("baidu_sync_task_error id={} err={}", task.id, err),
           );
-          return handle_baidu_sync_failure(context.db.as_ref(), task, settings.retry, &err);
+          return handle_baidu_sync_failure(&context, task, settings.retry, &err);
+        }
+      };
+      match check {
+        Err(err) => {
+          append_log(
+            context.app_log_path.as_ref(),
+            &format!("baidu_sync_task_verify_fail id={} remote={} err={}", task.id, remote_path, err),
+          );
+          record_baidu_sync_verification(context.db.as_ref(), task.id, "MISMATCH", &meta)?;
+          return handle_baidu_sync_failure(&context, task, settings.retry, &err);
+        }
+        Ok(UploadCheck::Md5Mismatch(local_md5)) => {
+          record_baidu_sync_verification(context.db.as_ref(), task.id, "SIZE_ONLY", &meta)?;
+          append_log(
+            context.app_log_path.as_ref(),
+            &format!(
+              "baidu_sync_task_verified_size_only id={} size={} local_md5={} remote_md5={}",
+              task.id,
+              meta.size,
+              local_md5,
+              meta.md5.as_deref().unwrap_or("-")
+            ),
+          );
+        }
+        Ok(UploadCheck::Verified) => {
+          record_baidu_sync_verification(context.db.as_ref(), task.id, "VERIFIED", &meta)?;
+          append_log(
+            context.app_log_path.as_ref(),
+            &format!(
+              "baidu_sync_task_verified id={} size={} md5={}",
+              task.id,
+              meta.size,
+              meta.md5.as_deref().unwrap_or("-")
+            ),
+          );
         }
       }
       update_baidu_sync_status(context.db.as_ref(), task.id, "SUCCESS", 100.0, None)?;
@@ -1206,7 +1387,7 @@ async fn run_baidu_sync_task(
       }
       append_log(
         context.app_log_path.as_ref(),
-        &format!("baidu_sync_task_ok id={} output={}", task.id, output.stdout.len()),
+        &format!("baidu_sync_task_ok id={} backend={}", task.id, backend),
       );
       Ok(())
     }
@@ -1215,17 +1396,18 @@ async fn run_baidu_sync_task(
         context.app_log_path.as_ref(),
         &format!("baidu_sync_task_error id={} err={}", task.id, err),
       );
-      handle_baidu_sync_failure(context.db.as_ref(), task, settings.retry, &err)
+      handle_baidu_sync_failure(&context, task, settings.retry, &err)
     }
   }
 }
 
 fn handle_baidu_sync_failure(
-  db: &Db,
+  context: &BaiduSyncContext,
   task: BaiduSyncTask,
   max_retry: i64,
   err: &str,
 ) -> Result<(), String> {
+  let db = context.db.as_ref();
   let next_retry = task.retry_count + 1;
   if next_retry <= max_retry {
     let now = now_rfc3339();
@@ -1239,7 +1421,24 @@ fn handle_baidu_sync_failure(
     .map_err(|err| err.to_string())?;
     Ok(())
   } else {
-    update_baidu_sync_status(db, task.id, "FAILED", 0.0, Some(err.to_string()))
+    update_baidu_sync_status(db, task.id, "FAILED", 0.0, Some(err.to_string()))?;
+    emit_webhook_event(
+      db,
+      context.app_log_path.as_ref(),
+      EVENT_BAIDU_SYNC_FAILED,
+      &format!("百度网盘同步失败: {}", task.remote_name),
+      serde_json::json!({
+        "taskId": task.id,
+        "sourceType": task.source_type,
+        "sourceId": task.source_id,
+        "localPath": task.local_path,
+        "remoteDir": task.remote_dir,
+        "remoteName": task.remote_name,
+        "retryCount": task.retry_count,
+        "error": err,
+      }),
+    );
+    Ok(())
   }
 }
 
@@ -1261,6 +1460,79 @@ fn update_baidu_sync_status(
   .map_err(|err| err.to_string())
 }
 
+enum UploadCheck {
+  Verified,
+  Md5Mismatch(String),
+}
+
+fn verify_uploaded_file(local_path: &str, meta: &RemoteFileMeta) -> Result<UploadCheck, String> {
+  if meta.size == 0 {
+    return Err("上传后文件大小为0".to_string());
+  }
+  let local_size = fs::metadata(local_path)
+    .map_err(|err| format!("读取本地文件失败: {}", err))?
+    .len();
+  if local_size != meta.size {
+    return Err(format!(
+      "上传校验失败: 本地大小 {} 与网盘大小 {} 不一致",
+      local_size, meta.size
+    ));
+  }
+  if let Some(remote_md5) = meta.md5.as_deref() {
+    let local_md5 = compute_file_md5(Path::new(local_path))?;
+    if !local_md5.eq_ignore_ascii_case(remote_md5) {
+      return Ok(UploadCheck::Md5Mismatch(local_md5));
+    }
+  }
+  Ok(UploadCheck::Verified)
+}
+
+fn compute_file_md5(path: &Path) -> Result<String, String> {
+  let mut file = fs::File::open(path).map_err(|err| format!("读取本地文件失败: {}", err))?;
+  let mut context = md5::Context::new();
+  let mut buffer = vec![0u8; 1024 * 1024];
+  loop {
+    let read = file
+      .read(&mut buffer)
+      .map_err(|err| format!("读取本地文件失败: {}", err))?;
+    if read == 0 {
+      break;
+    }
+    context.consume(&buffer[..read]);
+  }
+  Ok(format!("{:x}", context.compute()))
+}
+
+fn record_baidu_sync_verification(
+  db: &Db,
+  task_id: i64,
+  verify_status: &str,
+  meta: &RemoteFileMeta,
+) -> Result<(), String> {
+  let now = now_rfc3339();
+  let policy_override = if verify_status == "MISMATCH" {
+    Some("overwrite")
+  } else {
+    None
+  };
+  db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE baidu_sync_task SET verify_status = ?1, remote_size = ?2, remote_md5 = ?3, verified_at = ?4, \
+       policy = COALESCE(?5, policy), updated_at = ?4 WHERE id = ?6",
+      (
+        verify_status,
+        meta.size as i64,
+        meta.md5.as_deref(),
+        &now,
+        policy_override,
+        task_id,
+      ),
+    )?;
+    Ok(())
+  })
+  .map_err(|err| err.to_string())
+}
+
 fn update_baidu_sync_progress(db: &Db, task_id: i64, progress: f64) -> Result<(), String> {
   let now = now_rfc3339();
   db.with_conn(|conn| {
@@ -1374,18 +1646,22 @@ fn map_baidu_sync_task(row: &rusqlite::Row<'_>) -> rusqlite::Result<BaiduSyncTas
     error: row.get(9)?,
     retry_count: row.get(10)?,
     policy: row.get(11)?,
-    created_at: row.get(12)?,
-    updated_at: row.get(13)?,
+    verify_status: row.get(12)?,
+    remote_size: row.get(13)?,
+    remote_md5: row.get(14)?,
+    verified_at: row.get(15)?,
+    created_at: row.get(16)?,
+    updated_at: row.get(17)?,
   })
 }
 
-fn read_setting(conn: &rusqlite::Connection, key: &str) -> Option<String> {
+pub(crate) fn read_setting(conn: &rusqlite::Connection, key: &str) -> Option<String> {
   conn
     .query_row("SELECT value FROM app_settings WHERE key = ?1", [key], |row| row.get(0))
     .ok()
 }
 
-fn upsert_setting(
+pub(crate) fn upsert_setting(
   conn: &rusqlite::Connection,
   key: &str,
   value: &str,
@@ -1474,7 +1750,7 @@ fn load_room_baidu_sync_config(
   .map_err(|err| err.to_string())
 }
 
-fn parse_baidu_ls_dirs(output: &str, base_path: &str) -> Vec<BaiduRemoteDir> {
+fn parse_baidu_ls_dirs(output: &str, base_path: &str) -> Vec<RemoteDir> {
   let mut dirs = Vec::new();
   for line in output.lines() {
     let trimmed = strip_ansi(line).trim().to_string();
@@ -1516,7 +1792,7 @@ fn parse_baidu_ls_dirs(output: &str, base_path: &str) -> Vec<BaiduRemoteDir> {
       continue;
     }
     let path = join_baidu_path(base_path, &name);
-    dirs.push(BaiduRemoteDir {
+    dirs.push(RemoteDir {
       name,
       path,
     });
@@ -1592,7 +1868,8 @@ where
   F: FnOnce(Arc<Mutex<Child>>),
 {
   let save_dir = local_dir.to_string_lossy().to_string();
-  let mut child = Command::new(exec_path)
+  let mut command = Command::new(exec_path);
+  command
     .current_dir(local_dir)
     .args([
       "download".to_string(),
@@ -1601,9 +1878,8 @@ where
       remote_path.to_string(),
     ])
     .stdout(Stdio::piped())
-    .stderr(Stdio::piped())
-    .spawn()
-    .map_err(|err| format!("BaiduPCS-Go 执行失败: {}", err))?;
+    .stderr(Stdio::piped());
+  let (mut child, _slot) = spawn_baidu_transfer(exec_path, &mut command, TransferClass::Download)?;
 
   let stdout = child.stdout.take();
   let stderr = child.stderr.take();
@@ -1653,14 +1929,6 @@ where
   Err(format!("BaiduPCS-Go 执行失败: {}", stderr.trim()))
 }
 
-fn run_baidu_pcs_download(
-  exec_path: &Path,
-  remote_path: &str,
-  local_dir: &Path,
-) -> Result<CommandOutput, String> {
-  run_baidu_pcs_download_with_hook(exec_path, remote_path, local_dir, |_| {})
-}
-
 fn run_baidu_pcs_upload<F>(
   exec_path: &Path,
   args: &[String],
@@ -1669,12 +1937,9 @@ fn run_baidu_pcs_upload<F>(
 where
   F: FnMut(f64),
 {
-  let mut child = Command::new(exec_path)
-    .args(args)
-    .stdout(Stdio::piped())
-    .stderr(Stdio::piped())
-    .spawn()
-    .map_err(|err| format!("BaiduPCS-Go 执行失败: {}", err))?;
+  let mut command = Command::new(exec_path);
+  command.args(args).stdout(Stdio::piped()).stderr(Stdio::piped());
+  let (mut child, _slot) = spawn_baidu_transfer(exec_path, &mut command, TransferClass::Sync)?;
 
   let mut stdout = child
     .stdout
@@ -1867,6 +2132,16 @@ fn parse_meta_size(output: &str) -> Option<u64> {
   None
 }
 
+fn parse_meta_md5(output: &str) -> Option<String> {
+  output
+    .lines()
+    .filter(|line| line.to_ascii_lowercase().contains("md5"))
+    .flat_map(|line| line.split_whitespace())
+    .map(|value| value.trim_matches(|ch: char| !ch.is_ascii_alphanumeric()))
+    .find(|value| value.len() == 32 && value.chars().all(|ch| ch.is_ascii_hexdigit()))
+    .map(|value| value.to_ascii_lowercase())
+}
+
 fn parse_who_output(output: &str) -> (bool, Option<String>, Option<String>) {
   if output.contains("请先登录") || output.contains("uid: 0") {
     return (false, None, None);
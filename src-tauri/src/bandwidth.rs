@@ -0,0 +1,279 @@
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::{Local, Timelike};
+
+use crate::commands::settings::BandwidthSettings;
+use crate::record_schedule::parse_time_windows;
+
+const ACTIVE_WINDOW_SECS: f64 = 3.0;
+const RECORDING_SAMPLE_SECS: f64 = 1.0;
+const RECORDING_SMOOTHING: f64 = 0.3;
+const MIN_SHARED_BYTES_PER_SEC: f64 = 32.0 * 1024.0;
+
+static BANDWIDTH_MANAGER: OnceLock<Mutex<BandwidthManager>> = OnceLock::new();
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TransferClass {
+  Recording,
+  Download,
+  Upload,
+  Sync,
+}
+
+const SHARED_CLASSES: [TransferClass; 3] = [
+  TransferClass::Download,
+  TransferClass::Upload,
+  TransferClass::Sync,
+];
+
+impl TransferClass {
+  fn index(self) -> usize {
+    match self {
+      TransferClass::Recording => 0,
+      TransferClass::Download => 1,
+      TransferClass::Upload => 2,
+      TransferClass::Sync => 3,
+    }
+  }
+}
+
+#[derive(Clone, Copy, Default)]
+struct BandwidthLimits {
+  total: f64,
+  classes: [f64; 4],
+}
+
+struct TimeRule {
+  windows: Vec<(u32, u32)>,
+  limits: BandwidthLimits,
+}
+
+#[derive(Clone, Copy)]
+struct ClassBucket {
+  tokens: f64,
+  refreshed_at: Instant,
+  active_at: Option<Instant>,
+}
+
+struct RecordingMeter {
+  sample_start: Instant,
+  sample_bytes: u64,
+  rate: f64,
+  updated_at: Option<Instant>,
+}
+
+struct BandwidthManager {
+  limits: BandwidthLimits,
+  priorities: [f64; 4],
+  rules: Vec<TimeRule>,
+  buckets: [ClassBucket; 4],
+  recording: RecordingMeter,
+}
+
+impl BandwidthManager {
+  fn new() -> Self {
+    let now = Instant::now();
+    Self {
+      limits: BandwidthLimits::default(),
+      priorities: [1.0; 4],
+      rules: Vec::new(),
+      buckets: [ClassBucket {
+        tokens: 0.0,
+        refreshed_at: now,
+        active_at: None,
+      }; 4],
+      recording: RecordingMeter {
+        sample_start: now,
+        sample_bytes: 0,
+        rate: 0.0,
+        updated_at: None,
+      },
+    }
+  }
+
+  fn current_limits(&self) -> BandwidthLimits {
+    let now = Local::now();
+    let minute = now.hour() * 60 + now.minute();
+    self
+      .rules
+      .iter()
+      .find(|rule| {
+        rule.windows.iter().any(|&(start, end)| {
+          if start < end {
+            minute >= start && minute < end
+          } else {
+            minute >= start || minute < end
+          }
+        })
+      })
+      .map(|rule| rule.limits)
+      .unwrap_or(self.limits)
+  }
+
+  fn is_active(&self, class: TransferClass, now: Instant) -> bool {
+    self.buckets[class.index()]
+      .active_at
+      .map(|value| now.duration_since(value).as_secs_f64() < ACTIVE_WINDOW_SECS)
+      .unwrap_or(false)
+  }
+
+  fn recording_rate(&self, now: Instant) -> f64 {
+    match self.recording.updated_at {
+      Some(value) if now.duration_since(value).as_secs_f64() < ACTIVE_WINDOW_SECS => {
+        self.recording.rate
+      }
+      _ => 0.0,
+    }
+  }
+
+  fn class_rate(&self, class: TransferClass, now: Instant) -> Option<f64> {
+    if class == TransferClass::Recording {
+      return None;
+    }
+    let limits = self.current_limits();
+    let cap = limits.classes[class.index()];
+    let cap = (cap > 0.0).then_some(cap);
+    if limits.total <= 0.0 {
+      return cap;
+    }
+    let available = (limits.total - self.recording_rate(now)).max(MIN_SHARED_BYTES_PER_SEC);
+    let weight_sum: f64 = SHARED_CLASSES
+      .iter()
+      .filter(|item| **item == class || self.is_active(**item, now))
+      .map(|item| self.priorities[item.index()])
+      .sum();
+    let share = available * self.priorities[class.index()] / weight_sum.max(1.0);
+    Some(cap.map_or(share, |value| value.min(share)))
+  }
+
+  fn reserve(&mut self, class: TransferClass, bytes: u64) -> Duration {
+    let now = Instant::now();
+    let rate = self.class_rate(class, now);
+    let bucket = &mut self.buckets[class.index()];
+    bucket.active_at = Some(now);
+    let Some(rate) = rate else {
+      bucket.tokens = 0.0;
+      bucket.refreshed_at = now;
+      return Duration::ZERO;
+    };
+    let refill = now.duration_since(bucket.refreshed_at).as_secs_f64() * rate;
+    bucket.tokens = (bucket.tokens + refill).min(rate) - bytes as f64;
+    bucket.refreshed_at = now;
+    if bucket.tokens < 0.0 {
+      Duration::from_secs_f64(-bucket.tokens / rate)
+    } else {
+      Duration::ZERO
+    }
+  }
+
+  fn record_recording(&mut self, bytes: u64) {
+    let now = Instant::now();
+    let meter = &mut self.recording;
+    meter.sample_bytes += bytes;
+    let elapsed = now.duration_since(meter.sample_start).as_secs_f64();
+    if elapsed < RECORDING_SAMPLE_SECS {
+      return;
+    }
+    let sample = meter.sample_bytes as f64 / elapsed;
+    let fresh = meter
+      .updated_at
+      .map(|value| now.duration_since(value).as_secs_f64() < ACTIVE_WINDOW_SECS)
+      .unwrap_or(false);
+    meter.rate = if fresh {
+      meter.rate + (sample - meter.rate) * RECORDING_SMOOTHING
+    } else {
+      sample
+    };
+    meter.sample_start = now;
+    meter.sample_bytes = 0;
+    meter.updated_at = Some(now);
+  }
+}
+
+pub fn configure(settings: &BandwidthSettings) {
+  let limits = |total: i64, download: i64, upload: i64, sync: i64| BandwidthLimits {
+    total: kbps_to_bytes(total),
+    classes: [
+      0.0,
+      kbps_to_bytes(download),
+      kbps_to_bytes(upload),
+      kbps_to_bytes(sync),
+    ],
+  };
+  let rules = settings
+    .time_rules
+    .iter()
+    .filter_map(|rule| {
+      let windows = parse_time_windows(&rule.time_window).ok()?;
+      if windows.is_empty() {
+        return None;
+      }
+      Some(TimeRule {
+        windows,
+        limits: limits(
+          rule.total_limit_kbps,
+          rule.download_limit_kbps,
+          rule.upload_limit_kbps,
+          rule.sync_limit_kbps,
+        ),
+      })
+    })
+    .collect();
+  let mut manager = lock_manager();
+  manager.limits = limits(
+    settings.total_limit_kbps,
+    settings.download_limit_kbps,
+    settings.upload_limit_kbps,
+    settings.sync_limit_kbps,
+  );
+  manager.priorities = [
+    1.0,
+    settings.download_priority.max(1) as f64,
+    settings.upload_priority.max(1) as f64,
+    settings.sync_priority.max(1) as f64,
+  ];
+  manager.rules = rules;
+}
+
+pub async fn acquire(class: TransferClass, bytes: u64) {
+  let wait = lock_manager().reserve(class, bytes);
+  if !wait.is_zero() {
+    tokio::time::sleep(wait).await;
+  }
+}
+
+pub fn acquire_blocking(class: TransferClass, bytes: u64) {
+  let wait = lock_manager().reserve(class, bytes);
+  if !wait.is_zero() {
+    thread::sleep(wait);
+  }
+}
+
+pub fn record_usage(class: TransferClass, bytes: u64) {
+  let mut manager = lock_manager();
+  manager.buckets[class.index()].active_at = Some(Instant::now());
+  if class == TransferClass::Recording {
+    manager.record_recording(bytes);
+  }
+}
+
+pub fn class_limit_kbps(class: TransferClass) -> i64 {
+  let manager = lock_manager();
+  manager
+    .class_rate(class, Instant::now())
+    .map(|rate| ((rate / 1024.0) as i64).max(1))
+    .unwrap_or(0)
+}
+
+fn kbps_to_bytes(kbps: i64) -> f64 {
+  kbps.max(0) as f64 * 1024.0
+}
+
+fn lock_manager() -> std::sync::MutexGuard<'static, BandwidthManager> {
+  BANDWIDTH_MANAGER
+    .get_or_init(|| Mutex::new(BandwidthManager::new()))
+    .lock()
+    .unwrap_or_else(|err| err.into_inner())
+}
@@ -79,6 +79,44 @@ impl BilibiliClient {
     parse_response(&response)
   }
 
+  pub async fn get_bytes(
+    &self,
+    url: &str,
+    params: &[(String, String)],
+    auth: Option<&AuthInfo>,
+  ) -> Result<Vec<u8>, String> {
+    let full_url = if params.is_empty() {
+      url.to_string()
+    } else {
+      format!("{}?{}", url, build_query(params))
+    };
+
+    let mut headers = default_headers();
+    headers.insert(REFERER, HeaderValue::from_static("https://www.bilibili.com/"));
+    if let Some(auth) = auth {
+      headers.insert(
+        "Cookie",
+        HeaderValue::from_str(&auth.cookie).map_err(|_| "Invalid cookie header".to_string())?,
+      );
+    }
+
+    let response = self
+      .client
+      .get(full_url)
+      .headers(headers)
+      .send()
+      .await
+      .map_err(|err| format!("Request failed: {}", err))?;
+    if !response.status().is_success() {
+      return Err(format!("Request failed: HTTP {}", response.status()));
+    }
+    response
+      .bytes()
+      .await
+      .map(|bytes| bytes.to_vec())
+      .map_err(|err| format!("Failed to read response: {}", err))
+  }
+
   #[allow(dead_code)]
   pub async fn post_json(
     &self,
@@ -0,0 +1,3 @@
+fn main() {
+    reaction_cut_rust_lib::run_daemon()
+}
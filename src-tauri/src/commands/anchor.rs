@@ -4,7 +4,9 @@ use tauri::State;
 
 use crate::api::ApiResponse;
 use crate::commands::settings::{default_live_settings, load_live_settings_from_db};
-use crate::live_recorder::{fetch_room_info, start_recording, stop_recording, LiveContext};
+use crate::live_recorder::{
+  auto_record_block_reason, fetch_room_info, start_recording, stop_recording, LiveContext,
+};
 use crate::utils::{append_log, now_rfc3339};
 use crate::AppState;
 
@@ -33,6 +35,11 @@ pub struct Anchor {
   pub auto_record: bool,
   pub baidu_sync_enabled: bool,
   pub baidu_sync_path: Option<String>,
+  pub account_user_id: Option<i64>,
+  pub record_weekdays: Option<String>,
+  pub record_time_windows: Option<String>,
+  pub max_record_minutes: Option<i64>,
+  pub title_regex: Option<String>,
   pub recording_status: Option<String>,
   pub recording_file: Option<String>,
   pub recording_start_time: Option<String>,
@@ -50,6 +57,13 @@ struct AnchorLiveInfo {
 pub async fn anchor_subscribe(
   state: State<'_, AppState>,
   payload: SubscribeRequest,
+) -> Result<ApiResponse<Vec<Anchor>>, String> {
+  anchor_subscribe_inner(&state, payload).await
+}
+
+pub async fn anchor_subscribe_inner(
+  state: &AppState,
+  payload: SubscribeRequest,
 ) -> Result<ApiResponse<Vec<Anchor>>, String> {
   let now = now_rfc3339();
   let settings = load_live_settings_from_db(&state.db).unwrap_or_else(|_| default_live_settings());
@@ -59,6 +73,7 @@ pub async fn anchor_subscribe(
     login_store: state.login_store.clone(),
     app_log_path: state.app_log_path.clone(),
     live_runtime: state.live_runtime.clone(),
+    edit_upload_state: state.edit_upload_state.clone(),
   };
   append_log(
     &state.app_log_path,
@@ -71,7 +86,7 @@ pub async fn anchor_subscribe(
       continue;
     }
 
-    let info = match fetch_live_info(&state, &uid).await {
+    let info = match fetch_live_info(state, &uid).await {
       Ok(value) => value,
       Err(_) => AnchorLiveInfo {
         nickname: None,
@@ -118,8 +133,10 @@ pub async fn anchor_subscribe(
 
     if info.live_status == 1 {
       if let Ok(room_info) = fetch_room_info(&state.bilibili, &uid).await {
-        if !state.live_runtime.is_recording(&uid) {
-          if let Err(err) = start_recording(context.clone(), &uid, room_info, settings.clone()) {
+        if !state.live_runtime.is_recording(&uid)
+          && auto_record_block_reason(&context, &uid, &room_info).is_none()
+        {
+          if let Err(err) = start_recording(context.clone(), &uid, room_info, settings.clone(), false) {
             append_log(
               &state.app_log_path,
               &format!("auto_record_subscribe_failed room={} err={}", uid, err),
@@ -135,14 +152,19 @@ pub async fn anchor_subscribe(
     }
   }
 
-  Ok(anchor_list(state))
+  Ok(anchor_list_inner(state))
 }
 
 #[tauri::command]
 pub fn anchor_list(state: State<'_, AppState>) -> ApiResponse<Vec<Anchor>> {
+  anchor_list_inner(&state)
+}
+
+pub fn anchor_list_inner(state: &AppState) -> ApiResponse<Vec<Anchor>> {
   match state.db.with_conn(|conn| {
     let mut stmt = conn.prepare(
-      "SELECT a.id, a.uid, a.nickname, a.live_status, a.last_check_time, a.create_time, a.update_time, IFNULL(l.auto_record, 1), IFNULL(l.baidu_sync_enabled, 0), l.baidu_sync_path \
+      "SELECT a.id, a.uid, a.nickname, a.live_status, a.last_check_time, a.create_time, a.update_time, IFNULL(l.auto_record, 1), IFNULL(l.baidu_sync_enabled, 0), l.baidu_sync_path, l.account_user_id, \
+       l.record_weekdays, l.record_time_windows, l.max_record_minutes, l.title_regex \
        FROM anchor a LEFT JOIN live_room_settings l ON a.uid = l.room_id ORDER BY a.id DESC",
     )?;
     let anchors = stmt
@@ -165,6 +187,11 @@ pub fn anchor_list(state: State<'_, AppState>) -> ApiResponse<Vec<Anchor>> {
           auto_record: auto_record != 0,
           baidu_sync_enabled: sync_enabled != 0,
           baidu_sync_path: row.get(9)?,
+          account_user_id: row.get(10)?,
+          record_weekdays: row.get(11)?,
+          record_time_windows: row.get(12)?,
+          max_record_minutes: row.get(13)?,
+          title_regex: row.get(14)?,
           recording_status: record_info.as_ref().map(|_| "RECORDING".to_string()),
           recording_file: record_info.as_ref().map(|info| info.file_path.clone()),
           recording_start_time: record_info.map(|info| info.start_time),
@@ -180,12 +207,17 @@ pub fn anchor_list(state: State<'_, AppState>) -> ApiResponse<Vec<Anchor>> {
 
 #[tauri::command]
 pub fn anchor_unsubscribe(state: State<'_, AppState>, uid: String) -> ApiResponse<String> {
+  anchor_unsubscribe_inner(&state, uid)
+}
+
+pub fn anchor_unsubscribe_inner(state: &AppState, uid: String) -> ApiResponse<String> {
   let context = LiveContext {
     db: state.db.clone(),
     bilibili: state.bilibili.clone(),
     login_store: state.login_store.clone(),
     app_log_path: state.app_log_path.clone(),
     live_runtime: state.live_runtime.clone(),
+    edit_upload_state: state.edit_upload_state.clone(),
   };
   stop_recording(context, &uid, "取消订阅");
   let uid_value = uid;
@@ -201,6 +233,10 @@ pub fn anchor_unsubscribe(state: State<'_, AppState>, uid: String) -> ApiRespons
 
 #[tauri::command]
 pub async fn anchor_check(state: State<'_, AppState>) -> Result<ApiResponse<Vec<Anchor>>, String> {
+  anchor_check_inner(&state).await
+}
+
+pub async fn anchor_check_inner(state: &AppState) -> Result<ApiResponse<Vec<Anchor>>, String> {
   let settings = load_live_settings_from_db(&state.db).unwrap_or_else(|_| default_live_settings());
   let context = LiveContext {
     db: state.db.clone(),
@@ -208,10 +244,12 @@ pub async fn anchor_check(state: State<'_, AppState>) -> Result<ApiResponse<Vec<
     login_store: state.login_store.clone(),
     app_log_path: state.app_log_path.clone(),
     live_runtime: state.live_runtime.clone(),
+    edit_upload_state: state.edit_upload_state.clone(),
   };
   let anchors = match state.db.with_conn(|conn| {
     let mut stmt = conn.prepare(
-      "SELECT a.id, a.uid, a.nickname, a.live_status, a.last_check_time, a.create_time, a.update_time, IFNULL(l.auto_record, 1), IFNULL(l.baidu_sync_enabled, 0), l.baidu_sync_path \
+      "SELECT a.id, a.uid, a.nickname, a.live_status, a.last_check_time, a.create_time, a.update_time, IFNULL(l.auto_record, 1), IFNULL(l.baidu_sync_enabled, 0), l.baidu_sync_path, l.account_user_id, \
+       l.record_weekdays, l.record_time_windows, l.max_record_minutes, l.title_regex \
        FROM anchor a LEFT JOIN live_room_settings l ON a.uid = l.room_id ORDER BY a.id DESC",
     )?;
     let list = stmt
@@ -231,6 +269,11 @@ pub async fn anchor_check(state: State<'_, AppState>) -> Result<ApiResponse<Vec<
           auto_record: row.get::<_, i64>(7)? != 0,
           baidu_sync_enabled: sync_enabled != 0,
           baidu_sync_path: row.get(9)?,
+          account_user_id: row.get(10)?,
+          record_weekdays: row.get(11)?,
+          record_time_windows: row.get(12)?,
+          max_record_minutes: row.get(13)?,
+          title_regex: row.get(14)?,
           recording_status: None,
           recording_file: None,
           recording_start_time: None,
@@ -246,7 +289,7 @@ pub async fn anchor_check(state: State<'_, AppState>) -> Result<ApiResponse<Vec<
   let now = now_rfc3339();
   let mut updated = Vec::new();
   for anchor in anchors {
-    let info = match fetch_live_info(&state, &anchor.uid).await {
+    let info = match fetch_live_info(state, &anchor.uid).await {
       Ok(value) => value,
       Err(_) => AnchorLiveInfo {
         nickname: anchor.nickname.clone(),
@@ -287,6 +330,11 @@ pub async fn anchor_check(state: State<'_, AppState>) -> Result<ApiResponse<Vec<
       auto_record: anchor.auto_record,
       baidu_sync_enabled: anchor.baidu_sync_enabled,
       baidu_sync_path: anchor.baidu_sync_path,
+      account_user_id: anchor.account_user_id,
+      record_weekdays: anchor.record_weekdays,
+      record_time_windows: anchor.record_time_windows,
+      max_record_minutes: anchor.max_record_minutes,
+      title_regex: anchor.title_regex,
       recording_status: record_info.as_ref().map(|_| "RECORDING".to_string()),
       recording_file: record_info.as_ref().map(|info| info.file_path.clone()),
       recording_start_time: record_info.map(|info| info.start_time),
@@ -294,7 +342,12 @@ pub async fn anchor_check(state: State<'_, AppState>) -> Result<ApiResponse<Vec<
 
     if anchor.auto_record && info.live_status == 1 && !state.live_runtime.is_recording(&room_id) {
       if let Ok(room_info) = fetch_room_info(&state.bilibili, &room_id).await {
-        if let Err(err) = start_recording(context.clone(), &room_id, room_info, settings.clone()) {
+        if let Some(reason) = auto_record_block_reason(&context, &room_id, &room_info) {
+          append_log(
+            &state.app_log_path,
+            &format!("auto_record_check_skip room={} reason={}", room_id, reason),
+          );
+        } else if let Err(err) = start_recording(context.clone(), &room_id, room_info, settings.clone(), false) {
           append_log(
             &state.app_log_path,
             &format!("auto_record_check_failed room={} err={}", room_id, err),
@@ -314,7 +367,7 @@ pub async fn anchor_check(state: State<'_, AppState>) -> Result<ApiResponse<Vec<
 
 
 async fn fetch_live_info(
-  state: &State<'_, AppState>,
+  state: &AppState,
   uid: &str,
 ) -> Result<AnchorLiveInfo, String> {
   let params = vec![("room_id".to_string(), uid.to_string())];
@@ -15,7 +15,7 @@ use url::Url;
 use crate::api::ApiResponse;
 use crate::bilibili::client::BilibiliClient;
 use crate::login_refresh;
-use crate::login_store::AuthInfo;
+use crate::login_store::{AuthInfo, LoginAccount};
 use crate::AppState;
 
 const QR_CODE_GENERATE_PATH: &str = "/x/passport-login/web/qrcode/generate";
@@ -225,12 +225,18 @@ pub async fn auth_pwd_login(
 #[tauri::command]
 pub async fn auth_status(
   state: State<'_, AppState>,
+) -> Result<ApiResponse<HashMap<String, Value>>, String> {
+  auth_status_inner(&state).await
+}
+
+pub async fn auth_status_inner(
+  state: &AppState,
 ) -> Result<ApiResponse<HashMap<String, Value>>, String> {
   append_auth_log(
     Some(state.log_path.as_path()),
     &format!("cmd=auth_status ts={}", now_millis()),
   );
-  match build_auth_status(&state).await {
+  match build_auth_status(state, None).await {
     Ok(data) => Ok(ApiResponse::success(data)),
     Err(err) => Ok(ApiResponse::error(err)),
   }
@@ -239,22 +245,28 @@ pub async fn auth_status(
 #[tauri::command]
 pub async fn auth_refresh(
   state: State<'_, AppState>,
+  user_id: Option<i64>,
 ) -> Result<ApiResponse<HashMap<String, Value>>, String> {
   append_auth_log(
     Some(state.log_path.as_path()),
-    &format!("cmd=auth_refresh ts={}", now_millis()),
+    &format!(
+      "cmd=auth_refresh ts={} user_id={}",
+      now_millis(),
+      user_id.unwrap_or(0)
+    ),
   );
   let refresh_result = login_refresh::refresh_cookie(
     &state.bilibili,
     &state.login_store,
     &state.db,
     &state.app_log_path,
+    user_id,
   )
   .await;
   if let Err(err) = refresh_result {
     return Ok(ApiResponse::error(format!("刷新登录失败: {}", err)));
   }
-  match build_auth_status(&state).await {
+  match build_auth_status(&state, user_id).await {
     Ok(data) => Ok(ApiResponse::success(data)),
     Err(err) => Ok(ApiResponse::error(err)),
   }
@@ -280,6 +292,61 @@ pub async fn auth_logout(state: State<'_, AppState>) -> Result<ApiResponse<Strin
   }
 }
 
+#[tauri::command]
+pub fn auth_account_list(state: State<'_, AppState>) -> ApiResponse<Vec<LoginAccount>> {
+  auth_account_list_inner(&state)
+}
+
+pub fn auth_account_list_inner(state: &AppState) -> ApiResponse<Vec<LoginAccount>> {
+  match state.login_store.list_accounts(&state.db) {
+    Ok(accounts) => ApiResponse::success(accounts),
+    Err(err) => ApiResponse::error(format!("读取账号列表失败: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn auth_account_set_default(
+  state: State<'_, AppState>,
+  user_id: i64,
+) -> ApiResponse<Vec<LoginAccount>> {
+  auth_account_set_default_inner(&state, user_id)
+}
+
+pub fn auth_account_set_default_inner(
+  state: &AppState,
+  user_id: i64,
+) -> ApiResponse<Vec<LoginAccount>> {
+  match state.login_store.set_default_account(&state.db, user_id) {
+    Ok(true) => {}
+    Ok(false) => return ApiResponse::error("账号不存在"),
+    Err(err) => return ApiResponse::error(format!("切换默认账号失败: {}", err)),
+  }
+  append_auth_log(
+    Some(state.log_path.as_path()),
+    &format!("account_set_default ts={} user_id={}", now_millis(), user_id),
+  );
+  auth_account_list_inner(state)
+}
+
+#[tauri::command]
+pub fn auth_account_remove(
+  state: State<'_, AppState>,
+  user_id: i64,
+) -> ApiResponse<Vec<LoginAccount>> {
+  auth_account_remove_inner(&state, user_id)
+}
+
+pub fn auth_account_remove_inner(state: &AppState, user_id: i64) -> ApiResponse<Vec<LoginAccount>> {
+  if let Err(err) = state.login_store.remove_account(&state.db, user_id) {
+    return ApiResponse::error(format!("移除账号失败: {}", err));
+  }
+  append_auth_log(
+    Some(state.log_path.as_path()),
+    &format!("account_remove ts={} user_id={}", now_millis(), user_id),
+  );
+  auth_account_list_inner(state)
+}
+
 #[tauri::command]
 pub async fn auth_perform_qrcode_login(
   state: State<'_, AppState>,
@@ -321,7 +388,7 @@ pub async fn auth_perform_qrcode_login(
 }
 
 async fn poll_qrcode_once(
-  state: &State<'_, AppState>,
+  state: &AppState,
   qrcode_key: &str,
 ) -> Result<PollResult, String> {
   let result = poll_qrcode_once_inner(
@@ -505,8 +572,11 @@ fn build_login_payload(
   Value::Object(map)
 }
 
-async fn build_auth_status(state: &AppState) -> Result<HashMap<String, Value>, String> {
-  let auth_info = match state.login_store.load_auth_info(&state.db) {
+async fn build_auth_status(
+  state: &AppState,
+  user_id: Option<i64>,
+) -> Result<HashMap<String, Value>, String> {
+  let auth_info = match state.login_store.load_auth_info_for(&state.db, user_id) {
     Ok(info) => info,
     Err(err) => {
       return Err(format!("Failed to load login info: {}", err));
@@ -519,7 +589,9 @@ async fn build_auth_status(state: &AppState) -> Result<HashMap<String, Value>, S
       if let Ok(profile) = fetch_profile(&state.bilibili, &info.cookie).await {
         let refresh_token = extract_refresh_token(&info.data);
         let login_data = build_login_payload(&info.cookie, Some(profile), refresh_token);
-        let _ = state.login_store.save_login_info(&state.db, &login_data);
+        let _ = state
+          .login_store
+          .update_account_login_info(&state.db, &login_data);
         user_info = login_data;
       }
     }
@@ -16,6 +16,7 @@ use crate::api::ApiResponse;
 use crate::app_log;
 use crate::baidu_sync;
 use crate::config;
+use crate::remote_storage::{self, RemoteStorageSettings};
 use crate::utils::{append_log, now_rfc3339};
 use crate::AppState;
 
@@ -107,7 +108,13 @@ pub struct BaiduAccountLoginInput {
 }
 
 #[tauri::command]
-pub fn baidu_sync_settings(state: State<'_, AppState>) -> ApiResponse<baidu_sync::BaiduSyncSettings> {
+pub fn baidu_sync_settings(
+  state: State<'_, AppState>,
+) -> ApiResponse<baidu_sync::BaiduSyncSettings> {
+  baidu_sync_settings_inner(&state)
+}
+
+pub fn baidu_sync_settings_inner(state: &AppState) -> ApiResponse<baidu_sync::BaiduSyncSettings> {
   match baidu_sync::load_baidu_sync_settings(&state.db) {
     Ok(settings) => ApiResponse::success(settings),
     Err(err) => ApiResponse::error(err),
@@ -115,9 +122,73 @@ pub fn baidu_sync_settings(state: State<'_, AppState>) -> ApiResponse<baidu_sync
 }
 
 #[tauri::command]
-pub fn baidu_sync_status(
+pub fn sync_backend_settings(state: State<'_, AppState>) -> ApiResponse<RemoteStorageSettings> {
+  sync_backend_settings_inner(&state)
+}
+
+pub fn sync_backend_settings_inner(state: &AppState) -> ApiResponse<RemoteStorageSettings> {
+  match remote_storage::load_remote_storage_settings(&state.db) {
+    Ok(settings) => ApiResponse::success(remote_storage::mask_remote_storage_secrets(settings)),
+    Err(err) => ApiResponse::error(err),
+  }
+}
+
+#[tauri::command]
+pub fn sync_backend_update_settings(
   state: State<'_, AppState>,
-) -> ApiResponse<baidu_sync::BaiduLoginInfo> {
+  payload: RemoteStorageSettings,
+) -> ApiResponse<RemoteStorageSettings> {
+  sync_backend_update_settings_inner(&state, payload)
+}
+
+pub fn sync_backend_update_settings_inner(
+  state: &AppState,
+  payload: RemoteStorageSettings,
+) -> ApiResponse<RemoteStorageSettings> {
+  match remote_storage::update_remote_storage_settings(&state.db, payload) {
+    Ok(settings) => {
+      append_log(
+        &state.app_log_path,
+        &format!("sync_backend_update backend={}", settings.backend),
+      );
+      ApiResponse::success(remote_storage::mask_remote_storage_secrets(settings))
+    }
+    Err(err) => ApiResponse::error(err),
+  }
+}
+
+#[tauri::command]
+pub async fn sync_backend_test(
+  state: State<'_, AppState>,
+  payload: RemoteStorageSettings,
+) -> Result<ApiResponse<usize>, String> {
+  sync_backend_test_inner(state.inner(), payload).await
+}
+
+pub async fn sync_backend_test_inner(
+  state: &AppState,
+  payload: RemoteStorageSettings,
+) -> Result<ApiResponse<usize>, String> {
+  let db = state.db.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    let payload = remote_storage::restore_masked_secrets(db.as_ref(), payload)?;
+    let storage = remote_storage::build_remote_storage(db.as_ref(), &payload)?;
+    storage.list_dirs("/").map(|dirs| dirs.len())
+  })
+  .await
+  .map_err(|_| "连接测试执行失败".to_string())?;
+  Ok(match result {
+    Ok(count) => ApiResponse::success(count),
+    Err(err) => ApiResponse::error(err),
+  })
+}
+
+#[tauri::command]
+pub fn baidu_sync_status(state: State<'_, AppState>) -> ApiResponse<baidu_sync::BaiduLoginInfo> {
+  baidu_sync_status_inner(&state)
+}
+
+pub fn baidu_sync_status_inner(state: &AppState) -> ApiResponse<baidu_sync::BaiduLoginInfo> {
   match baidu_sync::check_baidu_login(&state.db) {
     Ok(info) => ApiResponse::success(info),
     Err(err) => ApiResponse::error(err),
@@ -198,6 +269,13 @@ pub fn baidu_sync_logout(state: State<'_, AppState>) -> ApiResponse<String> {
 pub fn baidu_sync_list(
   state: State<'_, AppState>,
   request: Option<BaiduSyncListRequest>,
+) -> ApiResponse<Vec<baidu_sync::BaiduSyncTaskRecord>> {
+  baidu_sync_list_inner(&state, request)
+}
+
+pub fn baidu_sync_list_inner(
+  state: &AppState,
+  request: Option<BaiduSyncListRequest>,
 ) -> ApiResponse<Vec<baidu_sync::BaiduSyncTaskRecord>> {
   let request = request.unwrap_or(BaiduSyncListRequest {
     status: None,
@@ -220,7 +298,7 @@ pub fn baidu_sync_remote_dirs(
   let path = request
     .and_then(|value| value.path)
     .unwrap_or_else(|| "/".to_string());
-  match baidu_sync::list_baidu_remote_dirs(&state.db, &path) {
+  match baidu_sync::list_remote_dirs(&state.db, &path) {
     Ok(list) => ApiResponse::success(
       list
         .into_iter()
@@ -245,7 +323,7 @@ pub fn baidu_sync_create_dir(
   });
   let parent_path = request.parent_path.unwrap_or_else(|| "/".to_string());
   let name = request.name.unwrap_or_else(|| "新建文件夹".to_string());
-  match baidu_sync::create_baidu_remote_dir(&state.db, &parent_path, &name) {
+  match baidu_sync::create_remote_dir(&state.db, &parent_path, &name) {
     Ok(entry) => ApiResponse::success(BaiduRemoteEntry {
       name: entry.name,
       path: entry.path,
@@ -269,7 +347,7 @@ pub fn baidu_sync_rename_dir(
     return ApiResponse::error("原目录不能为空".to_string());
   }
   let name = request.name.unwrap_or_default();
-  match baidu_sync::rename_baidu_remote_dir(&state.db, &from_path, &name) {
+  match baidu_sync::rename_remote_dir(&state.db, &from_path, &name) {
     Ok(entry) => ApiResponse::success(BaiduRemoteEntry {
       name: entry.name,
       path: entry.path,
@@ -280,6 +358,10 @@ pub fn baidu_sync_rename_dir(
 
 #[tauri::command]
 pub fn baidu_sync_retry(state: State<'_, AppState>, task_id: i64) -> ApiResponse<String> {
+  baidu_sync_retry_inner(&state, task_id)
+}
+
+pub fn baidu_sync_retry_inner(state: &AppState, task_id: i64) -> ApiResponse<String> {
   match baidu_sync::retry_baidu_sync_task(&state.db, task_id) {
     Ok(()) => ApiResponse::success("ok".to_string()),
     Err(err) => ApiResponse::error(err),
@@ -288,6 +370,10 @@ pub fn baidu_sync_retry(state: State<'_, AppState>, task_id: i64) -> ApiResponse
 
 #[tauri::command]
 pub fn baidu_sync_cancel(state: State<'_, AppState>, task_id: i64) -> ApiResponse<String> {
+  baidu_sync_cancel_inner(&state, task_id)
+}
+
+pub fn baidu_sync_cancel_inner(state: &AppState, task_id: i64) -> ApiResponse<String> {
   match baidu_sync::cancel_baidu_sync_task(&state.db, task_id) {
     Ok(()) => ApiResponse::success("ok".to_string()),
     Err(err) => ApiResponse::error(err),
@@ -296,6 +382,10 @@ pub fn baidu_sync_cancel(state: State<'_, AppState>, task_id: i64) -> ApiRespons
 
 #[tauri::command]
 pub fn baidu_sync_pause(state: State<'_, AppState>, task_id: i64) -> ApiResponse<String> {
+  baidu_sync_pause_inner(&state, task_id)
+}
+
+pub fn baidu_sync_pause_inner(state: &AppState, task_id: i64) -> ApiResponse<String> {
   match baidu_sync::pause_baidu_sync_task(&state.db, task_id) {
     Ok(()) => ApiResponse::success("ok".to_string()),
     Err(err) => ApiResponse::error(err),
@@ -304,6 +394,10 @@ pub fn baidu_sync_pause(state: State<'_, AppState>, task_id: i64) -> ApiResponse
 
 #[tauri::command]
 pub fn baidu_sync_delete(state: State<'_, AppState>, task_id: i64) -> ApiResponse<String> {
+  baidu_sync_delete_inner(&state, task_id)
+}
+
+pub fn baidu_sync_delete_inner(state: &AppState, task_id: i64) -> ApiResponse<String> {
   match baidu_sync::delete_baidu_sync_task(&state.db, task_id) {
     Ok(()) => ApiResponse::success("ok".to_string()),
     Err(err) => ApiResponse::error(err),
@@ -314,6 +408,13 @@ pub fn baidu_sync_delete(state: State<'_, AppState>, task_id: i64) -> ApiRespons
 pub fn baidu_sync_update_settings(
   state: State<'_, AppState>,
   request: Option<BaiduSyncUpdateRequest>,
+) -> ApiResponse<String> {
+  baidu_sync_update_settings_inner(&state, request)
+}
+
+pub fn baidu_sync_update_settings_inner(
+  state: &AppState,
+  request: Option<BaiduSyncUpdateRequest>,
 ) -> ApiResponse<String> {
   let request = request.unwrap_or(BaiduSyncUpdateRequest {
     concurrency: None,
@@ -1,39 +1,47 @@
 use std::collections::{HashMap, HashSet};
-use std::io::{BufReader, Read};
-use std::net::TcpListener;
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command, Stdio};
+use std::process::Child;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::thread;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::time::Duration;
 
-use reqwest::blocking::Client;
-use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde_json::{json, Value};
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension};
 use tauri::State;
 use tokio::time::{interval, sleep};
 use url::Url;
 
 use crate::api::ApiResponse;
 use crate::baidu_sync;
-use crate::config::{default_download_dir, resolve_aria2c_candidates};
+use crate::config::default_download_dir;
 use crate::commands::settings::load_download_settings_from_db;
 use crate::ffmpeg::{run_ffmpeg, run_ffmpeg_with_progress, run_ffprobe_json};
 use crate::login_store::AuthInfo;
 use crate::utils::{append_log, build_output_path, now_rfc3339, sanitize_filename};
 use crate::bilibili::client::BilibiliClient;
 use crate::db::Db;
+use crate::download_source::{resolve_download_source, DownloadSourceQuery, DownloadSourceVideo};
+use crate::download_sidecar::{
+  download_sidecars, sidecar_file_paths, DownloadSidecarFiles, DownloadSidecarOptions,
+};
 use crate::login_store::LoginStore;
+use crate::segment_download::{
+  cleanup_segment_files, download_segmented, has_resume_state,
+  state_path as segment_state_path, SegmentDownloadOptions,
+};
+use crate::storage::ensure_free_space;
+use crate::webhook::{emit_webhook_event, EVENT_DOWNLOAD_COMPLETED, EVENT_DOWNLOAD_FAILED};
 use crate::AppState;
 
 pub const DOWNLOAD_SOURCE_BILIBILI: &str = "BILIBILI";
 pub const DOWNLOAD_SOURCE_BAIDU: &str = "BAIDU";
 const BAIDU_DOWNLOAD_SUFFIX: &str = ".BaiduPCS-Go-downloading";
 
+static DOWNLOAD_PAUSED_LOW_SPACE: AtomicBool = AtomicBool::new(false);
+
 #[derive(Clone)]
-struct DownloadContext {
+pub(crate) struct DownloadContext {
   db: Arc<Db>,
   bilibili: Arc<BilibiliClient>,
   login_store: Arc<LoginStore>,
@@ -43,18 +51,7 @@ struct DownloadContext {
 }
 
 impl DownloadContext {
-  fn new(state: &State<'_, AppState>) -> Self {
-    Self {
-      db: state.db.clone(),
-      bilibili: state.bilibili.clone(),
-      login_store: state.login_store.clone(),
-      download_runtime: state.download_runtime.clone(),
-      app_log_path: state.app_log_path.clone(),
-      edit_upload_state: state.edit_upload_state.clone(),
-    }
-  }
-
-  fn from_state(state: &AppState) -> Self {
+  pub(crate) fn new(state: &AppState) -> Self {
     Self {
       db: state.db.clone(),
       bilibili: state.bilibili.clone(),
@@ -122,6 +119,10 @@ pub struct DownloadConfig {
   pub codec: Option<String>,
   pub format: Option<String>,
   pub content: Option<String>,
+  #[serde(default)]
+  pub sidecar: Option<DownloadSidecarOptions>,
+  #[serde(default)]
+  pub account_user_id: Option<i64>,
 }
 
 #[derive(Deserialize, Clone)]
@@ -169,6 +170,9 @@ pub struct SubmissionRequest {
   pub baidu_sync_enabled: Option<bool>,
   pub baidu_sync_path: Option<String>,
   pub baidu_sync_filename: Option<String>,
+  pub account_user_id: Option<i64>,
+  pub scheduled_publish_time: Option<i64>,
+  pub scheduled_publish_mode: Option<String>,
   pub video_parts: Vec<SubmissionVideoPart>,
 }
 
@@ -206,6 +210,7 @@ pub struct VideoDownloadRecord {
   pub create_time: String,
   pub update_time: String,
   pub source_type: String,
+  pub sidecar_files: Option<DownloadSidecarFiles>,
 }
 
 struct PendingDownloadRecord {
@@ -224,6 +229,29 @@ struct PendingDownloadRecord {
   source_type: String,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadSourceRequest {
+  #[serde(flatten)]
+  pub query: DownloadSourceQuery,
+  pub config: DownloadConfig,
+}
+
+#[derive(serde::Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadSourceResult {
+  pub matched: usize,
+  pub queued: usize,
+  pub skipped: usize,
+  pub task_ids: Vec<i64>,
+  pub failed: Vec<String>,
+}
+
+pub(crate) struct SourceEnqueueResult {
+  pub download_ids: Vec<i64>,
+  pub submission_task_id: Option<String>,
+}
+
 #[derive(Clone)]
 struct DownloadTaskCreateResult {
   id: i64,
@@ -237,7 +265,14 @@ pub async fn download_video(
   state: State<'_, AppState>,
   payload: Value,
 ) -> Result<ApiResponse<Value>, String> {
-  let context = DownloadContext::new(&state);
+  download_video_inner(&state, payload).await
+}
+
+pub async fn download_video_inner(
+  state: &AppState,
+  payload: Value,
+) -> Result<ApiResponse<Value>, String> {
+  let context = DownloadContext::new(state);
   let integration = payload.get("downloadRequest").is_some() || payload.get("downloadRequests").is_some();
   if integration {
     let request: IntegrationRequest = match serde_json::from_value(payload) {
@@ -269,11 +304,205 @@ pub async fn download_video(
   }
 }
 
+#[tauri::command]
+pub async fn download_source_preview(
+  state: State<'_, AppState>,
+  payload: DownloadSourceQuery,
+) -> Result<ApiResponse<Vec<DownloadSourceVideo>>, String> {
+  download_source_preview_inner(state.inner(), payload).await
+}
+
+pub async fn download_source_preview_inner(
+  state: &AppState,
+  payload: DownloadSourceQuery,
+) -> Result<ApiResponse<Vec<DownloadSourceVideo>>, String> {
+  let context = DownloadContext::new(state);
+  let auth = load_auth(&context, payload.account_user_id);
+  match resolve_download_source(&context.bilibili, auth.as_ref(), &payload).await {
+    Ok(videos) => Ok(ApiResponse::success(videos)),
+    Err(err) => Ok(ApiResponse::error(err)),
+  }
+}
+
+#[tauri::command]
+pub async fn download_source_enqueue(
+  state: State<'_, AppState>,
+  payload: DownloadSourceRequest,
+) -> Result<ApiResponse<DownloadSourceResult>, String> {
+  download_source_enqueue_inner(state.inner(), payload).await
+}
+
+pub async fn download_source_enqueue_inner(
+  state: &AppState,
+  payload: DownloadSourceRequest,
+) -> Result<ApiResponse<DownloadSourceResult>, String> {
+  let context = DownloadContext::new(state);
+  let mut payload = payload;
+  if payload.config.account_user_id.is_none() {
+    payload.config.account_user_id = payload.query.account_user_id;
+  }
+  let auth = load_auth(&context, payload.config.account_user_id);
+  let videos = match resolve_download_source(&context.bilibili, auth.as_ref(), &payload.query).await
+  {
+    Ok(videos) => videos,
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+  let mut result = DownloadSourceResult {
+    matched: videos.len(),
+    ..Default::default()
+  };
+  for video in videos {
+    match enqueue_source_video(&context, &video.bvid, &payload.config, None).await {
+      Ok(Some(enqueued)) => {
+        result.queued += 1;
+        result.task_ids.extend(enqueued.download_ids);
+      }
+      Ok(None) => result.skipped += 1,
+      Err(err) => result.failed.push(format!("{}: {}", video.bvid, err)),
+    }
+  }
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "download_source_enqueue source={} matched={} queued={} skipped={} failed={}",
+      payload.query.source_type,
+      result.matched,
+      result.queued,
+      result.skipped,
+      result.failed.len()
+    ),
+  );
+  Ok(ApiResponse::success(result))
+}
+
+pub(crate) async fn enqueue_source_video(
+  context: &DownloadContext,
+  bvid: &str,
+  config: &DownloadConfig,
+  submission: Option<(SubmissionRequest, Value)>,
+) -> Result<Option<SourceEnqueueResult>, String> {
+  let Some(request) = build_source_download_request(context, bvid, config).await? else {
+    return Ok(None);
+  };
+  let Some((mut submission_request, workflow_config)) = submission else {
+    let records = create_download_tasks(context.clone(), request).await?;
+    return Ok(Some(SourceEnqueueResult {
+      download_ids: records.iter().map(|record| record.id).collect(),
+      submission_task_id: None,
+    }));
+  };
+  submission_request.video_parts = request
+    .parts
+    .iter()
+    .map(|part| SubmissionVideoPart {
+      original_title: part.title.clone(),
+      file_path: String::new(),
+      start_time: None,
+      end_time: None,
+      cid: Some(part.cid),
+    })
+    .collect();
+  let response = handle_integration_download(
+    context.clone(),
+    IntegrationRequest {
+      enable_submission: true,
+      workflow_config: Some(workflow_config),
+      download_request: None,
+      download_requests: Some(vec![request]),
+      submission_request,
+    },
+  )
+  .await;
+  if response.code != 0 {
+    return Err(response.message);
+  }
+  let data = response.data.unwrap_or(Value::Null);
+  Ok(Some(SourceEnqueueResult {
+    download_ids: data
+      .get("downloadTaskIds")
+      .and_then(|value| value.as_array())
+      .map(|items| items.iter().filter_map(|item| item.as_i64()).collect())
+      .unwrap_or_default(),
+    submission_task_id: data
+      .get("submissionTaskId")
+      .and_then(|value| value.as_str())
+      .map(|value| value.to_string()),
+  }))
+}
+
+async fn build_source_download_request(
+  context: &DownloadContext,
+  bvid: &str,
+  config: &DownloadConfig,
+) -> Result<Option<DownloadRequest>, String> {
+  let video_url = format!("https://www.bilibili.com/video/{}", bvid);
+  let parts = fetch_video_parts(context, bvid, config.account_user_id).await?;
+  let mut pending = Vec::new();
+  for part in parts {
+    if find_reusable_download_record(context, Some(part.cid), &video_url, &part.title)?.is_none() {
+      pending.push(part);
+    }
+  }
+  if pending.is_empty() {
+    return Ok(None);
+  }
+  Ok(Some(DownloadRequest {
+    video_url,
+    parts: pending,
+    config: DownloadConfig {
+      download_name: None,
+      ..config.clone()
+    },
+  }))
+}
+
+async fn fetch_video_parts(
+  context: &DownloadContext,
+  bvid: &str,
+  account_user_id: Option<i64>,
+) -> Result<Vec<DownloadPart>, String> {
+  let auth = load_auth(context, account_user_id);
+  let url = format!("{}/x/player/pagelist", context.bilibili.base_url());
+  let params = vec![("bvid".to_string(), bvid.to_string())];
+  let data = context
+    .bilibili
+    .get_json(&url, &params, auth.as_ref(), false)
+    .await?;
+  let parts: Vec<DownloadPart> = data
+    .as_array()
+    .map(|items| {
+      items
+        .iter()
+        .filter_map(|item| {
+          Some(DownloadPart {
+            cid: item.get("cid")?.as_i64()?,
+            title: item
+              .get("part")
+              .and_then(|value| value.as_str())
+              .filter(|value| !value.trim().is_empty())
+              .unwrap_or(bvid)
+              .to_string(),
+            duration: item.get("duration").and_then(|value| value.as_i64()),
+          })
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+  if parts.is_empty() {
+    return Err("未获取到分P信息".to_string());
+  }
+  Ok(parts)
+}
+
 #[tauri::command]
 pub fn download_get(state: State<'_, AppState>, task_id: i64) -> ApiResponse<VideoDownloadRecord> {
+  download_get_inner(&state, task_id)
+}
+
+pub fn download_get_inner(state: &AppState, task_id: i64) -> ApiResponse<VideoDownloadRecord> {
   match state.db.with_conn(|conn| {
     conn.query_row(
-      "SELECT id, bvid, aid, title, part_title, part_count, current_part, download_url, local_path, resolution, codec, format, status, progress, progress_total, progress_done, create_time, update_time, source_type \
+      "SELECT id, bvid, aid, title, part_title, part_count, current_part, download_url, local_path, resolution, codec, format, status, progress, progress_total, progress_done, create_time, update_time, source_type, sidecar_files \
        FROM video_download WHERE id = ?1",
       [task_id],
       |row| {
@@ -299,6 +528,7 @@ pub fn download_get(state: State<'_, AppState>, task_id: i64) -> ApiResponse<Vid
           source_type: row
             .get::<_, Option<String>>(18)?
             .unwrap_or_else(|| DOWNLOAD_SOURCE_BILIBILI.to_string()),
+          sidecar_files: parse_sidecar_files(row.get(19)?),
         })
       },
     )
@@ -312,10 +542,17 @@ pub fn download_get(state: State<'_, AppState>, task_id: i64) -> ApiResponse<Vid
 pub fn download_list_by_status(
   state: State<'_, AppState>,
   status: i64,
+) -> ApiResponse<Vec<VideoDownloadRecord>> {
+  download_list_by_status_inner(&state, status)
+}
+
+pub fn download_list_by_status_inner(
+  state: &AppState,
+  status: i64,
 ) -> ApiResponse<Vec<VideoDownloadRecord>> {
   match state.db.with_conn(|conn| {
     let mut stmt = conn.prepare(
-      "SELECT id, bvid, aid, title, part_title, part_count, current_part, download_url, local_path, resolution, codec, format, status, progress, progress_total, progress_done, create_time, update_time, source_type \
+      "SELECT id, bvid, aid, title, part_title, part_count, current_part, download_url, local_path, resolution, codec, format, status, progress, progress_total, progress_done, create_time, update_time, source_type, sidecar_files \
        FROM video_download WHERE status = ?1 ORDER BY id DESC",
     )?;
     let list = stmt
@@ -342,6 +579,7 @@ pub fn download_list_by_status(
           source_type: row
             .get::<_, Option<String>>(18)?
             .unwrap_or_else(|| DOWNLOAD_SOURCE_BILIBILI.to_string()),
+          sidecar_files: parse_sidecar_files(row.get(19)?),
         })
       })?
       .collect::<Result<Vec<_>, _>>()?;
@@ -357,17 +595,26 @@ pub fn download_delete(
   state: State<'_, AppState>,
   task_id: i64,
   delete_file: Option<bool>,
+) -> ApiResponse<String> {
+  download_delete_inner(&state, task_id, delete_file)
+}
+
+pub fn download_delete_inner(
+  state: &AppState,
+  task_id: i64,
+  delete_file: Option<bool>,
 ) -> ApiResponse<String> {
   let delete_file = delete_file.unwrap_or(false);
   let record = match state.db.with_conn(|conn| {
     conn.query_row(
-      "SELECT local_path, status, source_type FROM video_download WHERE id = ?1",
+      "SELECT local_path, status, source_type, sidecar_files FROM video_download WHERE id = ?1",
       [task_id],
       |row| {
         Ok((
           row.get::<_, Option<String>>(0)?,
           row.get::<_, i64>(1)?,
           row.get::<_, Option<String>>(2)?,
+          row.get::<_, Option<String>>(3)?,
         ))
       },
     )
@@ -375,7 +622,7 @@ pub fn download_delete(
     Ok(value) => value,
     Err(err) => return ApiResponse::error(format!("Failed to load download record: {}", err)),
   };
-  let (local_path, status, source_type) = record;
+  let (local_path, status, source_type, sidecar_files) = record;
   let source_type = source_type
     .unwrap_or_else(|| DOWNLOAD_SOURCE_BILIBILI.to_string())
     .to_ascii_uppercase();
@@ -383,7 +630,7 @@ pub fn download_delete(
     return ApiResponse::error("任务正在下载，暂不支持删除".to_string());
   }
   if status == 1 && source_type == DOWNLOAD_SOURCE_BAIDU {
-    let context = DownloadContext::new(&state);
+    let context = DownloadContext::new(state);
     if let Err(err) = cancel_baidu_download_process(&context, task_id) {
       return ApiResponse::error(format!("取消网盘下载失败: {}", err));
     }
@@ -412,6 +659,11 @@ pub fn download_delete(
       BAIDU_DOWNLOAD_SUFFIX
     ));
     let _ = std::fs::remove_file(baidu_temp);
+    if let Some(files) = parse_sidecar_files(sidecar_files) {
+      for sidecar in sidecar_file_paths(&files) {
+        let _ = std::fs::remove_file(sidecar);
+      }
+    }
 
     if let Some(parent) = path.parent() {
       if is_dir_empty(parent) {
@@ -436,7 +688,14 @@ pub async fn download_retry(
   state: State<'_, AppState>,
   task_id: i64,
 ) -> Result<ApiResponse<String>, String> {
-  let context = DownloadContext::new(&state);
+  download_retry_inner(&state, task_id).await
+}
+
+pub async fn download_retry_inner(
+  state: &AppState,
+  task_id: i64,
+) -> Result<ApiResponse<String>, String> {
+  let context = DownloadContext::new(state);
   let record = context
     .db
     .with_conn(|conn| {
@@ -547,6 +806,8 @@ pub async fn download_retry(
     codec,
     format,
     content,
+    sidecar: None,
+    account_user_id: load_download_account_user_id(&context.db, task_id),
   };
 
   let duration = if bvid.is_some() || aid.is_some() {
@@ -609,7 +870,14 @@ pub async fn download_resume(
   state: State<'_, AppState>,
   task_id: i64,
 ) -> Result<ApiResponse<String>, String> {
-  let context = DownloadContext::new(&state);
+  download_resume_inner(&state, task_id).await
+}
+
+pub async fn download_resume_inner(
+  state: &AppState,
+  task_id: i64,
+) -> Result<ApiResponse<String>, String> {
+  let context = DownloadContext::new(state);
   let record = context
     .db
     .with_conn(|conn| {
@@ -719,6 +987,8 @@ pub async fn download_resume(
     codec,
     format,
     content,
+    sidecar: None,
+    account_user_id: load_download_account_user_id(&context.db, task_id),
   };
 
   let duration = if bvid.is_some() || aid.is_some() {
@@ -759,7 +1029,7 @@ pub async fn download_resume(
 }
 
 pub async fn requeue_integrated_downloads(
-  state: &State<'_, AppState>,
+  state: &AppState,
   download_ids: &[i64],
 ) -> Result<(), String> {
   if download_ids.is_empty() {
@@ -831,6 +1101,8 @@ async fn requeue_download_record(
     codec,
     format,
     content,
+    sidecar: None,
+    account_user_id: load_download_account_user_id(&context.db, record_id),
   };
 
   let duration = if bvid.is_some() || aid.is_some() {
@@ -866,7 +1138,7 @@ async fn requeue_download_record(
 }
 
 pub fn recover_stale_downloads(state: &AppState) {
-  let context = DownloadContext::from_state(state);
+  let context = DownloadContext::new(state);
   let stale_ids = context
     .db
     .with_conn(|conn| {
@@ -909,7 +1181,7 @@ pub fn recover_stale_downloads(state: &AppState) {
 }
 
 pub fn start_download_queue_loop(state: &AppState) {
-  let context = DownloadContext::from_state(state);
+  let context = DownloadContext::new(state);
   tauri::async_runtime::spawn(async move {
     schedule_pending_downloads(context.clone()).await;
     loop {
@@ -935,9 +1207,20 @@ async fn handle_integration_download(
   if download_requests.is_empty() {
     return ApiResponse::error("Missing download requests".to_string());
   }
+  let (scheduled_publish_time, scheduled_publish_mode) =
+    match crate::commands::submission::normalize_publish_schedule(
+      request.submission_request.scheduled_publish_time,
+      request.submission_request.scheduled_publish_mode.as_deref(),
+    ) {
+      Ok(value) => value,
+      Err(err) => return ApiResponse::error(err),
+    };
 
   let mut download_results = Vec::new();
-  for download_request in download_requests {
+  for mut download_request in download_requests {
+    if download_request.config.account_user_id.is_none() {
+      download_request.config.account_user_id = request.submission_request.account_user_id;
+    }
     match create_download_tasks(context.clone(), download_request).await {
       Ok(task_results) => download_results.extend(task_results),
       Err(err) => return ApiResponse::error(err),
@@ -985,8 +1268,8 @@ async fn handle_integration_download(
 
   let insert_result = context.db.with_conn(|conn| {
     conn.execute(
-      "INSERT INTO submission_task (task_id, status, priority, title, description, cover_url, partition_id, tags, topic_id, mission_id, activity_title, video_type, collection_id, bvid, aid, created_at, updated_at, segment_prefix, baidu_sync_enabled, baidu_sync_path, baidu_sync_filename) \
-       VALUES (?1, ?2, ?3, ?4, ?5, NULL, ?6, ?7, ?8, ?9, ?10, ?11, ?12, NULL, NULL, ?13, ?14, ?15, ?16, ?17, ?18)",
+      "INSERT INTO submission_task (task_id, status, priority, title, description, cover_url, partition_id, tags, topic_id, mission_id, activity_title, video_type, collection_id, bvid, aid, created_at, updated_at, segment_prefix, baidu_sync_enabled, baidu_sync_path, baidu_sync_filename, account_user_id, scheduled_publish_time, scheduled_publish_mode) \
+       VALUES (?1, ?2, ?3, ?4, ?5, NULL, ?6, ?7, ?8, ?9, ?10, ?11, ?12, NULL, NULL, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)",
       params![
         &submission_id,
         "PENDING",
@@ -1010,6 +1293,9 @@ async fn handle_integration_download(
         },
         submission.baidu_sync_path.as_deref(),
         normalized_baidu_sync_filename.as_deref(),
+        submission.account_user_id,
+        scheduled_publish_time,
+        scheduled_publish_mode.as_deref(),
       ],
     )?;
 
@@ -1101,7 +1387,13 @@ async fn create_download_tasks(
   request: DownloadRequest,
 ) -> Result<Vec<DownloadTaskCreateResult>, String> {
   let (bvid, aid) = parse_video_id(&request.video_url);
-  let video_title = fetch_video_title(&context, bvid.as_deref(), aid.as_deref()).await;
+  let video_title = fetch_video_title(
+    &context,
+    bvid.as_deref(),
+    aid.as_deref(),
+    request.config.account_user_id,
+  )
+  .await;
 
   let folder_name = request
     .config
@@ -1129,6 +1421,13 @@ async fn create_download_tasks(
   } else {
     base_dir
   };
+  let sidecar_options = request
+    .config
+    .sidecar
+    .clone()
+    .map(DownloadSidecarOptions::normalize)
+    .filter(|options| options.is_enabled())
+    .and_then(|options| serde_json::to_string(&options).ok());
   for (index, part) in parts.iter().enumerate() {
     let file_name = format!("{}.mp4", sanitize_filename(&part.title));
     let output_path = build_output_path(&base_dir, &sanitized_folder, &file_name);
@@ -1161,9 +1460,9 @@ async fn create_download_tasks(
       .db
       .with_conn(|conn| {
         conn.execute(
-          "INSERT INTO video_download (bvid, aid, title, part_title, part_count, current_part, download_url, local_path, status, progress, progress_total, progress_done, create_time, update_time, resolution, codec, format, cid, content, source_type) \
-           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 0, 0, 0, 0, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
-          (
+          "INSERT INTO video_download (bvid, aid, title, part_title, part_count, current_part, download_url, local_path, status, progress, progress_total, progress_done, create_time, update_time, resolution, codec, format, cid, content, source_type, sidecar_options, account_user_id) \
+           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 0, 0, 0, 0, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+          params![
             bvid.as_deref(),
             aid.as_deref(),
             video_title.as_deref(),
@@ -1180,7 +1479,9 @@ async fn create_download_tasks(
             part.cid,
             request.config.content.as_deref(),
             DOWNLOAD_SOURCE_BILIBILI,
-          ),
+            sidecar_options.as_deref(),
+            request.config.account_user_id,
+          ],
         )?;
         Ok(conn.last_insert_rowid())
       })
@@ -1298,11 +1599,25 @@ fn download_path_conflict(
   if output_path.with_extension("audio").exists() {
     return Ok(true);
   }
-  let aria2_path = PathBuf::from(format!("{}.aria2", output_path.to_string_lossy()));
-  Ok(aria2_path.exists())
+  Ok(segment_state_path(output_path).exists())
 }
 
 async fn schedule_pending_downloads(context: DownloadContext) {
+  let download_dir = load_download_settings_from_db(&context.db)
+    .map(|settings| settings.download_path)
+    .unwrap_or_else(|_| default_download_dir().to_string_lossy().to_string());
+  if let Err(err) = ensure_free_space(&context.db, Path::new(&download_dir)) {
+    if !DOWNLOAD_PAUSED_LOW_SPACE.swap(true, Ordering::SeqCst) {
+      append_log(
+        &context.app_log_path,
+        &format!("download_schedule_paused reason=low_disk_space err={}", err),
+      );
+    }
+    return;
+  }
+  if DOWNLOAD_PAUSED_LOW_SPACE.swap(false, Ordering::SeqCst) {
+    append_log(&context.app_log_path, "download_schedule_resumed");
+  }
   let available = match available_download_slots(&context) {
     Ok(value) => value,
     Err(err) => {
@@ -1464,6 +1779,8 @@ fn start_pending_download(
     codec: record.codec,
     format: record.format,
     content: record.content,
+    sidecar: None,
+    account_user_id: load_download_account_user_id(&context.db, record.id),
   };
 
   try_start_download_job(
@@ -1624,6 +1941,7 @@ async fn run_download_job(
     &format!("download_job_start record_id={} cid={}", record_id, part.cid),
   );
 
+  let sidecar_target = (bvid.clone(), aid.clone(), part.cid, output_path.clone());
   let result =
     download_part(&context, record_id, bvid, aid, part, config, output_path, resume_progress)
       .await;
@@ -1634,6 +1952,8 @@ async fn run_download_job(
   });
   match result {
     Ok(()) => {
+      let (bvid, aid, cid, output_path) = sidecar_target;
+      save_download_sidecars(&context, record_id, bvid, aid, cid, &output_path).await;
       let _ = update_download_status(&context, record_id, 2, 100);
       clear_download_progress(&context, record_id);
       append_log(
@@ -1666,6 +1986,73 @@ async fn run_download_job(
   }
 }
 
+async fn save_download_sidecars(
+  context: &DownloadContext,
+  record_id: i64,
+  bvid: Option<String>,
+  aid: Option<String>,
+  cid: i64,
+  output_path: &Path,
+) {
+  let options = context
+    .db
+    .with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT sidecar_options FROM video_download WHERE id = ?1",
+          [record_id],
+          |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()
+    })
+    .ok()
+    .flatten()
+    .flatten()
+    .and_then(|value| serde_json::from_str::<DownloadSidecarOptions>(&value).ok())
+    .map(DownloadSidecarOptions::normalize);
+  let Some(options) = options.filter(|options| options.is_enabled()) else {
+    return;
+  };
+  let auth = load_auth(context, load_download_account_user_id(&context.db, record_id));
+  let files = download_sidecars(
+    &context.bilibili,
+    auth.as_ref(),
+    bvid.as_deref(),
+    aid.as_deref(),
+    cid,
+    output_path,
+    &options,
+  )
+  .await;
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "download_sidecar_done record_id={} subtitles={} danmaku={} cover={} metadata={} errors={}",
+      record_id,
+      files.subtitles.len(),
+      files.danmaku_count,
+      files.cover.is_some(),
+      files.metadata.is_some(),
+      files.errors.join("; ")
+    ),
+  );
+  let payload = match serde_json::to_string(&files) {
+    Ok(value) => value,
+    Err(_) => return,
+  };
+  let _ = context.db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE video_download SET sidecar_files = ?1, update_time = ?2 WHERE id = ?3",
+      (payload, now_rfc3339(), record_id),
+    )?;
+    Ok(())
+  });
+}
+
+fn parse_sidecar_files(value: Option<String>) -> Option<DownloadSidecarFiles> {
+  value.and_then(|value| serde_json::from_str(&value).ok())
+}
+
 fn extract_remote_name(remote_path: &str) -> String {
   remote_path
     .rsplit('/')
@@ -1749,10 +2136,15 @@ async fn run_baidu_download_job(
     }
   };
 
-  let total_size =
-    baidu_sync::fetch_baidu_remote_file_size(context.db.as_ref(), &remote_path)
-      .ok()
-      .unwrap_or(0);
+  let size_db = context.db.clone();
+  let size_path = remote_path.clone();
+  let total_size = tauri::async_runtime::spawn_blocking(move || {
+    baidu_sync::fetch_remote_file_size(size_db.as_ref(), &size_path)
+  })
+  .await
+  .ok()
+  .and_then(|result| result.ok())
+  .unwrap_or(0);
 
   let db = context.db.clone();
   let remote_path_clone = remote_path.clone();
@@ -1760,7 +2152,7 @@ async fn run_baidu_download_job(
   let download_runtime = context.download_runtime.clone();
   let record_id_clone = record_id;
   let mut download_handle = tauri::async_runtime::spawn_blocking(move || {
-    baidu_sync::download_baidu_file_with_hook(
+    baidu_sync::download_remote_file_with_hook(
       db.as_ref(),
       &remote_path_clone,
       &output_path_clone,
@@ -1878,9 +2270,8 @@ async fn download_part(
   let settings = load_download_settings_from_db(&context.db)
     .map_err(|err| format!("Failed to load download settings: {}", err))?;
   let block_pcdn = settings.block_pcdn;
-  let enable_aria2c = settings.enable_aria2c;
-  let aria2c_connections = settings.aria2c_connections.max(1).min(32);
-  let aria2c_split = settings.aria2c_split.max(1).min(32);
+  let download_connections = settings.download_connections.clamp(1, 32);
+  let download_split = settings.download_split.clamp(1, 32);
   let min_progress = resume_progress.filter(|value| *value > 0).map(|value| value.min(99));
   let play_info = fetch_play_info(context, bvid.clone(), aid.clone(), part.cid, &config).await?;
   let mut format = config.format.clone().unwrap_or_else(|| "dash".to_string());
@@ -1910,46 +2301,44 @@ async fn download_part(
     .max(0);
   let duration_ms = duration.checked_mul(1000);
   let expected_duration_seconds = duration as f64;
-  let track_progress = duration_ms.unwrap_or(0) > 0 || enable_aria2c;
+  let track_progress = true;
 
-  let header = build_ffmpeg_headers(context).unwrap_or_default();
+  let header = build_ffmpeg_headers(context, config.account_user_id).unwrap_or_default();
   let output_path_string = output_path.to_string_lossy().to_string();
 
   if format == "mp4" || format == "flv" {
     let urls = collect_durl_urls(&play_info, block_pcdn)?;
-      if enable_aria2c {
-      if let Err(err) = download_with_aria2c(
-        context,
-        record_id,
-        track_progress,
-        &output_path,
-        &urls,
-        &header,
-        aria2c_connections,
-        aria2c_split,
-        "main",
-      )
-      .await
-      {
-        if has_partial_file(&output_path) {
-          append_log(
-            &context.app_log_path,
-            &format!(
-              "aria2c_resume_pending record_id={} output={}",
-              record_id,
-              output_path.to_string_lossy()
-            ),
-          );
-          return Err("aria2c下载中断，可重试续传".to_string());
-        }
-        cleanup_aria2c_files(&output_path);
+    if let Err(err) = download_with_segments(
+      context,
+      record_id,
+      track_progress,
+      &output_path,
+      &urls,
+      &header,
+      download_connections,
+      download_split,
+      "main",
+    )
+    .await
+    {
+      if has_resume_state(&output_path) {
         append_log(
           &context.app_log_path,
-          &format!("aria2c_fallback record_id={} err={}", record_id, err),
+          &format!(
+            "segment_resume_pending record_id={} output={}",
+            record_id,
+            output_path.to_string_lossy()
+          ),
         );
-      } else {
-        return Ok(());
+        return Err("分段下载中断，可重试续传".to_string());
       }
+      cleanup_segment_files(&output_path);
+      append_log(
+        &context.app_log_path,
+        &format!("segment_fallback record_id={} err={}", record_id, err),
+      );
+    } else {
+      return Ok(());
     }
     run_ffmpeg_job_with_url_fallback(
       context,
@@ -1995,39 +2384,37 @@ async fn download_part(
         .first()
         .map(|candidate| candidate.urls.clone())
         .ok_or_else(|| "Missing video URL".to_string())?;
-      if enable_aria2c {
-      if let Err(err) = download_with_aria2c(
+      if let Err(err) = download_with_segments(
         context,
         record_id,
         track_progress,
         &output_path,
         &video_urls,
         &header,
-        aria2c_connections,
-        aria2c_split,
+        download_connections,
+        download_split,
         "main",
       )
       .await
       {
-          if has_partial_file(&output_path) {
-            append_log(
-              &context.app_log_path,
-              &format!(
-                "aria2c_resume_pending record_id={} output={}",
-                record_id,
-                output_path.to_string_lossy()
-              ),
-            );
-            return Err("aria2c下载中断，可重试续传".to_string());
-          }
-          cleanup_aria2c_files(&output_path);
+        if has_resume_state(&output_path) {
           append_log(
             &context.app_log_path,
-            &format!("aria2c_fallback record_id={} err={}", record_id, err),
+            &format!(
+              "segment_resume_pending record_id={} output={}",
+              record_id,
+              output_path.to_string_lossy()
+            ),
           );
-        } else {
-          return Ok(());
+          return Err("分段下载中断，可重试续传".to_string());
         }
+        cleanup_segment_files(&output_path);
+        append_log(
+          &context.app_log_path,
+          &format!("segment_fallback record_id={} err={}", record_id, err),
+        );
+      } else {
+        return Ok(());
       }
       run_ffmpeg_job_with_url_fallback(
         context,
@@ -2065,39 +2452,37 @@ async fn download_part(
         .first()
         .map(|candidate| candidate.urls.clone())
         .ok_or_else(|| "Missing audio URL".to_string())?;
-      if enable_aria2c {
-      if let Err(err) = download_with_aria2c(
+      if let Err(err) = download_with_segments(
         context,
         record_id,
         track_progress,
         &output_path,
         &audio_urls,
         &header,
-        aria2c_connections,
-        aria2c_split,
+        download_connections,
+        download_split,
         "main",
       )
       .await
       {
-          if has_partial_file(&output_path) {
-            append_log(
-              &context.app_log_path,
-              &format!(
-                "aria2c_resume_pending record_id={} output={}",
-                record_id,
-                output_path.to_string_lossy()
-              ),
-            );
-            return Err("aria2c下载中断，可重试续传".to_string());
-          }
-          cleanup_aria2c_files(&output_path);
+        if has_resume_state(&output_path) {
           append_log(
             &context.app_log_path,
-            &format!("aria2c_fallback record_id={} err={}", record_id, err),
+            &format!(
+              "segment_resume_pending record_id={} output={}",
+              record_id,
+              output_path.to_string_lossy()
+            ),
           );
-        } else {
-          return Ok(());
+          return Err("分段下载中断，可重试续传".to_string());
         }
+        cleanup_segment_files(&output_path);
+        append_log(
+          &context.app_log_path,
+          &format!("segment_fallback record_id={} err={}", record_id, err),
+        );
+      } else {
+        return Ok(());
       }
       run_ffmpeg_job_with_url_fallback(
         context,
@@ -2134,232 +2519,223 @@ async fn download_part(
         select_video_candidates(dash, config.resolution.as_deref(), config.codec.as_deref(), block_pcdn)?;
       let audio_candidates = select_audio_candidates(dash, block_pcdn)?;
       let mut last_error: Option<String> = None;
-      let mut aria2c_enabled = enable_aria2c;
       for (video_index, video_candidate) in video_candidates.iter().enumerate() {
         for (audio_index, audio_candidate) in audio_candidates.iter().enumerate() {
-          let mut aria2c_failed = !aria2c_enabled;
+          let mut segmented_failed = false;
           let temp_video_path = output_path.with_extension("video");
           let temp_audio_path = output_path.with_extension("audio");
-          if aria2c_enabled {
-            let (video_result, audio_result) = tokio::join!(
-              download_with_aria2c(
-                context,
-                record_id,
-                track_progress,
-                &temp_video_path,
-                &video_candidate.urls,
-                &header,
-                aria2c_connections,
-                aria2c_split,
-                "video",
-              ),
-              download_with_aria2c(
-                context,
-                record_id,
-                track_progress,
-                &temp_audio_path,
-                &audio_candidate.urls,
-                &header,
-                aria2c_connections,
-                aria2c_split,
-                "audio",
-              ),
+          let (video_result, audio_result) = tokio::join!(
+            download_with_segments(
+              context,
+              record_id,
+              track_progress,
+              &temp_video_path,
+              &video_candidate.urls,
+              &header,
+              download_connections,
+              download_split,
+              "video",
+            ),
+            download_with_segments(
+              context,
+              record_id,
+              track_progress,
+              &temp_audio_path,
+              &audio_candidate.urls,
+              &header,
+              download_connections,
+              download_split,
+              "audio",
+            ),
+          );
+          if let Err(err) = &video_result {
+            append_log(
+              &context.app_log_path,
+              &format!("segment_fallback record_id={} err={}", record_id, err),
+            );
+          }
+          if let Err(err) = &audio_result {
+            append_log(
+              &context.app_log_path,
+              &format!("segment_fallback record_id={} err={}", record_id, err),
             );
-            if let Err(err) = &video_result {
+          }
+          if video_result.is_err() || audio_result.is_err() {
+            if has_resume_state(&temp_video_path) || has_resume_state(&temp_audio_path) {
+              let resume_path = if has_resume_state(&temp_audio_path) {
+                &temp_audio_path
+              } else {
+                &temp_video_path
+              };
               append_log(
                 &context.app_log_path,
-                &format!("aria2c_fallback record_id={} err={}", record_id, err),
+                &format!(
+                  "segment_resume_pending record_id={} output={}",
+                  record_id,
+                  resume_path.to_string_lossy()
+                ),
               );
-              if is_aria2c_missing_error(err) {
-                aria2c_enabled = false;
-              }
+              return Err("分段下载中断，可重试续传".to_string());
             }
-            if let Err(err) = &audio_result {
+            cleanup_segment_files(&temp_video_path);
+            cleanup_segment_files(&temp_audio_path);
+            segmented_failed = true;
+          } else {
+            let _ = update_download_progress(context, record_id, 95);
+            let video_timing = log_ffprobe_source_duration(
+              &context.app_log_path,
+              record_id,
+              "dash_segment_video",
+              &temp_video_path,
+            );
+            let audio_timing = log_ffprobe_source_duration(
+              &context.app_log_path,
+              record_id,
+              "dash_segment_audio",
+              &temp_audio_path,
+            );
+            let mut video_delay = 0.0;
+            let mut audio_trim = 0.0;
+            if let (Some(video_timing), Some(audio_timing)) = (video_timing, audio_timing) {
+              let offset = video_timing.video_start - audio_timing.audio_start;
+              if offset > 0.1 {
+                audio_trim = offset;
+              } else if offset < -0.1 {
+                video_delay = -offset;
+              }
               append_log(
                 &context.app_log_path,
-                &format!("aria2c_fallback record_id={} err={}", record_id, err),
-              );
-              if is_aria2c_missing_error(err) {
-                aria2c_enabled = false;
-              }
+                &format!(
+                  "ffmpeg_merge_offset record_id={} v_start={:.3} a_start={:.3} v_delay={:.3} a_trim={:.3}",
+                  record_id,
+                  video_timing.video_start,
+                  audio_timing.audio_start,
+                  video_delay,
+                  audio_trim
+                ),
+              );
             }
-            if video_result.is_err() || audio_result.is_err() {
-              if has_partial_file(&temp_video_path) || has_partial_file(&temp_audio_path) {
-                let resume_path = if has_partial_file(&temp_audio_path) {
-                  &temp_audio_path
-                } else {
-                  &temp_video_path
-                };
-                append_log(
-                  &context.app_log_path,
-                  &format!(
-                    "aria2c_resume_pending record_id={} output={}",
+            let mut args = Vec::new();
+            if video_delay > 0.0 {
+              args.push("-itsoffset".to_string());
+              args.push(format!("{:.3}", video_delay));
+            }
+            args.push("-i".to_string());
+            args.push(temp_video_path.to_string_lossy().to_string());
+            args.push("-i".to_string());
+            args.push(temp_audio_path.to_string_lossy().to_string());
+            if audio_trim > 0.0 {
+              args.push("-af".to_string());
+              args.push(format!(
+                "atrim=start={:.3},asetpts=PTS-STARTPTS",
+                audio_trim
+              ));
+            }
+            args.extend([
+              "-map".to_string(),
+              "0:v:0".to_string(),
+              "-map".to_string(),
+              "1:a:0".to_string(),
+              "-c:v".to_string(),
+              "copy".to_string(),
+              "-c:a".to_string(),
+              if audio_trim > 0.0 {
+                "aac".to_string()
+              } else {
+                "copy".to_string()
+              },
+              "-shortest".to_string(),
+            ]);
+            if output_path.extension().and_then(|value| value.to_str()) == Some("mp4") {
+              args.push("-movflags".to_string());
+              args.push("+faststart".to_string());
+            }
+            args.push(output_path_string.clone());
+            match run_ffmpeg_job(
+              context,
+              record_id,
+              false,
+              duration_ms,
+              min_progress,
+              &format,
+              &output_path,
+              args,
+            )
+            .await
+            {
+              Ok(_) => {
+                let _ = update_download_progress(context, record_id, 99);
+                match probe_stream_durations(&output_path) {
+                Ok((video_duration, audio_duration)) => {
+                  log_ffprobe_av_duration(
+                    &context.app_log_path,
                     record_id,
-                    resume_path.to_string_lossy()
-                  ),
-                );
-                return Err("aria2c下载中断，可重试续传".to_string());
-              }
-              cleanup_aria2c_files(&temp_video_path);
-              cleanup_aria2c_files(&temp_audio_path);
-              aria2c_failed = true;
-            } else {
-              let _ = update_download_progress(context, record_id, 95);
-              let video_timing = log_ffprobe_source_duration(
-                &context.app_log_path,
-                record_id,
-                "dash_aria2c_video",
-                &temp_video_path,
-              );
-              let audio_timing = log_ffprobe_source_duration(
-                &context.app_log_path,
-                record_id,
-                "dash_aria2c_audio",
-                &temp_audio_path,
-              );
-              let mut video_delay = 0.0;
-              let mut audio_trim = 0.0;
-              if let (Some(video_timing), Some(audio_timing)) = (video_timing, audio_timing) {
-                let offset = video_timing.video_start - audio_timing.audio_start;
-                if offset > 0.1 {
-                  audio_trim = offset;
-                } else if offset < -0.1 {
-                  video_delay = -offset;
-                }
-                append_log(
-                  &context.app_log_path,
-                  &format!(
-                    "ffmpeg_merge_offset record_id={} v_start={:.3} a_start={:.3} v_delay={:.3} a_trim={:.3}",
+                    "dash_segment_merge",
+                    &output_path,
+                    expected_duration_seconds,
+                    video_duration,
+                    audio_duration,
+                  );
+                  log_ffprobe_av_timing(
+                    &context.app_log_path,
                     record_id,
-                    video_timing.video_start,
-                    audio_timing.audio_start,
-                    video_delay,
-                    audio_trim
-                  ),
-                );
-              }
-              let mut args = Vec::new();
-              if video_delay > 0.0 {
-                args.push("-itsoffset".to_string());
-                args.push(format!("{:.3}", video_delay));
-              }
-              args.push("-i".to_string());
-              args.push(temp_video_path.to_string_lossy().to_string());
-              args.push("-i".to_string());
-              args.push(temp_audio_path.to_string_lossy().to_string());
-              if audio_trim > 0.0 {
-                args.push("-af".to_string());
-                args.push(format!(
-                  "atrim=start={:.3},asetpts=PTS-STARTPTS",
-                  audio_trim
-                ));
-              }
-              args.extend([
-                "-map".to_string(),
-                "0:v:0".to_string(),
-                "-map".to_string(),
-                "1:a:0".to_string(),
-                "-c:v".to_string(),
-                "copy".to_string(),
-                "-c:a".to_string(),
-                if audio_trim > 0.0 {
-                  "aac".to_string()
-                } else {
-                  "copy".to_string()
-                },
-                "-shortest".to_string(),
-              ]);
-              if output_path.extension().and_then(|value| value.to_str()) == Some("mp4") {
-                args.push("-movflags".to_string());
-                args.push("+faststart".to_string());
-              }
-              args.push(output_path_string.clone());
-              match run_ffmpeg_job(
-                context,
-                record_id,
-                false,
-                duration_ms,
-                min_progress,
-                &format,
-                &output_path,
-                args,
-              )
-              .await
-              {
-                Ok(_) => {
-                  let _ = update_download_progress(context, record_id, 99);
-                  match probe_stream_durations(&output_path) {
-                  Ok((video_duration, audio_duration)) => {
-                    log_ffprobe_av_duration(
-                      &context.app_log_path,
-                      record_id,
-                      "dash_aria2c_merge",
-                      &output_path,
-                      expected_duration_seconds,
-                      video_duration,
-                      audio_duration,
-                    );
-                    log_ffprobe_av_timing(
+                    "dash_segment_merge",
+                    &output_path,
+                  );
+                  if !is_video_complete(
+                    video_duration,
+                    audio_duration,
+                    expected_duration_seconds,
+                  ) {
+                    append_log(
                       &context.app_log_path,
-                      record_id,
-                      "dash_aria2c_merge",
-                      &output_path,
+                      &format!(
+                        "ffprobe_video_short record_id={} video={:.3} audio={:.3} expected={:.3}",
+                        record_id, video_duration, audio_duration, expected_duration_seconds
+                      ),
                     );
-                    if !is_video_complete(
-                      video_duration,
-                      audio_duration,
-                      expected_duration_seconds,
-                    ) {
-                      append_log(
-                        &context.app_log_path,
-                        &format!(
-                          "ffprobe_video_short record_id={} video={:.3} audio={:.3} expected={:.3}",
-                          record_id, video_duration, audio_duration, expected_duration_seconds
-                        ),
-                      );
-                      let _ = std::fs::remove_file(&output_path);
-                      last_error = Some("Video stream too short".to_string());
-                    } else if is_audio_complete(video_duration, audio_duration) {
-                      let _ = std::fs::remove_file(&temp_video_path);
-                      let _ = std::fs::remove_file(&temp_audio_path);
-                      return Ok(());
-                    } else {
-                      append_log(
-                        &context.app_log_path,
-                        &format!(
-                          "ffprobe_audio_short record_id={} video={:.3} audio={:.3}",
-                          record_id, video_duration, audio_duration
-                        ),
-                      );
-                      let _ = std::fs::remove_file(&output_path);
-                      last_error = Some("Audio stream too short".to_string());
-                    }
-                  }
-                  Err(err) => {
+                    let _ = std::fs::remove_file(&output_path);
+                    last_error = Some("Video stream too short".to_string());
+                  } else if is_audio_complete(video_duration, audio_duration) {
+                    let _ = std::fs::remove_file(&temp_video_path);
+                    let _ = std::fs::remove_file(&temp_audio_path);
+                    return Ok(());
+                  } else {
                     append_log(
                       &context.app_log_path,
-                      &format!("ffprobe_check_fail record_id={} err={}", record_id, err),
+                      &format!(
+                        "ffprobe_audio_short record_id={} video={:.3} audio={:.3}",
+                        record_id, video_duration, audio_duration
+                      ),
                     );
                     let _ = std::fs::remove_file(&output_path);
-                    last_error = Some(err);
-                  }
+                    last_error = Some("Audio stream too short".to_string());
                   }
                 }
                 Err(err) => {
+                  append_log(
+                    &context.app_log_path,
+                    &format!("ffprobe_check_fail record_id={} err={}", record_id, err),
+                  );
                   let _ = std::fs::remove_file(&output_path);
                   last_error = Some(err);
                 }
+                }
               }
-              let _ = std::fs::remove_file(&temp_video_path);
-              let _ = std::fs::remove_file(&temp_audio_path);
-              if last_error.is_none() {
-                return Ok(());
+              Err(err) => {
+                let _ = std::fs::remove_file(&output_path);
+                last_error = Some(err);
               }
-              aria2c_failed = true;
             }
+            let _ = std::fs::remove_file(&temp_video_path);
+            let _ = std::fs::remove_file(&temp_audio_path);
+            if last_error.is_none() {
+              return Ok(());
+            }
+            segmented_failed = true;
           }
 
-          if !aria2c_failed {
+          if !segmented_failed {
             continue;
           }
 
@@ -2610,498 +2986,73 @@ where
   Err(last_error.unwrap_or_else(|| "Missing stream url".to_string()))
 }
 
-fn build_aria2c_args(
-  output_path: &Path,
-  urls: &[String],
-  header: &str,
-  connections: i64,
-  split: i64,
-) -> Result<Vec<String>, String> {
-  let parent = output_path
-    .parent()
-    .ok_or_else(|| "Missing output directory".to_string())?;
-  let file_name = output_path
-    .file_name()
-    .ok_or_else(|| "Missing output file name".to_string())?;
-  let mut args = vec![
-    "--allow-overwrite=true".to_string(),
-    "--auto-file-renaming=false".to_string(),
-    "--continue=true".to_string(),
-    "--disable-ipv6=true".to_string(),
-    "--file-allocation=none".to_string(),
-    "--summary-interval=1".to_string(),
-    "--console-log-level=warn".to_string(),
-    format!("--max-connection-per-server={}", connections),
-    format!("--split={}", split),
-    "--min-split-size=1M".to_string(),
-    "--referer=https://www.bilibili.com/".to_string(),
-    format!("--dir={}", parent.to_string_lossy()),
-    format!("--out={}", file_name.to_string_lossy()),
-  ];
-  for line in header.split("\r\n").map(|value| value.trim()) {
-    if !line.is_empty() {
-      args.push(format!("--header={}", line));
-    }
-  }
-  args.extend(urls.iter().cloned());
-  Ok(args)
-}
-
-#[derive(Clone)]
-struct Aria2cRpcConfig {
-  endpoint: String,
-  secret: String,
-  port: u16,
-}
-
-#[derive(Deserialize)]
-struct Aria2cRpcError {
-  code: i64,
-  message: String,
-}
-
-#[derive(Deserialize)]
-struct Aria2cRpcResponse<T> {
-  result: Option<T>,
-  error: Option<Aria2cRpcError>,
-}
-
-#[derive(Deserialize)]
-struct Aria2cTaskStatus {
-  status: String,
-  #[serde(rename = "totalLength")]
-  total_length: String,
-  #[serde(rename = "completedLength")]
-  completed_length: String,
-  #[serde(rename = "errorCode")]
-  error_code: Option<String>,
-  #[serde(rename = "errorMessage")]
-  error_message: Option<String>,
-}
-
-fn build_aria2c_rpc_config() -> Result<Aria2cRpcConfig, String> {
-  let listener = TcpListener::bind("127.0.0.1:0")
-    .map_err(|err| format!("Failed to bind aria2c rpc port: {}", err))?;
-  let port = listener
-    .local_addr()
-    .map_err(|err| format!("Failed to read aria2c rpc port: {}", err))?
-    .port();
-  drop(listener);
-  let nanos = SystemTime::now()
-    .duration_since(UNIX_EPOCH)
-    .unwrap_or_default()
-    .as_nanos();
-  let secret = format!("{}-{}", std::process::id(), nanos);
-  Ok(Aria2cRpcConfig {
-    endpoint: format!("http://127.0.0.1:{}/jsonrpc", port),
-    secret,
-    port,
-  })
-}
-
-fn append_aria2c_rpc_args(args: &mut Vec<String>, rpc: &Aria2cRpcConfig) {
-  args.push("--enable-rpc".to_string());
-  args.push("--rpc-listen-all=false".to_string());
-  args.push(format!("--rpc-listen-port={}", rpc.port));
-  args.push(format!("--rpc-secret={}", rpc.secret));
-}
-
-fn aria2c_rpc_request<T: DeserializeOwned>(
-  client: &Client,
-  rpc: &Aria2cRpcConfig,
-  method: &str,
-  mut params: Vec<Value>,
-) -> Result<T, String> {
-  params.insert(0, Value::String(format!("token:{}", rpc.secret)));
-  let payload = json!({
-    "jsonrpc": "2.0",
-    "id": "1",
-    "method": format!("aria2.{}", method),
-    "params": params,
-  });
-  let response = client
-    .post(&rpc.endpoint)
-    .json(&payload)
-    .send()
-    .map_err(|err| format!("aria2c rpc {} request failed: {}", method, err))?;
-  let body: Aria2cRpcResponse<T> = response
-    .json()
-    .map_err(|err| format!("aria2c rpc {} decode failed: {}", method, err))?;
-  if let Some(err) = body.error {
-    return Err(format!(
-      "aria2c rpc {} error: {} ({})",
-      method, err.message, err.code
-    ));
-  }
-  body
-    .result
-    .ok_or_else(|| format!("aria2c rpc {} empty result", method))
-}
-
-fn aria2c_rpc_fetch_status(
-  client: &Client,
-  rpc: &Aria2cRpcConfig,
-) -> Result<Option<Aria2cTaskStatus>, String> {
-  let active: Vec<Aria2cTaskStatus> = aria2c_rpc_request(client, rpc, "tellActive", vec![])?;
-  if let Some(status) = active.into_iter().next() {
-    return Ok(Some(status));
-  }
-  let waiting: Vec<Aria2cTaskStatus> =
-    aria2c_rpc_request(client, rpc, "tellWaiting", vec![json!(0), json!(1)])?;
-  if let Some(status) = waiting.into_iter().next() {
-    return Ok(Some(status));
-  }
-  let stopped: Vec<Aria2cTaskStatus> =
-    aria2c_rpc_request(client, rpc, "tellStopped", vec![json!(0), json!(1)])?;
-  Ok(stopped.into_iter().next())
-}
-
-fn aria2c_status_bytes(status: &Aria2cTaskStatus) -> Option<(u64, u64)> {
-  let total: u64 = status.total_length.parse().ok()?;
-  let completed: u64 = status.completed_length.parse().ok()?;
-  Some((total, completed))
-}
-
-fn aria2c_status_error(status: &Aria2cTaskStatus) -> Option<String> {
-  if status.status == "error" {
-    return Some(
-      status
-        .error_message
-        .clone()
-        .unwrap_or_else(|| "aria2c failed".to_string()),
-    );
-  }
-  if let Some(code) = &status.error_code {
-    if code != "0" && code != "31" {
-      let message = status
-        .error_message
-        .clone()
-        .unwrap_or_else(|| "aria2c failed".to_string());
-      return Some(format!("aria2c failed: {}", message));
-    }
-  }
-  None
-}
-
-fn has_partial_file(path: &Path) -> bool {
-  std::fs::metadata(path)
-    .map(|meta| meta.len() > 0)
-    .unwrap_or(false)
-}
-
-fn cleanup_aria2c_files(path: &Path) {
-  let _ = std::fs::remove_file(path);
-  let control_path = PathBuf::from(format!("{}.aria2", path.to_string_lossy()));
-  let _ = std::fs::remove_file(control_path);
-}
-
 fn cleanup_download_outputs(path: &Path) {
   let _ = std::fs::remove_file(path);
   let baidu_temp = PathBuf::from(format!("{}{}", path.to_string_lossy(), BAIDU_DOWNLOAD_SUFFIX));
   let _ = std::fs::remove_file(baidu_temp);
-  cleanup_aria2c_files(path);
+  cleanup_segment_files(path);
   let temp_video = path.with_extension("video");
   let temp_audio = path.with_extension("audio");
-  cleanup_aria2c_files(&temp_video);
-  cleanup_aria2c_files(&temp_audio);
-}
-
-fn run_aria2c_with_path<F>(
-  app_log_path: &Path,
-  record_id: i64,
-  progress_key: &str,
-  path: &str,
-  args: &[String],
-  track_progress: bool,
-  on_progress: &mut F,
-  rpc: &Aria2cRpcConfig,
-) -> Result<(), String>
-where
-  F: FnMut(u64, u64),
-{
-  let mut child = Command::new(path)
-    .args(args)
-    .stdout(Stdio::piped())
-    .stderr(Stdio::piped())
-    .spawn()
-    .map_err(|err| format!("Failed to start aria2c: {}", err))?;
-
-  let stdout = child
-    .stdout
-    .take()
-    .ok_or_else(|| "Failed to capture aria2c stdout".to_string())?;
-  let mut stderr = child
-    .stderr
-    .take()
-    .ok_or_else(|| "Failed to capture aria2c stderr".to_string())?;
-
-  let (stderr_tx, stderr_rx) = std::sync::mpsc::channel();
-  thread::spawn(move || {
-    let mut buffer = String::new();
-    let _ = stderr.read_to_string(&mut buffer);
-    let _ = stderr_tx.send(buffer);
-  });
-  thread::spawn(move || {
-    let mut buffer = Vec::new();
-    let mut reader = BufReader::new(stdout);
-    let _ = reader.read_to_end(&mut buffer);
-  });
-
-  let client = Client::builder()
-    .timeout(Duration::from_secs(3))
-    .build()
-    .map_err(|err| format!("Failed to create aria2c rpc client: {}", err))?;
-
-  let mut rpc_ready = false;
-  for _ in 0..20 {
-    if aria2c_rpc_request::<Value>(&client, rpc, "getVersion", vec![]).is_ok() {
-      rpc_ready = true;
-      break;
-    }
-    thread::sleep(Duration::from_millis(100));
-  }
-  let mut logged_rpc_not_ready = false;
-  if !rpc_ready {
-    logged_rpc_not_ready = true;
-    append_log(
-      app_log_path,
-      &format!(
-        "aria2c_rpc_not_ready record_id={} key={} path={}",
-        record_id, progress_key, path
-      ),
-    );
-  }
-  let _ = logged_rpc_not_ready;
-
-  let mut last_error: Option<String> = None;
-  let mut exit_status: Option<std::process::ExitStatus> = None;
-  let mut completed = false;
-  let mut logged_no_status = false;
-  let mut logged_zero_total = false;
-  let mut logged_parse_error = false;
-  loop {
-    if rpc_ready {
-      match aria2c_rpc_fetch_status(&client, rpc) {
-        Ok(Some(status)) => {
-          if let Some(err) = aria2c_status_error(&status) {
-            last_error = Some(err);
-            break;
-          }
-          if track_progress {
-            if let Some((content, chunk)) = aria2c_status_bytes(&status) {
-              if content == 0 && !logged_zero_total {
-                logged_zero_total = true;
-                append_log(
-                  app_log_path,
-                  &format!(
-                    "aria2c_rpc_zero_total record_id={} key={} status={} total={} completed={}",
-                    record_id,
-                    progress_key,
-                    status.status,
-                    status.total_length,
-                    status.completed_length
-                  ),
-                );
-              }
-              on_progress(content, chunk);
-            } else if !logged_parse_error {
-              logged_parse_error = true;
-              append_log(
-                app_log_path,
-                &format!(
-                  "aria2c_rpc_bytes_parse_fail record_id={} key={} status={} total={} completed={}",
-                  record_id,
-                  progress_key,
-                  status.status,
-                  status.total_length,
-                  status.completed_length
-                ),
-              );
-            }
-          }
-          if status.status == "complete" {
-            if let Some((content, _chunk)) = aria2c_status_bytes(&status) {
-              on_progress(content, content);
-            }
-            completed = true;
-            break;
-          }
-        }
-        Ok(None) => {
-          if !logged_no_status {
-            logged_no_status = true;
-            append_log(
-              app_log_path,
-              &format!("aria2c_rpc_no_status record_id={} key={}", record_id, progress_key),
-            );
-          }
-        }
-        Err(_) => {}
-      }
-    } else if aria2c_rpc_request::<Value>(&client, rpc, "getVersion", vec![]).is_ok() {
-      rpc_ready = true;
-      append_log(
-        app_log_path,
-        &format!(
-          "aria2c_rpc_ready record_id={} key={} path={}",
-          record_id, progress_key, path
-        ),
-      );
-    }
-
-    match child
-      .try_wait()
-      .map_err(|err| format!("Failed to wait for aria2c: {}", err))?
-    {
-      Some(status) => {
-        exit_status = Some(status);
-        break;
-      }
-      None => {}
-    }
-    thread::sleep(Duration::from_secs(1));
-  }
-
-  if completed && rpc_ready {
-    let _ = aria2c_rpc_request::<Value>(&client, rpc, "shutdown", vec![]);
-  }
-
-  if exit_status.is_none() {
-    let _ = child.kill();
-    exit_status = Some(
-      child
-        .wait()
-        .map_err(|err| format!("Failed to wait for aria2c: {}", err))?,
-    );
-  }
-  let stderr_output = stderr_rx.recv().unwrap_or_default();
-  if let Some(err) = last_error {
-    let stderr_trimmed = stderr_output.trim();
-    if stderr_trimmed.is_empty() {
-      return Err(err);
-    }
-    return Err(format!("{}; {}", err, stderr_trimmed));
-  }
-  if completed {
-    return Ok(());
-  }
-  if let Some(status) = exit_status {
-    if status.success() {
-      return Ok(());
-    }
-  }
-
-  Err(format!("aria2c failed: {}", stderr_output.trim()))
-}
-
-fn run_aria2c_command<F>(
-  app_log_path: &Path,
-  record_id: i64,
-  progress_key: &str,
-  args: &[String],
-  track_progress: bool,
-  on_progress: &mut F,
-  rpc: &Aria2cRpcConfig,
-) -> Result<(), String>
-where
-  F: FnMut(u64, u64),
-{
-  let mut last_error = None;
-  for path in resolve_aria2c_candidates() {
-    match run_aria2c_with_path(
-      app_log_path,
-      record_id,
-      progress_key,
-      &path,
-      args,
-      track_progress,
-      on_progress,
-      rpc,
-    ) {
-      Ok(_) => return Ok(()),
-      Err(err) => {
-        last_error = Some(err);
-      }
-    }
-  }
-  Err(last_error.unwrap_or_else(|| "aria2c not available".to_string()))
-}
-
-fn is_aria2c_missing_error(message: &str) -> bool {
-  let lower = message.to_lowercase();
-  lower.contains("aria2c") && (lower.contains("no such file") || lower.contains("not found"))
+  cleanup_segment_files(&temp_video);
+  cleanup_segment_files(&temp_audio);
 }
 
 fn is_resume_error(message: &str) -> bool {
-  message.contains("可重试续传") || message.contains("aria2c下载中断")
+  message.contains("可重试续传")
 }
 
-async fn download_with_aria2c(
+async fn download_with_segments(
   context: &DownloadContext,
   record_id: i64,
   track_progress: bool,
   output_path: &Path,
   urls: &[String],
   header: &str,
-  aria2c_connections: i64,
-  aria2c_split: i64,
+  connections: i64,
+  split: i64,
   progress_key: &str,
 ) -> Result<(), String> {
-  if urls.is_empty() {
-    return Err("Missing stream url".to_string());
-  }
-  if let Some(parent) = output_path.parent() {
-    std::fs::create_dir_all(parent).map_err(|err| format!("Failed to create directory: {}", err))?;
-  }
-
-  let mut args = build_aria2c_args(output_path, urls, header, aria2c_connections, aria2c_split)?;
-  let rpc_config = build_aria2c_rpc_config()?;
-  append_aria2c_rpc_args(&mut args, &rpc_config);
   append_log(
     &context.app_log_path,
     &format!(
-      "aria2c_start record_id={} output={}",
+      "segment_download_start record_id={} key={} urls={} output={}",
       record_id,
+      progress_key,
+      urls.len(),
       output_path.to_string_lossy()
     ),
   );
-
-  let context_clone = context.clone();
-  let progress_key = progress_key.to_string();
-  let rpc_config_clone = rpc_config.clone();
-  let exec_result = tauri::async_runtime::spawn_blocking(move || {
-    let mut update = |content: u64, chunk: u64| {
-      let _ = update_download_bytes(&context_clone, record_id, &progress_key, content, chunk);
-    };
-    run_aria2c_command(
-      context_clone.app_log_path.as_ref(),
-      record_id,
-      &progress_key,
-      &args,
-      track_progress,
-      &mut update,
-      &rpc_config_clone,
-    )
+  let options = SegmentDownloadOptions {
+    connections: connections.max(1) as usize,
+    split: split.max(1) as usize,
+    header: header.to_string(),
+  };
+  let result = download_segmented(urls, output_path, &options, |content, chunk| {
+    if track_progress {
+      let _ = update_download_bytes(context, record_id, progress_key, content, chunk);
+    }
   })
-  .await
-  .map_err(|_| "Failed to execute download task".to_string())?;
+  .await;
 
-  match &exec_result {
+  match &result {
     Ok(_) => {
       append_log(
         &context.app_log_path,
-        &format!("aria2c_done record_id={} status=ok", record_id),
+        &format!("segment_download_done record_id={} key={} status=ok", record_id, progress_key),
       );
     }
     Err(err) => {
       append_log(
         &context.app_log_path,
-        &format!("aria2c_done record_id={} status=err msg={}", record_id, err),
+        &format!(
+          "segment_download_done record_id={} key={} status=err msg={}",
+          record_id, progress_key, err
+        ),
       );
     }
   }
 
-  exec_result
+  result
 }
 
 async fn fetch_play_info(
@@ -3112,7 +3063,7 @@ async fn fetch_play_info(
   config: &DownloadConfig,
 ) -> Result<Value, String> {
   let format = config.format.as_deref().unwrap_or("dash");
-  let auth = load_auth(context);
+  let auth = load_auth(context, config.account_user_id);
   let is_logged_in = auth.is_some();
   let qn = config
     .resolution
@@ -3744,6 +3695,7 @@ async fn fetch_video_title(
   context: &DownloadContext,
   bvid: Option<&str>,
   aid: Option<&str>,
+  account_user_id: Option<i64>,
 ) -> Option<String> {
   let mut params = Vec::new();
   if let Some(bvid) = bvid {
@@ -3753,7 +3705,7 @@ async fn fetch_video_title(
     params.push(("aid".to_string(), aid.to_string()));
   }
 
-  let auth = load_auth(context);
+  let auth = load_auth(context, account_user_id);
   let url = format!("{}/x/web-interface/view", context.bilibili.base_url());
   let data = context.bilibili.get_json(&url, &params, auth.as_ref(), false).await.ok()?;
   data
@@ -3804,8 +3756,8 @@ fn extract_aid(input: &str) -> Option<String> {
   None
 }
 
-fn build_ffmpeg_headers(context: &DownloadContext) -> Option<String> {
-  let auth = load_auth(context)?;
+fn build_ffmpeg_headers(context: &DownloadContext, account_user_id: Option<i64>) -> Option<String> {
+  let auth = load_auth(context, account_user_id)?;
   let mut headers = String::new();
   headers.push_str("Referer: https://www.bilibili.com\r\n");
   headers.push_str("Origin: https://www.bilibili.com\r\n");
@@ -3814,8 +3766,25 @@ fn build_ffmpeg_headers(context: &DownloadContext) -> Option<String> {
   Some(headers)
 }
 
-fn load_auth(context: &DownloadContext) -> Option<AuthInfo> {
-  context.login_store.load_auth_info(&context.db).ok().flatten()
+fn load_auth(context: &DownloadContext, account_user_id: Option<i64>) -> Option<AuthInfo> {
+  context
+    .login_store
+    .load_auth_info_or_default(&context.db, account_user_id, &context.app_log_path)
+}
+
+fn load_download_account_user_id(db: &Db, record_id: i64) -> Option<i64> {
+  db.with_conn(|conn| {
+    conn
+      .query_row(
+        "SELECT account_user_id FROM video_download WHERE id = ?1",
+        [record_id],
+        |row| row.get::<_, Option<i64>>(0),
+      )
+      .optional()
+  })
+  .ok()
+  .flatten()
+  .flatten()
 }
 
 fn update_download_status(
@@ -3834,7 +3803,52 @@ fn update_download_status(
       )?;
       Ok(())
     })
-    .map_err(|err| format!("Failed to update download status: {}", err))
+    .map_err(|err| format!("Failed to update download status: {}", err))?;
+  if status == 2 || status == 3 {
+    notify_download_finished(context, record_id, status);
+  }
+  Ok(())
+}
+
+fn notify_download_finished(context: &DownloadContext, record_id: i64, status: i64) {
+  let record = context.db.with_conn(|conn| {
+    conn
+      .query_row(
+        "SELECT title, bvid, local_path, source_type FROM video_download WHERE id = ?1",
+        [record_id],
+        |row| {
+          Ok((
+            row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, Option<String>>(3)?,
+          ))
+        },
+      )
+      .optional()
+  });
+  let (title, bvid, local_path, source_type) = match record {
+    Ok(Some(record)) => record,
+    _ => return,
+  };
+  let (event, summary) = if status == 2 {
+    (EVENT_DOWNLOAD_COMPLETED, format!("视频下载完成: {}", title))
+  } else {
+    (EVENT_DOWNLOAD_FAILED, format!("视频下载失败: {}", title))
+  };
+  emit_webhook_event(
+    &context.db,
+    &context.app_log_path,
+    event,
+    &summary,
+    json!({
+      "downloadId": record_id,
+      "title": title,
+      "bvid": bvid,
+      "localPath": local_path,
+      "sourceType": source_type,
+    }),
+  );
 }
 
 fn reset_download_record_progress(
@@ -4002,16 +4016,34 @@ fn update_submission_status(
   status: &str,
 ) -> Result<(), String> {
   let now = now_rfc3339();
-  context
+  let previous = context
     .db
     .with_conn(|conn| {
+      let previous = conn
+        .query_row(
+          "SELECT status, title FROM submission_task WHERE task_id = ?1",
+          [task_id],
+          |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)),
+        )
+        .optional()?;
       conn.execute(
         "UPDATE submission_task SET status = ?1, updated_at = ?2 WHERE task_id = ?3",
         (status, &now, task_id),
       )?;
-      Ok(())
+      Ok(previous)
     })
-    .map_err(|err| err.to_string())
+    .map_err(|err| err.to_string())?;
+  if let Some((previous_status, title)) = previous {
+    crate::commands::submission::notify_submission_status_change(
+      &context.db,
+      &context.app_log_path,
+      task_id,
+      title.as_deref().unwrap_or(""),
+      &previous_status,
+      status,
+    );
+  }
+  Ok(())
 }
 
 fn update_relation_workflow_status(
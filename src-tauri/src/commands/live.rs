@@ -1,15 +1,58 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use tauri::State;
 
 use crate::api::ApiResponse;
-use crate::commands::settings::{default_live_settings, load_live_settings_from_db};
-use crate::live_recorder::{fetch_room_info, start_recording, stop_recording, LiveContext};
+use crate::commands::settings::{
+  default_live_settings, load_live_settings_from_db, load_room_live_settings_override,
+  merge_live_settings, LiveSettings,
+};
+use crate::live_recorder::{
+  auto_record_block_reason, fetch_room_info, start_recording, stop_recording, LiveContext,
+};
+use crate::live_session::{
+  export_live_session, list_live_sessions, load_live_session, merge_live_sessions,
+  LiveSessionExportResult, LiveSessionView,
+};
+use crate::live_submission::{load_live_submission_rule, LiveSubmissionTemplate};
+use crate::record_schedule::{normalize_time_windows, normalize_weekdays, RecordSchedule};
 use crate::utils::{append_log, now_rfc3339};
 use crate::AppState;
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveRoomScheduleRequest {
+  pub record_weekdays: Option<String>,
+  pub record_time_windows: Option<String>,
+  pub max_record_minutes: Option<i64>,
+  pub title_regex: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveRoomSettingsView {
+  pub overrides: Map<String, Value>,
+  pub effective: LiveSettings,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveRoomAutoSubmitConfig {
+  pub enabled: bool,
+  pub template: Option<LiveSubmissionTemplate>,
+}
+
 #[tauri::command]
 pub async fn live_record_start(
   state: State<'_, AppState>,
   room_id: String,
+) -> Result<ApiResponse<String>, String> {
+  live_record_start_inner(&state, room_id).await
+}
+
+pub async fn live_record_start_inner(
+  state: &AppState,
+  room_id: String,
 ) -> Result<ApiResponse<String>, String> {
   let settings = load_live_settings_from_db(&state.db).unwrap_or_else(|_| default_live_settings());
   let room_info = fetch_room_info(&state.bilibili, &room_id).await?;
@@ -19,24 +62,27 @@ pub async fn live_record_start(
     login_store: state.login_store.clone(),
     app_log_path: state.app_log_path.clone(),
     live_runtime: state.live_runtime.clone(),
+    edit_upload_state: state.edit_upload_state.clone(),
   };
-  match start_recording(context, &room_id, room_info, settings) {
+  match start_recording(context, &room_id, room_info, settings, true) {
     Ok(()) => Ok(ApiResponse::success("录制已启动".to_string())),
     Err(err) => Ok(ApiResponse::error(err)),
   }
 }
 
 #[tauri::command]
-pub fn live_record_stop(
-  state: State<'_, AppState>,
-  room_id: String,
-) -> ApiResponse<String> {
+pub fn live_record_stop(state: State<'_, AppState>, room_id: String) -> ApiResponse<String> {
+  live_record_stop_inner(&state, room_id)
+}
+
+pub fn live_record_stop_inner(state: &AppState, room_id: String) -> ApiResponse<String> {
   let context = LiveContext {
     db: state.db.clone(),
     bilibili: state.bilibili.clone(),
     login_store: state.login_store.clone(),
     app_log_path: state.app_log_path.clone(),
     live_runtime: state.live_runtime.clone(),
+    edit_upload_state: state.edit_upload_state.clone(),
   };
   stop_recording(context, &room_id, "手动停止");
   ApiResponse::success("录制已停止".to_string())
@@ -47,6 +93,14 @@ pub async fn live_room_auto_record_update(
   state: State<'_, AppState>,
   room_id: String,
   auto_record: bool,
+) -> Result<ApiResponse<String>, String> {
+  live_room_auto_record_update_inner(&state, room_id, auto_record).await
+}
+
+pub async fn live_room_auto_record_update_inner(
+  state: &AppState,
+  room_id: String,
+  auto_record: bool,
 ) -> Result<ApiResponse<String>, String> {
   let now = now_rfc3339();
   let result = state.db.with_conn(|conn| {
@@ -71,8 +125,16 @@ pub async fn live_room_auto_record_update(
         login_store: state.login_store.clone(),
         app_log_path: state.app_log_path.clone(),
         live_runtime: state.live_runtime.clone(),
+        edit_upload_state: state.edit_upload_state.clone(),
       };
-      match start_recording(context, &room_id, room_info, settings) {
+      if let Some(reason) = auto_record_block_reason(&context, &room_id, &room_info) {
+        append_log(
+          &state.app_log_path,
+          &format!("auto_record_toggle_skip room={} reason={}", room_id, reason),
+        );
+        return Ok(ApiResponse::success("已更新".to_string()));
+      }
+      match start_recording(context, &room_id, room_info, settings, false) {
         Ok(()) => {
           append_log(&state.app_log_path, &format!("auto_record_toggle_start room={}", room_id));
         }
@@ -94,6 +156,14 @@ pub fn live_room_baidu_sync_update(
   state: State<'_, AppState>,
   room_id: String,
   baidu_sync_path: String,
+) -> ApiResponse<String> {
+  live_room_baidu_sync_update_inner(&state, room_id, baidu_sync_path)
+}
+
+pub fn live_room_baidu_sync_update_inner(
+  state: &AppState,
+  room_id: String,
+  baidu_sync_path: String,
 ) -> ApiResponse<String> {
   let now = now_rfc3339();
   let trimmed = baidu_sync_path.trim().to_string();
@@ -122,6 +192,14 @@ pub fn live_room_baidu_sync_toggle(
   state: State<'_, AppState>,
   room_id: String,
   enabled: bool,
+) -> ApiResponse<String> {
+  live_room_baidu_sync_toggle_inner(&state, room_id, enabled)
+}
+
+pub fn live_room_baidu_sync_toggle_inner(
+  state: &AppState,
+  room_id: String,
+  enabled: bool,
 ) -> ApiResponse<String> {
   let now = now_rfc3339();
   let path = state.db.with_conn(|conn| {
@@ -160,3 +238,349 @@ pub fn live_room_baidu_sync_toggle(
     Err(err) => ApiResponse::error(format!("Failed to update sync toggle: {}", err)),
   }
 }
+
+#[tauri::command]
+pub fn live_room_account_update(
+  state: State<'_, AppState>,
+  room_id: String,
+  account_user_id: Option<i64>,
+) -> ApiResponse<String> {
+  live_room_account_update_inner(&state, room_id, account_user_id)
+}
+
+pub fn live_room_account_update_inner(
+  state: &AppState,
+  room_id: String,
+  account_user_id: Option<i64>,
+) -> ApiResponse<String> {
+  let now = now_rfc3339();
+  let result = state.db.with_conn(|conn| {
+    conn.execute(
+      "INSERT INTO live_room_settings (room_id, auto_record, account_user_id, update_time) \
+       VALUES (?1, 1, ?2, ?3) \
+       ON CONFLICT(room_id) DO UPDATE SET \
+         account_user_id = excluded.account_user_id, \
+         update_time = excluded.update_time",
+      (room_id.as_str(), account_user_id, &now),
+    )?;
+    Ok(())
+  });
+  match result {
+    Ok(()) => ApiResponse::success("已更新".to_string()),
+    Err(err) => ApiResponse::error(format!("Failed to update room account: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn live_room_schedule_update(
+  state: State<'_, AppState>,
+  room_id: String,
+  payload: LiveRoomScheduleRequest,
+) -> ApiResponse<String> {
+  live_room_schedule_update_inner(&state, room_id, payload)
+}
+
+pub fn live_room_schedule_update_inner(
+  state: &AppState,
+  room_id: String,
+  payload: LiveRoomScheduleRequest,
+) -> ApiResponse<String> {
+  let weekdays = match normalize_weekdays(payload.record_weekdays.as_deref().unwrap_or_default()) {
+    Ok(value) => value,
+    Err(err) => return ApiResponse::error(err),
+  };
+  let windows =
+    match normalize_time_windows(payload.record_time_windows.as_deref().unwrap_or_default()) {
+      Ok(value) => value,
+      Err(err) => return ApiResponse::error(err),
+    };
+  let max_minutes = payload.max_record_minutes.filter(|value| *value > 0);
+  let title_regex = payload
+    .title_regex
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty());
+  if let Err(err) = RecordSchedule::parse(None, None, None, title_regex.as_deref()) {
+    return ApiResponse::error(err);
+  }
+  let now = now_rfc3339();
+  let result = state.db.with_conn(|conn| {
+    conn.execute(
+      "INSERT INTO live_room_settings (room_id, auto_record, record_weekdays, record_time_windows, max_record_minutes, title_regex, update_time) \
+       VALUES (?1, 1, ?2, ?3, ?4, ?5, ?6) \
+       ON CONFLICT(room_id) DO UPDATE SET \
+         record_weekdays = excluded.record_weekdays, \
+         record_time_windows = excluded.record_time_windows, \
+         max_record_minutes = excluded.max_record_minutes, \
+         title_regex = excluded.title_regex, \
+         update_time = excluded.update_time",
+      (
+        room_id.as_str(),
+        weekdays.as_deref(),
+        windows.as_deref(),
+        max_minutes,
+        title_regex.as_deref(),
+        &now,
+      ),
+    )?;
+    Ok(())
+  });
+  match result {
+    Ok(()) => ApiResponse::success("已更新".to_string()),
+    Err(err) => ApiResponse::error(format!("Failed to update record schedule: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn live_room_settings_get(
+  state: State<'_, AppState>,
+  room_id: String,
+) -> ApiResponse<LiveRoomSettingsView> {
+  live_room_settings_get_inner(&state, room_id)
+}
+
+pub fn live_room_settings_get_inner(
+  state: &AppState,
+  room_id: String,
+) -> ApiResponse<LiveRoomSettingsView> {
+  let base = load_live_settings_from_db(&state.db).unwrap_or_else(|_| default_live_settings());
+  let overrides = match load_room_live_settings_override(&state.db, &room_id) {
+    Ok(value) => value,
+    Err(err) => return ApiResponse::error(err),
+  };
+  match merge_live_settings(&base, &overrides) {
+    Ok(effective) => ApiResponse::success(LiveRoomSettingsView {
+      overrides,
+      effective,
+    }),
+    Err(err) => ApiResponse::error(err),
+  }
+}
+
+#[tauri::command]
+pub fn live_room_settings_update(
+  state: State<'_, AppState>,
+  room_id: String,
+  overrides: Value,
+) -> ApiResponse<LiveRoomSettingsView> {
+  live_room_settings_update_inner(&state, room_id, overrides)
+}
+
+pub fn live_room_settings_update_inner(
+  state: &AppState,
+  room_id: String,
+  overrides: Value,
+) -> ApiResponse<LiveRoomSettingsView> {
+  let overrides = match overrides {
+    Value::Object(map) => map,
+    Value::Null => Map::new(),
+    _ => return ApiResponse::error("直播间设置覆盖必须为对象".to_string()),
+  };
+  let base = load_live_settings_from_db(&state.db).unwrap_or_else(|_| default_live_settings());
+  let effective = match merge_live_settings(&base, &overrides) {
+    Ok(value) => value,
+    Err(err) => return ApiResponse::error(err),
+  };
+  let stored = if overrides.is_empty() {
+    None
+  } else {
+    Some(Value::Object(overrides.clone()).to_string())
+  };
+  let now = now_rfc3339();
+  let result = state.db.with_conn(|conn| {
+    conn.execute(
+      "INSERT INTO live_room_settings (room_id, auto_record, settings_override, update_time) \
+       VALUES (?1, 1, ?2, ?3) \
+       ON CONFLICT(room_id) DO UPDATE SET \
+         settings_override = excluded.settings_override, \
+         update_time = excluded.update_time",
+      (room_id.as_str(), stored.as_deref(), &now),
+    )?;
+    Ok(())
+  });
+  match result {
+    Ok(()) => ApiResponse::success(LiveRoomSettingsView {
+      overrides,
+      effective,
+    }),
+    Err(err) => ApiResponse::error(format!("Failed to update room settings: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn live_room_auto_submit_get(
+  state: State<'_, AppState>,
+  room_id: String,
+) -> ApiResponse<LiveRoomAutoSubmitConfig> {
+  live_room_auto_submit_get_inner(&state, room_id)
+}
+
+pub fn live_room_auto_submit_get_inner(
+  state: &AppState,
+  room_id: String,
+) -> ApiResponse<LiveRoomAutoSubmitConfig> {
+  match load_live_submission_rule(&state.db, &room_id) {
+    Ok(rule) => ApiResponse::success(LiveRoomAutoSubmitConfig {
+      enabled: rule.enabled,
+      template: rule.template,
+    }),
+    Err(err) => ApiResponse::error(err),
+  }
+}
+
+#[tauri::command]
+pub fn live_room_auto_submit_update(
+  state: State<'_, AppState>,
+  room_id: String,
+  payload: LiveRoomAutoSubmitConfig,
+) -> ApiResponse<String> {
+  live_room_auto_submit_update_inner(&state, room_id, payload)
+}
+
+pub fn live_room_auto_submit_update_inner(
+  state: &AppState,
+  room_id: String,
+  payload: LiveRoomAutoSubmitConfig,
+) -> ApiResponse<String> {
+  match payload.template.as_ref() {
+    Some(template) => {
+      if let Err(err) = template.validate() {
+        return ApiResponse::error(err);
+      }
+    }
+    None if payload.enabled => return ApiResponse::error("请先配置投稿模板".to_string()),
+    None => {}
+  }
+  let template_json = match payload.template.as_ref().map(serde_json::to_string).transpose() {
+    Ok(value) => value,
+    Err(err) => return ApiResponse::error(err.to_string()),
+  };
+  let now = now_rfc3339();
+  let result = state.db.with_conn(|conn| {
+    conn.execute(
+      "INSERT INTO live_room_settings (room_id, auto_record, auto_submit_enabled, auto_submit_template, update_time) \
+       VALUES (?1, 1, ?2, ?3, ?4) \
+       ON CONFLICT(room_id) DO UPDATE SET \
+         auto_submit_enabled = excluded.auto_submit_enabled, \
+         auto_submit_template = excluded.auto_submit_template, \
+         update_time = excluded.update_time",
+      (
+        room_id.as_str(),
+        if payload.enabled { 1 } else { 0 },
+        template_json.as_deref(),
+        &now,
+      ),
+    )?;
+    Ok(())
+  });
+  match result {
+    Ok(()) => ApiResponse::success("已更新".to_string()),
+    Err(err) => ApiResponse::error(format!("Failed to update auto submit: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn live_session_list(
+  state: State<'_, AppState>,
+  room_id: Option<String>,
+  limit: Option<i64>,
+) -> ApiResponse<Vec<LiveSessionView>> {
+  live_session_list_inner(&state, room_id, limit)
+}
+
+pub fn live_session_list_inner(
+  state: &AppState,
+  room_id: Option<String>,
+  limit: Option<i64>,
+) -> ApiResponse<Vec<LiveSessionView>> {
+  let room_id = room_id
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty());
+  match list_live_sessions(&state.db, room_id.as_deref(), limit.unwrap_or(50)) {
+    Ok(sessions) => ApiResponse::success(sessions),
+    Err(err) => ApiResponse::error(format!("Failed to load live sessions: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn live_session_merge(
+  state: State<'_, AppState>,
+  session_ids: Vec<i64>,
+) -> ApiResponse<LiveSessionView> {
+  live_session_merge_inner(&state, session_ids)
+}
+
+pub fn live_session_merge_inner(
+  state: &AppState,
+  session_ids: Vec<i64>,
+) -> ApiResponse<LiveSessionView> {
+  match merge_live_sessions(&state.db, &session_ids) {
+    Ok(session) => {
+      append_log(
+        &state.app_log_path,
+        &format!(
+          "live_session_merge target={} sources={:?}",
+          session.id, session_ids
+        ),
+      );
+      ApiResponse::success(session)
+    }
+    Err(err) => ApiResponse::error(err),
+  }
+}
+
+#[tauri::command]
+pub async fn live_session_export(
+  state: State<'_, AppState>,
+  session_id: i64,
+  target_dir: String,
+) -> Result<ApiResponse<LiveSessionExportResult>, String> {
+  live_session_export_inner(&state, session_id, target_dir).await
+}
+
+pub async fn live_session_export_inner(
+  state: &AppState,
+  session_id: i64,
+  target_dir: String,
+) -> Result<ApiResponse<LiveSessionExportResult>, String> {
+  let target_dir = target_dir.trim().to_string();
+  if target_dir.is_empty() {
+    return Ok(ApiResponse::error("请选择导出目录"));
+  }
+  let session = match load_live_session(&state.db, session_id) {
+    Ok(session) => session,
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+  if session.status == "RECORDING" {
+    return Ok(ApiResponse::error("录制中的场次不能导出"));
+  }
+  append_log(
+    &state.app_log_path,
+    &format!("live_session_export_start session_id={} target={}", session_id, target_dir),
+  );
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    export_live_session(&session, std::path::Path::new(&target_dir))
+  })
+  .await
+  .map_err(|_| "场次导出执行失败".to_string())?;
+  match result {
+    Ok(result) => {
+      append_log(
+        &state.app_log_path,
+        &format!(
+          "live_session_export_done session_id={} output={} files={}",
+          session_id,
+          result.output_dir,
+          result.files.len()
+        ),
+      );
+      Ok(ApiResponse::success(result))
+    }
+    Err(err) => {
+      append_log(
+        &state.app_log_path,
+        &format!("live_session_export_fail session_id={} err={}", session_id, err),
+      );
+      Ok(ApiResponse::error(err))
+    }
+  }
+}
@@ -4,8 +4,10 @@ pub mod settings;
 pub mod anchor;
 pub mod video;
 pub mod download;
+pub mod subscription;
 pub mod process;
 pub mod submission;
 pub mod live;
 pub mod toolbox;
 pub mod baidu_sync;
+pub mod webhook;
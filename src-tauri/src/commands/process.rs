@@ -5,6 +5,7 @@ use serde_json::json;
 use tauri::State;
 
 use crate::api::ApiResponse;
+use crate::commands::settings::{default_encoder_settings, load_encoder_settings_from_db};
 use crate::config::{default_download_dir, default_temp_dir};
 use crate::processing::{clip_sources, decide_clip_copy, merge_files, ClipSource};
 use crate::utils::{now_rfc3339, sanitize_filename};
@@ -17,7 +18,7 @@ struct ProcessContext {
 }
 
 impl ProcessContext {
-  fn new(state: &State<'_, AppState>) -> Self {
+  fn new(state: &AppState) -> Self {
     Self {
       db: state.db.clone(),
     }
@@ -39,6 +40,7 @@ pub struct ProcessClipRequest {
 pub struct ProcessRequest {
   pub task_name: String,
   pub clips: Vec<ProcessClipRequest>,
+  pub burn_danmaku: Option<bool>,
 }
 
 #[derive(Serialize)]
@@ -60,6 +62,13 @@ pub struct VideoProcessTask {
 pub async fn process_create(
   state: State<'_, AppState>,
   request: ProcessRequest,
+) -> Result<ApiResponse<i64>, String> {
+  process_create_inner(&state, request).await
+}
+
+pub async fn process_create_inner(
+  state: &AppState,
+  request: ProcessRequest,
 ) -> Result<ApiResponse<i64>, String> {
   if request.task_name.trim().is_empty() {
     return Ok(ApiResponse::error("Task name is required"));
@@ -88,7 +97,7 @@ pub async fn process_create(
     }
   };
 
-  let context = ProcessContext::new(&state);
+  let context = ProcessContext::new(state);
   tauri::async_runtime::spawn(async move {
     let _ = run_process_task(context, task_id, request).await;
   });
@@ -98,6 +107,10 @@ pub async fn process_create(
 
 #[tauri::command]
 pub fn process_status(state: State<'_, AppState>, task_id: i64) -> ApiResponse<VideoProcessTask> {
+  process_status_inner(&state, task_id)
+}
+
+pub fn process_status_inner(state: &AppState, task_id: i64) -> ApiResponse<VideoProcessTask> {
   match state.db.with_conn(|conn| {
     conn.query_row(
       "SELECT id, task_name, status, progress, input_files, output_path, upload_status, bilibili_url, create_time, update_time \
@@ -149,8 +162,11 @@ async fn run_process_task(
     reason: None,
   });
   let use_copy = copy_decision.use_copy;
+  let burn_danmaku = request.burn_danmaku.unwrap_or(false);
+  let encoder_settings =
+    load_encoder_settings_from_db(&context.db).unwrap_or_else(|_| default_encoder_settings());
   let clip_outputs = tauri::async_runtime::spawn_blocking(move || {
-    clip_sources(&sources, &temp_dir, use_copy)
+    clip_sources(&sources, &temp_dir, use_copy, burn_danmaku, &encoder_settings)
   })
   .await
   .map_err(|_| "Failed to clip videos".to_string())??;
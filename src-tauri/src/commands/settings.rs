@@ -1,11 +1,14 @@
 use chrono::Utc;
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use tauri::State;
 
 use crate::api::ApiResponse;
 use crate::config::default_download_dir;
 use crate::db::Db;
+use crate::encoder::{detect_available_encoders, resolve_video_encoder, SUPPORTED_VIDEO_ENCODERS};
+use crate::storage::{run_retention, RetentionSummary};
 use crate::AppState;
 
 pub const DEFAULT_THREADS: i64 = 3;
@@ -15,11 +18,25 @@ pub const MAX_UPLOAD_CONCURRENCY: i64 = 5;
 pub const DEFAULT_SUBMISSION_REMOTE_REFRESH_MINUTES: i64 = 10;
 pub const DEFAULT_BLOCK_PCDN: bool = true;
 #[allow(dead_code)]
-pub const DEFAULT_ENABLE_ARIA2C: bool = true;
-pub const DEFAULT_ARIA2C_CONNECTIONS: i64 = 4;
-pub const DEFAULT_ARIA2C_SPLIT: i64 = 4;
+pub const DEFAULT_DOWNLOAD_CONNECTIONS: i64 = 4;
+pub const DEFAULT_DOWNLOAD_SPLIT: i64 = 4;
 pub const DEFAULT_BAIDU_MAX_PARALLEL: i64 = 3;
+pub const DEFAULT_BANDWIDTH_DOWNLOAD_PRIORITY: i64 = 5;
+pub const DEFAULT_BANDWIDTH_UPLOAD_PRIORITY: i64 = 3;
+pub const DEFAULT_BANDWIDTH_SYNC_PRIORITY: i64 = 2;
+pub const MAX_BANDWIDTH_PRIORITY: i64 = 10;
 pub const LOG_DIR_SETTING_KEY: &str = "log_dir";
+pub const DEFAULT_VIDEO_ENCODER: &str = "h264_videotoolbox";
+pub const DEFAULT_VIDEO_BITRATE_KBPS: i64 = 5000;
+pub const DEFAULT_VIDEO_CRF: i64 = 23;
+pub const DEFAULT_OUTPUT_FPS: i64 = 60;
+pub const DEFAULT_AUDIO_BITRATE_KBPS: i64 = 128;
+pub const DEFAULT_HTTP_API_BIND_ADDRESS: &str = "127.0.0.1";
+pub const DEFAULT_HTTP_API_PORT: i64 = 17890;
+pub const DEFAULT_MIN_FREE_SPACE_MB: i64 = 5120;
+pub const DEFAULT_STREAM_PROTOCOL_ORDER: &str = "http_stream,http_hls";
+pub const DEFAULT_STREAM_FORMAT_ORDER: &str = "flv,ts,fmp4";
+pub const DEFAULT_STREAM_CODEC_ORDER: &str = "avc,hevc";
 pub const LEGACY_LIVE_FILE_TEMPLATE: &str =
   "live/{{ roomId }}/录制-{{ roomId }}-{{ now }}-{{ title }}.flv";
 pub const LEGACY_LIVE_FILE_TEMPLATE_DATE: &str =
@@ -37,10 +54,73 @@ pub struct DownloadSettings {
   pub upload_concurrency: i64,
   pub submission_remote_refresh_minutes: i64,
   pub block_pcdn: bool,
-  pub enable_aria2c: bool,
-  pub aria2c_connections: i64,
-  pub aria2c_split: i64,
+  pub download_connections: i64,
+  pub download_split: i64,
   pub baidu_max_parallel: i64,
+  pub download_speed_limit_kbps: i64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncoderSettings {
+  pub video_encoder: String,
+  pub rate_control: String,
+  pub crf: i64,
+  pub video_bitrate_kbps: i64,
+  pub preset: String,
+  pub fps_mode: String,
+  pub fps: i64,
+  pub audio_bitrate_kbps: i64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncoderCapabilities {
+  pub available_encoders: Vec<String>,
+  pub resolved_encoder: String,
+  pub fallback: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageSettings {
+  pub min_free_space_mb: i64,
+  pub retention_enabled: bool,
+  pub retention_baidu_sync_days: i64,
+  pub retention_submission_days: i64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BandwidthSettings {
+  pub total_limit_kbps: i64,
+  pub download_limit_kbps: i64,
+  pub upload_limit_kbps: i64,
+  pub sync_limit_kbps: i64,
+  pub download_priority: i64,
+  pub upload_priority: i64,
+  pub sync_priority: i64,
+  #[serde(default)]
+  pub time_rules: Vec<BandwidthTimeRule>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BandwidthTimeRule {
+  pub time_window: String,
+  pub total_limit_kbps: i64,
+  pub download_limit_kbps: i64,
+  pub upload_limit_kbps: i64,
+  pub sync_limit_kbps: i64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpApiSettings {
+  pub enabled: bool,
+  pub bind_address: String,
+  pub port: i64,
+  pub token: String,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -73,10 +153,19 @@ pub struct LiveSettings {
   pub flv_fix_disable_on_annexb: bool,
   pub baidu_sync_enabled: bool,
   pub baidu_sync_path: String,
+  pub stream_protocol_order: String,
+  pub stream_format_order: String,
+  pub stream_codec_order: String,
+  pub stream_cdn_order: String,
+  pub stream_cdn_failover: bool,
 }
 
 #[tauri::command]
 pub fn get_download_settings(state: State<'_, AppState>) -> ApiResponse<DownloadSettings> {
+  get_download_settings_inner(&state)
+}
+
+pub fn get_download_settings_inner(state: &AppState) -> ApiResponse<DownloadSettings> {
   match load_download_settings_from_db(&state.db) {
     Ok(settings) => ApiResponse::success(settings),
     Err(err) => ApiResponse::error(format!("Failed to load download settings: {}", err)),
@@ -85,12 +174,269 @@ pub fn get_download_settings(state: State<'_, AppState>) -> ApiResponse<Download
 
 #[tauri::command]
 pub fn get_live_settings(state: State<'_, AppState>) -> ApiResponse<LiveSettings> {
+  get_live_settings_inner(&state)
+}
+
+pub fn get_live_settings_inner(state: &AppState) -> ApiResponse<LiveSettings> {
   match load_live_settings_from_db(&state.db) {
     Ok(settings) => ApiResponse::success(settings),
     Err(err) => ApiResponse::error(format!("Failed to load live settings: {}", err)),
   }
 }
 
+#[tauri::command]
+pub fn get_encoder_settings(state: State<'_, AppState>) -> ApiResponse<EncoderSettings> {
+  get_encoder_settings_inner(&state)
+}
+
+pub fn get_encoder_settings_inner(state: &AppState) -> ApiResponse<EncoderSettings> {
+  match load_encoder_settings_from_db(&state.db) {
+    Ok(settings) => ApiResponse::success(settings),
+    Err(err) => ApiResponse::error(format!("Failed to load encoder settings: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn update_encoder_settings(
+  state: State<'_, AppState>,
+  payload: EncoderSettings,
+) -> ApiResponse<EncoderSettings> {
+  update_encoder_settings_inner(&state, payload)
+}
+
+pub fn update_encoder_settings_inner(
+  state: &AppState,
+  payload: EncoderSettings,
+) -> ApiResponse<EncoderSettings> {
+  let settings = normalize_encoder_settings(payload);
+  if !SUPPORTED_VIDEO_ENCODERS.contains(&settings.video_encoder.as_str()) {
+    return ApiResponse::error(format!("不支持的编码器: {}", settings.video_encoder));
+  }
+  let now = Utc::now().to_rfc3339();
+  let entries = [
+    ("encoder_video_encoder", settings.video_encoder.clone()),
+    ("encoder_rate_control", settings.rate_control.clone()),
+    ("encoder_crf", settings.crf.to_string()),
+    ("encoder_video_bitrate_kbps", settings.video_bitrate_kbps.to_string()),
+    ("encoder_preset", settings.preset.clone()),
+    ("encoder_fps_mode", settings.fps_mode.clone()),
+    ("encoder_fps", settings.fps.to_string()),
+    ("encoder_audio_bitrate_kbps", settings.audio_bitrate_kbps.to_string()),
+  ];
+  let result = state.db.with_conn(|conn| {
+    for (key, value) in &entries {
+      conn.execute(
+        "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        (key, value, &now),
+      )?;
+    }
+    Ok(())
+  });
+
+  if let Err(err) = result {
+    return ApiResponse::error(format!("Failed to update encoder settings: {}", err));
+  }
+
+  ApiResponse::success(settings)
+}
+
+#[tauri::command]
+pub fn get_storage_settings(state: State<'_, AppState>) -> ApiResponse<StorageSettings> {
+  get_storage_settings_inner(&state)
+}
+
+pub fn get_storage_settings_inner(state: &AppState) -> ApiResponse<StorageSettings> {
+  match load_storage_settings_from_db(&state.db) {
+    Ok(settings) => ApiResponse::success(settings),
+    Err(err) => ApiResponse::error(format!("Failed to load storage settings: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn update_storage_settings(
+  state: State<'_, AppState>,
+  payload: StorageSettings,
+) -> ApiResponse<StorageSettings> {
+  update_storage_settings_inner(&state, payload)
+}
+
+pub fn update_storage_settings_inner(
+  state: &AppState,
+  payload: StorageSettings,
+) -> ApiResponse<StorageSettings> {
+  let settings = normalize_storage_settings(payload);
+  let now = Utc::now().to_rfc3339();
+  let entries = [
+    ("storage_min_free_space_mb", settings.min_free_space_mb.to_string()),
+    ("storage_retention_enabled", settings.retention_enabled.to_string()),
+    ("storage_retention_baidu_sync_days", settings.retention_baidu_sync_days.to_string()),
+    ("storage_retention_submission_days", settings.retention_submission_days.to_string()),
+  ];
+  let result = state.db.with_conn(|conn| {
+    for (key, value) in &entries {
+      conn.execute(
+        "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        (key, value, &now),
+      )?;
+    }
+    Ok(())
+  });
+
+  if let Err(err) = result {
+    return ApiResponse::error(format!("Failed to update storage settings: {}", err));
+  }
+
+  ApiResponse::success(settings)
+}
+
+#[tauri::command]
+pub fn get_bandwidth_settings(state: State<'_, AppState>) -> ApiResponse<BandwidthSettings> {
+  get_bandwidth_settings_inner(&state)
+}
+
+pub fn get_bandwidth_settings_inner(state: &AppState) -> ApiResponse<BandwidthSettings> {
+  match load_bandwidth_settings_from_db(&state.db) {
+    Ok(settings) => ApiResponse::success(settings),
+    Err(err) => ApiResponse::error(format!("Failed to load bandwidth settings: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn update_bandwidth_settings(
+  state: State<'_, AppState>,
+  payload: BandwidthSettings,
+) -> ApiResponse<BandwidthSettings> {
+  update_bandwidth_settings_inner(&state, payload)
+}
+
+pub fn update_bandwidth_settings_inner(
+  state: &AppState,
+  payload: BandwidthSettings,
+) -> ApiResponse<BandwidthSettings> {
+  let settings = match normalize_bandwidth_settings(payload) {
+    Ok(value) => value,
+    Err(err) => return ApiResponse::error(err),
+  };
+  let time_rules = match serde_json::to_string(&settings.time_rules) {
+    Ok(value) => value,
+    Err(err) => return ApiResponse::error(format!("带宽时段规则序列化失败: {}", err)),
+  };
+  let now = Utc::now().to_rfc3339();
+  let entries = [
+    ("bandwidth_total_limit_kbps", settings.total_limit_kbps.to_string()),
+    ("download_speed_limit_kbps", settings.download_limit_kbps.to_string()),
+    ("bandwidth_upload_limit_kbps", settings.upload_limit_kbps.to_string()),
+    ("bandwidth_sync_limit_kbps", settings.sync_limit_kbps.to_string()),
+    ("bandwidth_download_priority", settings.download_priority.to_string()),
+    ("bandwidth_upload_priority", settings.upload_priority.to_string()),
+    ("bandwidth_sync_priority", settings.sync_priority.to_string()),
+    ("bandwidth_time_rules", time_rules),
+  ];
+  let result = state.db.with_conn(|conn| {
+    for (key, value) in &entries {
+      conn.execute(
+        "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        (key, value, &now),
+      )?;
+    }
+    Ok(())
+  });
+
+  if let Err(err) = result {
+    return ApiResponse::error(format!("Failed to update bandwidth settings: {}", err));
+  }
+  crate::bandwidth::configure(&settings);
+
+  ApiResponse::success(settings)
+}
+
+#[tauri::command]
+pub async fn storage_retention_run(
+  state: State<'_, AppState>,
+) -> Result<ApiResponse<RetentionSummary>, String> {
+  storage_retention_run_inner(state.inner()).await
+}
+
+pub async fn storage_retention_run_inner(
+  state: &AppState,
+) -> Result<ApiResponse<RetentionSummary>, String> {
+  let db = state.db.clone();
+  let log_path = state.app_log_path.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || run_retention(db.as_ref(), &log_path))
+    .await
+    .map_err(|_| "清理任务执行失败".to_string())?;
+  match result {
+    Ok(summary) => Ok(ApiResponse::success(summary)),
+    Err(err) => Ok(ApiResponse::error(err)),
+  }
+}
+
+#[tauri::command]
+pub async fn get_encoder_capabilities(
+  state: State<'_, AppState>,
+) -> Result<ApiResponse<EncoderCapabilities>, String> {
+  let settings = load_encoder_settings_from_db(&state.db).unwrap_or_else(|_| default_encoder_settings());
+  let capabilities = tauri::async_runtime::spawn_blocking(move || {
+    let resolved = resolve_video_encoder(&settings.video_encoder);
+    EncoderCapabilities {
+      available_encoders: detect_available_encoders(),
+      resolved_encoder: resolved.name,
+      fallback: resolved.fallback,
+    }
+  })
+  .await
+  .map_err(|_| "编码器检测执行失败".to_string())?;
+  Ok(ApiResponse::success(capabilities))
+}
+
+#[tauri::command]
+pub fn get_http_api_settings(state: State<'_, AppState>) -> ApiResponse<HttpApiSettings> {
+  match load_http_api_settings_from_db(&state.db) {
+    Ok(settings) => ApiResponse::success(settings),
+    Err(err) => ApiResponse::error(format!("Failed to load http api settings: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn update_http_api_settings(
+  state: State<'_, AppState>,
+  payload: HttpApiSettings,
+) -> ApiResponse<HttpApiSettings> {
+  let settings = normalize_http_api_settings(payload);
+  if settings.bind_address.parse::<std::net::IpAddr>().is_err() {
+    return ApiResponse::error(format!("监听地址无效: {}", settings.bind_address));
+  }
+  let now = Utc::now().to_rfc3339();
+  let entries = [
+    ("http_api_enabled", settings.enabled.to_string()),
+    ("http_api_bind_address", settings.bind_address.clone()),
+    ("http_api_port", settings.port.to_string()),
+    ("http_api_token", settings.token.clone()),
+  ];
+  let result = state.db.with_conn(|conn| {
+    for (key, value) in &entries {
+      conn.execute(
+        "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        (key, value, &now),
+      )?;
+    }
+    Ok(())
+  });
+
+  if let Err(err) = result {
+    return ApiResponse::error(format!("Failed to update http api settings: {}", err));
+  }
+  if let Err(err) = crate::http_api::restart_http_api(&state, &settings) {
+    return ApiResponse::error(format!("HTTP 接口启动失败: {}", err));
+  }
+
+  ApiResponse::success(settings)
+}
+
 #[tauri::command]
 pub fn update_download_settings(
   state: State<'_, AppState>,
@@ -101,16 +447,16 @@ pub fn update_download_settings(
   upload_concurrency: i64,
   submission_remote_refresh_minutes: i64,
   block_pcdn: bool,
-  aria2c_connections: i64,
-  aria2c_split: i64,
+  download_connections: i64,
+  download_split: i64,
   baidu_max_parallel: i64,
-  _enable_aria2c: bool,
+  download_speed_limit_kbps: Option<i64>,
 ) -> ApiResponse<DownloadSettings> {
   if threads <= 0
     || queue_size <= 0
     || submission_remote_refresh_minutes <= 0
-    || aria2c_connections <= 0
-    || aria2c_split <= 0
+    || download_connections <= 0
+    || download_split <= 0
     || baidu_max_parallel <= 0
   {
     return ApiResponse::error("Values must be greater than 0");
@@ -132,12 +478,12 @@ pub fn update_download_settings(
   } else {
     log_dir.trim().to_string()
   };
-  let normalized_aria2c_connections = aria2c_connections.clamp(1, 32);
-  let normalized_aria2c_split = aria2c_split.clamp(1, 32);
+  let normalized_download_connections = download_connections.clamp(1, 32);
+  let normalized_download_split = download_split.clamp(1, 32);
   let normalized_baidu_max_parallel = baidu_max_parallel.clamp(1, 100);
+  let normalized_speed_limit = download_speed_limit_kbps.unwrap_or(0).max(0);
 
   let now = Utc::now().to_rfc3339();
-  let enable_aria2c = true;
   let result = state.db.with_conn(|conn| {
     conn.execute(
       "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
@@ -182,24 +528,19 @@ pub fn update_download_settings(
        ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
       ("download_block_pcdn", if block_pcdn { "1" } else { "0" }, &now),
     )?;
-    conn.execute(
-      "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
-       ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-      ("download_enable_aria2c", "1", &now),
-    )?;
     conn.execute(
       "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
        ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
       (
-        "download_aria2c_connections",
-        normalized_aria2c_connections.to_string(),
+        "download_connections",
+        normalized_download_connections.to_string(),
         &now,
       ),
     )?;
     conn.execute(
       "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
        ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
-      ("download_aria2c_split", normalized_aria2c_split.to_string(), &now),
+      ("download_split", normalized_download_split.to_string(), &now),
     )?;
     conn.execute(
       "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
@@ -210,12 +551,22 @@ pub fn update_download_settings(
         &now,
       ),
     )?;
+    conn.execute(
+      "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
+       ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+      (
+        "download_speed_limit_kbps",
+        normalized_speed_limit.to_string(),
+        &now,
+      ),
+    )?;
     Ok(())
   });
 
   if let Err(err) = result {
     return ApiResponse::error(format!("Failed to update download settings: {}", err));
   }
+  apply_bandwidth_settings(&state.db);
 
   ApiResponse::success(DownloadSettings {
     threads,
@@ -225,10 +576,10 @@ pub fn update_download_settings(
     upload_concurrency,
     submission_remote_refresh_minutes,
     block_pcdn,
-    enable_aria2c,
-    aria2c_connections: normalized_aria2c_connections,
-    aria2c_split: normalized_aria2c_split,
+    download_connections: normalized_download_connections,
+    download_split: normalized_download_split,
     baidu_max_parallel: normalized_baidu_max_parallel,
+    download_speed_limit_kbps: normalized_speed_limit,
   })
 }
 
@@ -236,12 +587,19 @@ pub fn update_download_settings(
 pub fn update_live_settings(
   state: State<'_, AppState>,
   payload: LiveSettings,
+) -> ApiResponse<LiveSettings> {
+  update_live_settings_inner(&state, payload)
+}
+
+pub fn update_live_settings_inner(
+  state: &AppState,
+  payload: LiveSettings,
 ) -> ApiResponse<LiveSettings> {
   let now = Utc::now().to_rfc3339();
   let result = state.db.with_conn(|conn| {
     conn.execute(
-      "INSERT INTO live_settings (id, file_name_template, record_path, write_metadata, save_cover, recording_quality, record_mode, cutting_mode, cutting_number, cutting_by_title, title_split_min_seconds, danmaku_transport, record_danmaku, record_danmaku_raw, record_danmaku_superchat, record_danmaku_gift, record_danmaku_guard, stream_retry_ms, stream_retry_no_qn_sec, stream_connect_timeout_ms, stream_read_timeout_ms, check_interval_sec, flv_fix_split_on_missing, flv_fix_adjust_timestamp_jump, flv_fix_split_on_timestamp_jump, flv_fix_disable_on_annexb, baidu_sync_enabled, baidu_sync_path, create_time, update_time) \
-       VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29) \
+      "INSERT INTO live_settings (id, file_name_template, record_path, write_metadata, save_cover, recording_quality, record_mode, cutting_mode, cutting_number, cutting_by_title, title_split_min_seconds, danmaku_transport, record_danmaku, record_danmaku_raw, record_danmaku_superchat, record_danmaku_gift, record_danmaku_guard, stream_retry_ms, stream_retry_no_qn_sec, stream_connect_timeout_ms, stream_read_timeout_ms, check_interval_sec, flv_fix_split_on_missing, flv_fix_adjust_timestamp_jump, flv_fix_split_on_timestamp_jump, flv_fix_disable_on_annexb, baidu_sync_enabled, baidu_sync_path, stream_protocol_order, stream_format_order, stream_codec_order, stream_cdn_order, stream_cdn_failover, create_time, update_time) \
+       VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34) \
        ON CONFLICT(id) DO UPDATE SET \
        file_name_template = excluded.file_name_template, \
        record_path = excluded.record_path, \
@@ -270,6 +628,11 @@ pub fn update_live_settings(
        flv_fix_disable_on_annexb = excluded.flv_fix_disable_on_annexb, \
        baidu_sync_enabled = excluded.baidu_sync_enabled, \
        baidu_sync_path = excluded.baidu_sync_path, \
+       stream_protocol_order = excluded.stream_protocol_order, \
+       stream_format_order = excluded.stream_format_order, \
+       stream_codec_order = excluded.stream_codec_order, \
+       stream_cdn_order = excluded.stream_cdn_order, \
+       stream_cdn_failover = excluded.stream_cdn_failover, \
        update_time = excluded.update_time",
       params![
         payload.file_name_template.as_str(),
@@ -299,6 +662,11 @@ pub fn update_live_settings(
         payload.flv_fix_disable_on_annexb as i64,
         payload.baidu_sync_enabled as i64,
         payload.baidu_sync_path.as_str(),
+        payload.stream_protocol_order.as_str(),
+        payload.stream_format_order.as_str(),
+        payload.stream_codec_order.as_str(),
+        payload.stream_cdn_order.as_str(),
+        payload.stream_cdn_failover as i64,
         &now,
         &now,
       ],
@@ -357,30 +725,30 @@ pub fn load_download_settings_from_db(db: &Db) -> Result<DownloadSettings, crate
         |row| row.get(0),
       )
       .ok();
-    let _enable_aria2c: Option<String> = conn
+    let download_connections: Option<String> = conn
       .query_row(
-        "SELECT value FROM app_settings WHERE key = 'download_enable_aria2c'",
+        "SELECT value FROM app_settings WHERE key = 'download_connections'",
         [],
         |row| row.get(0),
       )
       .ok();
-    let aria2c_connections: Option<String> = conn
+    let download_split: Option<String> = conn
       .query_row(
-        "SELECT value FROM app_settings WHERE key = 'download_aria2c_connections'",
+        "SELECT value FROM app_settings WHERE key = 'download_split'",
         [],
         |row| row.get(0),
       )
       .ok();
-    let aria2c_split: Option<String> = conn
+    let baidu_max_parallel: Option<String> = conn
       .query_row(
-        "SELECT value FROM app_settings WHERE key = 'download_aria2c_split'",
+        "SELECT value FROM app_settings WHERE key = 'download_baidu_max_parallel'",
         [],
         |row| row.get(0),
       )
       .ok();
-    let baidu_max_parallel: Option<String> = conn
+    let download_speed_limit_kbps: Option<String> = conn
       .query_row(
-        "SELECT value FROM app_settings WHERE key = 'download_baidu_max_parallel'",
+        "SELECT value FROM app_settings WHERE key = 'download_speed_limit_kbps'",
         [],
         |row| row.get(0),
       )
@@ -392,8 +760,6 @@ pub fn load_download_settings_from_db(db: &Db) -> Result<DownloadSettings, crate
         |row| row.get(0),
       )
       .ok();
-    let enable_aria2c = true;
-
     let resolved_download_path = download_path
       .unwrap_or_else(|| default_download_dir().to_string_lossy().to_string());
     let resolved_log_dir = log_dir.unwrap_or_else(|| {
@@ -423,19 +789,22 @@ pub fn load_download_settings_from_db(db: &Db) -> Result<DownloadSettings, crate
       block_pcdn: block_pcdn
         .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
         .unwrap_or(DEFAULT_BLOCK_PCDN),
-      enable_aria2c,
-      aria2c_connections: aria2c_connections
+      download_connections: download_connections
         .and_then(|value| value.parse::<i64>().ok())
-        .unwrap_or(DEFAULT_ARIA2C_CONNECTIONS)
+        .unwrap_or(DEFAULT_DOWNLOAD_CONNECTIONS)
         .clamp(1, 32),
-      aria2c_split: aria2c_split
+      download_split: download_split
         .and_then(|value| value.parse::<i64>().ok())
-        .unwrap_or(DEFAULT_ARIA2C_SPLIT)
+        .unwrap_or(DEFAULT_DOWNLOAD_SPLIT)
         .clamp(1, 32),
       baidu_max_parallel: baidu_max_parallel
         .and_then(|value| value.parse::<i64>().ok())
         .unwrap_or(DEFAULT_BAIDU_MAX_PARALLEL)
         .clamp(1, 100),
+      download_speed_limit_kbps: download_speed_limit_kbps
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(0)
+        .max(0),
     })
   })
 }
@@ -472,10 +841,246 @@ pub fn ensure_log_dir(db: &Db, download_dir: &std::path::Path) -> String {
   resolved
 }
 
+pub fn load_encoder_settings_from_db(db: &Db) -> Result<EncoderSettings, crate::db::DbError> {
+  db.with_conn(|conn| {
+    let read = |key: &str| -> Option<String> {
+      conn
+        .query_row("SELECT value FROM app_settings WHERE key = ?1", [key], |row| row.get(0))
+        .ok()
+    };
+    let defaults = default_encoder_settings();
+    let read_i64 = |key: &str, fallback: i64| {
+      read(key)
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(fallback)
+    };
+    Ok(normalize_encoder_settings(EncoderSettings {
+      video_encoder: read("encoder_video_encoder").unwrap_or(defaults.video_encoder),
+      rate_control: read("encoder_rate_control").unwrap_or(defaults.rate_control),
+      crf: read_i64("encoder_crf", defaults.crf),
+      video_bitrate_kbps: read_i64("encoder_video_bitrate_kbps", defaults.video_bitrate_kbps),
+      preset: read("encoder_preset").unwrap_or(defaults.preset),
+      fps_mode: read("encoder_fps_mode").unwrap_or(defaults.fps_mode),
+      fps: read_i64("encoder_fps", defaults.fps),
+      audio_bitrate_kbps: read_i64("encoder_audio_bitrate_kbps", defaults.audio_bitrate_kbps),
+    }))
+  })
+}
+
+pub fn load_storage_settings_from_db(db: &Db) -> Result<StorageSettings, crate::db::DbError> {
+  db.with_conn(|conn| {
+    let read = |key: &str| -> Option<String> {
+      conn
+        .query_row("SELECT value FROM app_settings WHERE key = ?1", [key], |row| row.get(0))
+        .ok()
+    };
+    let defaults = default_storage_settings();
+    let read_i64 = |key: &str, fallback: i64| {
+      read(key)
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(fallback)
+    };
+    Ok(normalize_storage_settings(StorageSettings {
+      min_free_space_mb: read_i64("storage_min_free_space_mb", defaults.min_free_space_mb),
+      retention_enabled: read("storage_retention_enabled")
+        .map(|value| value == "true")
+        .unwrap_or(defaults.retention_enabled),
+      retention_baidu_sync_days: read_i64(
+        "storage_retention_baidu_sync_days",
+        defaults.retention_baidu_sync_days,
+      ),
+      retention_submission_days: read_i64(
+        "storage_retention_submission_days",
+        defaults.retention_submission_days,
+      ),
+    }))
+  })
+}
+
+pub fn default_storage_settings() -> StorageSettings {
+  StorageSettings {
+    min_free_space_mb: DEFAULT_MIN_FREE_SPACE_MB,
+    retention_enabled: false,
+    retention_baidu_sync_days: 0,
+    retention_submission_days: 0,
+  }
+}
+
+fn normalize_storage_settings(settings: StorageSettings) -> StorageSettings {
+  StorageSettings {
+    min_free_space_mb: settings.min_free_space_mb.max(0),
+    retention_enabled: settings.retention_enabled,
+    retention_baidu_sync_days: settings.retention_baidu_sync_days.max(0),
+    retention_submission_days: settings.retention_submission_days.max(0),
+  }
+}
+
+pub fn load_bandwidth_settings_from_db(db: &Db) -> Result<BandwidthSettings, crate::db::DbError> {
+  db.with_conn(|conn| {
+    let read = |key: &str| -> Option<String> {
+      conn
+        .query_row("SELECT value FROM app_settings WHERE key = ?1", [key], |row| row.get(0))
+        .ok()
+    };
+    let defaults = default_bandwidth_settings();
+    let read_i64 = |key: &str, fallback: i64| {
+      read(key)
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(fallback)
+    };
+    let time_rules = read("bandwidth_time_rules")
+      .and_then(|value| serde_json::from_str::<Vec<BandwidthTimeRule>>(&value).ok())
+      .unwrap_or_default();
+    let settings = BandwidthSettings {
+      total_limit_kbps: read_i64("bandwidth_total_limit_kbps", defaults.total_limit_kbps),
+      download_limit_kbps: read_i64("download_speed_limit_kbps", defaults.download_limit_kbps),
+      upload_limit_kbps: read_i64("bandwidth_upload_limit_kbps", defaults.upload_limit_kbps),
+      sync_limit_kbps: read_i64("bandwidth_sync_limit_kbps", defaults.sync_limit_kbps),
+      download_priority: read_i64("bandwidth_download_priority", defaults.download_priority),
+      upload_priority: read_i64("bandwidth_upload_priority", defaults.upload_priority),
+      sync_priority: read_i64("bandwidth_sync_priority", defaults.sync_priority),
+      time_rules,
+    };
+    Ok(normalize_bandwidth_settings(settings.clone()).unwrap_or(BandwidthSettings {
+      time_rules: Vec::new(),
+      ..settings
+    }))
+  })
+}
+
+pub fn default_bandwidth_settings() -> BandwidthSettings {
+  BandwidthSettings {
+    total_limit_kbps: 0,
+    download_limit_kbps: 0,
+    upload_limit_kbps: 0,
+    sync_limit_kbps: 0,
+    download_priority: DEFAULT_BANDWIDTH_DOWNLOAD_PRIORITY,
+    upload_priority: DEFAULT_BANDWIDTH_UPLOAD_PRIORITY,
+    sync_priority: DEFAULT_BANDWIDTH_SYNC_PRIORITY,
+    time_rules: Vec::new(),
+  }
+}
+
+fn normalize_bandwidth_settings(settings: BandwidthSettings) -> Result<BandwidthSettings, String> {
+  let mut time_rules = Vec::with_capacity(settings.time_rules.len());
+  for rule in settings.time_rules {
+    let time_window = match crate::record_schedule::normalize_time_windows(&rule.time_window)? {
+      Some(value) => value,
+      None => continue,
+    };
+    time_rules.push(BandwidthTimeRule {
+      time_window,
+      total_limit_kbps: rule.total_limit_kbps.max(0),
+      download_limit_kbps: rule.download_limit_kbps.max(0),
+      upload_limit_kbps: rule.upload_limit_kbps.max(0),
+      sync_limit_kbps: rule.sync_limit_kbps.max(0),
+    });
+  }
+  Ok(BandwidthSettings {
+    total_limit_kbps: settings.total_limit_kbps.max(0),
+    download_limit_kbps: settings.download_limit_kbps.max(0),
+    upload_limit_kbps: settings.upload_limit_kbps.max(0),
+    sync_limit_kbps: settings.sync_limit_kbps.max(0),
+    download_priority: settings.download_priority.clamp(1, MAX_BANDWIDTH_PRIORITY),
+    upload_priority: settings.upload_priority.clamp(1, MAX_BANDWIDTH_PRIORITY),
+    sync_priority: settings.sync_priority.clamp(1, MAX_BANDWIDTH_PRIORITY),
+    time_rules,
+  })
+}
+
+pub fn apply_bandwidth_settings(db: &Db) {
+  if let Ok(settings) = load_bandwidth_settings_from_db(db) {
+    crate::bandwidth::configure(&settings);
+  }
+}
+
+pub fn default_encoder_settings() -> EncoderSettings {
+  EncoderSettings {
+    video_encoder: DEFAULT_VIDEO_ENCODER.to_string(),
+    rate_control: "BITRATE".to_string(),
+    crf: DEFAULT_VIDEO_CRF,
+    video_bitrate_kbps: DEFAULT_VIDEO_BITRATE_KBPS,
+    preset: String::new(),
+    fps_mode: "FIXED".to_string(),
+    fps: DEFAULT_OUTPUT_FPS,
+    audio_bitrate_kbps: DEFAULT_AUDIO_BITRATE_KBPS,
+  }
+}
+
+fn normalize_encoder_settings(settings: EncoderSettings) -> EncoderSettings {
+  let rate_control = match settings.rate_control.trim().to_ascii_uppercase().as_str() {
+    "CRF" => "CRF",
+    _ => "BITRATE",
+  };
+  let fps_mode = match settings.fps_mode.trim().to_ascii_uppercase().as_str() {
+    "SOURCE" => "SOURCE",
+    "MAX" => "MAX",
+    _ => "FIXED",
+  };
+  EncoderSettings {
+    video_encoder: settings.video_encoder.trim().to_string(),
+    rate_control: rate_control.to_string(),
+    crf: settings.crf.clamp(0, 51),
+    video_bitrate_kbps: settings.video_bitrate_kbps.clamp(100, 200_000),
+    preset: settings.preset.trim().to_string(),
+    fps_mode: fps_mode.to_string(),
+    fps: settings.fps.clamp(1, 240),
+    audio_bitrate_kbps: settings.audio_bitrate_kbps.clamp(32, 512),
+  }
+}
+
+pub fn load_http_api_settings_from_db(db: &Db) -> Result<HttpApiSettings, crate::db::DbError> {
+  db.with_conn(|conn| {
+    let read = |key: &str| -> Option<String> {
+      conn
+        .query_row("SELECT value FROM app_settings WHERE key = ?1", [key], |row| row.get(0))
+        .ok()
+    };
+    let defaults = default_http_api_settings();
+    Ok(HttpApiSettings {
+      enabled: read("http_api_enabled")
+        .map(|value| value == "true")
+        .unwrap_or(defaults.enabled),
+      bind_address: read("http_api_bind_address").unwrap_or(defaults.bind_address),
+      port: read("http_api_port")
+        .and_then(|value| value.parse::<i64>().ok())
+        .unwrap_or(defaults.port),
+      token: read("http_api_token").unwrap_or(defaults.token),
+    })
+  })
+}
+
+pub fn default_http_api_settings() -> HttpApiSettings {
+  HttpApiSettings {
+    enabled: false,
+    bind_address: DEFAULT_HTTP_API_BIND_ADDRESS.to_string(),
+    port: DEFAULT_HTTP_API_PORT,
+    token: String::new(),
+  }
+}
+
+fn normalize_http_api_settings(settings: HttpApiSettings) -> HttpApiSettings {
+  let bind_address = settings.bind_address.trim();
+  let mut token = settings.token.trim().to_string();
+  if settings.enabled && token.is_empty() {
+    token = uuid::Uuid::new_v4().simple().to_string();
+  }
+  HttpApiSettings {
+    enabled: settings.enabled,
+    bind_address: if bind_address.is_empty() {
+      DEFAULT_HTTP_API_BIND_ADDRESS.to_string()
+    } else {
+      bind_address.to_string()
+    },
+    port: settings.port.clamp(1, 65535),
+    token,
+  }
+}
+
 pub fn load_live_settings_from_db(db: &Db) -> Result<LiveSettings, crate::db::DbError> {
   db.with_conn(|conn| {
     let mut stmt = conn.prepare(
-      "SELECT file_name_template, record_path, write_metadata, save_cover, recording_quality, record_mode, cutting_mode, cutting_number, cutting_by_title, title_split_min_seconds, danmaku_transport, record_danmaku, record_danmaku_raw, record_danmaku_superchat, record_danmaku_gift, record_danmaku_guard, stream_retry_ms, stream_retry_no_qn_sec, stream_connect_timeout_ms, stream_read_timeout_ms, check_interval_sec, flv_fix_split_on_missing, flv_fix_adjust_timestamp_jump, flv_fix_split_on_timestamp_jump, flv_fix_disable_on_annexb, baidu_sync_enabled, baidu_sync_path \
+      "SELECT file_name_template, record_path, write_metadata, save_cover, recording_quality, record_mode, cutting_mode, cutting_number, cutting_by_title, title_split_min_seconds, danmaku_transport, record_danmaku, record_danmaku_raw, record_danmaku_superchat, record_danmaku_gift, record_danmaku_guard, stream_retry_ms, stream_retry_no_qn_sec, stream_connect_timeout_ms, stream_read_timeout_ms, check_interval_sec, flv_fix_split_on_missing, flv_fix_adjust_timestamp_jump, flv_fix_split_on_timestamp_jump, flv_fix_disable_on_annexb, baidu_sync_enabled, baidu_sync_path, stream_protocol_order, stream_format_order, stream_codec_order, stream_cdn_order, stream_cdn_failover \
        FROM live_settings WHERE id = 1",
     )?;
 
@@ -508,6 +1113,17 @@ pub fn load_live_settings_from_db(db: &Db) -> Result<LiveSettings, crate::db::Db
         flv_fix_disable_on_annexb: row.get::<_, i64>(24)? != 0,
         baidu_sync_enabled: row.get::<_, i64>(25)? != 0,
         baidu_sync_path: row.get::<_, Option<String>>(26)?.unwrap_or_default(),
+        stream_protocol_order: row
+          .get::<_, Option<String>>(27)?
+          .unwrap_or_else(|| DEFAULT_STREAM_PROTOCOL_ORDER.to_string()),
+        stream_format_order: row
+          .get::<_, Option<String>>(28)?
+          .unwrap_or_else(|| DEFAULT_STREAM_FORMAT_ORDER.to_string()),
+        stream_codec_order: row
+          .get::<_, Option<String>>(29)?
+          .unwrap_or_else(|| DEFAULT_STREAM_CODEC_ORDER.to_string()),
+        stream_cdn_order: row.get::<_, Option<String>>(30)?.unwrap_or_default(),
+        stream_cdn_failover: row.get::<_, Option<i64>>(31)?.unwrap_or(1) != 0,
       })
     });
 
@@ -529,6 +1145,54 @@ pub fn load_live_settings_from_db(db: &Db) -> Result<LiveSettings, crate::db::Db
   })
 }
 
+pub fn load_room_live_settings_override(db: &Db, room_id: &str) -> Result<Map<String, Value>, String> {
+  let raw = db
+    .with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT settings_override FROM live_room_settings WHERE room_id = ?1",
+          [room_id],
+          |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()
+    })
+    .map_err(|err| err.to_string())?
+    .flatten();
+  match raw.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+    Some(value) => serde_json::from_str(value).map_err(|err| format!("直播间设置覆盖解析失败: {}", err)),
+    None => Ok(Map::new()),
+  }
+}
+
+pub fn merge_live_settings(
+  base: &LiveSettings,
+  overrides: &Map<String, Value>,
+) -> Result<LiveSettings, String> {
+  let mut merged = serde_json::to_value(base).map_err(|err| err.to_string())?;
+  let fields = merged
+    .as_object_mut()
+    .ok_or_else(|| "直播设置格式错误".to_string())?;
+  for (key, value) in overrides {
+    if !fields.contains_key(key) {
+      return Err(format!("未知的直播设置字段: {}", key));
+    }
+    fields.insert(key.clone(), value.clone());
+  }
+  serde_json::from_value(merged).map_err(|err| format!("直播设置覆盖值无效: {}", err))
+}
+
+pub fn apply_room_live_settings(
+  db: &Db,
+  room_id: &str,
+  base: &LiveSettings,
+) -> Result<LiveSettings, String> {
+  let overrides = load_room_live_settings_override(db, room_id)?;
+  if overrides.is_empty() {
+    return Ok(base.clone());
+  }
+  merge_live_settings(base, &overrides)
+}
+
 pub fn default_live_settings() -> LiveSettings {
   LiveSettings {
     file_name_template: DEFAULT_LIVE_FILE_TEMPLATE.to_string(),
@@ -558,5 +1222,10 @@ pub fn default_live_settings() -> LiveSettings {
     flv_fix_disable_on_annexb: false,
     baidu_sync_enabled: false,
     baidu_sync_path: "/录播".to_string(),
+    stream_protocol_order: DEFAULT_STREAM_PROTOCOL_ORDER.to_string(),
+    stream_format_order: DEFAULT_STREAM_FORMAT_ORDER.to_string(),
+    stream_codec_order: DEFAULT_STREAM_CODEC_ORDER.to_string(),
+    stream_cdn_order: String::new(),
+    stream_cdn_failover: true,
   }
 }
@@ -7,7 +7,9 @@ use std::time::{Duration, Instant};
 
 use chrono::Utc;
 use futures_util::stream::{FuturesUnordered, StreamExt};
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, ACCEPT_LANGUAGE, CONTENT_TYPE, USER_AGENT};
+use reqwest::header::{
+  HeaderMap, HeaderValue, ACCEPT, ACCEPT_LANGUAGE, CONTENT_LENGTH, CONTENT_TYPE, USER_AGENT,
+};
 use reqwest::{Client, StatusCode};
 use rusqlite::params;
 use rusqlite::OptionalExtension;
@@ -21,20 +23,26 @@ use url::form_urlencoded;
 
 use crate::api::ApiResponse;
 use crate::baidu_sync;
+use crate::bandwidth::{acquire, TransferClass};
 use crate::bilibili::client::BilibiliClient;
 use crate::commands::settings::{
-  load_download_settings_from_db, DEFAULT_SUBMISSION_REMOTE_REFRESH_MINUTES,
-  DEFAULT_UPLOAD_CONCURRENCY,
+  default_encoder_settings, load_download_settings_from_db, load_encoder_settings_from_db,
+  DEFAULT_SUBMISSION_REMOTE_REFRESH_MINUTES, DEFAULT_UPLOAD_CONCURRENCY,
 };
 use crate::config::default_download_dir;
+use crate::danmaku_ass::parse_segment_start;
+use crate::danmaku_highlight::{propose_highlight_sources, HighlightOptions, HighlightReport};
 use crate::db::Db;
 use crate::login_refresh;
 use crate::login_store::{AuthInfo, LoginStore};
 use crate::processing::{
-  clip_sources, decide_clip_copy, merge_files, parse_time_to_seconds, probe_duration_seconds,
-  segment_file, ClipSource,
+  clip_sources, decide_clip_copy, format_timecode_seconds, merge_files, parse_time_to_seconds,
+  probe_duration_seconds, segment_file, whole_segment_bounds, ClipSource, SegmentMode,
+  SegmentOptions,
 };
+use crate::storage::ensure_free_space;
 use crate::utils::{append_log, now_rfc3339, sanitize_filename};
+use crate::webhook::{emit_webhook_event, EVENT_SUBMISSION_REJECTED, EVENT_SUBMISSION_STATUS};
 use crate::AppState;
 
 #[derive(Clone)]
@@ -45,7 +53,7 @@ struct SubmissionContext {
 }
 
 impl SubmissionContext {
-  fn new(state: &State<'_, AppState>) -> Self {
+  fn new(state: &AppState) -> Self {
     Self {
       db: state.db.clone(),
       app_log_path: state.app_log_path.clone(),
@@ -61,18 +69,25 @@ struct UploadContext {
   login_store: Arc<LoginStore>,
   app_log_path: Arc<PathBuf>,
   edit_upload_state: Arc<Mutex<EditUploadState>>,
+  account_user_id: Option<i64>,
 }
 
 impl UploadContext {
-  fn new(state: &State<'_, AppState>) -> Self {
+  fn new(state: &AppState) -> Self {
     Self {
       db: state.db.clone(),
       bilibili: state.bilibili.clone(),
       login_store: state.login_store.clone(),
       app_log_path: state.app_log_path.clone(),
       edit_upload_state: state.edit_upload_state.clone(),
+      account_user_id: None,
     }
   }
+
+  fn for_task(mut self, task_id: &str) -> Self {
+    self.account_user_id = load_task_account_user_id(&self.db, task_id).unwrap_or(None);
+    self
+  }
 }
 
 #[derive(Clone)]
@@ -84,7 +99,7 @@ struct SubmissionQueueContext {
   edit_upload_state: Arc<Mutex<EditUploadState>>,
 }
 
-fn build_submission_queue_context(state: &State<'_, AppState>) -> SubmissionQueueContext {
+fn build_submission_queue_context(state: &AppState) -> SubmissionQueueContext {
   SubmissionQueueContext {
     db: state.db.clone(),
     bilibili: state.bilibili.clone(),
@@ -120,6 +135,10 @@ pub fn start_submission_background_tasks(
   tauri::async_runtime::spawn(async move {
     submission_remote_refresh_loop(refresh_context).await;
   });
+  let schedule_context = context.clone();
+  tauri::async_runtime::spawn(async move {
+    submission_schedule_loop(schedule_context).await;
+  });
 }
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -139,6 +158,9 @@ pub struct SubmissionTaskInput {
   pub baidu_sync_enabled: Option<bool>,
   pub baidu_sync_path: Option<String>,
   pub baidu_sync_filename: Option<String>,
+  pub account_user_id: Option<i64>,
+  pub scheduled_publish_time: Option<i64>,
+  pub scheduled_publish_mode: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -158,6 +180,14 @@ pub struct SubmissionCreateRequest {
   pub workflow_config: Option<Value>,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionHighlightRequest {
+  pub source_file_path: String,
+  pub start_time: Option<String>,
+  pub options: Option<HighlightOptions>,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmissionUpdateRequest {
@@ -167,6 +197,7 @@ pub struct SubmissionUpdateRequest {
   pub baidu_sync_enabled: Option<bool>,
   pub baidu_sync_path: Option<String>,
   pub baidu_sync_filename: Option<String>,
+  pub account_user_id: Option<i64>,
 }
 
 #[derive(Deserialize)]
@@ -174,6 +205,11 @@ pub struct SubmissionUpdateRequest {
 pub struct SubmissionResegmentRequest {
   pub task_id: String,
   pub segment_duration_seconds: i64,
+  pub segment_mode: Option<String>,
+  pub search_window_seconds: Option<f64>,
+  pub silence_noise_db: Option<f64>,
+  pub silence_min_seconds: Option<f64>,
+  pub scene_threshold: Option<f64>,
   pub mode: Option<String>,
   pub merged_video_id: Option<i64>,
   pub integrate_current_bvid: Option<bool>,
@@ -396,6 +432,9 @@ pub struct SubmissionTaskRecord {
   pub baidu_sync_filename: Option<String>,
   pub has_integrated_downloads: bool,
   pub workflow_status: Option<WorkflowStatusRecord>,
+  pub account_user_id: Option<i64>,
+  pub scheduled_publish_time: Option<i64>,
+  pub scheduled_publish_mode: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -471,6 +510,8 @@ pub struct TaskOutputSegmentRecord {
   pub upload_uri: Option<String>,
   pub upload_chunk_size: i64,
   pub upload_last_part_index: i64,
+  pub segment_start_seconds: Option<f64>,
+  pub segment_end_seconds: Option<f64>,
 }
 
 #[derive(Default)]
@@ -595,7 +636,14 @@ pub async fn submission_create(
   state: State<'_, AppState>,
   request: SubmissionCreateRequest,
 ) -> Result<ApiResponse<TaskCreationResult>, String> {
-  let context = SubmissionContext::new(&state);
+  submission_create_inner(&state, request).await
+}
+
+pub async fn submission_create_inner(
+  state: &AppState,
+  request: SubmissionCreateRequest,
+) -> Result<ApiResponse<TaskCreationResult>, String> {
+  let context = SubmissionContext::new(state);
   let task_id = uuid::Uuid::new_v4().to_string();
   let now = now_rfc3339();
   append_log(
@@ -603,12 +651,20 @@ pub async fn submission_create(
     &format!("submission_create_start task_id={}", task_id),
   );
 
+  let (scheduled_publish_time, scheduled_publish_mode) = match normalize_publish_schedule(
+    request.task.scheduled_publish_time,
+    request.task.scheduled_publish_mode.as_deref(),
+  ) {
+    Ok(value) => value,
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+
   let result = context.db.with_conn(|conn| {
     let normalized_baidu_sync_filename =
       normalize_baidu_sync_filename(request.task.baidu_sync_filename.as_deref());
     conn.execute(
-      "INSERT INTO submission_task (task_id, status, priority, title, description, cover_url, partition_id, tags, topic_id, mission_id, activity_title, video_type, collection_id, bvid, aid, created_at, updated_at, segment_prefix, baidu_sync_enabled, baidu_sync_path, baidu_sync_filename) \
-       VALUES (?1, 'PENDING', ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, NULL, NULL, ?13, ?14, ?15, ?16, ?17, ?18)",
+      "INSERT INTO submission_task (task_id, status, priority, title, description, cover_url, partition_id, tags, topic_id, mission_id, activity_title, video_type, collection_id, bvid, aid, created_at, updated_at, segment_prefix, baidu_sync_enabled, baidu_sync_path, baidu_sync_filename, account_user_id, scheduled_publish_time, scheduled_publish_mode) \
+       VALUES (?1, 'PENDING', ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, NULL, NULL, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)",
       params![
         &task_id,
         if request.task.priority.unwrap_or(false) { 1 } else { 0 },
@@ -632,6 +688,9 @@ pub async fn submission_create(
         },
         request.task.baidu_sync_path.as_deref(),
         normalized_baidu_sync_filename.as_deref(),
+        request.task.account_user_id.filter(|value| *value > 0),
+        scheduled_publish_time,
+        scheduled_publish_mode.as_deref(),
       ],
     )?;
 
@@ -692,12 +751,81 @@ pub async fn submission_create(
   Ok(ApiResponse::success(result))
 }
 
+#[tauri::command]
+pub async fn submission_highlight_proposals(
+  state: State<'_, AppState>,
+  request: SubmissionHighlightRequest,
+) -> Result<ApiResponse<HighlightReport>, String> {
+  submission_highlight_proposals_inner(&state, request).await
+}
+
+pub async fn submission_highlight_proposals_inner(
+  state: &AppState,
+  request: SubmissionHighlightRequest,
+) -> Result<ApiResponse<HighlightReport>, String> {
+  let source_path = request.source_file_path.trim().to_string();
+  if source_path.is_empty() {
+    return Ok(ApiResponse::error("请选择源视频"));
+  }
+  let segment_start = match request.start_time.as_deref() {
+    Some(value) if !value.trim().is_empty() => match parse_segment_start(value) {
+      Some(start) => Some(start),
+      None => return Ok(ApiResponse::error("开始时间格式无效")),
+    },
+    _ => None,
+  };
+  let options = request.options.unwrap_or_default();
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "submission_highlight_start source={} window={} threshold={} padding={}",
+      source_path, options.window_seconds, options.threshold, options.padding_seconds
+    ),
+  );
+  let source_path_clone = source_path.clone();
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    propose_highlight_sources(Path::new(&source_path_clone), segment_start, &options)
+  })
+  .await
+  .map_err(|_| "高光检测执行失败".to_string())?;
+
+  match result {
+    Ok(report) => {
+      append_log(
+        &state.app_log_path,
+        &format!(
+          "submission_highlight_done source={} ranges={} baseline={:.2} cutoff={:.2}",
+          source_path,
+          report.ranges.len(),
+          report.baseline,
+          report.cutoff
+        ),
+      );
+      Ok(ApiResponse::success(report))
+    }
+    Err(err) => {
+      append_log(
+        &state.app_log_path,
+        &format!("submission_highlight_fail source={} err={}", source_path, err),
+      );
+      Ok(ApiResponse::error(err))
+    }
+  }
+}
+
 #[tauri::command]
 pub async fn submission_update(
   state: State<'_, AppState>,
   request: SubmissionUpdateRequest,
 ) -> Result<ApiResponse<String>, String> {
-  let context = SubmissionContext::new(&state);
+  submission_update_inner(&state, request).await
+}
+
+pub async fn submission_update_inner(
+  state: &AppState,
+  request: SubmissionUpdateRequest,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(state);
   let task_id = request.task_id.trim().to_string();
   if task_id.is_empty() {
     return Ok(ApiResponse::error("任务ID不能为空"));
@@ -733,6 +861,12 @@ pub async fn submission_update(
   ) {
     return Ok(ApiResponse::error(format!("更新百度同步配置失败: {}", err)));
   }
+  if let Some(account_user_id) = request.account_user_id {
+    let account_user_id = Some(account_user_id).filter(|value| *value > 0);
+    if let Err(err) = update_task_account_user_id(&context, &task_id, account_user_id) {
+      return Ok(ApiResponse::error(format!("更新投稿账号失败: {}", err)));
+    }
+  }
   if let Err(err) = reset_workflow_instances(&context, &task_id) {
     return Ok(ApiResponse::error(format!("重置工作流失败: {}", err)));
   }
@@ -761,7 +895,14 @@ pub async fn submission_repost(
   state: State<'_, AppState>,
   request: SubmissionRepostRequest,
 ) -> Result<ApiResponse<String>, String> {
-  let context = SubmissionContext::new(&state);
+  submission_repost_inner(&state, request).await
+}
+
+pub async fn submission_repost_inner(
+  state: &AppState,
+  request: SubmissionRepostRequest,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(state);
   let task_id = request.task_id.trim().to_string();
   if task_id.is_empty() {
     return Ok(ApiResponse::error("任务ID不能为空"));
@@ -864,7 +1005,7 @@ pub async fn submission_repost(
       )?;
       let new_download_ids =
         create_retry_download_records(&context, &task_id, &workflow_instance_id, &missing_records)?;
-      crate::commands::download::requeue_integrated_downloads(&state, &new_download_ids).await?;
+      crate::commands::download::requeue_integrated_downloads(state, &new_download_ids).await?;
       return Ok(ApiResponse::success(
         "源视频缺失，已创建下载任务，下载完成后自动重新投稿".to_string(),
       ));
@@ -926,7 +1067,7 @@ pub async fn submission_repost(
     let mut merge_all_list = Vec::new();
     let workflow_settings = load_workflow_settings(&context, &task_id);
     let enable_segmentation = workflow_settings.enable_segmentation;
-    let segment_seconds = workflow_settings.segment_duration_seconds.max(1);
+    let segment_options = workflow_settings.segment_options;
     let should_segment = integrate_current_bvid || enable_segmentation;
     let reprocess_source_paths = resolve_reprocess_source_paths(
       &context,
@@ -942,7 +1083,7 @@ pub async fn submission_repost(
     } else {
       None
     };
-    let base_dir = resolve_submission_base_dir(&context, &task_id);
+    let base_dir = resolve_submission_base_dir(&context.db, &task_id);
     let output_dir = base_dir
       .join("repost")
       .join(sanitize_filename(&format!("repost_{}", now_rfc3339())))
@@ -957,7 +1098,7 @@ pub async fn submission_repost(
         Ok(merged) => merged,
         Err(_) => {
           return handle_repost_missing_assets(
-            &state,
+            state,
             &context,
             &detail,
             &task_id,
@@ -973,7 +1114,7 @@ pub async fn submission_repost(
       if merged_path.trim().is_empty() || !PathBuf::from(&merged_path).exists() {
         if sources_ready {
           return handle_repost_missing_assets(
-            &state,
+            state,
             &context,
             &detail,
             &task_id,
@@ -1009,7 +1150,7 @@ pub async fn submission_repost(
       }
       if merged_path.trim().is_empty() {
         return handle_repost_missing_assets(
-          &state,
+          state,
           &context,
           &detail,
           &task_id,
@@ -1023,7 +1164,7 @@ pub async fn submission_repost(
       let merged_path_buf = PathBuf::from(merged_path.clone());
       if !merged_path_buf.exists() {
         return handle_repost_missing_assets(
-          &state,
+          state,
           &context,
           &detail,
           &task_id,
@@ -1042,7 +1183,7 @@ pub async fn submission_repost(
       };
       if merged_videos.is_empty() {
         return handle_repost_missing_assets(
-          &state,
+          state,
           &context,
           &detail,
           &task_id,
@@ -1058,7 +1199,7 @@ pub async fn submission_repost(
         if path.trim().is_empty() || !PathBuf::from(&path).exists() {
           if sources_ready {
             return handle_repost_missing_assets(
-              &state,
+              state,
               &context,
               &detail,
               &task_id,
@@ -1094,7 +1235,7 @@ pub async fn submission_repost(
         }
         if path.trim().is_empty() {
           return handle_repost_missing_assets(
-            &state,
+            state,
             &context,
             &detail,
             &task_id,
@@ -1108,7 +1249,7 @@ pub async fn submission_repost(
         let path_buf = PathBuf::from(&path);
         if !path_buf.exists() {
           return handle_repost_missing_assets(
-            &state,
+            state,
             &context,
             &detail,
             &task_id,
@@ -1147,21 +1288,9 @@ pub async fn submission_repost(
         Err(err) => return Ok(ApiResponse::error(format!("创建工作流失败: {}", err))),
       };
     let now = now_rfc3339();
-    let update_result = context.db.with_conn(|conn| {
-      if integrate_current_bvid {
-        conn.execute(
-          "UPDATE submission_task SET status = 'SEGMENTING', remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-          (&now, &task_id),
-        )?;
-      } else {
-        conn.execute(
-          "UPDATE submission_task SET status = 'SEGMENTING', bvid = NULL, aid = NULL, remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-          (&now, &task_id),
-        )?;
-      }
-      Ok(())
-    });
-    if let Err(err) = update_result {
+    if let Err(err) =
+      reset_submission_status(&context, &task_id, "SEGMENTING", !integrate_current_bvid)
+    {
       return Ok(ApiResponse::error(format!("重置任务状态失败: {}", err)));
     }
     let _ = context.db.with_conn(|conn| {
@@ -1197,8 +1326,14 @@ pub async fn submission_repost(
         let segment_outputs = if should_segment {
           if enable_segmentation {
             let merged_path_for_segment = merged_path_clone.clone();
+            let segment_log_path = app_log_path.clone();
             match tauri::async_runtime::spawn_blocking(move || {
-              segment_file(&merged_path_for_segment, &output_dir_clone, segment_seconds)
+              segment_file(
+                &merged_path_for_segment,
+                &output_dir_clone,
+                &segment_options,
+                &segment_log_path,
+              )
             })
             .await
             {
@@ -1206,7 +1341,7 @@ pub async fn submission_repost(
               Err(_) => Err("Failed to segment video".to_string()),
             }
           } else {
-            Ok(vec![merged_path_clone.clone()])
+            whole_segment_bounds(&merged_path_clone).map(|segment| vec![segment])
           }
         } else {
           Ok(Vec::new())
@@ -1416,8 +1551,14 @@ pub async fn submission_repost(
       let segment_outputs = if should_segment {
         if enable_segmentation {
           let merge_output_for_segment_clone = merge_output_for_segment.clone();
+          let segment_log_path = app_log_path.clone();
           match tauri::async_runtime::spawn_blocking(move || {
-            segment_file(&merge_output_for_segment_clone, &output_dir_clone, segment_seconds)
+            segment_file(
+              &merge_output_for_segment_clone,
+              &output_dir_clone,
+              &segment_options,
+              &segment_log_path,
+            )
           })
           .await
           {
@@ -1425,7 +1566,7 @@ pub async fn submission_repost(
             Err(_) => Err("Failed to segment video".to_string()),
           }
         } else {
-          Ok(vec![merge_output_for_segment.clone()])
+          whole_segment_bounds(&merge_output_for_segment).map(|segment| vec![segment])
         }
       } else {
         Ok(Vec::new())
@@ -1654,7 +1795,7 @@ pub async fn submission_repost(
     )?;
     let new_download_ids =
       create_retry_download_records(&context, &task_id, &workflow_instance_id, &missing_records)?;
-    crate::commands::download::requeue_integrated_downloads(&state, &new_download_ids).await?;
+    crate::commands::download::requeue_integrated_downloads(state, &new_download_ids).await?;
     return Ok(ApiResponse::success(
       "源视频缺失，已创建下载任务，下载完成后自动重新投稿".to_string(),
     ));
@@ -1847,7 +1988,7 @@ fn resolve_reprocess_source_paths(
 }
 
 async fn handle_repost_missing_assets(
-  state: &State<'_, AppState>,
+  state: &AppState,
   context: &SubmissionContext,
   detail: &SubmissionTaskDetail,
   task_id: &str,
@@ -2107,7 +2248,14 @@ async fn try_restore_merged_from_baidu(
       merged.id, remote_path
     ),
   );
-  let exists = match baidu_sync::check_baidu_remote_file_exists(context.db.as_ref(), &remote_path) {
+  let check_db = context.db.clone();
+  let check_path = remote_path.clone();
+  let exists = match tauri::async_runtime::spawn_blocking(move || {
+    baidu_sync::check_remote_file_exists(check_db.as_ref(), &check_path)
+  })
+  .await
+  .unwrap_or_else(|_| Err("网盘检查任务失败".to_string()))
+  {
     Ok(value) => value,
     Err(err) => {
       append_log(
@@ -2263,20 +2411,10 @@ fn prepare_workflow_for_baidu_restore(
   )
   .map_err(|err| format!("创建工作流失败: {}", err))?;
   let now = now_rfc3339();
+  reset_submission_status(context, task_id, "PENDING", clear_bvid)?;
   context
     .db
     .with_conn(|conn| {
-      if clear_bvid {
-        conn.execute(
-          "UPDATE submission_task SET status = 'PENDING', bvid = NULL, aid = NULL, remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-          (&now, task_id),
-        )?;
-      } else {
-        conn.execute(
-          "UPDATE submission_task SET status = 'PENDING', remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-          (&now, task_id),
-        )?;
-      }
       conn.execute(
         "UPDATE task_relations SET workflow_instance_id = ?1, updated_at = ?2 WHERE submission_task_id = ?3 AND relation_type = 'INTEGRATED'",
         (&instance_id, &now, task_id),
@@ -2378,11 +2516,11 @@ async fn resume_resegment_after_restore(
   merged_video_id: Option<i64>,
 ) -> Result<(), String> {
   let workflow_settings = load_workflow_settings(context, task_id);
-  let segment_seconds = workflow_settings.segment_duration_seconds.max(1);
+  let segment_options = workflow_settings.segment_options;
   let segment_prefix = workflow_settings.segment_prefix.clone();
   let config = load_latest_workflow_config(context, task_id).ok().flatten();
   let integrate_current_bvid = load_integrate_current_bvid(config.as_ref());
-  let base_dir = resolve_submission_base_dir(context, task_id);
+  let base_dir = resolve_submission_base_dir(&context.db, task_id);
   let output_dir = if mode == ReprocessMode::Legacy {
     base_dir.join("output")
   } else {
@@ -2393,27 +2531,16 @@ async fn resume_resegment_after_restore(
   };
 
   let update_segmenting = |clear_segments: bool| -> Result<(), String> {
-    let now = now_rfc3339();
-    context
-      .db
-      .with_conn(|conn| {
-        if clear_segments {
+    if clear_segments {
+      context
+        .db
+        .with_conn(|conn| {
           conn.execute("DELETE FROM task_output_segment WHERE task_id = ?1", [task_id])?;
-        }
-        if integrate_current_bvid {
-          conn.execute(
-            "UPDATE submission_task SET status = 'SEGMENTING', remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-            (&now, task_id),
-          )?;
-        } else {
-          conn.execute(
-            "UPDATE submission_task SET status = 'SEGMENTING', bvid = NULL, aid = NULL, remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-            (&now, task_id),
-          )?;
-        }
-        Ok(())
-      })
-      .map_err(|err| err.to_string())
+          Ok(())
+        })
+        .map_err(|err| err.to_string())?;
+    }
+    reset_submission_status(context, task_id, "SEGMENTING", !integrate_current_bvid)
   };
 
   if let Err(err) = clear_edit_upload_segments_by_task(context, task_id) {
@@ -2449,8 +2576,14 @@ async fn resume_resegment_after_restore(
           Some("SEGMENTING"),
           70.0,
         );
+        let segment_log_path = app_log_path.clone();
         let segment_outputs = match tauri::async_runtime::spawn_blocking(move || {
-          segment_file(&merged_path_clone, &output_dir_clone, segment_seconds)
+          segment_file(
+            &merged_path_clone,
+            &output_dir_clone,
+            &segment_options,
+            &segment_log_path,
+          )
         })
         .await
         {
@@ -2600,8 +2733,14 @@ async fn resume_resegment_after_restore(
           return;
         }
         let merge_output_for_segment_clone = merge_output_for_segment.clone();
+        let segment_log_path = app_log_path.clone();
         let segment_outputs = match tauri::async_runtime::spawn_blocking(move || {
-          segment_file(&merge_output_for_segment_clone, &output_dir_clone, segment_seconds)
+          segment_file(
+            &merge_output_for_segment_clone,
+            &output_dir_clone,
+            &segment_options,
+            &segment_log_path,
+          )
         })
         .await
         {
@@ -2794,8 +2933,14 @@ async fn resume_resegment_after_restore(
           Some("SEGMENTING"),
           70.0,
         );
+        let segment_log_path = app_log_path.clone();
         let segment_outputs = match tauri::async_runtime::spawn_blocking(move || {
-          segment_file(&merged_path_clone, &output_dir_clone, segment_seconds)
+          segment_file(
+            &merged_path_clone,
+            &output_dir_clone,
+            &segment_options,
+            &segment_log_path,
+          )
         })
         .await
         {
@@ -2910,33 +3055,16 @@ async fn resume_repost_after_restore(
   let integrate_current_bvid = workflow_type == "VIDEO_UPDATE";
   let workflow_settings = load_workflow_settings(context, task_id);
   let enable_segmentation = workflow_settings.enable_segmentation;
-  let segment_seconds = workflow_settings.segment_duration_seconds.max(1);
+  let segment_options = workflow_settings.segment_options;
   let segment_prefix = workflow_settings.segment_prefix.clone();
   let should_segment = integrate_current_bvid || enable_segmentation;
-  let base_dir = resolve_submission_base_dir(context, task_id);
+  let base_dir = resolve_submission_base_dir(&context.db, task_id);
   let output_dir = base_dir
     .join("repost")
     .join(sanitize_filename(&format!("repost_{}", now_rfc3339())))
     .join("output");
 
-  let now = now_rfc3339();
-  context
-    .db
-    .with_conn(|conn| {
-      if integrate_current_bvid {
-        conn.execute(
-          "UPDATE submission_task SET status = 'SEGMENTING', remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-          (&now, task_id),
-        )?;
-      } else {
-        conn.execute(
-          "UPDATE submission_task SET status = 'SEGMENTING', bvid = NULL, aid = NULL, remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-          (&now, task_id),
-        )?;
-      }
-      Ok(())
-    })
-    .map_err(|err| err.to_string())?;
+  reset_submission_status(context, task_id, "SEGMENTING", !integrate_current_bvid)?;
 
   match mode {
     ReprocessMode::Specified => {
@@ -2963,8 +3091,14 @@ async fn resume_repost_after_restore(
         let segment_outputs = if should_segment {
           if enable_segmentation {
             let merged_path_for_segment = merged_path_clone.clone();
+            let segment_log_path = app_log_path.clone();
             match tauri::async_runtime::spawn_blocking(move || {
-              segment_file(&merged_path_for_segment, &output_dir_clone, segment_seconds)
+              segment_file(
+                &merged_path_for_segment,
+                &output_dir_clone,
+                &segment_options,
+                &segment_log_path,
+              )
             })
             .await
             {
@@ -2972,7 +3106,7 @@ async fn resume_repost_after_restore(
               Err(_) => Err("Failed to segment video".to_string()),
             }
           } else {
-            Ok(vec![merged_path_clone.clone()])
+            whole_segment_bounds(&merged_path_clone).map(|segment| vec![segment])
           }
         } else {
           Ok(Vec::new())
@@ -3175,8 +3309,14 @@ async fn resume_repost_after_restore(
         let segment_outputs = if should_segment {
           if enable_segmentation {
             let merge_output_for_segment_clone = merge_output_for_segment.clone();
+            let segment_log_path = app_log_path.clone();
             match tauri::async_runtime::spawn_blocking(move || {
-              segment_file(&merge_output_for_segment_clone, &output_dir_clone, segment_seconds)
+              segment_file(
+                &merge_output_for_segment_clone,
+                &output_dir_clone,
+                &segment_options,
+                &segment_log_path,
+              )
             })
             .await
             {
@@ -3184,7 +3324,7 @@ async fn resume_repost_after_restore(
               Err(_) => Err("Failed to segment video".to_string()),
             }
           } else {
-            Ok(vec![merge_output_for_segment.clone()])
+            whole_segment_bounds(&merge_output_for_segment).map(|segment| vec![segment])
           }
         } else {
           Ok(Vec::new())
@@ -3494,9 +3634,10 @@ fn create_retry_download_records(
       let mut new_ids = Vec::with_capacity(records.len());
       for record in records {
         conn.execute(
-          "INSERT INTO video_download (bvid, aid, title, part_title, part_count, current_part, download_url, local_path, status, progress, progress_total, progress_done, create_time, update_time, resolution, codec, format, cid, content, source_type) \
-           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 0, 0, 0, 0, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
-          (
+          "INSERT INTO video_download (bvid, aid, title, part_title, part_count, current_part, download_url, local_path, status, progress, progress_total, progress_done, create_time, update_time, resolution, codec, format, cid, content, source_type, account_user_id) \
+           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 0, 0, 0, 0, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, \
+           (SELECT account_user_id FROM submission_task WHERE task_id = ?17))",
+          params![
             record.bvid.as_deref(),
             record.aid.as_deref(),
             record.title.as_deref(),
@@ -3513,7 +3654,8 @@ fn create_retry_download_records(
             record.cid,
             record.content.as_deref(),
             "BILIBILI",
-          ),
+            task_id,
+          ],
         )?;
         let new_id = conn.last_insert_rowid();
         conn.execute(
@@ -3564,7 +3706,7 @@ fn reset_submission_for_repost(
   if let Err(err) = cleanup_result {
     return Err(format!("清理任务数据失败: {}", err));
   }
-  let base_dir = resolve_submission_base_dir(context, task_id);
+  let base_dir = resolve_submission_base_dir(&context.db, task_id);
   if let Err(err) = cleanup_submission_derived_files(app_log_path, &base_dir) {
     append_log(
       app_log_path,
@@ -3587,21 +3729,7 @@ fn reset_submission_for_repost(
     }
   }
   let now = now_rfc3339();
-  let update_result = context.db.with_conn(|conn| {
-    if clear_bvid {
-      conn.execute(
-        "UPDATE submission_task SET status = 'PENDING', bvid = NULL, aid = NULL, remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-        (&now, task_id),
-      )?;
-    } else {
-      conn.execute(
-        "UPDATE submission_task SET status = 'PENDING', remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-        (&now, task_id),
-      )?;
-    }
-    Ok(())
-  });
-  if let Err(err) = update_result {
+  if let Err(err) = reset_submission_status(context, task_id, "PENDING", clear_bvid) {
     return Err(format!("重置任务状态失败: {}", err));
   }
   let (instance_id, _) =
@@ -3622,7 +3750,14 @@ pub async fn submission_resegment(
   state: State<'_, AppState>,
   request: SubmissionResegmentRequest,
 ) -> Result<ApiResponse<String>, String> {
-  let context = SubmissionContext::new(&state);
+  submission_resegment_inner(&state, request).await
+}
+
+pub async fn submission_resegment_inner(
+  state: &AppState,
+  request: SubmissionResegmentRequest,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(state);
   let task_id = request.task_id.trim().to_string();
   if task_id.is_empty() {
     return Ok(ApiResponse::error("任务ID不能为空"));
@@ -3634,7 +3769,7 @@ pub async fn submission_resegment(
     Ok(detail) => detail,
     Err(err) => return Ok(ApiResponse::error(err)),
   };
-  let base_dir = resolve_submission_base_dir(&context, &task_id);
+  let base_dir = resolve_submission_base_dir(&context.db, &task_id);
   let mode = parse_reprocess_mode(request.mode.as_deref());
   let integrate_current_bvid = request.integrate_current_bvid.unwrap_or(false);
   if integrate_current_bvid && detail.task.bvid.as_deref().unwrap_or("").trim().is_empty() {
@@ -3651,10 +3786,7 @@ pub async fn submission_resegment(
         if mode == ReprocessMode::MergeAll { "MERGE_ALL" } else { "SPECIFIED" }
       ),
     );
-    let mut updated_config = build_resegment_workflow_config(
-      detail.workflow_config.clone(),
-      request.segment_duration_seconds,
-    );
+    let mut updated_config = build_resegment_workflow_config(detail.workflow_config.clone(), &request);
     apply_reprocess_metadata(&mut updated_config, mode, request.merged_video_id);
     apply_integrate_current_bvid(&mut updated_config, integrate_current_bvid);
     if let Err(err) = clear_edit_upload_segments_by_task(&context, &task_id) {
@@ -3677,29 +3809,16 @@ pub async fn submission_resegment(
     ) {
       return Ok(ApiResponse::error(format!("创建工作流失败: {}", err)));
     }
-    let now = now_rfc3339();
-    let update_result = context.db.with_conn(|conn| {
-      if integrate_current_bvid {
-        conn.execute(
-          "UPDATE submission_task SET status = 'SEGMENTING', remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-          (&now, &task_id),
-        )?;
-      } else {
-        conn.execute(
-          "UPDATE submission_task SET status = 'SEGMENTING', bvid = NULL, aid = NULL, remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-          (&now, &task_id),
-        )?;
-      }
-      Ok(())
-    });
-    if let Err(err) = update_result {
+    if let Err(err) =
+      reset_submission_status(&context, &task_id, "SEGMENTING", !integrate_current_bvid)
+    {
       return Ok(ApiResponse::error(format!("重置任务数据失败: {}", err)));
     }
     let output_dir = base_dir
       .join("resegment")
       .join(sanitize_filename(&format!("resegment_{}", now_rfc3339())))
       .join("output");
-    let segment_seconds = request.segment_duration_seconds;
+    let segment_options = parse_workflow_settings(Some(updated_config.clone())).segment_options;
     let reprocess_sources = resolve_reprocess_sources(
       &context,
       &detail,
@@ -3729,7 +3848,7 @@ pub async fn submission_resegment(
         Err(err) => {
           if sources_ready {
             return handle_repost_missing_assets(
-              &state,
+              state,
               &context,
               &detail,
               &task_id,
@@ -3798,8 +3917,14 @@ pub async fn submission_resegment(
           70.0,
         );
         let merged_path_for_segment = merged_path_clone.clone();
+        let segment_log_path = app_log_path.clone();
         let segment_outputs = match tauri::async_runtime::spawn_blocking(move || {
-          segment_file(&merged_path_for_segment, &output_dir_clone, segment_seconds)
+          segment_file(
+            &merged_path_for_segment,
+            &output_dir_clone,
+            &segment_options,
+            &segment_log_path,
+          )
         })
         .await
         {
@@ -3907,7 +4032,7 @@ pub async fn submission_resegment(
     if merged_videos.is_empty() {
       if sources_ready {
         return handle_repost_missing_assets(
-          &state,
+          state,
           &context,
           &detail,
           &task_id,
@@ -3927,7 +4052,7 @@ pub async fn submission_resegment(
       if path.trim().is_empty() || !PathBuf::from(&path).exists() {
         if sources_ready {
           return handle_repost_missing_assets(
-            &state,
+            state,
             &context,
             &detail,
             &task_id,
@@ -3955,7 +4080,7 @@ pub async fn submission_resegment(
       }
       if path.trim().is_empty() {
         return handle_repost_missing_assets(
-          &state,
+          state,
           &context,
           &detail,
           &task_id,
@@ -3969,7 +4094,7 @@ pub async fn submission_resegment(
       let path_buf = PathBuf::from(&path);
       if !path_buf.exists() {
         return handle_repost_missing_assets(
-          &state,
+          state,
           &context,
           &detail,
           &task_id,
@@ -4023,8 +4148,14 @@ pub async fn submission_resegment(
         return;
       }
       let merge_output_for_segment_clone = merge_output_for_segment.clone();
+      let segment_log_path = app_log_path.clone();
       let segment_outputs = match tauri::async_runtime::spawn_blocking(move || {
-        segment_file(&merge_output_for_segment_clone, &output_dir_clone, segment_seconds)
+        segment_file(
+          &merge_output_for_segment_clone,
+          &output_dir_clone,
+          &segment_options,
+          &segment_log_path,
+        )
       })
       .await
       {
@@ -4187,10 +4318,7 @@ pub async fn submission_resegment(
     });
     return Ok(ApiResponse::success("重新分段已启动".to_string()));
   }
-  let mut updated_config = build_resegment_workflow_config(
-    detail.workflow_config.clone(),
-    request.segment_duration_seconds,
-  );
+  let mut updated_config = build_resegment_workflow_config(detail.workflow_config.clone(), &request);
   apply_reprocess_metadata(&mut updated_config, ReprocessMode::Legacy, None);
   apply_integrate_current_bvid(&mut updated_config, integrate_current_bvid);
   let mut merged = match load_latest_merged_video(&context, &task_id) {
@@ -4225,7 +4353,7 @@ pub async fn submission_resegment(
   }
   if merged_path.trim().is_empty() {
     return handle_repost_missing_assets(
-      &state,
+      state,
       &context,
       &detail,
       &task_id,
@@ -4240,7 +4368,7 @@ pub async fn submission_resegment(
   let merged_id = merged.id;
   if !merged_path_buf.exists() {
     return handle_repost_missing_assets(
-      &state,
+      state,
       &context,
       &detail,
       &task_id,
@@ -4275,26 +4403,20 @@ pub async fn submission_resegment(
   ) {
     return Ok(ApiResponse::error(format!("创建工作流失败: {}", err)));
   }
-  let now = now_rfc3339();
-  let cleanup_result = context.db.with_conn(|conn| {
-    conn.execute("DELETE FROM task_output_segment WHERE task_id = ?1", [&task_id])?;
-    if integrate_current_bvid {
-      conn.execute(
-        "UPDATE submission_task SET status = 'SEGMENTING', remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-        (&now, &task_id),
-      )?;
-    } else {
-      conn.execute(
-        "UPDATE submission_task SET status = 'SEGMENTING', bvid = NULL, aid = NULL, remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2",
-        (&now, &task_id),
-      )?;
-    }
-    Ok(())
-  });
+  let cleanup_result = context
+    .db
+    .with_conn(|conn| {
+      conn.execute("DELETE FROM task_output_segment WHERE task_id = ?1", [&task_id])?;
+      Ok(())
+    })
+    .map_err(|err| err.to_string())
+    .and_then(|_| {
+      reset_submission_status(&context, &task_id, "SEGMENTING", !integrate_current_bvid)
+    });
   if let Err(err) = cleanup_result {
     return Ok(ApiResponse::error(format!("重置任务数据失败: {}", err)));
   }
-  let base_dir = resolve_submission_base_dir(&context, &task_id);
+  let base_dir = resolve_submission_base_dir(&context.db, &task_id);
   let output_dir = base_dir.join("output");
   if let Err(err) = remove_path_if_exists(state.app_log_path.as_ref(), "output", &output_dir) {
     append_log(
@@ -4311,7 +4433,7 @@ pub async fn submission_resegment(
   let output_dir_clone = output_dir.clone();
   let integrate_current_bvid = integrate_current_bvid;
   let app_log_path = state.app_log_path.clone();
-  let segment_seconds = request.segment_duration_seconds;
+  let segment_options = parse_workflow_settings(Some(updated_config.clone())).segment_options;
   let segment_prefix = detail.task.segment_prefix.clone();
   tauri::async_runtime::spawn(async move {
     let _ = update_workflow_status(
@@ -4321,8 +4443,14 @@ pub async fn submission_resegment(
       Some("SEGMENTING"),
       70.0,
     );
+    let segment_log_path = app_log_path.clone();
     let segment_outputs = match tauri::async_runtime::spawn_blocking(move || {
-      segment_file(&merged_path_clone, &output_dir_clone, segment_seconds)
+      segment_file(
+        &merged_path_clone,
+        &output_dir_clone,
+        &segment_options,
+        &segment_log_path,
+      )
     })
     .await
     {
@@ -4431,9 +4559,18 @@ pub async fn submission_list(
   page_size: Option<i64>,
   refresh_remote: Option<bool>,
 ) -> Result<ApiResponse<PaginatedSubmissionTasks>, String> {
-  let context = SubmissionContext::new(&state);
+  submission_list_inner(&state, page, page_size, refresh_remote).await
+}
+
+pub async fn submission_list_inner(
+  state: &AppState,
+  page: Option<i64>,
+  page_size: Option<i64>,
+  refresh_remote: Option<bool>,
+) -> Result<ApiResponse<PaginatedSubmissionTasks>, String> {
+  let context = SubmissionContext::new(state);
   if refresh_remote.unwrap_or(false) {
-    let queue_context = build_submission_queue_context(&state);
+    let queue_context = build_submission_queue_context(state);
     if let Err(err) = refresh_submission_remote_state(&queue_context).await {
       append_log(
         &state.app_log_path,
@@ -4458,9 +4595,19 @@ pub async fn submission_list_by_status(
   page_size: Option<i64>,
   refresh_remote: Option<bool>,
 ) -> Result<ApiResponse<PaginatedSubmissionTasks>, String> {
-  let context = SubmissionContext::new(&state);
+  submission_list_by_status_inner(&state, status, page, page_size, refresh_remote).await
+}
+
+pub async fn submission_list_by_status_inner(
+  state: &AppState,
+  status: String,
+  page: Option<i64>,
+  page_size: Option<i64>,
+  refresh_remote: Option<bool>,
+) -> Result<ApiResponse<PaginatedSubmissionTasks>, String> {
+  let context = SubmissionContext::new(state);
   if refresh_remote.unwrap_or(false) {
-    let queue_context = build_submission_queue_context(&state);
+    let queue_context = build_submission_queue_context(state);
     if let Err(err) = refresh_submission_remote_state(&queue_context).await {
       append_log(
         &state.app_log_path,
@@ -4487,7 +4634,7 @@ pub fn submission_task_dir(state: State<'_, AppState>, task_id: String) -> ApiRe
     return ApiResponse::error("任务ID不能为空");
   }
   let context = SubmissionContext::new(&state);
-  let dir = resolve_submission_base_dir(&context, trimmed);
+  let dir = resolve_submission_base_dir(&context.db, trimmed);
   match fs::metadata(&dir) {
     Ok(meta) => {
       if meta.is_dir() {
@@ -4511,7 +4658,7 @@ pub fn submission_delete_preview(
   }
   let context = SubmissionContext::new(&state);
   let mut files: Vec<DeleteFilePreview> = Vec::new();
-  let base_dir = resolve_submission_base_dir(&context, trimmed);
+  let base_dir = resolve_submission_base_dir(&context.db, trimmed);
   if path_exists(&base_dir) {
     files.push(DeleteFilePreview {
       path: base_dir.to_string_lossy().to_string(),
@@ -4526,7 +4673,7 @@ pub fn submission_delete_preview(
     if !path_exists(Path::new(&path)) {
       continue;
     }
-    let conflicts = match find_active_references(&context, trimmed, &path) {
+    let conflicts = match find_active_references(&context.db, trimmed, &path) {
       Ok(conflicts) => conflicts,
       Err(err) => return ApiResponse::error(err),
     };
@@ -4543,7 +4690,14 @@ pub fn submission_detail(
   state: State<'_, AppState>,
   task_id: String,
 ) -> ApiResponse<SubmissionTaskDetail> {
-  let context = SubmissionContext::new(&state);
+  submission_detail_inner(&state, task_id)
+}
+
+pub fn submission_detail_inner(
+  state: &AppState,
+  task_id: String,
+) -> ApiResponse<SubmissionTaskDetail> {
+  let context = SubmissionContext::new(state);
   append_log(
     &state.app_log_path,
     &format!("submission_detail_request task_id={}", task_id),
@@ -4643,6 +4797,8 @@ pub fn submission_edit_prepare(
     upload_uri: None,
     upload_chunk_size: 0,
     upload_last_part_index: 0,
+    segment_start_seconds: None,
+    segment_end_seconds: None,
   });
   ApiResponse::success(detail)
 }
@@ -4718,6 +4874,8 @@ pub async fn submission_edit_add_segment(
     upload_uri: None,
     upload_chunk_size: 0,
     upload_last_part_index: 0,
+    segment_start_seconds: None,
+    segment_end_seconds: None,
   };
   let segment = match upsert_edit_upload_segment(&context, segment) {
     Ok(segment) => segment,
@@ -4739,7 +4897,7 @@ pub async fn submission_edit_add_segment(
       task_id, segment.segment_id
     ),
   );
-  let upload_context = UploadContext::new(&state);
+  let upload_context = UploadContext::new(&state).for_task(&task_id);
   let auth = match load_auth_or_refresh(&upload_context, "submission_edit_add_segment").await {
     Ok(auth) => auth,
     Err(err) => return Ok(ApiResponse::error(err)),
@@ -4867,6 +5025,8 @@ pub async fn submission_edit_reupload_segment(
     upload_uri: None,
     upload_chunk_size: 0,
     upload_last_part_index: 0,
+    segment_start_seconds: None,
+    segment_end_seconds: None,
   });
   segment.part_name = part_name;
   segment.segment_file_path = file_path;
@@ -4887,7 +5047,7 @@ pub async fn submission_edit_reupload_segment(
     Ok(segment) => segment,
     Err(err) => return Ok(ApiResponse::error(err)),
   };
-  let upload_context = UploadContext::new(&state);
+  let upload_context = UploadContext::new(&state).for_task(&task_id);
   let auth = match load_auth_or_refresh(&upload_context, "submission_edit_reupload").await {
     Ok(auth) => auth,
     Err(err) => return Ok(ApiResponse::error(err)),
@@ -5049,7 +5209,7 @@ pub async fn submission_edit_submit(
       title: part_name.to_string(),
     });
   }
-  let upload_context = UploadContext::new(&state);
+  let upload_context = UploadContext::new(&state).for_task(&task_id);
   let mut auth = match load_auth_or_refresh(&upload_context, "submission_edit_prepare").await {
     Ok(auth) => auth,
     Err(err) => return Ok(ApiResponse::error(err)),
@@ -5183,6 +5343,13 @@ pub async fn submission_edit_submit(
 pub fn submission_delete(
   state: State<'_, AppState>,
   request: SubmissionDeleteRequest,
+) -> ApiResponse<SubmissionDeleteResult> {
+  submission_delete_inner(&state, request)
+}
+
+pub fn submission_delete_inner(
+  state: &AppState,
+  request: SubmissionDeleteRequest,
 ) -> ApiResponse<SubmissionDeleteResult> {
   let task_id = request.task_id.trim().to_string();
   if task_id.is_empty() {
@@ -5191,8 +5358,8 @@ pub fn submission_delete(
   if !request.delete_task && !request.delete_files {
     return ApiResponse::error("至少选择删除任务或删除文件");
   }
-  let context = SubmissionContext::new(&state);
-  let base_dir = resolve_submission_base_dir(&context, &task_id);
+  let context = SubmissionContext::new(state);
+  let base_dir = resolve_submission_base_dir(&context.db, &task_id);
   append_log(
     &state.app_log_path,
     &format!(
@@ -5208,7 +5375,7 @@ pub fn submission_delete(
       if !path_exists(Path::new(path)) {
         continue;
       }
-      if let Ok(conflicts) = find_active_references(&context, &task_id, path) {
+      if let Ok(conflicts) = find_active_references(&context.db, &task_id, path) {
         if !conflicts.is_empty() {
           conflict_files.push(DeleteFilePreview {
             path: path.to_string(),
@@ -5346,6 +5513,18 @@ fn cleanup_submission_files(log_path: &PathBuf, base_dir: &Path) -> Result<(), S
   Ok(())
 }
 
+pub fn cleanup_submission_workdir(db: &Db, log_path: &PathBuf, task_id: &str) -> Result<bool, String> {
+  let base_dir = resolve_submission_base_dir(db, task_id);
+  if !["cut", "merge", "output"]
+    .iter()
+    .any(|name| path_exists(&base_dir.join(name)))
+  {
+    return Ok(false);
+  }
+  cleanup_submission_derived_files(log_path, &base_dir)?;
+  Ok(true)
+}
+
 fn cleanup_submission_derived_files(log_path: &PathBuf, base_dir: &Path) -> Result<(), String> {
   let targets = [
     ("cut", base_dir.join("cut")),
@@ -5400,13 +5579,12 @@ fn load_source_video_paths(
     .map_err(|err| err.to_string())
 }
 
-fn find_active_references(
-  context: &SubmissionContext,
+pub fn find_active_references(
+  db: &Db,
   current_task_id: &str,
   file_path: &str,
 ) -> Result<Vec<DeleteConflictRef>, String> {
-  context
-    .db
+  db
     .with_conn(|conn| {
       let mut stmt = conn.prepare(
         "SELECT st.task_id, st.status, st.title \
@@ -5526,12 +5704,19 @@ fn cleanup_empty_parent_dir(log_path: &PathBuf, path: &Path) {
 pub async fn submission_execute(
   state: State<'_, AppState>,
   task_id: String,
+) -> Result<ApiResponse<String>, String> {
+  submission_execute_inner(&state, task_id).await
+}
+
+pub async fn submission_execute_inner(
+  state: &AppState,
+  task_id: String,
 ) -> Result<ApiResponse<String>, String> {
   append_log(
     &state.app_log_path,
     &format!("submission_execute_request task_id={}", task_id),
   );
-  let context = SubmissionContext::new(&state);
+  let context = SubmissionContext::new(state);
   start_submission_workflow(
     context.db.clone(),
     context.app_log_path.clone(),
@@ -5547,7 +5732,14 @@ pub async fn submission_integrated_execute(
   state: State<'_, AppState>,
   task_id: String,
 ) -> Result<ApiResponse<String>, String> {
-  let context = SubmissionContext::new(&state);
+  submission_integrated_execute_inner(&state, task_id).await
+}
+
+pub async fn submission_integrated_execute_inner(
+  state: &AppState,
+  task_id: String,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(state);
   let task_id = task_id.trim().to_string();
   if task_id.is_empty() {
     return Ok(ApiResponse::error("任务ID不能为空"));
@@ -5599,7 +5791,14 @@ pub async fn submission_upload_execute(
   state: State<'_, AppState>,
   task_id: String,
 ) -> Result<ApiResponse<String>, String> {
-  let context = SubmissionContext::new(&state);
+  submission_upload_execute_inner(&state, task_id).await
+}
+
+pub async fn submission_upload_execute_inner(
+  state: &AppState,
+  task_id: String,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(state);
   let task_id = task_id.trim().to_string();
   if task_id.is_empty() {
     return Ok(ApiResponse::error("任务ID不能为空"));
@@ -5642,7 +5841,14 @@ pub async fn submission_queue_prioritize(
   state: State<'_, AppState>,
   task_id: String,
 ) -> Result<ApiResponse<String>, String> {
-  let context = SubmissionContext::new(&state);
+  submission_queue_prioritize_inner(&state, task_id).await
+}
+
+pub async fn submission_queue_prioritize_inner(
+  state: &AppState,
+  task_id: String,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(state);
   let task_id = task_id.trim().to_string();
   if task_id.is_empty() {
     return Ok(ApiResponse::error("任务ID不能为空"));
@@ -5668,34 +5874,105 @@ pub async fn submission_queue_prioritize(
 }
 
 #[tauri::command]
-pub async fn submission_retry_segment_upload(
+pub fn submission_schedule_update(
   state: State<'_, AppState>,
-  segment_id: String,
-) -> Result<ApiResponse<String>, String> {
-  let context = SubmissionContext::new(&state);
-  let segment_id = segment_id.trim().to_string();
-  if segment_id.is_empty() {
-    return Ok(ApiResponse::error("分段ID不能为空"));
-  }
-  let segment = match load_output_segment_by_id(&context, &segment_id) {
-    Ok(Some(segment)) => segment,
-    Ok(None) => return Ok(ApiResponse::error("未找到分段信息")),
-    Err(err) => return Ok(ApiResponse::error(err)),
-  };
-  if segment.upload_status == "SUCCESS" {
-    return Ok(ApiResponse::success("分段已上传成功".to_string()));
+  task_id: String,
+  scheduled_publish_time: Option<i64>,
+  scheduled_publish_mode: Option<String>,
+) -> ApiResponse<String> {
+  submission_schedule_update_inner(
+    &state,
+    task_id,
+    scheduled_publish_time,
+    scheduled_publish_mode,
+  )
+}
+
+pub fn submission_schedule_update_inner(
+  state: &AppState,
+  task_id: String,
+  scheduled_publish_time: Option<i64>,
+  scheduled_publish_mode: Option<String>,
+) -> ApiResponse<String> {
+  let context = SubmissionContext::new(state);
+  let task_id = task_id.trim().to_string();
+  if task_id.is_empty() {
+    return ApiResponse::error("任务ID不能为空");
   }
-  let status = match load_task_status(&context, &segment.task_id) {
+  let status = match load_task_status(&context, &task_id) {
     Ok(status) => status,
-    Err(err) => return Ok(ApiResponse::error(format!("读取任务状态失败: {}", err))),
+    Err(err) => return ApiResponse::error(format!("读取任务状态失败: {}", err)),
   };
-  if status == "UPLOADING" {
-    return Ok(ApiResponse::error("任务正在投稿中，请稍后重试"));
+  if status == "UPLOADING" || status == "COMPLETED" {
+    return ApiResponse::error("任务已投稿，无法修改定时发布");
   }
-
-  let upload_context = UploadContext::new(&state);
-  let auth = match load_auth_or_refresh(&upload_context, "submission_retry_segment").await {
-    Ok(auth) => auth,
+  let (publish_time, publish_mode) =
+    match normalize_publish_schedule(scheduled_publish_time, scheduled_publish_mode.as_deref()) {
+      Ok(value) => value,
+      Err(err) => return ApiResponse::error(err),
+    };
+  let now = now_rfc3339();
+  if let Err(err) = context.db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE submission_task SET scheduled_publish_time = ?1, scheduled_publish_mode = ?2, updated_at = ?3 WHERE task_id = ?4",
+      (publish_time, publish_mode.as_deref(), &now, &task_id),
+    )?;
+    Ok(())
+  }) {
+    return ApiResponse::error(format!("更新定时发布失败: {}", err));
+  }
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "submission_schedule_update task_id={} publish_at={} mode={}",
+      task_id,
+      publish_time.unwrap_or(0),
+      publish_mode.as_deref().unwrap_or("")
+    ),
+  );
+  if publish_time.is_some() {
+    ApiResponse::success("定时发布已更新".to_string())
+  } else {
+    ApiResponse::success("定时发布已取消".to_string())
+  }
+}
+
+#[tauri::command]
+pub async fn submission_retry_segment_upload(
+  state: State<'_, AppState>,
+  segment_id: String,
+) -> Result<ApiResponse<String>, String> {
+  submission_retry_segment_upload_inner(&state, segment_id).await
+}
+
+pub async fn submission_retry_segment_upload_inner(
+  state: &AppState,
+  segment_id: String,
+) -> Result<ApiResponse<String>, String> {
+  let context = SubmissionContext::new(state);
+  let segment_id = segment_id.trim().to_string();
+  if segment_id.is_empty() {
+    return Ok(ApiResponse::error("分段ID不能为空"));
+  }
+  let segment = match load_output_segment_by_id(&context, &segment_id) {
+    Ok(Some(segment)) => segment,
+    Ok(None) => return Ok(ApiResponse::error("未找到分段信息")),
+    Err(err) => return Ok(ApiResponse::error(err)),
+  };
+  if segment.upload_status == "SUCCESS" {
+    return Ok(ApiResponse::success("分段已上传成功".to_string()));
+  }
+  let status = match load_task_status(&context, &segment.task_id) {
+    Ok(status) => status,
+    Err(err) => return Ok(ApiResponse::error(format!("读取任务状态失败: {}", err))),
+  };
+  if status == "UPLOADING" {
+    return Ok(ApiResponse::error("任务正在投稿中，请稍后重试"));
+  }
+
+  let upload_context = UploadContext::new(state).for_task(&segment.task_id);
+  let auth = match load_auth_or_refresh(&upload_context, "submission_retry_segment").await {
+    Ok(auth) => auth,
     Err(err) => return Ok(ApiResponse::error(err)),
   };
 
@@ -5743,7 +6020,14 @@ pub fn workflow_status(
   state: State<'_, AppState>,
   task_id: String,
 ) -> ApiResponse<Option<WorkflowStatusRecord>> {
-  let context = SubmissionContext::new(&state);
+  workflow_status_inner(&state, task_id)
+}
+
+pub fn workflow_status_inner(
+  state: &AppState,
+  task_id: String,
+) -> ApiResponse<Option<WorkflowStatusRecord>> {
+  let context = SubmissionContext::new(state);
   match load_workflow_status(&context, &task_id) {
     Ok(status) => ApiResponse::success(status),
     Err(err) => ApiResponse::error(format!("Failed to load workflow status: {}", err)),
@@ -5752,7 +6036,11 @@ pub fn workflow_status(
 
 #[tauri::command]
 pub fn workflow_pause(state: State<'_, AppState>, task_id: String) -> ApiResponse<String> {
-  let context = SubmissionContext::new(&state);
+  workflow_pause_inner(&state, task_id)
+}
+
+pub fn workflow_pause_inner(state: &AppState, task_id: String) -> ApiResponse<String> {
+  let context = SubmissionContext::new(state);
   match load_workflow_status(&context, &task_id) {
     Ok(Some(status)) => {
       if status.status != "RUNNING" {
@@ -5770,7 +6058,11 @@ pub fn workflow_pause(state: State<'_, AppState>, task_id: String) -> ApiRespons
 
 #[tauri::command]
 pub fn workflow_resume(state: State<'_, AppState>, task_id: String) -> ApiResponse<String> {
-  let context = SubmissionContext::new(&state);
+  workflow_resume_inner(&state, task_id)
+}
+
+pub fn workflow_resume_inner(state: &AppState, task_id: String) -> ApiResponse<String> {
+  let context = SubmissionContext::new(state);
   match load_workflow_status(&context, &task_id) {
     Ok(Some(status)) => {
       if status.status != "PAUSED" {
@@ -5788,7 +6080,11 @@ pub fn workflow_resume(state: State<'_, AppState>, task_id: String) -> ApiRespon
 
 #[tauri::command]
 pub fn workflow_cancel(state: State<'_, AppState>, task_id: String) -> ApiResponse<String> {
-  let context = SubmissionContext::new(&state);
+  workflow_cancel_inner(&state, task_id)
+}
+
+pub fn workflow_cancel_inner(state: &AppState, task_id: String) -> ApiResponse<String> {
+  let context = SubmissionContext::new(state);
   match set_workflow_instance_status(&context, &task_id, "CANCELLED") {
     Ok(()) => {
       let _ = update_submission_status(&context, &task_id, "CANCELLED");
@@ -5853,7 +6149,7 @@ fn load_tasks(
         format!(
           "SELECT st.task_id, st.status, st.priority, st.title, st.description, st.cover_url, st.partition_id, st.tags, st.topic_id, st.mission_id, st.activity_title, st.video_type, st.collection_id, st.bvid, st.aid, st.remote_state, st.reject_reason, st.created_at, st.updated_at, st.segment_prefix, st.baidu_sync_enabled, st.baidu_sync_path, st.baidu_sync_filename, \
                   CASE WHEN EXISTS (SELECT 1 FROM task_relations tr WHERE tr.submission_task_id = st.task_id) THEN 1 ELSE 0 END, \
-                  wi.status, wi.current_step, wi.progress \
+                  wi.status, wi.current_step, wi.progress, st.account_user_id, st.scheduled_publish_time, st.scheduled_publish_mode \
            FROM submission_task st \
            LEFT JOIN workflow_instances wi ON wi.task_id = st.task_id \
            WHERE st.status = ?1 {} LIMIT ?2 OFFSET ?3",
@@ -5863,7 +6159,7 @@ fn load_tasks(
         format!(
           "SELECT st.task_id, st.status, st.priority, st.title, st.description, st.cover_url, st.partition_id, st.tags, st.topic_id, st.mission_id, st.activity_title, st.video_type, st.collection_id, st.bvid, st.aid, st.remote_state, st.reject_reason, st.created_at, st.updated_at, st.segment_prefix, st.baidu_sync_enabled, st.baidu_sync_path, st.baidu_sync_filename, \
                   CASE WHEN EXISTS (SELECT 1 FROM task_relations tr WHERE tr.submission_task_id = st.task_id) THEN 1 ELSE 0 END, \
-                  wi.status, wi.current_step, wi.progress \
+                  wi.status, wi.current_step, wi.progress, st.account_user_id, st.scheduled_publish_time, st.scheduled_publish_mode \
            FROM submission_task st \
            LEFT JOIN workflow_instances wi ON wi.task_id = st.task_id \
            {} LIMIT ?1 OFFSET ?2",
@@ -5926,6 +6222,9 @@ fn map_submission_task(row: &rusqlite::Row<'_>) -> rusqlite::Result<SubmissionTa
     baidu_sync_filename: row.get(22)?,
     has_integrated_downloads: has_integrated_downloads != 0,
     workflow_status,
+    account_user_id: row.get(27)?,
+    scheduled_publish_time: row.get(28)?,
+    scheduled_publish_mode: row.get(29)?,
   })
 }
 
@@ -5948,7 +6247,7 @@ fn load_task_detail(
       let task = conn.query_row(
         "SELECT st.task_id, st.status, st.priority, st.title, st.description, st.cover_url, st.partition_id, st.tags, st.topic_id, st.mission_id, st.activity_title, st.video_type, st.collection_id, st.bvid, st.aid, st.remote_state, st.reject_reason, st.created_at, st.updated_at, st.segment_prefix, st.baidu_sync_enabled, st.baidu_sync_path, st.baidu_sync_filename, \
                 CASE WHEN EXISTS (SELECT 1 FROM task_relations tr WHERE tr.submission_task_id = st.task_id) THEN 1 ELSE 0 END, \
-                wi.status, wi.current_step, wi.progress \
+                wi.status, wi.current_step, wi.progress, st.account_user_id, st.scheduled_publish_time, st.scheduled_publish_mode \
          FROM submission_task st \
          LEFT JOIN workflow_instances wi ON wi.task_id = st.task_id \
          WHERE st.task_id = ?1",
@@ -5975,7 +6274,8 @@ fn load_task_detail(
       let mut segment_stmt = conn.prepare(
         "SELECT segment_id, task_id, merged_id, part_name, segment_file_path, part_order, upload_status, cid, file_name, \
                 upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, \
-                upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index \
+                upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index, \
+                segment_start_seconds, segment_end_seconds \
          FROM task_output_segment WHERE task_id = ?1 ORDER BY part_order ASC",
       )?;
       let output_segments = segment_stmt
@@ -6000,6 +6300,8 @@ fn load_task_detail(
             upload_uri: row.get(16)?,
             upload_chunk_size: row.get(17)?,
             upload_last_part_index: row.get(18)?,
+            segment_start_seconds: row.get(19)?,
+            segment_end_seconds: row.get(20)?,
           })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -6121,18 +6423,6 @@ struct SourceReadyInfo {
   size: u64,
 }
 
-fn format_timecode_seconds(seconds: f64) -> String {
-  let total = if seconds.is_finite() { seconds.max(0.0) } else { 0.0 };
-  let hours = (total / 3600.0).floor() as i64;
-  let minutes = ((total - (hours as f64 * 3600.0)) / 60.0).floor() as i64;
-  let secs = total - (hours as f64 * 3600.0) - (minutes as f64 * 60.0);
-  if secs.fract().abs() < 0.001 {
-    format!("{:02}:{:02}:{:02}", hours, minutes, secs.floor() as i64)
-  } else {
-    format!("{:02}:{:02}:{:06.3}", hours, minutes, secs)
-  }
-}
-
 async fn check_sources_ready(
   context: &SubmissionContext,
   task_id: &str,
@@ -6371,13 +6661,14 @@ async fn run_submission_workflow(
   let _ = update_workflow_status(&context, &task_id, "RUNNING", Some("CLIPPING"), 0.0);
   update_submission_status(&context, &task_id, "CLIPPING")?;
 
-  let base_dir = resolve_submission_base_dir(&context, &task_id);
+  let base_dir = resolve_submission_base_dir(&context.db, &task_id);
   let workflow_dir = if is_update_workflow {
     let update_stamp = sanitize_filename(&format!("update_{}", now_rfc3339()));
     base_dir.join("updates").join(update_stamp)
   } else {
     base_dir.clone()
   };
+  ensure_workflow_free_space(&context, &task_id, "CLIPPING", &workflow_dir)?;
   let clip_dir = workflow_dir.join("cut");
   let copy_decision = match decide_clip_copy(&sources) {
     Ok(decision) => decision,
@@ -6402,13 +6693,15 @@ async fn run_submission_workflow(
       ),
     );
   }
+  let burn_danmaku = parse_workflow_settings(workflow_config.clone()).burn_danmaku;
   append_log(
     &context.app_log_path,
     &format!(
-      "submission_clip_start task_id={} sources={} use_copy={} output_dir={}",
+      "submission_clip_start task_id={} sources={} use_copy={} burn_danmaku={} output_dir={}",
       task_id,
       sources.len(),
       use_copy,
+      burn_danmaku,
       clip_dir.to_string_lossy()
     ),
   );
@@ -6425,10 +6718,18 @@ async fn run_submission_workflow(
       ),
     );
   }
+  let encoder_settings = load_encoder_settings_from_db(context.db.as_ref())
+    .unwrap_or_else(|_| default_encoder_settings());
   let sources_clone = sources.clone();
   let clip_dir_clone = clip_dir.clone();
   let clip_outputs = match tauri::async_runtime::spawn_blocking(move || {
-    clip_sources(&sources_clone, &clip_dir_clone, use_copy)
+    clip_sources(
+      &sources_clone,
+      &clip_dir_clone,
+      use_copy,
+      burn_danmaku,
+      &encoder_settings,
+    )
   })
   .await
   {
@@ -6467,6 +6768,7 @@ async fn run_submission_workflow(
     !is_update_workflow,
   )?;
 
+  ensure_workflow_free_space(&context, &task_id, "MERGING", &workflow_dir)?;
   update_submission_status(&context, &task_id, "MERGING")?;
   let _ = update_workflow_status(&context, &task_id, "RUNNING", Some("MERGING"), 40.0);
   let merge_output = build_merge_output_path(&workflow_dir, &task_id);
@@ -6538,6 +6840,7 @@ async fn run_submission_workflow(
   let workflow_settings = load_workflow_settings(&context, &task_id);
   if workflow_settings.enable_segmentation {
     let _ = wait_for_workflow_ready(&context, &task_id).await?;
+    ensure_workflow_free_space(&context, &task_id, "SEGMENTING", &workflow_dir)?;
     update_submission_status(&context, &task_id, "SEGMENTING")?;
     let _ = update_workflow_status(&context, &task_id, "RUNNING", Some("SEGMENTING"), 70.0);
     let segment_dir = workflow_dir.join("output");
@@ -6545,19 +6848,22 @@ async fn run_submission_workflow(
     append_log(
       &context.app_log_path,
       &format!(
-        "submission_segment_start task_id={} input={} output_dir={} segment_seconds={} mode=segment_copy",
+        "submission_segment_start task_id={} input={} output_dir={} segment_seconds={} mode=segment_copy cut_mode={}",
         task_id,
         merge_output_segment.to_string_lossy(),
         segment_dir.to_string_lossy(),
-        workflow_settings.segment_duration_seconds
+        workflow_settings.segment_duration_seconds,
+        workflow_settings.segment_options.mode.as_str()
       ),
     );
     let segment_dir_clone = segment_dir.clone();
+    let segment_log_path = context.app_log_path.clone();
     let segment_outputs = tauri::async_runtime::spawn_blocking(move || {
       segment_file(
         &merge_output_segment,
         &segment_dir_clone,
-        workflow_settings.segment_duration_seconds,
+        &workflow_settings.segment_options,
+        &segment_log_path,
       )
     })
     .await
@@ -6610,7 +6916,7 @@ async fn run_submission_workflow(
     append_output_segments(
       &context,
       &task_id,
-      &[merge_output.clone()],
+      &[whole_segment_bounds(&merge_output)?],
       Some(merged_id),
       workflow_settings.segment_prefix.as_deref(),
       max_order + 1,
@@ -6627,6 +6933,27 @@ async fn run_submission_workflow(
   Ok(())
 }
 
+fn ensure_workflow_free_space(
+  context: &SubmissionContext,
+  task_id: &str,
+  step: &str,
+  workflow_dir: &Path,
+) -> Result<(), String> {
+  if let Err(err) = ensure_free_space(&context.db, workflow_dir) {
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "submission_workflow_low_space task_id={} step={} err={}",
+        task_id, step, err
+      ),
+    );
+    let _ = update_workflow_status(context, task_id, "FAILED", Some(step), 0.0);
+    update_submission_status(context, task_id, "FAILED")?;
+    return Err(err);
+  }
+  Ok(())
+}
+
 pub fn start_submission_workflow(
   db: Arc<Db>,
   app_log_path: Arc<PathBuf>,
@@ -6766,10 +7093,16 @@ const REMOTE_AUDIT_STATUS: &str = "is_pubing,not_pubed";
 const REMOTE_DEBUG_BVID: &str = "BV1VJkFBZENQ";
 const UPLOAD_RETRY_BASE_DELAY_SECS: u64 = 2;
 const UPLOAD_RETRY_MAX_DELAY_SECS: u64 = 30;
+const UPLOAD_THROTTLE_SLICE_BYTES: usize = 64 * 1024;
 const PREUPLOAD_PARSE_RETRY_BASE_SECS: u64 = 60;
 const PREUPLOAD_PARSE_RETRY_MAX_SECS: u64 = 30 * 60;
 const PREUPLOAD_PARSE_RETRY_LIMIT: u32 = 6;
 const PREUPLOAD_MIN_INTERVAL_MS: u64 = 1000;
+const PUBLISH_MODE_LOCAL: &str = "LOCAL";
+const PUBLISH_MODE_REMOTE: &str = "REMOTE";
+const REMOTE_PUBLISH_MIN_LEAD_SECS: i64 = 2 * 60 * 60 + 5 * 60;
+const REMOTE_PUBLISH_MAX_LEAD_SECS: i64 = 15 * 24 * 60 * 60;
+const SUBMISSION_SCHEDULE_POLL_SECS: u64 = 30;
 
 static PREUPLOAD_THROTTLE: OnceLock<AsyncMutex<Option<Instant>>> = OnceLock::new();
 
@@ -7297,66 +7630,294 @@ async fn run_submission_upload(
       }
     }
   } else {
-    let submit_result = submit_video_in_batches(&context, &auth, &detail.task, &parts, &csrf).await;
-    match submit_result {
-      Ok(result) => {
-        update_submission_bvid_and_aid(&submission_context, &task_id, &result.bvid, result.aid)?;
-        if let Some(collection_id) = detail.task.collection_id {
-          if collection_id > 0 {
-            let cid = parts.first().map(|item| item.cid).unwrap_or(0);
-            let add_result = add_video_to_collection_with_refresh(
-              &context,
-              &auth,
-              &detail.task.title,
-              collection_id,
-              result.aid,
-              cid,
-              &csrf,
-            )
-            .await;
-            if let Err(err) = add_result {
-              if is_collection_not_found_error(&err) {
-                append_log(
-                  &context.app_log_path,
-                  &format!(
-                    "submission_collection_skip task_id={} collection_id={} err={}",
-                    task_id, collection_id, err
-                  ),
+    if let PublishSchedule::Local(publish_at) =
+      resolve_publish_schedule(&detail.task, Utc::now().timestamp())
+    {
+      update_submission_status(&submission_context, &task_id, "SCHEDULED")?;
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "submission_schedule_hold task_id={} publish_at={}",
+          task_id, publish_at
+        ),
+      );
+      return Ok(());
+    }
+    submit_new_archive(&context, &submission_context, &auth, &csrf, &task_id, &detail, &parts).await
+  }
+}
+
+async fn submit_new_archive(
+  context: &UploadContext,
+  submission_context: &SubmissionContext,
+  auth: &AuthInfo,
+  csrf: &str,
+  task_id: &str,
+  detail: &SubmissionTaskDetail,
+  parts: &[UploadedVideoPart],
+) -> Result<(), String> {
+  let submit_result = submit_video_in_batches(context, auth, &detail.task, parts, csrf).await;
+  match submit_result {
+    Ok(result) => {
+      update_submission_bvid_and_aid(submission_context, task_id, &result.bvid, result.aid)?;
+      if let Some(collection_id) = detail.task.collection_id {
+        if collection_id > 0 {
+          let cid = parts.first().map(|item| item.cid).unwrap_or(0);
+          let add_result = add_video_to_collection_with_refresh(
+            context,
+            auth,
+            &detail.task.title,
+            collection_id,
+            result.aid,
+            cid,
+            csrf,
+          )
+          .await;
+          if let Err(err) = add_result {
+            if is_collection_not_found_error(&err) {
+              append_log(
+                &context.app_log_path,
+                &format!(
+                  "submission_collection_skip task_id={} collection_id={} err={}",
+                  task_id, collection_id, err
+                ),
+              );
+            } else {
+              update_submission_status(submission_context, task_id, "FAILED")?;
+              append_log(
+                &context.app_log_path,
+                &format!(
+                  "submission_collection_fail task_id={} collection_id={} err={}",
+                  task_id, collection_id, err
+                ),
+              );
+              return Err(err);
+            }
+          }
+        }
+      }
+      update_submission_status(submission_context, task_id, "COMPLETED")?;
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "submission_upload_ok task_id={} bvid={} aid={}",
+          task_id, result.bvid, result.aid
+        ),
+      );
+      Ok(())
+    }
+    Err(err) => {
+      update_submission_status(submission_context, task_id, "FAILED")?;
+      append_log(
+        &context.app_log_path,
+        &format!("submission_upload_submit_fail task_id={} err={}", task_id, err),
+      );
+      Err(err)
+    }
+  }
+}
+
+enum PublishSchedule {
+  Immediate,
+  Remote(i64),
+  Local(i64),
+}
+
+fn resolve_publish_schedule(task: &SubmissionTaskRecord, now: i64) -> PublishSchedule {
+  let Some(publish_at) = task.scheduled_publish_time.filter(|value| *value > now) else {
+    return PublishSchedule::Immediate;
+  };
+  let lead = publish_at - now;
+  if task.scheduled_publish_mode.as_deref() == Some(PUBLISH_MODE_REMOTE)
+    && (REMOTE_PUBLISH_MIN_LEAD_SECS..=REMOTE_PUBLISH_MAX_LEAD_SECS).contains(&lead)
+  {
+    return PublishSchedule::Remote(publish_at);
+  }
+  PublishSchedule::Local(publish_at)
+}
+
+pub fn normalize_publish_schedule(
+  publish_time: Option<i64>,
+  mode: Option<&str>,
+) -> Result<(Option<i64>, Option<String>), String> {
+  let Some(publish_time) = publish_time.filter(|value| *value > 0) else {
+    return Ok((None, None));
+  };
+  let now = Utc::now().timestamp();
+  if publish_time <= now {
+    return Err("定时发布时间需晚于当前时间".to_string());
+  }
+  let mode = mode.unwrap_or("").trim().to_ascii_uppercase();
+  let mode = if mode.is_empty() {
+    PUBLISH_MODE_LOCAL.to_string()
+  } else {
+    mode
+  };
+  if mode == PUBLISH_MODE_REMOTE {
+    let lead = publish_time - now;
+    if lead < REMOTE_PUBLISH_MIN_LEAD_SECS {
+      return Err("B站定时发布时间需晚于当前时间2小时以上".to_string());
+    }
+    if lead > REMOTE_PUBLISH_MAX_LEAD_SECS {
+      return Err("B站定时发布时间需在15天以内".to_string());
+    }
+  } else if mode != PUBLISH_MODE_LOCAL {
+    return Err(format!("不支持的定时发布方式: {}", mode));
+  }
+  Ok((Some(publish_time), Some(mode)))
+}
+
+async fn submission_schedule_loop(context: SubmissionQueueContext) {
+  let submission_context = SubmissionContext {
+    db: context.db.clone(),
+    app_log_path: context.app_log_path.clone(),
+    edit_upload_state: context.edit_upload_state.clone(),
+  };
+  let mut retry_state: HashMap<String, (u32, i64)> = HashMap::new();
+  loop {
+    let now = Utc::now().timestamp();
+    match load_due_scheduled_tasks(&submission_context, now) {
+      Ok(task_ids) => {
+        retry_state.retain(|task_id, _| task_ids.contains(task_id));
+        for task_id in task_ids {
+          if let Some((_, retry_at)) = retry_state.get(&task_id) {
+            if *retry_at > now {
+              continue;
+            }
+          }
+          append_log(
+            &context.app_log_path,
+            &format!("submission_schedule_due task_id={}", task_id),
+          );
+          match publish_scheduled_task(&context, &submission_context, &task_id).await {
+            Ok(()) => {
+              retry_state.remove(&task_id);
+            }
+            Err(err) => {
+              append_log(
+                &context.app_log_path,
+                &format!("submission_schedule_publish_fail task_id={} err={}", task_id, err),
+              );
+              if is_retryable_submission_error(&err) {
+                let attempt = retry_state.get(&task_id).map(|(value, _)| *value).unwrap_or(0) + 1;
+                let wait_secs = submission_queue_retry_delay_secs(attempt);
+                retry_state.insert(
+                  task_id.clone(),
+                  (attempt, Utc::now().timestamp() + wait_secs as i64),
                 );
-              } else {
-                update_submission_status(&submission_context, &task_id, "FAILED")?;
                 append_log(
                   &context.app_log_path,
                   &format!(
-                    "submission_collection_fail task_id={} collection_id={} err={}",
-                    task_id, collection_id, err
+                    "submission_schedule_retry_wait task_id={} attempt={} wait_secs={}",
+                    task_id, attempt, wait_secs
                   ),
                 );
-                return Err(err);
+                let _ = update_submission_status(&submission_context, &task_id, "SCHEDULED");
+              } else {
+                retry_state.remove(&task_id);
               }
             }
           }
         }
-        update_submission_status(&submission_context, &task_id, "COMPLETED")?;
-        append_log(
-          &context.app_log_path,
-          &format!(
-            "submission_upload_ok task_id={} bvid={} aid={}",
-            task_id, result.bvid, result.aid
-          ),
-        );
-        Ok(())
       }
       Err(err) => {
-        update_submission_status(&submission_context, &task_id, "FAILED")?;
         append_log(
           &context.app_log_path,
-          &format!("submission_upload_submit_fail task_id={} err={}", task_id, err),
+          &format!("submission_schedule_load_fail err={}", err),
         );
-        Err(err)
       }
     }
+    sleep(Duration::from_secs(SUBMISSION_SCHEDULE_POLL_SECS)).await;
+  }
+}
+
+fn load_due_scheduled_tasks(context: &SubmissionContext, now: i64) -> Result<Vec<String>, String> {
+  context
+    .db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT task_id FROM submission_task WHERE status = 'SCHEDULED' \
+         AND (scheduled_publish_time IS NULL OR scheduled_publish_time <= ?1) \
+         ORDER BY scheduled_publish_time ASC",
+      )?;
+      let rows = stmt.query_map([now], |row| row.get(0))?;
+      rows.collect::<Result<Vec<String>, _>>()
+    })
+    .map_err(|err| err.to_string())
+}
+
+async fn publish_scheduled_task(
+  context: &SubmissionQueueContext,
+  submission_context: &SubmissionContext,
+  task_id: &str,
+) -> Result<(), String> {
+  let upload_context = UploadContext {
+    db: context.db.clone(),
+    bilibili: context.bilibili.clone(),
+    login_store: context.login_store.clone(),
+    app_log_path: context.app_log_path.clone(),
+    edit_upload_state: context.edit_upload_state.clone(),
+    account_user_id: load_task_account_user_id(&context.db, task_id).unwrap_or(None),
+  };
+  update_submission_status(submission_context, task_id, "UPLOADING")?;
+  let (auth, csrf, detail, parts) =
+    match prepare_scheduled_publish(&upload_context, submission_context, task_id).await {
+      Ok(prepared) => prepared,
+      Err(err) => {
+        update_submission_status(submission_context, task_id, "FAILED")?;
+        return Err(err);
+      }
+    };
+  submit_new_archive(
+    &upload_context,
+    submission_context,
+    &auth,
+    &csrf,
+    task_id,
+    &detail,
+    &parts,
+  )
+  .await
+}
+
+async fn prepare_scheduled_publish(
+  context: &UploadContext,
+  submission_context: &SubmissionContext,
+  task_id: &str,
+) -> Result<(AuthInfo, String, SubmissionTaskDetail, Vec<UploadedVideoPart>), String> {
+  let mut auth = load_auth_or_refresh(context, "submission_schedule_publish").await?;
+  if auth.csrf.is_none() {
+    auth = refresh_auth(context, "submission_schedule_publish_csrf").await?;
+  }
+  let csrf = auth
+    .csrf
+    .clone()
+    .ok_or_else(|| "登录信息缺少CSRF".to_string())?;
+  let detail = load_task_detail(submission_context, task_id)?;
+  let settings = load_workflow_settings(submission_context, task_id);
+  let parts = if settings.enable_segmentation {
+    build_uploaded_parts(&detail, false)?
+  } else {
+    let merged = load_latest_merged_video(submission_context, task_id)?
+      .ok_or_else(|| "未找到合并视频".to_string())?;
+    let cid = merged
+      .upload_cid
+      .filter(|value| *value > 0)
+      .ok_or_else(|| "合并视频上传信息缺失，请重新投稿".to_string())?;
+    let filename = merged
+      .upload_file_name
+      .filter(|value| !value.trim().is_empty())
+      .ok_or_else(|| "合并视频上传信息缺失，请重新投稿".to_string())?;
+    vec![UploadedVideoPart {
+      filename,
+      cid,
+      title: build_part_title(detail.task.segment_prefix.as_deref(), 1),
+    }]
+  };
+  if parts.is_empty() {
+    return Err("投稿文件为空".to_string());
   }
+  Ok((auth, csrf, detail, parts))
 }
 
 async fn submission_queue_loop(context: SubmissionQueueContext) {
@@ -7391,6 +7952,7 @@ async fn submission_queue_loop(context: SubmissionQueueContext) {
       login_store: context.login_store.clone(),
       app_log_path: context.app_log_path.clone(),
       edit_upload_state: context.edit_upload_state.clone(),
+      account_user_id: load_task_account_user_id(&context.db, &task_id).unwrap_or(None),
     };
     let mut queue_retry_round: u32 = 0;
     loop {
@@ -7496,18 +8058,58 @@ async fn submission_remote_refresh_loop(context: SubmissionQueueContext) {
 async fn refresh_submission_remote_state(
   context: &SubmissionQueueContext,
 ) -> Result<(), String> {
-  let auth = match load_auth_from_queue_context(context) {
-    Ok(auth) => auth,
-    Err(err) => {
-      append_log(
-        &context.app_log_path,
-        &format!("submission_remote_refresh_skip reason={}", err),
-      );
-      return Ok(());
+  let task_accounts = load_task_bvids(context)?;
+  if task_accounts.is_empty() {
+    return Ok(());
+  }
+  let default_user_id = context.login_store.default_user_id(&context.db).unwrap_or(None);
+  let mut account_ids = task_accounts
+    .iter()
+    .map(|(_, _, account_user_id)| account_user_id.or(default_user_id))
+    .collect::<Vec<_>>();
+  account_ids.sort();
+  account_ids.dedup();
+  let mut remote_map = HashMap::new();
+  let mut refreshed_accounts = HashSet::new();
+  for account_user_id in account_ids {
+    let auth = match load_auth_from_queue_context(context, account_user_id) {
+      Ok(auth) => auth,
+      Err(err) => {
+        append_log(
+          &context.app_log_path,
+          &format!(
+            "submission_remote_refresh_skip user_id={} reason={}",
+            account_user_id.unwrap_or(0),
+            err
+          ),
+        );
+        continue;
+      }
+    };
+    match fetch_remote_audit_map(context, &auth).await {
+      Ok(map) => {
+        remote_map.extend(map);
+        refreshed_accounts.insert(account_user_id);
+      }
+      Err(err) => {
+        append_log(
+          &context.app_log_path,
+          &format!(
+            "submission_remote_refresh_fail user_id={} err={}",
+            account_user_id.unwrap_or(0),
+            err
+          ),
+        );
+      }
     }
-  };
-  let remote_map = fetch_remote_audit_map(context, &auth).await?;
-  let task_bvids = load_task_bvids(context)?;
+  }
+  let task_bvids: Vec<(String, String)> = task_accounts
+    .into_iter()
+    .filter(|(_, _, account_user_id)| {
+      refreshed_accounts.contains(&account_user_id.or(default_user_id))
+    })
+    .map(|(task_id, bvid, _)| (task_id, bvid))
+    .collect();
   if task_bvids.is_empty() {
     return Ok(());
   }
@@ -7551,10 +8153,11 @@ async fn refresh_submission_remote_state(
       ),
     );
   }
-  context
+  let rejected = context
     .db
     .with_conn_mut(|conn| {
       let tx = conn.transaction()?;
+      let mut rejected = Vec::new();
       for (task_id, bvid) in task_bvids {
         if bvid == REMOTE_DEBUG_BVID {
           if let Some(info) = remote_map.get(&bvid) {
@@ -7575,10 +8178,19 @@ async fn refresh_submission_remote_state(
           }
         }
         if let Some(info) = remote_map.get(&bvid) {
+          let (title, previous_reason) = tx.query_row(
+            "SELECT title, reject_reason FROM submission_task WHERE task_id = ?1",
+            [&task_id],
+            |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, Option<String>>(1)?)),
+          )?;
           tx.execute(
             "UPDATE submission_task SET remote_state = ?1, reject_reason = ?2 WHERE task_id = ?3",
             (info.state, info.reject_reason.as_deref(), &task_id),
           )?;
+          let reason = info.reject_reason.as_deref().unwrap_or("").trim();
+          if !reason.is_empty() && previous_reason.as_deref().map(str::trim) != Some(reason) {
+            rejected.push((task_id, bvid, title.unwrap_or_default(), reason.to_string(), info.state));
+          }
         } else {
           tx.execute(
             "UPDATE submission_task SET remote_state = ?1, reject_reason = NULL WHERE task_id = ?2",
@@ -7587,21 +8199,45 @@ async fn refresh_submission_remote_state(
         }
       }
       tx.commit()?;
-      Ok(())
+      Ok(rejected)
     })
     .map_err(|err| err.to_string())?;
+  for (task_id, bvid, title, reason, remote_state) in rejected {
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "submission_remote_rejected task_id={} bvid={} reason={}",
+        task_id, bvid, reason
+      ),
+    );
+    emit_webhook_event(
+      &context.db,
+      &context.app_log_path,
+      EVENT_SUBMISSION_REJECTED,
+      &format!("稿件审核未通过: {} ({})", title, reason),
+      serde_json::json!({
+        "taskId": task_id,
+        "bvid": bvid,
+        "title": title,
+        "remoteState": remote_state,
+        "rejectReason": reason,
+      }),
+    );
+  }
   Ok(())
 }
 
-fn load_task_bvids(context: &SubmissionQueueContext) -> Result<Vec<(String, String)>, String> {
+fn load_task_bvids(
+  context: &SubmissionQueueContext,
+) -> Result<Vec<(String, String, Option<i64>)>, String> {
   context
     .db
     .with_conn(|conn| {
       let mut stmt = conn.prepare(
-        "SELECT task_id, bvid FROM submission_task WHERE bvid IS NOT NULL AND TRIM(bvid) != ''",
+        "SELECT task_id, bvid, account_user_id FROM submission_task WHERE bvid IS NOT NULL AND TRIM(bvid) != ''",
       )?;
-      let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
-      let list = rows.collect::<Result<Vec<(String, String)>, _>>()?;
+      let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+      let list = rows.collect::<Result<Vec<(String, String, Option<i64>)>, _>>()?;
       Ok(list)
     })
     .map_err(|err| err.to_string())
@@ -8486,6 +9122,7 @@ async fn upload_video_chunks(
       .read_exact(&mut buffer)
       .await
       .map_err(|err| format!("读取分片失败: {}", err))?;
+    let buffer = Arc::new(buffer);
     let start = offset;
     let end = offset + current_size as u64;
     let params = vec![
@@ -8509,12 +9146,13 @@ async fn upload_video_chunks(
         "Content-Type",
         HeaderValue::from_static("application/octet-stream"),
       );
+      headers.insert(CONTENT_LENGTH, HeaderValue::from(current_size as u64));
 
       let response = client
         .put(upload_url.clone())
         .headers(headers)
         .query(&params)
-        .body(buffer.clone())
+        .body(throttled_upload_body(Arc::clone(&buffer)))
         .send()
         .await
         .map_err(|err| format!("上传分片失败: {}", err))?;
@@ -8555,6 +9193,21 @@ async fn upload_video_chunks(
   Ok(total_chunks)
 }
 
+fn throttled_upload_body(data: Arc<Vec<u8>>) -> reqwest::Body {
+  let stream = futures_util::stream::unfold(0usize, move |offset| {
+    let data = Arc::clone(&data);
+    async move {
+      if offset >= data.len() {
+        return None;
+      }
+      let end = (offset + UPLOAD_THROTTLE_SLICE_BYTES).min(data.len());
+      acquire(TransferClass::Upload, (end - offset) as u64).await;
+      Some((Ok::<_, std::io::Error>(data[offset..end].to_vec()), end))
+    }
+  });
+  reqwest::Body::wrap_stream(stream)
+}
+
 async fn end_upload(
   context: &SubmissionContext,
   target: &UploadTarget,
@@ -8995,6 +9648,9 @@ fn build_add_payload(task: &SubmissionTaskRecord, parts: &[UploadedVideoPart]) -
       payload["mission_id"] = serde_json::json!(mission_id);
     }
   }
+  if let PublishSchedule::Remote(dtime) = resolve_publish_schedule(task, Utc::now().timestamp()) {
+    payload["dtime"] = serde_json::json!(dtime);
+  }
 
   payload
 }
@@ -9508,7 +10164,16 @@ fn rebuild_missing_merged_video(
     use_copy: false,
     reason: Some("rebuild_copy_decision_failed".to_string()),
   });
-  let clip_outputs = clip_sources(sources, &rebuild_dir, copy_decision.use_copy)?;
+  let burn_danmaku = load_workflow_settings(context, task_id).burn_danmaku;
+  let encoder_settings = load_encoder_settings_from_db(context.db.as_ref())
+    .unwrap_or_else(|_| default_encoder_settings());
+  let clip_outputs = clip_sources(
+    sources,
+    &rebuild_dir,
+    copy_decision.use_copy,
+    burn_danmaku,
+    &encoder_settings,
+  )?;
   merge_files(&clip_outputs, &target_path)?;
   update_merged_video_path(context, merged.id, &target_path)?;
   save_merged_source_bindings(context, task_id, merged.id, sources)?;
@@ -9944,13 +10609,14 @@ fn parse_leading_number(value: &str) -> Option<usize> {
 fn append_output_segments(
   context: &SubmissionContext,
   task_id: &str,
-  segments: &[PathBuf],
+  segments: &[(PathBuf, f64, f64)],
   merged_id: Option<i64>,
   prefix: Option<&str>,
   part_order_start: i64,
   name_start_index: usize,
 ) -> Result<(), String> {
-  let segments = rename_segment_outputs_with_prefix(segments, prefix, name_start_index)?;
+  let (paths, bounds) = split_segment_bounds(segments);
+  let segments = rename_segment_outputs_with_prefix(&paths, prefix, name_start_index)?;
   context
     .db
     .with_conn(|conn| {
@@ -9961,8 +10627,8 @@ fn append_output_segments(
         let part_order = part_order_start + index as i64;
         let part_name = build_part_title(prefix, name_start_index + index);
         conn.execute(
-          "INSERT INTO task_output_segment (segment_id, task_id, merged_id, part_name, segment_file_path, part_order, upload_status, cid, file_name, upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index) \
-           VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'PENDING', NULL, ?7, 0, 0, ?8, NULL, 0, NULL, NULL, NULL, 0, 0)",
+          "INSERT INTO task_output_segment (segment_id, task_id, merged_id, part_name, segment_file_path, part_order, upload_status, cid, file_name, upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index, segment_start_seconds, segment_end_seconds) \
+           VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'PENDING', NULL, ?7, 0, 0, ?8, NULL, 0, NULL, NULL, NULL, 0, 0, ?9, ?10)",
           (
             segment_id,
             task_id,
@@ -9972,6 +10638,8 @@ fn append_output_segments(
             part_order,
             file_name,
             total_bytes as i64,
+            bounds[index].0,
+            bounds[index].1,
           ),
         )?;
       }
@@ -9980,14 +10648,22 @@ fn append_output_segments(
     .map_err(|err| err.to_string())
 }
 
+fn split_segment_bounds(segments: &[(PathBuf, f64, f64)]) -> (Vec<PathBuf>, Vec<(f64, f64)>) {
+  segments
+    .iter()
+    .map(|(path, start, end)| (path.clone(), (*start, *end)))
+    .unzip()
+}
+
 fn save_output_segments(
   context: &SubmissionContext,
   task_id: &str,
-  segments: &[PathBuf],
+  segments: &[(PathBuf, f64, f64)],
   merged_id: Option<i64>,
   prefix: Option<&str>,
 ) -> Result<(), String> {
-  let segments = rename_segment_outputs_with_prefix(segments, prefix, 1)?;
+  let (paths, bounds) = split_segment_bounds(segments);
+  let segments = rename_segment_outputs_with_prefix(&paths, prefix, 1)?;
   let has_prefix = prefix.map(|value| !value.trim().is_empty()).unwrap_or(false);
   context
     .db
@@ -10003,8 +10679,8 @@ fn save_output_segments(
           format!("Part {}", index + 1)
         };
         conn.execute(
-          "INSERT INTO task_output_segment (segment_id, task_id, merged_id, part_name, segment_file_path, part_order, upload_status, cid, file_name, upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index) \
-           VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'PENDING', NULL, ?7, 0, 0, ?8, NULL, 0, NULL, NULL, NULL, 0, 0)",
+          "INSERT INTO task_output_segment (segment_id, task_id, merged_id, part_name, segment_file_path, part_order, upload_status, cid, file_name, upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index, segment_start_seconds, segment_end_seconds) \
+           VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'PENDING', NULL, ?7, 0, 0, ?8, NULL, 0, NULL, NULL, NULL, 0, 0, ?9, ?10)",
           (
             segment_id,
             task_id,
@@ -10014,6 +10690,8 @@ fn save_output_segments(
             (index + 1) as i64,
             file_name,
             total_bytes as i64,
+            bounds[index].0,
+            bounds[index].1,
           ),
         )?;
       }
@@ -10348,7 +11026,8 @@ fn load_output_segment_by_id(
       let mut stmt = conn.prepare(
         "SELECT segment_id, task_id, merged_id, part_name, segment_file_path, part_order, upload_status, cid, file_name, \
                 upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, \
-                upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index \
+                upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index, \
+                segment_start_seconds, segment_end_seconds \
          FROM task_output_segment WHERE segment_id = ?1",
       )?;
       let result = stmt
@@ -10373,6 +11052,8 @@ fn load_output_segment_by_id(
             upload_uri: row.get(16)?,
             upload_chunk_size: row.get(17)?,
             upload_last_part_index: row.get(18)?,
+            segment_start_seconds: row.get(19)?,
+            segment_end_seconds: row.get(20)?,
           })
         })
         .ok();
@@ -10447,6 +11128,24 @@ fn update_submission_task_for_edit(
     .map_err(|err| err.to_string())
 }
 
+fn update_task_account_user_id(
+  context: &SubmissionContext,
+  task_id: &str,
+  account_user_id: Option<i64>,
+) -> Result<(), String> {
+  let now = now_rfc3339();
+  context
+    .db
+    .with_conn(|conn| {
+      conn.execute(
+        "UPDATE submission_task SET account_user_id = ?1, updated_at = ?2 WHERE task_id = ?3",
+        (account_user_id, &now, task_id),
+      )?;
+      Ok(())
+    })
+    .map_err(|err| err.to_string())
+}
+
 fn update_baidu_sync_config(
   context: &SubmissionContext,
   task_id: &str,
@@ -10653,6 +11352,20 @@ fn load_task_status(context: &SubmissionContext, task_id: &str) -> Result<String
     .map_err(|err| err.to_string())
 }
 
+fn load_task_account_user_id(db: &Db, task_id: &str) -> Result<Option<i64>, String> {
+  db.with_conn(|conn| {
+    conn
+      .query_row(
+        "SELECT account_user_id FROM submission_task WHERE task_id = ?1",
+        [task_id],
+        |row| row.get::<_, Option<i64>>(0),
+      )
+      .optional()
+      .map(|value| value.flatten())
+  })
+  .map_err(|err| err.to_string())
+}
+
 struct IntegratedDownloadStats {
   total: i64,
   completed: i64,
@@ -10723,7 +11436,7 @@ async fn load_auth_or_refresh(
 ) -> Result<AuthInfo, String> {
   if let Some(auth) = context
     .login_store
-    .load_auth_info(&context.db)
+    .load_auth_info_for(&context.db, context.account_user_id)
     .ok()
     .flatten()
   {
@@ -10738,13 +11451,18 @@ async fn refresh_auth(
 ) -> Result<AuthInfo, String> {
   append_log(
     &context.app_log_path,
-    &format!("submission_cookie_refresh_start reason={}", reason),
+    &format!(
+      "submission_cookie_refresh_start reason={} user_id={}",
+      reason,
+      context.account_user_id.unwrap_or(0)
+    ),
   );
   match login_refresh::refresh_cookie(
     &context.bilibili,
     &context.login_store,
     &context.db,
     &context.app_log_path,
+    context.account_user_id,
   )
   .await
   {
@@ -10775,10 +11493,11 @@ fn is_auth_error(err: &str) -> bool {
 
 fn load_auth_from_queue_context(
   context: &SubmissionQueueContext,
+  account_user_id: Option<i64>,
 ) -> Result<AuthInfo, String> {
   context
     .login_store
-    .load_auth_info(&context.db)
+    .load_auth_info_for(&context.db, account_user_id)
     .ok()
     .flatten()
     .ok_or_else(|| "请先登录".to_string())
@@ -10947,7 +11666,7 @@ fn ensure_merged_video_records(
   context: &SubmissionContext,
   task_id: &str,
 ) -> Result<(), String> {
-  let base_dir = resolve_submission_base_dir(context, task_id);
+  let base_dir = resolve_submission_base_dir(&context.db, task_id);
   let candidates = collect_merge_video_paths(&base_dir);
   if candidates.is_empty() {
     return Ok(());
@@ -11102,7 +11821,7 @@ fn replace_segments_for_merged(
   context: &SubmissionContext,
   task_id: &str,
   merged_id: i64,
-  outputs: &[PathBuf],
+  outputs: &[(PathBuf, f64, f64)],
   segment_prefix: Option<&str>,
 ) -> Result<(), String> {
   if outputs.is_empty() {
@@ -11120,7 +11839,8 @@ fn replace_segments_for_merged(
     }
   };
   let name_start_index = if start_order > 0 { start_order as usize } else { 1 };
-  let outputs = rename_segment_outputs_with_prefix(outputs, segment_prefix, name_start_index)?;
+  let (paths, bounds) = split_segment_bounds(outputs);
+  let outputs = rename_segment_outputs_with_prefix(&paths, segment_prefix, name_start_index)?;
   let delta = outputs.len() as i64 - old_count;
   context
     .db
@@ -11161,8 +11881,8 @@ fn replace_segments_for_merged(
         let part_index = if part_order > 0 { part_order as usize } else { 1 };
         let part_name = build_part_title(segment_prefix, part_index);
         tx.execute(
-          "INSERT INTO task_output_segment (segment_id, task_id, merged_id, part_name, segment_file_path, part_order, upload_status, cid, file_name, upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index) \
-           VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'PENDING', NULL, ?7, 0, 0, ?8, NULL, 0, NULL, NULL, NULL, 0, 0)",
+          "INSERT INTO task_output_segment (segment_id, task_id, merged_id, part_name, segment_file_path, part_order, upload_status, cid, file_name, upload_progress, upload_uploaded_bytes, upload_total_bytes, upload_session_id, upload_biz_id, upload_endpoint, upload_auth, upload_uri, upload_chunk_size, upload_last_part_index, segment_start_seconds, segment_end_seconds) \
+           VALUES (?1, ?2, ?3, ?4, ?5, ?6, 'PENDING', NULL, ?7, 0, 0, ?8, NULL, 0, NULL, NULL, NULL, 0, 0, ?9, ?10)",
           (
             segment_id,
             task_id,
@@ -11172,6 +11892,8 @@ fn replace_segments_for_merged(
             part_order,
             file_name,
             total_bytes as i64,
+            bounds[index].0,
+            bounds[index].1,
           ),
         )?;
       }
@@ -11230,16 +11952,81 @@ fn update_submission_status(
   status: &str,
 ) -> Result<(), String> {
   let now = now_rfc3339();
-  context
+  let previous = context
     .db
     .with_conn(|conn| {
+      let previous = conn
+        .query_row(
+          "SELECT status, title FROM submission_task WHERE task_id = ?1",
+          [task_id],
+          |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)),
+        )
+        .optional()?;
       conn.execute(
         "UPDATE submission_task SET status = ?1, updated_at = ?2 WHERE task_id = ?3",
         (status, &now, task_id),
       )?;
+      Ok(previous)
+    })
+    .map_err(|err| err.to_string())?;
+  if let Some((previous_status, title)) = previous {
+    notify_submission_status_change(
+      &context.db,
+      &context.app_log_path,
+      task_id,
+      title.as_deref().unwrap_or(""),
+      &previous_status,
+      status,
+    );
+  }
+  Ok(())
+}
+
+fn reset_submission_status(
+  context: &SubmissionContext,
+  task_id: &str,
+  status: &str,
+  clear_bvid: bool,
+) -> Result<(), String> {
+  let now = now_rfc3339();
+  let sql = if clear_bvid {
+    "UPDATE submission_task SET bvid = NULL, aid = NULL, remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2"
+  } else {
+    "UPDATE submission_task SET remote_state = NULL, reject_reason = NULL, updated_at = ?1 WHERE task_id = ?2"
+  };
+  context
+    .db
+    .with_conn(|conn| {
+      conn.execute(sql, (&now, task_id))?;
       Ok(())
     })
-    .map_err(|err| err.to_string())
+    .map_err(|err| err.to_string())?;
+  update_submission_status(context, task_id, status)
+}
+
+pub(crate) fn notify_submission_status_change(
+  db: &Db,
+  log_path: &Path,
+  task_id: &str,
+  title: &str,
+  previous: &str,
+  status: &str,
+) {
+  if previous == status {
+    return;
+  }
+  emit_webhook_event(
+    db,
+    log_path,
+    EVENT_SUBMISSION_STATUS,
+    &format!("投稿任务状态变更 {} -> {}: {}", previous, status, title),
+    serde_json::json!({
+      "taskId": task_id,
+      "title": title,
+      "previousStatus": previous,
+      "status": status,
+    }),
+  );
 }
 
 fn reset_failed_segments_to_pending(
@@ -11258,8 +12045,8 @@ fn reset_failed_segments_to_pending(
     .map_err(|err| err.to_string())
 }
 
-fn resolve_submission_base_dir(context: &SubmissionContext, task_id: &str) -> PathBuf {
-  let configured = load_download_settings_from_db(&context.db)
+fn resolve_submission_base_dir(db: &Db, task_id: &str) -> PathBuf {
+  let configured = load_download_settings_from_db(db)
     .map(|settings| settings.download_path)
     .ok()
     .unwrap_or_default();
@@ -11435,6 +12222,8 @@ struct WorkflowSettings {
   enable_segmentation: bool,
   segment_duration_seconds: i64,
   segment_prefix: Option<String>,
+  segment_options: SegmentOptions,
+  burn_danmaku: bool,
 }
 
 fn load_workflow_settings(context: &SubmissionContext, task_id: &str) -> WorkflowSettings {
@@ -11462,16 +12251,47 @@ fn parse_workflow_settings(config: Option<Value>) -> WorkflowSettings {
       .and_then(|value| value.get("segmentDurationSeconds"))
       .and_then(|value| value.as_i64())
       .unwrap_or(133);
+    let segment_defaults = SegmentOptions::default();
+    let segment_options = SegmentOptions {
+      segment_seconds: segment_duration_seconds.max(1),
+      mode: SegmentMode::parse(
+        segmentation
+          .and_then(|value| value.get("mode"))
+          .and_then(|value| value.as_str()),
+      ),
+      search_window_seconds: segmentation
+        .and_then(|value| value.get("searchWindowSeconds"))
+        .and_then(|value| value.as_f64())
+        .unwrap_or(segment_defaults.search_window_seconds),
+      silence_noise_db: segmentation
+        .and_then(|value| value.get("silenceNoiseDb"))
+        .and_then(|value| value.as_f64())
+        .unwrap_or(segment_defaults.silence_noise_db),
+      silence_min_seconds: segmentation
+        .and_then(|value| value.get("silenceMinSeconds"))
+        .and_then(|value| value.as_f64())
+        .unwrap_or(segment_defaults.silence_min_seconds),
+      scene_threshold: segmentation
+        .and_then(|value| value.get("sceneThreshold"))
+        .and_then(|value| value.as_f64())
+        .unwrap_or(segment_defaults.scene_threshold),
+    };
     let segment_prefix = config
       .get("segmentPrefix")
       .and_then(|value| value.as_str())
       .map(|value| value.trim().to_string())
       .filter(|value| !value.is_empty());
+    let burn_danmaku = config
+      .get("burnDanmaku")
+      .and_then(|value| value.as_bool())
+      .unwrap_or(false);
 
     return WorkflowSettings {
       enable_segmentation,
       segment_duration_seconds,
       segment_prefix,
+      segment_options,
+      burn_danmaku,
     };
   }
 
@@ -11479,13 +12299,12 @@ fn parse_workflow_settings(config: Option<Value>) -> WorkflowSettings {
     enable_segmentation: false,
     segment_duration_seconds: 133,
     segment_prefix: None,
+    segment_options: SegmentOptions::default(),
+    burn_danmaku: false,
   }
 }
 
-fn build_resegment_workflow_config(
-  config: Option<Value>,
-  segment_duration_seconds: i64,
-) -> Value {
+fn build_resegment_workflow_config(config: Option<Value>, request: &SubmissionResegmentRequest) -> Value {
   let mut config = match config {
     Some(Value::Object(map)) => Value::Object(map),
     Some(_) => Value::Object(Map::new()),
@@ -11506,8 +12325,25 @@ fn build_resegment_workflow_config(
       seg_map.insert("enabled".to_string(), Value::Bool(true));
       seg_map.insert(
         "segmentDurationSeconds".to_string(),
-        Value::Number(Number::from(segment_duration_seconds.max(1))),
+        Value::Number(Number::from(request.segment_duration_seconds.max(1))),
       );
+      if let Some(mode) = request.segment_mode.as_deref() {
+        seg_map.insert(
+          "mode".to_string(),
+          Value::String(SegmentMode::parse(Some(mode)).as_str().to_string()),
+        );
+      }
+      let tuning = [
+        ("searchWindowSeconds", request.search_window_seconds),
+        ("silenceNoiseDb", request.silence_noise_db),
+        ("silenceMinSeconds", request.silence_min_seconds),
+        ("sceneThreshold", request.scene_threshold),
+      ];
+      for (key, value) in tuning {
+        if let Some(number) = value.and_then(Number::from_f64) {
+          seg_map.insert(key.to_string(), Value::Number(number));
+        }
+      }
     }
   }
   config
@@ -0,0 +1,714 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Utc};
+use rusqlite::{params, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::State;
+
+use crate::api::ApiResponse;
+use crate::bilibili::client::BilibiliClient;
+use crate::commands::download::{
+  enqueue_source_video, DownloadConfig, DownloadContext, SubmissionRequest,
+};
+use crate::db::Db;
+use crate::download_source::{
+  resolve_download_source, DownloadSourceQuery, DownloadSourceVideo, SOURCE_FAVORITE,
+  SOURCE_SEASON, SOURCE_SERIES, SOURCE_UPLOADER,
+};
+use crate::live_submission::LiveSubmissionTemplate;
+use crate::login_store::LoginStore;
+use crate::utils::{append_log, now_rfc3339};
+use crate::webhook::{emit_webhook_event, EVENT_VOD_NEW_UPLOAD};
+use crate::AppState;
+
+const LOOP_INTERVAL_SECS: u64 = 60;
+const MIN_CHECK_INTERVAL_MINUTES: i64 = 5;
+const DEFAULT_CHECK_INTERVAL_MINUTES: i64 = 30;
+const MAX_VIDEOS_PER_CHECK: i64 = 30;
+const MAX_TITLE_CHARS: usize = 80;
+const ITEM_LIST_LIMIT: i64 = 100;
+const MAX_ITEM_RETRIES: i64 = 5;
+
+static VOD_CHECK_RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone)]
+struct VodSubscriptionContext {
+  db: Arc<Db>,
+  bilibili: Arc<BilibiliClient>,
+  login_store: Arc<LoginStore>,
+  app_log_path: Arc<PathBuf>,
+  download: DownloadContext,
+}
+
+impl VodSubscriptionContext {
+  fn new(state: &AppState) -> Self {
+    Self {
+      db: state.db.clone(),
+      bilibili: state.bilibili.clone(),
+      login_store: state.login_store.clone(),
+      app_log_path: state.app_log_path.clone(),
+      download: DownloadContext::new(state),
+    }
+  }
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VodSubscription {
+  pub id: i64,
+  pub source_type: String,
+  pub source_id: String,
+  pub mid: Option<String>,
+  pub name: Option<String>,
+  pub enabled: bool,
+  pub keyword: Option<String>,
+  pub start_time: i64,
+  pub check_interval_minutes: i64,
+  pub download_config: Option<Value>,
+  pub auto_submit_enabled: bool,
+  pub auto_submit_template: Option<Value>,
+  pub last_check_time: Option<String>,
+  pub last_error: Option<String>,
+  pub create_time: String,
+  pub update_time: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VodSubscriptionPayload {
+  pub id: Option<i64>,
+  pub source_type: String,
+  pub source_id: String,
+  pub mid: Option<String>,
+  pub name: Option<String>,
+  pub enabled: Option<bool>,
+  pub keyword: Option<String>,
+  pub start_time: Option<i64>,
+  pub check_interval_minutes: Option<i64>,
+  pub download_config: Option<Value>,
+  pub auto_submit_enabled: Option<bool>,
+  pub auto_submit_template: Option<Value>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VodSubscriptionItem {
+  pub id: i64,
+  pub subscription_id: i64,
+  pub bvid: String,
+  pub title: Option<String>,
+  pub pubdate: Option<i64>,
+  pub status: String,
+  pub download_task_ids: Option<String>,
+  pub submission_task_id: Option<String>,
+  pub error: Option<String>,
+  pub retry_count: i64,
+  pub create_time: String,
+}
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct VodCheckSummary {
+  pub checked: usize,
+  pub new_videos: usize,
+  pub queued: usize,
+  pub skipped: usize,
+  pub failed: usize,
+}
+
+#[tauri::command]
+pub fn vod_subscription_list(state: State<'_, AppState>) -> ApiResponse<Vec<VodSubscription>> {
+  vod_subscription_list_inner(&state)
+}
+
+pub fn vod_subscription_list_inner(state: &AppState) -> ApiResponse<Vec<VodSubscription>> {
+  match load_subscriptions(&state.db, false) {
+    Ok(list) => ApiResponse::success(list),
+    Err(err) => ApiResponse::error(format!("读取订阅失败: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn vod_subscription_save(
+  state: State<'_, AppState>,
+  payload: VodSubscriptionPayload,
+) -> ApiResponse<VodSubscription> {
+  vod_subscription_save_inner(&state, payload)
+}
+
+pub fn vod_subscription_save_inner(
+  state: &AppState,
+  payload: VodSubscriptionPayload,
+) -> ApiResponse<VodSubscription> {
+  let source_type = payload.source_type.trim().to_ascii_lowercase();
+  if ![SOURCE_UPLOADER, SOURCE_SEASON, SOURCE_SERIES, SOURCE_FAVORITE].contains(&source_type.as_str())
+  {
+    return ApiResponse::error(format!("不支持的订阅来源: {}", payload.source_type));
+  }
+  let source_id = payload.source_id.trim().to_string();
+  if source_id.is_empty() {
+    return ApiResponse::error("请填写订阅 ID");
+  }
+  let mid = normalize_text(payload.mid);
+  if source_type == SOURCE_SERIES && mid.is_none() {
+    return ApiResponse::error("视频列表需要填写 UP 主 UID");
+  }
+  if let Some(config) = payload.download_config.as_ref() {
+    if let Err(err) = serde_json::from_value::<DownloadConfig>(config.clone()) {
+      return ApiResponse::error(format!("下载配置无效: {}", err));
+    }
+  }
+  let auto_submit_enabled = payload.auto_submit_enabled.unwrap_or(false);
+  if let Some(template) = payload.auto_submit_template.as_ref() {
+    match serde_json::from_value::<LiveSubmissionTemplate>(template.clone()) {
+      Ok(template) if auto_submit_enabled => {
+        if let Err(err) = template.validate() {
+          return ApiResponse::error(err);
+        }
+      }
+      Ok(_) => {}
+      Err(err) => return ApiResponse::error(format!("自动投稿模板解析失败: {}", err)),
+    }
+  } else if auto_submit_enabled {
+    return ApiResponse::error("请先配置自动投稿模板");
+  }
+  let check_interval = payload
+    .check_interval_minutes
+    .unwrap_or(DEFAULT_CHECK_INTERVAL_MINUTES)
+    .max(MIN_CHECK_INTERVAL_MINUTES);
+  let download_config = payload.download_config.map(|value| value.to_string());
+  let auto_submit_template = payload.auto_submit_template.map(|value| value.to_string());
+  let now = now_rfc3339();
+  let result = state.db.with_conn(|conn| {
+    let id = match payload.id {
+      Some(id) => {
+        conn.execute(
+          "UPDATE vod_subscription SET source_type = ?1, source_id = ?2, mid = ?3, name = ?4, enabled = ?5, keyword = ?6, \
+           start_time = COALESCE(?7, start_time), check_interval_minutes = ?8, download_config = ?9, auto_submit_enabled = ?10, \
+           auto_submit_template = ?11, update_time = ?12 WHERE id = ?13",
+          params![
+            &source_type,
+            &source_id,
+            mid.as_deref(),
+            normalize_text(payload.name.clone()).as_deref(),
+            if payload.enabled.unwrap_or(true) { 1 } else { 0 },
+            normalize_text(payload.keyword.clone()).as_deref(),
+            payload.start_time,
+            check_interval,
+            download_config.as_deref(),
+            if auto_submit_enabled { 1 } else { 0 },
+            auto_submit_template.as_deref(),
+            &now,
+            id,
+          ],
+        )?;
+        id
+      }
+      None => {
+        conn.execute(
+          "INSERT INTO vod_subscription (source_type, source_id, mid, name, enabled, keyword, start_time, check_interval_minutes, \
+           download_config, auto_submit_enabled, auto_submit_template, create_time, update_time) \
+           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+          params![
+            &source_type,
+            &source_id,
+            mid.as_deref(),
+            normalize_text(payload.name.clone()).as_deref(),
+            if payload.enabled.unwrap_or(true) { 1 } else { 0 },
+            normalize_text(payload.keyword.clone()).as_deref(),
+            payload.start_time.unwrap_or_else(|| Utc::now().timestamp()),
+            check_interval,
+            download_config.as_deref(),
+            if auto_submit_enabled { 1 } else { 0 },
+            auto_submit_template.as_deref(),
+            &now,
+            &now,
+          ],
+        )?;
+        conn.last_insert_rowid()
+      }
+    };
+    Ok(id)
+  });
+  let id = match result {
+    Ok(id) => id,
+    Err(err) => return ApiResponse::error(format!("保存订阅失败: {}", err)),
+  };
+  append_log(
+    &state.app_log_path,
+    &format!(
+      "vod_subscription_save id={} source={} source_id={}",
+      id, source_type, source_id
+    ),
+  );
+  match load_subscription(&state.db, id) {
+    Ok(Some(subscription)) => ApiResponse::success(subscription),
+    Ok(None) => ApiResponse::error("订阅不存在"),
+    Err(err) => ApiResponse::error(format!("读取订阅失败: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn vod_subscription_delete(state: State<'_, AppState>, id: i64) -> ApiResponse<String> {
+  vod_subscription_delete_inner(&state, id)
+}
+
+pub fn vod_subscription_delete_inner(state: &AppState, id: i64) -> ApiResponse<String> {
+  match state.db.with_conn(|conn| {
+    conn.execute("DELETE FROM vod_subscription_item WHERE subscription_id = ?1", [id])?;
+    conn.execute("DELETE FROM vod_subscription WHERE id = ?1", [id])?;
+    Ok(())
+  }) {
+    Ok(()) => ApiResponse::success("Deleted".to_string()),
+    Err(err) => ApiResponse::error(format!("删除订阅失败: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn vod_subscription_items(
+  state: State<'_, AppState>,
+  subscription_id: i64,
+) -> ApiResponse<Vec<VodSubscriptionItem>> {
+  vod_subscription_items_inner(&state, subscription_id)
+}
+
+pub fn vod_subscription_items_inner(
+  state: &AppState,
+  subscription_id: i64,
+) -> ApiResponse<Vec<VodSubscriptionItem>> {
+  match state.db.with_conn(|conn| {
+    let mut stmt = conn.prepare(
+      "SELECT id, subscription_id, bvid, title, pubdate, status, download_task_ids, submission_task_id, error, \
+       COALESCE(retry_count, 0), create_time \
+       FROM vod_subscription_item WHERE subscription_id = ?1 ORDER BY id DESC LIMIT ?2",
+    )?;
+    let items = stmt
+      .query_map((subscription_id, ITEM_LIST_LIMIT), |row| {
+        Ok(VodSubscriptionItem {
+          id: row.get(0)?,
+          subscription_id: row.get(1)?,
+          bvid: row.get(2)?,
+          title: row.get(3)?,
+          pubdate: row.get(4)?,
+          status: row.get(5)?,
+          download_task_ids: row.get(6)?,
+          submission_task_id: row.get(7)?,
+          error: row.get(8)?,
+          retry_count: row.get(9)?,
+          create_time: row.get(10)?,
+        })
+      })?
+      .collect::<Result<Vec<_>, _>>()?;
+    Ok(items)
+  }) {
+    Ok(items) => ApiResponse::success(items),
+    Err(err) => ApiResponse::error(format!("读取订阅记录失败: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub async fn vod_subscription_check(
+  state: State<'_, AppState>,
+  id: Option<i64>,
+) -> Result<ApiResponse<VodCheckSummary>, String> {
+  vod_subscription_check_inner(state.inner(), id).await
+}
+
+pub async fn vod_subscription_check_inner(
+  state: &AppState,
+  id: Option<i64>,
+) -> Result<ApiResponse<VodCheckSummary>, String> {
+  let context = VodSubscriptionContext::new(state);
+  let subscriptions = match load_subscriptions(&context.db, false) {
+    Ok(list) => list
+      .into_iter()
+      .filter(|item| id.map(|id| item.id == id).unwrap_or(item.enabled))
+      .collect::<Vec<_>>(),
+    Err(err) => return Ok(ApiResponse::error(format!("读取订阅失败: {}", err))),
+  };
+  if VOD_CHECK_RUNNING.swap(true, Ordering::SeqCst) {
+    return Ok(ApiResponse::error("订阅检查正在进行，请稍后再试"));
+  }
+  let mut summary = VodCheckSummary::default();
+  for subscription in subscriptions {
+    check_subscription(&context, &subscription, &mut summary).await;
+  }
+  VOD_CHECK_RUNNING.store(false, Ordering::SeqCst);
+  Ok(ApiResponse::success(summary))
+}
+
+pub fn start_vod_subscription_loop(state: &AppState) {
+  let context = VodSubscriptionContext::new(state);
+  tauri::async_runtime::spawn(async move {
+    loop {
+      tokio::time::sleep(Duration::from_secs(LOOP_INTERVAL_SECS)).await;
+      let subscriptions = match load_subscriptions(&context.db, true) {
+        Ok(list) => list,
+        Err(err) => {
+          append_log(
+            &context.app_log_path,
+            &format!("vod_subscription_load_error err={}", err),
+          );
+          continue;
+        }
+      };
+      let due: Vec<VodSubscription> = subscriptions.into_iter().filter(is_due).collect();
+      if due.is_empty() || VOD_CHECK_RUNNING.swap(true, Ordering::SeqCst) {
+        continue;
+      }
+      let mut summary = VodCheckSummary::default();
+      for subscription in due {
+        check_subscription(&context, &subscription, &mut summary).await;
+      }
+      VOD_CHECK_RUNNING.store(false, Ordering::SeqCst);
+    }
+  });
+}
+
+fn parse_subscription_config(
+  subscription: &VodSubscription,
+) -> Result<(DownloadConfig, Option<LiveSubmissionTemplate>), String> {
+  let config = match subscription.download_config.clone() {
+    Some(value) => serde_json::from_value::<DownloadConfig>(value)
+      .map_err(|err| format!("下载配置解析失败: {}", err))?,
+    None => default_download_config(),
+  };
+  if !subscription.auto_submit_enabled {
+    return Ok((config, None));
+  }
+  let template = subscription
+    .auto_submit_template
+    .clone()
+    .ok_or_else(|| "自动投稿模板为空".to_string())
+    .and_then(|value| {
+      serde_json::from_value::<LiveSubmissionTemplate>(value)
+        .map_err(|err| format!("自动投稿模板解析失败: {}", err))
+    })?;
+  Ok((config, Some(template)))
+}
+
+async fn check_subscription(
+  context: &VodSubscriptionContext,
+  subscription: &VodSubscription,
+  summary: &mut VodCheckSummary,
+) {
+  summary.checked += 1;
+  let exclude_bvids = match load_settled_bvids(&context.db, subscription.id) {
+    Ok(value) => value,
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!("vod_subscription_item_error id={} err={}", subscription.id, err),
+      );
+      let _ = update_check_state(&context.db, subscription.id, Some(&err));
+      return;
+    }
+  };
+  let (mut config, template) = match parse_subscription_config(subscription) {
+    Ok(value) => value,
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!("vod_subscription_config_error id={} err={}", subscription.id, err),
+      );
+      let _ = update_check_state(&context.db, subscription.id, Some(&err));
+      return;
+    }
+  };
+  if config.account_user_id.is_none() {
+    config.account_user_id = template.as_ref().and_then(|template| template.account_user_id);
+  }
+  let query = DownloadSourceQuery {
+    source_type: subscription.source_type.clone(),
+    source_id: Some(subscription.source_id.clone()),
+    mid: subscription.mid.clone(),
+    start_time: Some(subscription.start_time),
+    end_time: None,
+    keyword: subscription.keyword.clone(),
+    max_count: Some(MAX_VIDEOS_PER_CHECK),
+    account_user_id: config.account_user_id,
+    exclude_bvids,
+  };
+  let auth = context.login_store.load_auth_info_or_default(
+    &context.db,
+    config.account_user_id,
+    &context.app_log_path,
+  );
+  let result = resolve_download_source(&context.bilibili, auth.as_ref(), &query).await;
+  let videos = match result {
+    Ok(videos) => videos,
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "vod_subscription_check_error id={} err={}",
+          subscription.id, err
+        ),
+      );
+      let _ = update_check_state(&context.db, subscription.id, Some(&err));
+      return;
+    }
+  };
+
+  for video in videos.into_iter().rev() {
+    summary.new_videos += 1;
+    let submission = template
+      .as_ref()
+      .map(|template| build_submission(template, subscription, &video));
+    let outcome = enqueue_source_video(&context.download, &video.bvid, &config, submission).await;
+    let (status, download_ids, submission_task_id, error) = match outcome {
+      Ok(Some(result)) => {
+        summary.queued += 1;
+        (
+          "QUEUED",
+          Some(
+            result
+              .download_ids
+              .iter()
+              .map(|id| id.to_string())
+              .collect::<Vec<_>>()
+              .join(","),
+          ),
+          result.submission_task_id,
+          None,
+        )
+      }
+      Ok(None) => {
+        summary.skipped += 1;
+        ("SKIPPED", None, None, None)
+      }
+      Err(err) => {
+        summary.failed += 1;
+        ("FAILED", None, None, Some(err))
+      }
+    };
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "vod_subscription_new id={} bvid={} status={} submission={} err={}",
+        subscription.id,
+        video.bvid,
+        status,
+        submission_task_id.as_deref().unwrap_or(""),
+        error.as_deref().unwrap_or("")
+      ),
+    );
+    let _ = context.db.with_conn(|conn| {
+      conn.execute(
+        "INSERT INTO vod_subscription_item (subscription_id, bvid, title, pubdate, status, download_task_ids, submission_task_id, error, create_time) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9) \
+         ON CONFLICT(subscription_id, bvid) DO UPDATE SET status = excluded.status, \
+         download_task_ids = excluded.download_task_ids, submission_task_id = excluded.submission_task_id, \
+         error = excluded.error, retry_count = COALESCE(vod_subscription_item.retry_count, 0) + 1",
+        params![
+          subscription.id,
+          &video.bvid,
+          &video.title,
+          video.pubdate,
+          status,
+          download_ids.as_deref(),
+          submission_task_id.as_deref(),
+          error.as_deref(),
+          now_rfc3339(),
+        ],
+      )?;
+      Ok(())
+    });
+    if status == "QUEUED" {
+      emit_webhook_event(
+        &context.db,
+        &context.app_log_path,
+        EVENT_VOD_NEW_UPLOAD,
+        &format!(
+          "{} 有新投稿: {}",
+          subscription_label(subscription),
+          video.title
+        ),
+        json!({
+          "subscriptionId": subscription.id,
+          "bvid": video.bvid,
+          "title": video.title,
+          "submissionTaskId": submission_task_id,
+        }),
+      );
+    }
+  }
+  let _ = update_check_state(&context.db, subscription.id, None);
+}
+
+fn build_submission(
+  template: &LiveSubmissionTemplate,
+  subscription: &VodSubscription,
+  video: &DownloadSourceVideo,
+) -> (SubmissionRequest, Value) {
+  let name = subscription_label(subscription);
+  let render = |value: &str| render_vod_template(value, &name, video);
+  let title: String = render(&template.title_template)
+    .chars()
+    .take(MAX_TITLE_CHARS)
+    .collect();
+  let request = SubmissionRequest {
+    title: if title.is_empty() {
+      video.title.clone()
+    } else {
+      title
+    },
+    description: Some(render(&template.description_template)).filter(|value| !value.is_empty()),
+    partition_id: template.partition_id,
+    tags: Some(render(&template.tags_template)).filter(|value| !value.is_empty()),
+    topic_id: None,
+    mission_id: None,
+    activity_title: None,
+    video_type: template.video_type.clone(),
+    collection_id: template.collection_id,
+    segment_prefix: template.segment_prefix.clone(),
+    priority: Some(template.priority),
+    baidu_sync_enabled: None,
+    baidu_sync_path: None,
+    baidu_sync_filename: None,
+    account_user_id: template.account_user_id,
+    scheduled_publish_time: None,
+    scheduled_publish_mode: None,
+    video_parts: Vec::new(),
+  };
+  (request, template.workflow_config())
+}
+
+fn render_vod_template(template: &str, name: &str, video: &DownloadSourceVideo) -> String {
+  let published = DateTime::from_timestamp(video.pubdate, 0)
+    .unwrap_or_else(Utc::now)
+    .with_timezone(&Local);
+  template
+    .replace("{{ title }}", &video.title)
+    .replace("{{ name }}", name)
+    .replace("{{ bvid }}", &video.bvid)
+    .replace("{{ date }}", &published.format("%Y-%m-%d").to_string())
+    .replace("{{ time }}", &published.format("%H:%M").to_string())
+    .trim()
+    .to_string()
+}
+
+fn subscription_label(subscription: &VodSubscription) -> String {
+  subscription
+    .name
+    .clone()
+    .unwrap_or_else(|| subscription.source_id.clone())
+}
+
+fn default_download_config() -> DownloadConfig {
+  DownloadConfig {
+    download_name: None,
+    download_path: None,
+    resolution: None,
+    codec: None,
+    format: None,
+    content: None,
+    sidecar: None,
+    account_user_id: None,
+  }
+}
+
+fn is_due(subscription: &VodSubscription) -> bool {
+  let Some(last_check) = subscription
+    .last_check_time
+    .as_deref()
+    .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+  else {
+    return true;
+  };
+  let elapsed = Utc::now() - last_check.with_timezone(&Utc);
+  elapsed.num_minutes() >= subscription.check_interval_minutes.max(MIN_CHECK_INTERVAL_MINUTES)
+}
+
+fn load_settled_bvids(db: &Db, subscription_id: i64) -> Result<HashSet<String>, String> {
+  db.with_conn(|conn| {
+    let mut stmt = conn.prepare(
+      "SELECT bvid FROM vod_subscription_item WHERE subscription_id = ?1 \
+       AND (status != 'FAILED' OR COALESCE(retry_count, 0) >= ?2)",
+    )?;
+    let bvids = stmt
+      .query_map((subscription_id, MAX_ITEM_RETRIES), |row| row.get::<_, String>(0))?
+      .collect::<Result<HashSet<_>, _>>()?;
+    Ok(bvids)
+  })
+  .map_err(|err| err.to_string())
+}
+
+fn update_check_state(db: &Db, id: i64, error: Option<&str>) -> Result<(), String> {
+  let now = now_rfc3339();
+  db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE vod_subscription SET last_check_time = ?1, last_error = ?2 WHERE id = ?3",
+      (&now, error, id),
+    )?;
+    Ok(())
+  })
+  .map_err(|err| err.to_string())
+}
+
+fn load_subscriptions(db: &Db, enabled_only: bool) -> Result<Vec<VodSubscription>, String> {
+  db.with_conn(|conn| {
+    let sql = format!(
+      "SELECT {} FROM vod_subscription {} ORDER BY id DESC",
+      SUBSCRIPTION_COLUMNS,
+      if enabled_only { "WHERE enabled = 1" } else { "" }
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let list = stmt
+      .query_map([], map_subscription)?
+      .collect::<Result<Vec<_>, _>>()?;
+    Ok(list)
+  })
+  .map_err(|err| err.to_string())
+}
+
+fn load_subscription(db: &Db, id: i64) -> Result<Option<VodSubscription>, String> {
+  db.with_conn(|conn| {
+    conn
+      .query_row(
+        &format!("SELECT {} FROM vod_subscription WHERE id = ?1", SUBSCRIPTION_COLUMNS),
+        [id],
+        map_subscription,
+      )
+      .optional()
+  })
+  .map_err(|err| err.to_string())
+}
+
+const SUBSCRIPTION_COLUMNS: &str = "id, source_type, source_id, mid, name, enabled, keyword, start_time, check_interval_minutes, \
+  download_config, auto_submit_enabled, auto_submit_template, last_check_time, last_error, create_time, update_time";
+
+fn map_subscription(row: &Row) -> rusqlite::Result<VodSubscription> {
+  let parse_json = |value: Option<String>| value.and_then(|value| serde_json::from_str(&value).ok());
+  Ok(VodSubscription {
+    id: row.get(0)?,
+    source_type: row.get(1)?,
+    source_id: row.get(2)?,
+    mid: row.get(3)?,
+    name: row.get(4)?,
+    enabled: row.get::<_, Option<i64>>(5)?.unwrap_or(1) != 0,
+    keyword: row.get(6)?,
+    start_time: row.get(7)?,
+    check_interval_minutes: row
+      .get::<_, Option<i64>>(8)?
+      .unwrap_or(DEFAULT_CHECK_INTERVAL_MINUTES),
+    download_config: parse_json(row.get(9)?),
+    auto_submit_enabled: row.get::<_, Option<i64>>(10)?.unwrap_or(0) != 0,
+    auto_submit_template: parse_json(row.get(11)?),
+    last_check_time: row.get(12)?,
+    last_error: row.get(13)?,
+    create_time: row.get(14)?,
+    update_time: row.get(15)?,
+  })
+}
+
+fn normalize_text(value: Option<String>) -> Option<String> {
+  value
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty())
+}
@@ -1,11 +1,16 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 use tauri::State;
 
 use crate::api::ApiResponse;
+use crate::danmaku_ass::{
+  convert_danmaku_to_ass, danmaku_ass_path, danmaku_jsonl_path, load_segment_start,
+  parse_segment_start, DanmakuAssOptions, DanmakuAssSummary,
+};
 use crate::ffmpeg::run_ffmpeg;
+use crate::flv::{repair_flv_file, FlvFixOptions, FlvFixReport};
 use crate::utils;
 use crate::AppState;
 
@@ -16,10 +21,35 @@ pub struct RemuxPayload {
   pub target_path: String,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DanmakuAssPayload {
+  pub source_path: String,
+  pub target_path: Option<String>,
+  pub start_time: Option<String>,
+  pub duration_seconds: Option<f64>,
+  pub options: Option<DanmakuAssOptions>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlvFixPayload {
+  pub source_path: String,
+  pub target_path: Option<String>,
+  pub options: Option<FlvFixOptions>,
+}
+
 #[tauri::command]
 pub async fn toolbox_remux(
   state: State<'_, AppState>,
   payload: RemuxPayload,
+) -> Result<ApiResponse<bool>, String> {
+  toolbox_remux_inner(&state, payload).await
+}
+
+pub async fn toolbox_remux_inner(
+  state: &AppState,
+  payload: RemuxPayload,
 ) -> Result<ApiResponse<bool>, String> {
   let source = payload.source_path.trim();
   if source.is_empty() {
@@ -82,3 +112,170 @@ pub async fn toolbox_remux(
     }
   }
 }
+
+#[tauri::command]
+pub async fn toolbox_danmaku_ass(
+  state: State<'_, AppState>,
+  payload: DanmakuAssPayload,
+) -> Result<ApiResponse<DanmakuAssSummary>, String> {
+  toolbox_danmaku_ass_inner(&state, payload).await
+}
+
+pub async fn toolbox_danmaku_ass_inner(
+  state: &AppState,
+  payload: DanmakuAssPayload,
+) -> Result<ApiResponse<DanmakuAssSummary>, String> {
+  let source = payload.source_path.trim();
+  if source.is_empty() {
+    return Ok(ApiResponse::error("请选择源文件"));
+  }
+  let (media_path, jsonl_path) = match source.strip_suffix(".danmaku.jsonl") {
+    Some(stem) => (PathBuf::from(stem), PathBuf::from(source)),
+    None => {
+      let media_path = PathBuf::from(source);
+      let jsonl_path = danmaku_jsonl_path(&media_path);
+      (media_path, jsonl_path)
+    }
+  };
+  if !jsonl_path.is_file() {
+    return Ok(ApiResponse::error("弹幕文件不存在"));
+  }
+
+  let segment_start = match payload.start_time.as_deref() {
+    Some(value) if !value.trim().is_empty() => match parse_segment_start(value) {
+      Some(start) => start,
+      None => return Ok(ApiResponse::error("开始时间格式无效")),
+    },
+    _ => match load_segment_start(&media_path, &jsonl_path) {
+      Some(start) => start,
+      None => return Ok(ApiResponse::error("无法确定录制开始时间")),
+    },
+  };
+  let target_path = payload
+    .target_path
+    .as_deref()
+    .map(str::trim)
+    .filter(|value| !value.is_empty())
+    .map(PathBuf::from)
+    .unwrap_or_else(|| danmaku_ass_path(&media_path));
+  let options = payload.options.unwrap_or_default();
+  let duration_seconds = payload.duration_seconds;
+
+  let log_path = state.app_log_path.clone();
+  utils::append_log(
+    log_path.as_ref(),
+    &format!(
+      "toolbox_danmaku_ass_start source={} target={}",
+      jsonl_path.to_string_lossy(),
+      target_path.to_string_lossy()
+    ),
+  );
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    convert_danmaku_to_ass(&jsonl_path, &target_path, segment_start, duration_seconds, &options)
+  })
+  .await
+  .map_err(|_| "弹幕转换执行失败".to_string())?;
+
+  match result {
+    Ok(summary) => {
+      utils::append_log(
+        log_path.as_ref(),
+        &format!(
+          "toolbox_danmaku_ass_done status=ok scroll={} superchat={} gift={} dropped={}",
+          summary.scroll_count, summary.superchat_count, summary.gift_count, summary.dropped_count
+        ),
+      );
+      Ok(ApiResponse::success(summary))
+    }
+    Err(err) => {
+      utils::append_log(
+        log_path.as_ref(),
+        &format!("toolbox_danmaku_ass_done status=err err={}", err),
+      );
+      Ok(ApiResponse::error(err))
+    }
+  }
+}
+
+#[tauri::command]
+pub async fn toolbox_flv_fix(
+  state: State<'_, AppState>,
+  payload: FlvFixPayload,
+) -> Result<ApiResponse<FlvFixReport>, String> {
+  toolbox_flv_fix_inner(&state, payload).await
+}
+
+pub async fn toolbox_flv_fix_inner(
+  state: &AppState,
+  payload: FlvFixPayload,
+) -> Result<ApiResponse<FlvFixReport>, String> {
+  let source = payload.source_path.trim();
+  if source.is_empty() {
+    return Ok(ApiResponse::error("请选择源文件"));
+  }
+  let source_path = PathBuf::from(source);
+  if !source_path.is_file() {
+    return Ok(ApiResponse::error("源文件不存在"));
+  }
+  let target_path = payload
+    .target_path
+    .as_deref()
+    .map(str::trim)
+    .filter(|value| !value.is_empty())
+    .map(PathBuf::from)
+    .unwrap_or_else(|| {
+      let stem = source_path
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .unwrap_or("output");
+      source_path.with_file_name(format!("{}_fixed.flv", stem))
+    });
+  if target_path == source_path {
+    return Ok(ApiResponse::error("输出路径不能与源文件相同"));
+  }
+  if let Some(parent) = target_path.parent() {
+    if let Err(err) = fs::create_dir_all(parent) {
+      return Ok(ApiResponse::error(format!("创建输出目录失败: {}", err)));
+    }
+  }
+  let options = payload.options.unwrap_or_default();
+
+  let log_path = state.app_log_path.clone();
+  utils::append_log(
+    log_path.as_ref(),
+    &format!(
+      "toolbox_flv_fix_start source={} target={}",
+      source_path.to_string_lossy(),
+      target_path.to_string_lossy()
+    ),
+  );
+  let result = tauri::async_runtime::spawn_blocking(move || {
+    repair_flv_file(&source_path, &target_path, &options)
+  })
+  .await
+  .map_err(|_| "FLV 修复执行失败".to_string())?;
+
+  match result {
+    Ok(report) => {
+      utils::append_log(
+        log_path.as_ref(),
+        &format!(
+          "toolbox_flv_fix_done status=ok outputs={} jumps={} injected={} dropped={} truncated={}",
+          report.output_paths.len(),
+          report.timestamp_jump_count,
+          report.injected_headers,
+          report.dropped_tags,
+          report.truncated_bytes
+        ),
+      );
+      Ok(ApiResponse::success(report))
+    }
+    Err(err) => {
+      utils::append_log(
+        log_path.as_ref(),
+        &format!("toolbox_flv_fix_done status=err err={}", err),
+      );
+      Ok(ApiResponse::error(err))
+    }
+  }
+}
@@ -42,6 +42,7 @@ pub async fn video_detail(
   state: State<'_, AppState>,
   bvid: Option<String>,
   aid: Option<i64>,
+  account_user_id: Option<i64>,
 ) -> Result<ApiResponse<Value>, String> {
   if bvid.is_none() && aid.is_none() {
     return Ok(ApiResponse::error("Missing bvid or aid"));
@@ -55,7 +56,7 @@ pub async fn video_detail(
     params.push(("aid".to_string(), aid.to_string()));
   }
 
-  let auth = load_auth(&state);
+  let auth = load_auth(&state, account_user_id);
   let url = format!("{}/x/web-interface/view", state.bilibili.base_url());
   match state.bilibili.get_json(&url, &params, auth.as_ref(), false).await {
     Ok(data) => Ok(ApiResponse::success(data)),
@@ -72,6 +73,7 @@ pub async fn video_playurl(
   fnval: Option<String>,
   fnver: Option<String>,
   fourk: Option<String>,
+  account_user_id: Option<i64>,
 ) -> Result<ApiResponse<Value>, String> {
   let params = vec![
     ("bvid".to_string(), bvid),
@@ -82,7 +84,7 @@ pub async fn video_playurl(
     ("fourk".to_string(), fourk.unwrap_or_else(|| "1".to_string())),
   ];
 
-  let auth = load_auth(&state);
+  let auth = load_auth(&state, account_user_id);
   let url = format!("{}/x/player/wbi/playurl", state.bilibili.base_url());
   match state.bilibili.get_json(&url, &params, auth.as_ref(), true).await {
     Ok(data) => Ok(ApiResponse::success(data)),
@@ -99,6 +101,7 @@ pub async fn video_playurl_by_aid(
   fnval: Option<String>,
   fnver: Option<String>,
   fourk: Option<String>,
+  account_user_id: Option<i64>,
 ) -> Result<ApiResponse<Value>, String> {
   let params = vec![
     ("avid".to_string(), aid),
@@ -109,7 +112,7 @@ pub async fn video_playurl_by_aid(
     ("fourk".to_string(), fourk.unwrap_or_else(|| "1".to_string())),
   ];
 
-  let auth = load_auth(&state);
+  let auth = load_auth(&state, account_user_id);
   let url = format!("{}/x/player/wbi/playurl", state.bilibili.base_url());
   match state.bilibili.get_json(&url, &params, auth.as_ref(), true).await {
     Ok(data) => Ok(ApiResponse::success(data)),
@@ -172,8 +175,9 @@ pub async fn video_proxy_image(url: String) -> Result<ApiResponse<String>, Strin
 pub async fn bilibili_collections(
   state: State<'_, AppState>,
   mid: i64,
+  account_user_id: Option<i64>,
 ) -> Result<ApiResponse<Vec<Collection>>, String> {
-  let auth = load_auth(&state);
+  let auth = load_auth(&state, account_user_id);
   append_log(
     &state.app_log_path,
     &format!("collections_start mid={} has_auth={}", mid, auth.is_some()),
@@ -241,8 +245,9 @@ pub async fn bilibili_collections(
 #[tauri::command]
 pub async fn bilibili_partitions(
   state: State<'_, AppState>,
+  account_user_id: Option<i64>,
 ) -> Result<ApiResponse<Vec<Partition>>, String> {
-  let auth = load_auth(&state);
+  let auth = load_auth(&state, account_user_id);
   let params = vec![("t".to_string(), format!("{}", Utc::now().timestamp_millis()))];
   let url = "https://member.bilibili.com/x/vupre/web/archive/human/type2/list";
 
@@ -282,8 +287,9 @@ pub async fn bilibili_partitions(
 pub async fn bilibili_topics(
   state: State<'_, AppState>,
   partition_id: Option<i64>,
+  account_user_id: Option<i64>,
 ) -> Result<ApiResponse<Vec<ActivityTopic>>, String> {
-  let auth = load_auth(&state);
+  let auth = load_auth(&state, account_user_id);
   if auth.is_none() {
     return Ok(ApiResponse::error("Login required"));
   }
@@ -401,6 +407,8 @@ fn default_partitions() -> Vec<Partition> {
   ]
 }
 
-fn load_auth(state: &State<'_, AppState>) -> Option<AuthInfo> {
-  state.login_store.load_auth_info(&state.db).ok().flatten()
+fn load_auth(state: &AppState, account_user_id: Option<i64>) -> Option<AuthInfo> {
+  state
+    .login_store
+    .load_auth_info_or_default(&state.db, account_user_id, &state.app_log_path)
 }
@@ -0,0 +1,72 @@
+use tauri::State;
+
+use crate::api::ApiResponse;
+use crate::utils::append_log;
+use crate::webhook::{
+  delete_webhook_endpoint, list_webhook_endpoints, load_webhook_endpoint, save_webhook_endpoint,
+  send_test_webhook, WebhookEndpointInput, WebhookEndpointRecord, WEBHOOK_EVENTS,
+};
+use crate::AppState;
+
+#[tauri::command]
+pub fn webhook_list(state: State<'_, AppState>) -> ApiResponse<Vec<WebhookEndpointRecord>> {
+  match list_webhook_endpoints(&state.db) {
+    Ok(list) => ApiResponse::success(list),
+    Err(err) => ApiResponse::error(format!("读取推送配置失败: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn webhook_events() -> ApiResponse<Vec<String>> {
+  ApiResponse::success(WEBHOOK_EVENTS.iter().map(|event| event.to_string()).collect())
+}
+
+#[tauri::command]
+pub fn webhook_save(
+  state: State<'_, AppState>,
+  payload: WebhookEndpointInput,
+) -> ApiResponse<WebhookEndpointRecord> {
+  let id = match save_webhook_endpoint(&state.db, payload) {
+    Ok(id) => id,
+    Err(err) => return ApiResponse::error(format!("保存推送配置失败: {}", err)),
+  };
+  append_log(&state.app_log_path, &format!("webhook_save id={}", id));
+  match load_webhook_endpoint(&state.db, id) {
+    Ok(Some(record)) => ApiResponse::success(record),
+    Ok(None) => ApiResponse::error("推送配置不存在"),
+    Err(err) => ApiResponse::error(format!("读取推送配置失败: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub fn webhook_delete(state: State<'_, AppState>, id: i64) -> ApiResponse<String> {
+  match delete_webhook_endpoint(&state.db, id) {
+    Ok(()) => {
+      append_log(&state.app_log_path, &format!("webhook_delete id={}", id));
+      ApiResponse::success("deleted".to_string())
+    }
+    Err(err) => ApiResponse::error(format!("删除推送配置失败: {}", err)),
+  }
+}
+
+#[tauri::command]
+pub async fn webhook_test(
+  state: State<'_, AppState>,
+  id: i64,
+) -> Result<ApiResponse<String>, String> {
+  let endpoint = match load_webhook_endpoint(&state.db, id) {
+    Ok(Some(endpoint)) => endpoint,
+    Ok(None) => return Ok(ApiResponse::error("推送配置不存在")),
+    Err(err) => return Ok(ApiResponse::error(format!("读取推送配置失败: {}", err))),
+  };
+  match send_test_webhook(&endpoint).await {
+    Ok(()) => Ok(ApiResponse::success("sent".to_string())),
+    Err(err) => {
+      append_log(
+        &state.app_log_path,
+        &format!("webhook_test_fail id={} err={}", id, err),
+      );
+      Ok(ApiResponse::error(format!("测试推送失败: {}", err)))
+    }
+  }
+}
@@ -9,14 +9,20 @@ use tauri::Manager;
 
 pub const DEFAULT_FFMPEG_PATH: &str = "/opt/homebrew/bin/ffmpeg";
 pub const DEFAULT_FFPROBE_PATH: &str = "/opt/homebrew/bin/ffprobe";
-pub const DEFAULT_ARIA2C_PATH: &str = "/opt/homebrew/bin/aria2c";
 pub const DEFAULT_BAIDU_PCS_PATH: &str = "/opt/homebrew/bin/BaiduPCS-Go";
 
 const ENV_FFMPEG_PATH: &str = "REACTION_CUT_FFMPEG_PATH";
 const ENV_FFPROBE_PATH: &str = "REACTION_CUT_FFPROBE_PATH";
-const ENV_ARIA2C_PATH: &str = "REACTION_CUT_ARIA2C_PATH";
 const ENV_BAIDU_PCS_PATH: &str = "REACTION_CUT_BAIDU_PCS_PATH";
 const ENV_BAIDU_PCS_CONFIG_DIR: &str = "BAIDUPCS_GO_CONFIG_DIR";
+const APP_IDENTIFIER: &str = "com.tbw.reaction-cut-rust";
+
+#[derive(Default)]
+pub struct BinPathOverrides {
+  pub ffmpeg: Option<String>,
+  pub ffprobe: Option<String>,
+  pub baidu_pcs: Option<String>,
+}
 
 fn resolve_home_dir() -> Option<PathBuf> {
   if cfg!(target_os = "windows") {
@@ -80,16 +86,57 @@ pub fn init_resource_bins(app_handle: &AppHandle) {
   if let Some(path) = ffprobe_path {
     set_env_if_exists(ENV_FFPROBE_PATH, path);
   }
-  let aria2c_path = resolve_bin_in_dirs(&platform_dir, &base_dir, "aria2c");
-  if let Some(path) = aria2c_path {
-    set_env_if_exists(ENV_ARIA2C_PATH, path);
-  }
   let baidu_pcs_path = resolve_bin_in_dirs(&platform_dir, &base_dir, "BaiduPCS-Go");
   if let Some(path) = baidu_pcs_path {
     set_env_if_exists(ENV_BAIDU_PCS_PATH, path);
   }
 }
 
+pub fn default_app_data_dir() -> PathBuf {
+  let base = if cfg!(target_os = "windows") {
+    env::var_os("APPDATA").map(PathBuf::from)
+  } else if cfg!(target_os = "macos") {
+    resolve_home_dir().map(|home| home.join("Library").join("Application Support"))
+  } else {
+    env::var_os("XDG_DATA_HOME")
+      .map(PathBuf::from)
+      .or_else(|| resolve_home_dir().map(|home| home.join(".local").join("share")))
+  };
+  base
+    .unwrap_or_else(|| PathBuf::from("."))
+    .join(APP_IDENTIFIER)
+}
+
+pub fn init_headless_bins(data_dir: &std::path::Path, overrides: &BinPathOverrides) {
+  let config_dir = data_dir.join("baidu_pcs");
+  if std::fs::create_dir_all(&config_dir).is_ok() {
+    set_env_if_dir(ENV_BAIDU_PCS_CONFIG_DIR, config_dir);
+  }
+  let bins = [
+    (ENV_FFMPEG_PATH, DEFAULT_FFMPEG_PATH, "ffmpeg", overrides.ffmpeg.as_deref()),
+    (ENV_FFPROBE_PATH, DEFAULT_FFPROBE_PATH, "ffprobe", overrides.ffprobe.as_deref()),
+    (
+      ENV_BAIDU_PCS_PATH,
+      DEFAULT_BAIDU_PCS_PATH,
+      "BaiduPCS-Go",
+      overrides.baidu_pcs.as_deref(),
+    ),
+  ];
+  for (env_key, default_path, base, override_path) in bins {
+    match override_path.map(str::trim).filter(|value| !value.is_empty()) {
+      Some(path) => env::set_var(env_key, path),
+      None => {
+        let env_set = env::var(env_key)
+          .map(|value| !value.trim().is_empty())
+          .unwrap_or(false);
+        if !env_set && !PathBuf::from(default_path).exists() {
+          env::set_var(env_key, bin_name(base));
+        }
+      }
+    }
+  }
+}
+
 pub fn resolve_resource_bin_dir(app_handle: &AppHandle) -> Option<PathBuf> {
   let primary = app_handle.path().resolve("bin", BaseDirectory::Resource).ok();
   if let Some(path) = primary.as_ref() {
@@ -117,24 +164,6 @@ pub fn resolve_ffprobe_path() -> PathBuf {
   resolve_bin_path(ENV_FFPROBE_PATH, DEFAULT_FFPROBE_PATH)
 }
 
-pub fn resolve_aria2c_candidates() -> Vec<String> {
-  let mut candidates = Vec::new();
-  if let Ok(value) = env::var(ENV_ARIA2C_PATH) {
-    if !value.trim().is_empty() {
-      candidates.push(value);
-    }
-  }
-  if !candidates.iter().any(|item| item == DEFAULT_ARIA2C_PATH) {
-    candidates.push(DEFAULT_ARIA2C_PATH.to_string());
-  }
-  candidates.push("aria2c".to_string());
-  if cfg!(target_os = "windows") {
-    candidates.push("aria2c.exe".to_string());
-  }
-  candidates.dedup();
-  candidates
-}
-
 pub fn resolve_baidu_pcs_path() -> PathBuf {
   resolve_bin_path(ENV_BAIDU_PCS_PATH, DEFAULT_BAIDU_PCS_PATH)
 }
@@ -0,0 +1,211 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use tokio::time::sleep;
+
+use crate::commands::settings::{
+  default_http_api_settings, load_http_api_settings_from_db, HttpApiSettings,
+};
+use crate::config::{self, BinPathOverrides};
+use crate::{build_app_state, build_live_context, http_api, live_recorder, start_background_tasks, utils};
+use crate::{AppState, BackgroundTasks};
+
+const ENV_DAEMON_CONFIG: &str = "REACTION_CUT_DAEMON_CONFIG";
+const DEFAULT_CONFIG_FILE: &str = "daemon.json";
+const SHUTDOWN_WAIT_SECS: u64 = 60;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct DaemonConfig {
+  data_dir: Option<String>,
+  ffmpeg_path: Option<String>,
+  ffprobe_path: Option<String>,
+  baidu_pcs_path: Option<String>,
+  download_queue: bool,
+  live_recording: bool,
+  submission_queue: bool,
+  baidu_sync: bool,
+  cookie_refresh: bool,
+  http_api_enabled: Option<bool>,
+  http_api_bind_address: Option<String>,
+  http_api_port: Option<i64>,
+  http_api_token: Option<String>,
+}
+
+impl Default for DaemonConfig {
+  fn default() -> Self {
+    Self {
+      data_dir: None,
+      ffmpeg_path: None,
+      ffprobe_path: None,
+      baidu_pcs_path: None,
+      download_queue: true,
+      live_recording: true,
+      submission_queue: true,
+      baidu_sync: true,
+      cookie_refresh: true,
+      http_api_enabled: None,
+      http_api_bind_address: None,
+      http_api_port: None,
+      http_api_token: None,
+    }
+  }
+}
+
+pub fn run_daemon() {
+  if let Err(err) = start_daemon() {
+    eprintln!("reaction-cut-daemon: {}", err);
+    std::process::exit(1);
+  }
+}
+
+fn start_daemon() -> Result<(), String> {
+  let (config, config_path) = load_daemon_config()?;
+  let data_dir = config
+    .data_dir
+    .as_deref()
+    .map(str::trim)
+    .filter(|value| !value.is_empty())
+    .map(PathBuf::from)
+    .unwrap_or_else(config::default_app_data_dir);
+  fs::create_dir_all(&data_dir).map_err(|err| format!("创建数据目录失败: {}", err))?;
+  config::init_headless_bins(
+    &data_dir,
+    &BinPathOverrides {
+      ffmpeg: config.ffmpeg_path.clone(),
+      ffprobe: config.ffprobe_path.clone(),
+      baidu_pcs: config.baidu_pcs_path.clone(),
+    },
+  );
+
+  let state = build_app_state(&data_dir).map_err(|err| format!("初始化失败: {}", err))?;
+  let tasks = BackgroundTasks {
+    downloads: config.download_queue,
+    live_recording: config.live_recording,
+    submissions: config.submission_queue,
+    baidu_sync: config.baidu_sync,
+    cookie_refresh: config.cookie_refresh,
+  };
+  utils::append_log(
+    &state.app_log_path,
+    &format!(
+      "daemon_start data_dir={} config={} downloads={} live={} submissions={} baidu_sync={} cookie_refresh={}",
+      data_dir.to_string_lossy(),
+      config_path
+        .as_ref()
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|| "default".to_string()),
+      tasks.downloads,
+      tasks.live_recording,
+      tasks.submissions,
+      tasks.baidu_sync,
+      tasks.cookie_refresh
+    ),
+  );
+  println!(
+    "reaction-cut-daemon running data_dir={} log={}",
+    data_dir.to_string_lossy(),
+    state.app_log_path.to_string_lossy()
+  );
+  start_background_tasks(&state, &tasks);
+  let http_api_settings = resolve_http_api_settings(&state, &config);
+  if let Err(err) = http_api::restart_http_api(&state, &http_api_settings) {
+    eprintln!("reaction-cut-daemon: HTTP 接口启动失败: {}", err);
+  }
+
+  tauri::async_runtime::block_on(async move {
+    wait_for_shutdown_signal().await;
+    shutdown(&state).await;
+  });
+  Ok(())
+}
+
+fn resolve_http_api_settings(state: &AppState, config: &DaemonConfig) -> HttpApiSettings {
+  let mut settings =
+    load_http_api_settings_from_db(&state.db).unwrap_or_else(|_| default_http_api_settings());
+  if let Some(enabled) = config.http_api_enabled {
+    settings.enabled = enabled;
+  }
+  if let Some(bind_address) = config.http_api_bind_address.as_ref() {
+    settings.bind_address = bind_address.trim().to_string();
+  }
+  if let Some(port) = config.http_api_port {
+    settings.port = port;
+  }
+  if let Some(token) = config.http_api_token.as_ref() {
+    settings.token = token.trim().to_string();
+  }
+  settings
+}
+
+async fn wait_for_shutdown_signal() {
+  #[cfg(unix)]
+  {
+    use tokio::signal::unix::{signal, SignalKind};
+    if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+      tokio::select! {
+        _ = tokio::signal::ctrl_c() => {}
+        _ = terminate.recv() => {}
+      }
+      return;
+    }
+  }
+  let _ = tokio::signal::ctrl_c().await;
+}
+
+async fn shutdown(state: &AppState) {
+  utils::append_log(&state.app_log_path, "daemon_stop_requested");
+  http_api::stop_http_api(state);
+  let live_context = build_live_context(state);
+  for room_id in state.live_runtime.active_room_ids() {
+    live_recorder::stop_recording(live_context.clone(), &room_id, "daemon_shutdown");
+  }
+  let deadline = Instant::now() + Duration::from_secs(SHUTDOWN_WAIT_SECS);
+  while !state.live_runtime.active_room_ids().is_empty() && Instant::now() < deadline {
+    sleep(Duration::from_millis(500)).await;
+  }
+  utils::append_log(
+    &state.app_log_path,
+    &format!(
+      "daemon_stop remaining_records={}",
+      state.live_runtime.active_room_ids().len()
+    ),
+  );
+}
+
+fn load_daemon_config() -> Result<(DaemonConfig, Option<PathBuf>), String> {
+  let path = resolve_config_path()?;
+  let path = match path {
+    Some(path) => path,
+    None => return Ok((DaemonConfig::default(), None)),
+  };
+  let content = fs::read_to_string(&path)
+    .map_err(|err| format!("读取配置文件失败 path={} err={}", path.to_string_lossy(), err))?;
+  let config = serde_json::from_str::<DaemonConfig>(&content)
+    .map_err(|err| format!("解析配置文件失败 path={} err={}", path.to_string_lossy(), err))?;
+  Ok((config, Some(path)))
+}
+
+fn resolve_config_path() -> Result<Option<PathBuf>, String> {
+  let mut args = std::env::args().skip(1);
+  while let Some(arg) = args.next() {
+    if arg == "--config" || arg == "-c" {
+      return args
+        .next()
+        .map(|value| Some(PathBuf::from(value)))
+        .ok_or_else(|| "缺少配置文件路径".to_string());
+    }
+    if let Some(value) = arg.strip_prefix("--config=") {
+      return Ok(Some(PathBuf::from(value)));
+    }
+  }
+  if let Ok(value) = std::env::var(ENV_DAEMON_CONFIG) {
+    if !value.trim().is_empty() {
+      return Ok(Some(PathBuf::from(value)));
+    }
+  }
+  let fallback = config::default_app_data_dir().join(DEFAULT_CONFIG_FILE);
+  Ok(fallback.is_file().then_some(fallback))
+}
@@ -0,0 +1,505 @@
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const DEFAULT_PLAY_RES_X: i64 = 1920;
+const DEFAULT_PLAY_RES_Y: i64 = 1080;
+const LINE_HEIGHT_RATIO: f64 = 1.2;
+const SUPERCHAT_MIN_SECONDS: f64 = 5.0;
+const SUPERCHAT_MAX_SECONDS: f64 = 15.0;
+const SUPERCHAT_MAX_LANES: usize = 4;
+const GIFT_DISPLAY_SECONDS: f64 = 5.0;
+const GIFT_MAX_LANES: usize = 6;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DanmakuAssOptions {
+  pub width: i64,
+  pub height: i64,
+  pub font_name: String,
+  pub font_size: i64,
+  pub scroll_seconds: f64,
+  pub scroll_area_ratio: f64,
+  pub opacity: f64,
+  pub include_superchat: bool,
+  pub include_gift: bool,
+}
+
+impl Default for DanmakuAssOptions {
+  fn default() -> Self {
+    Self {
+      width: DEFAULT_PLAY_RES_X,
+      height: DEFAULT_PLAY_RES_Y,
+      font_name: "Microsoft YaHei".to_string(),
+      font_size: 48,
+      scroll_seconds: 10.0,
+      scroll_area_ratio: 0.6,
+      opacity: 0.8,
+      include_superchat: true,
+      include_gift: true,
+    }
+  }
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DanmakuAssSummary {
+  pub output_path: String,
+  pub scroll_count: usize,
+  pub superchat_count: usize,
+  pub gift_count: usize,
+  pub dropped_count: usize,
+}
+
+enum DanmakuKind {
+  Scroll,
+  SuperChat { seconds: f64, color: Option<u32> },
+  Gift,
+}
+
+struct DanmakuEvent {
+  offset: f64,
+  kind: DanmakuKind,
+  text: String,
+  color: u32,
+}
+
+pub struct VideoDanmakuItem {
+  pub offset: f64,
+  pub color: u32,
+  pub text: String,
+}
+
+struct ScrollLane {
+  start: f64,
+  width: f64,
+  speed: f64,
+}
+
+pub fn danmaku_jsonl_path(media_path: &Path) -> PathBuf {
+  media_path.with_extension("danmaku.jsonl")
+}
+
+pub fn danmaku_ass_path(media_path: &Path) -> PathBuf {
+  media_path.with_extension("danmaku.ass")
+}
+
+pub fn convert_danmaku_to_ass(
+  input_path: &Path,
+  output_path: &Path,
+  segment_start: DateTime<Utc>,
+  duration_seconds: Option<f64>,
+  options: &DanmakuAssOptions,
+) -> Result<DanmakuAssSummary, String> {
+  let mut events = read_danmaku_events(input_path, segment_start, options)?;
+  if let Some(limit) = duration_seconds.filter(|value| *value > 0.0) {
+    events.retain(|event| event.offset <= limit);
+  }
+  write_danmaku_ass(events, output_path, options)
+}
+
+pub fn convert_video_danmaku_to_ass(
+  items: &[VideoDanmakuItem],
+  output_path: &Path,
+  options: &DanmakuAssOptions,
+) -> Result<DanmakuAssSummary, String> {
+  let events = items
+    .iter()
+    .filter(|item| !item.text.trim().is_empty())
+    .map(|item| DanmakuEvent {
+      offset: item.offset.max(0.0),
+      kind: DanmakuKind::Scroll,
+      text: item.text.trim().to_string(),
+      color: item.color,
+    })
+    .collect();
+  write_danmaku_ass(events, output_path, options)
+}
+
+fn write_danmaku_ass(
+  mut events: Vec<DanmakuEvent>,
+  output_path: &Path,
+  options: &DanmakuAssOptions,
+) -> Result<DanmakuAssSummary, String> {
+  events.sort_by(|a, b| a.offset.partial_cmp(&b.offset).unwrap_or(std::cmp::Ordering::Equal));
+
+  let width = options.width.max(1) as f64;
+  let height = options.height.max(1) as f64;
+  let font_size = options.font_size.max(1) as f64;
+  let line_height = font_size * LINE_HEIGHT_RATIO;
+  let scroll_seconds = options.scroll_seconds.max(1.0);
+  let lane_count = ((height * options.scroll_area_ratio.clamp(0.1, 1.0)) / line_height)
+    .floor()
+    .max(1.0) as usize;
+  let mut scroll_lanes: Vec<Option<ScrollLane>> = (0..lane_count).map(|_| None).collect();
+  let mut superchat_lanes: Vec<f64> = vec![f64::MIN; SUPERCHAT_MAX_LANES];
+  let mut gift_lanes: Vec<f64> = vec![f64::MIN; GIFT_MAX_LANES];
+
+  let mut summary = DanmakuAssSummary {
+    output_path: output_path.to_string_lossy().to_string(),
+    ..Default::default()
+  };
+  let mut dialogues = Vec::with_capacity(events.len());
+  for event in &events {
+    match &event.kind {
+      DanmakuKind::Scroll => {
+        let text_width = estimate_text_width(&event.text, font_size);
+        let speed = (width + text_width) / scroll_seconds;
+        let lane = match pick_scroll_lane(&scroll_lanes, event.offset, width, speed, scroll_seconds) {
+          Some(lane) => lane,
+          None => {
+            summary.dropped_count += 1;
+            continue;
+          }
+        };
+        scroll_lanes[lane] = Some(ScrollLane {
+          start: event.offset,
+          width: text_width,
+          speed,
+        });
+        let y = lane as f64 * line_height;
+        dialogues.push(format!(
+          "Dialogue: 0,{},{},Danmaku,,0,0,0,,{{\\move({:.0},{:.0},{:.0},{:.0}){}}}{}",
+          format_ass_time(event.offset),
+          format_ass_time(event.offset + scroll_seconds),
+          width,
+          y,
+          -text_width,
+          y,
+          color_override(event.color),
+          escape_ass_text(&event.text)
+        ));
+        summary.scroll_count += 1;
+      }
+      DanmakuKind::SuperChat { seconds, color } => {
+        let end = event.offset + seconds;
+        let lane = match pick_fixed_lane(&mut superchat_lanes, event.offset, end) {
+          Some(lane) => lane,
+          None => {
+            summary.dropped_count += 1;
+            continue;
+          }
+        };
+        let box_color = color
+          .map(|value| format!("\\3c{}", to_ass_color(value)))
+          .unwrap_or_default();
+        let margin_v = (lane as f64 * line_height * 1.5).round() as i64 + 20;
+        dialogues.push(format!(
+          "Dialogue: 2,{},{},SuperChat,,20,20,{},,{{{}}}{}",
+          format_ass_time(event.offset),
+          format_ass_time(end),
+          margin_v,
+          box_color,
+          escape_ass_text(&event.text)
+        ));
+        summary.superchat_count += 1;
+      }
+      DanmakuKind::Gift => {
+        let end = event.offset + GIFT_DISPLAY_SECONDS;
+        let lane = match pick_fixed_lane(&mut gift_lanes, event.offset, end) {
+          Some(lane) => lane,
+          None => {
+            summary.dropped_count += 1;
+            continue;
+          }
+        };
+        let y = height - (lane as f64 + 1.0) * line_height * 0.8;
+        dialogues.push(format!(
+          "Dialogue: 1,{},{},Gift,,0,0,0,,{{\\pos(20,{:.0}){}}}{}",
+          format_ass_time(event.offset),
+          format_ass_time(end),
+          y,
+          color_override(event.color),
+          escape_ass_text(&event.text)
+        ));
+        summary.gift_count += 1;
+      }
+    }
+  }
+
+  if let Some(parent) = output_path.parent() {
+    if !parent.as_os_str().is_empty() {
+      fs::create_dir_all(parent).map_err(|err| format!("创建字幕目录失败: {}", err))?;
+    }
+  }
+  let mut file = File::create(output_path).map_err(|err| format!("创建字幕文件失败: {}", err))?;
+  let header = build_ass_header(options);
+  file
+    .write_all(header.as_bytes())
+    .map_err(|err| format!("写入字幕失败: {}", err))?;
+  for line in dialogues {
+    writeln!(file, "{}", line).map_err(|err| format!("写入字幕失败: {}", err))?;
+  }
+  Ok(summary)
+}
+
+pub fn resolve_burn_subtitle(
+  media_path: &Path,
+  options: &DanmakuAssOptions,
+) -> Result<Option<PathBuf>, String> {
+  let ass_path = danmaku_ass_path(media_path);
+  if ass_path.exists() {
+    return Ok(Some(ass_path));
+  }
+  let jsonl_path = danmaku_jsonl_path(media_path);
+  if !jsonl_path.exists() {
+    return Ok(None);
+  }
+  let segment_start = match load_segment_start(media_path, &jsonl_path) {
+    Some(value) => value,
+    None => return Ok(None),
+  };
+  convert_danmaku_to_ass(&jsonl_path, &ass_path, segment_start, None, options)?;
+  Ok(Some(ass_path))
+}
+
+pub fn escape_filter_path(path: &Path) -> String {
+  path
+    .to_string_lossy()
+    .replace('\\', "/")
+    .replace(':', "\\:")
+    .replace('\'', "'\\''")
+}
+
+pub fn parse_segment_start(value: &str) -> Option<DateTime<Utc>> {
+  DateTime::parse_from_rfc3339(value.trim())
+    .ok()
+    .map(|value| value.with_timezone(&Utc))
+}
+
+pub fn load_segment_start(media_path: &Path, jsonl_path: &Path) -> Option<DateTime<Utc>> {
+  let metadata_path = media_path.with_extension("metadata.json");
+  if let Ok(content) = fs::read_to_string(&metadata_path) {
+    if let Ok(value) = serde_json::from_str::<Value>(&content) {
+      if let Some(start) = value
+        .get("startTime")
+        .and_then(|value| value.as_str())
+        .and_then(parse_segment_start)
+      {
+        return Some(start);
+      }
+    }
+  }
+  let file = File::open(jsonl_path).ok()?;
+  BufReader::new(file).lines().map_while(Result::ok).find_map(|line| {
+    serde_json::from_str::<Value>(&line)
+      .ok()?
+      .get("timestamp")
+      .and_then(|value| value.as_str())
+      .and_then(parse_segment_start)
+  })
+}
+
+fn read_danmaku_events(
+  input_path: &Path,
+  segment_start: DateTime<Utc>,
+  options: &DanmakuAssOptions,
+) -> Result<Vec<DanmakuEvent>, String> {
+  let file = File::open(input_path).map_err(|err| format!("读取弹幕文件失败: {}", err))?;
+  let mut events = Vec::new();
+  for line in BufReader::new(file).lines().map_while(Result::ok) {
+    let value = match serde_json::from_str::<Value>(line.trim()) {
+      Ok(value) => value,
+      Err(_) => continue,
+    };
+    let received_at = match value
+      .get("timestamp")
+      .and_then(|value| value.as_str())
+      .and_then(parse_segment_start)
+    {
+      Some(value) => value,
+      None => continue,
+    };
+    let offset = (received_at - segment_start).num_milliseconds() as f64 / 1000.0;
+    if offset < 0.0 {
+      continue;
+    }
+    let cmd = value.get("cmd").and_then(|value| value.as_str()).unwrap_or("");
+    let data = value.get("data").unwrap_or(&Value::Null);
+    let event = match cmd {
+      "DANMU_MSG" => parse_danmu_msg(data, offset),
+      "SUPER_CHAT_MESSAGE" if options.include_superchat => {
+        parse_superchat(data, offset)
+      }
+      "SEND_GIFT" if options.include_gift => parse_gift(data, offset),
+      "GUARD_BUY" if options.include_gift => parse_guard(data, offset),
+      _ => None,
+    };
+    if let Some(event) = event {
+      events.push(event);
+    }
+  }
+  Ok(events)
+}
+
+fn parse_danmu_msg(data: &Value, offset: f64) -> Option<DanmakuEvent> {
+  let info = data.get("info")?.as_array()?;
+  let text = info.get(1)?.as_str()?.trim().to_string();
+  if text.is_empty() {
+    return None;
+  }
+  let color = info
+    .first()
+    .and_then(|value| value.get(3))
+    .and_then(|value| value.as_u64())
+    .map(|value| value as u32)
+    .unwrap_or(0xffffff);
+  Some(DanmakuEvent {
+    offset,
+    kind: DanmakuKind::Scroll,
+    text,
+    color,
+  })
+}
+
+fn parse_superchat(data: &Value, offset: f64) -> Option<DanmakuEvent> {
+  let body = data.get("data")?;
+  let message = body.get("message")?.as_str()?.trim();
+  let uname = body
+    .get("user_info")
+    .and_then(|value| value.get("uname"))
+    .and_then(|value| value.as_str())
+    .unwrap_or("");
+  let price = body.get("price").and_then(|value| value.as_f64()).unwrap_or(0.0);
+  let seconds = body
+    .get("time")
+    .and_then(|value| value.as_f64())
+    .unwrap_or(SUPERCHAT_MIN_SECONDS)
+    .clamp(SUPERCHAT_MIN_SECONDS, SUPERCHAT_MAX_SECONDS);
+  let color = body
+    .get("background_bottom_color")
+    .or_else(|| body.get("background_color"))
+    .and_then(|value| value.as_str())
+    .and_then(parse_hex_color);
+  Some(DanmakuEvent {
+    offset,
+    kind: DanmakuKind::SuperChat { seconds, color },
+    text: format!("¥{} {}: {}", price, uname, message),
+    color: 0xffffff,
+  })
+}
+
+fn parse_gift(data: &Value, offset: f64) -> Option<DanmakuEvent> {
+  let body = data.get("data")?;
+  let uname = body.get("uname")?.as_str()?;
+  let gift_name = body.get("giftName")?.as_str()?;
+  let action = body.get("action").and_then(|value| value.as_str()).unwrap_or("投喂");
+  let num = body.get("num").and_then(|value| value.as_i64()).unwrap_or(1);
+  Some(DanmakuEvent {
+    offset,
+    kind: DanmakuKind::Gift,
+    text: format!("{} {} {} x{}", uname, action, gift_name, num),
+    color: 0xffd700,
+  })
+}
+
+fn parse_guard(data: &Value, offset: f64) -> Option<DanmakuEvent> {
+  let body = data.get("data")?;
+  let uname = body.get("username")?.as_str()?;
+  let gift_name = body.get("gift_name").and_then(|value| value.as_str()).unwrap_or("舰长");
+  Some(DanmakuEvent {
+    offset,
+    kind: DanmakuKind::Gift,
+    text: format!("{} 开通了 {}", uname, gift_name),
+    color: 0x66ccff,
+  })
+}
+
+fn pick_scroll_lane(
+  lanes: &[Option<ScrollLane>],
+  start: f64,
+  screen_width: f64,
+  speed: f64,
+  scroll_seconds: f64,
+) -> Option<usize> {
+  lanes.iter().position(|lane| match lane {
+    None => true,
+    Some(prev) => {
+      let fully_entered = start >= prev.start + prev.width / prev.speed;
+      let catch_up = start + screen_width / speed < prev.start + scroll_seconds;
+      fully_entered && !catch_up
+    }
+  })
+}
+
+fn pick_fixed_lane(lanes: &mut [f64], start: f64, end: f64) -> Option<usize> {
+  let index = lanes.iter().position(|busy_until| *busy_until <= start)?;
+  lanes[index] = end;
+  Some(index)
+}
+
+fn estimate_text_width(text: &str, font_size: f64) -> f64 {
+  text
+    .chars()
+    .map(|ch| if ch.is_ascii() { font_size * 0.55 } else { font_size })
+    .sum()
+}
+
+fn build_ass_header(options: &DanmakuAssOptions) -> String {
+  let alpha = format!("{:02X}", ((1.0 - options.opacity.clamp(0.0, 1.0)) * 255.0).round() as u8);
+  let font_size = options.font_size.max(1);
+  let small_size = (font_size as f64 * 0.75).round() as i64;
+  format!(
+    "[Script Info]\n\
+     ScriptType: v4.00+\n\
+     PlayResX: {width}\n\
+     PlayResY: {height}\n\
+     WrapStyle: 2\n\
+     ScaledBorderAndShadow: yes\n\
+     \n\
+     [V4+ Styles]\n\
+     Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n\
+     Style: Danmaku,{font},{size},&H{alpha}FFFFFF,&H{alpha}FFFFFF,&H{alpha}000000,&H{alpha}000000,1,0,0,0,100,100,0,0,1,1.5,0,7,0,0,0,1\n\
+     Style: SuperChat,{font},{small},&H00FFFFFF,&H00FFFFFF,&H40B06A2E,&H40000000,1,0,0,0,100,100,0,0,3,6,0,1,20,20,20,1\n\
+     Style: Gift,{font},{small},&H{alpha}FFFFFF,&H{alpha}FFFFFF,&H{alpha}000000,&H{alpha}000000,0,0,0,0,100,100,0,0,1,1.5,0,7,0,0,0,1\n\
+     \n\
+     [Events]\n\
+     Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n",
+    width = options.width.max(1),
+    height = options.height.max(1),
+    font = options.font_name,
+    size = font_size,
+    small = small_size,
+    alpha = alpha,
+  )
+}
+
+pub(crate) fn format_ass_time(seconds: f64) -> String {
+  let total_cs = (seconds.max(0.0) * 100.0).round() as i64;
+  let hours = total_cs / 360_000;
+  let minutes = (total_cs / 6000) % 60;
+  let secs = (total_cs / 100) % 60;
+  let cs = total_cs % 100;
+  format!("{}:{:02}:{:02}.{:02}", hours, minutes, secs, cs)
+}
+
+fn to_ass_color(rgb: u32) -> String {
+  let r = (rgb >> 16) & 0xff;
+  let g = (rgb >> 8) & 0xff;
+  let b = rgb & 0xff;
+  format!("&H{:02X}{:02X}{:02X}&", b, g, r)
+}
+
+fn color_override(rgb: u32) -> String {
+  if rgb & 0xffffff == 0xffffff {
+    String::new()
+  } else {
+    format!("\\c{}", to_ass_color(rgb))
+  }
+}
+
+fn parse_hex_color(value: &str) -> Option<u32> {
+  u32::from_str_radix(value.trim().trim_start_matches('#'), 16).ok()
+}
+
+pub(crate) fn escape_ass_text(text: &str) -> String {
+  text
+    .replace('\\', "＼")
+    .replace('{', "｛")
+    .replace('}', "｝")
+    .replace(['\r', '\n'], " ")
+}
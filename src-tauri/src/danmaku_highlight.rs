@@ -0,0 +1,290 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::danmaku_ass::{danmaku_jsonl_path, load_segment_start, parse_segment_start};
+use crate::processing::{format_timecode_seconds, probe_duration_seconds, ClipSource};
+
+const MAX_DENSITY_SECONDS: f64 = 48.0 * 3600.0;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HighlightOptions {
+  pub window_seconds: f64,
+  pub threshold: f64,
+  pub min_score: f64,
+  pub padding_seconds: f64,
+  pub merge_gap_seconds: f64,
+  pub max_clips: usize,
+  pub message_weight: f64,
+  pub gift_weight: f64,
+  pub superchat_weight: f64,
+}
+
+impl Default for HighlightOptions {
+  fn default() -> Self {
+    Self {
+      window_seconds: 30.0,
+      threshold: 2.0,
+      min_score: 10.0,
+      padding_seconds: 15.0,
+      merge_gap_seconds: 10.0,
+      max_clips: 10,
+      message_weight: 1.0,
+      gift_weight: 2.0,
+      superchat_weight: 5.0,
+    }
+  }
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightRange {
+  pub start_seconds: f64,
+  pub end_seconds: f64,
+  pub peak_seconds: f64,
+  pub peak_score: f64,
+  pub message_count: u64,
+  pub gift_count: u64,
+  pub superchat_count: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HighlightReport {
+  pub duration_seconds: f64,
+  pub baseline: f64,
+  pub cutoff: f64,
+  pub density: Vec<f64>,
+  pub ranges: Vec<HighlightRange>,
+  pub sources: Vec<ClipSource>,
+}
+
+#[derive(Default, Clone)]
+struct SecondBucket {
+  messages: u64,
+  gifts: u64,
+  superchats: u64,
+}
+
+pub fn propose_highlight_sources(
+  media_path: &Path,
+  segment_start: Option<DateTime<Utc>>,
+  options: &HighlightOptions,
+) -> Result<HighlightReport, String> {
+  let jsonl_path = danmaku_jsonl_path(media_path);
+  if !jsonl_path.is_file() {
+    return Err("弹幕文件不存在".to_string());
+  }
+  let segment_start = match segment_start.or_else(|| load_segment_start(media_path, &jsonl_path)) {
+    Some(value) => value,
+    None => return Err("无法确定录制开始时间".to_string()),
+  };
+  let duration_seconds = if media_path.is_file() {
+    probe_duration_seconds(media_path).ok()
+  } else {
+    None
+  };
+  let mut report = detect_highlights(&jsonl_path, segment_start, duration_seconds, options)?;
+  let input_path = media_path.to_string_lossy().to_string();
+  report.sources = report
+    .ranges
+    .iter()
+    .enumerate()
+    .map(|(index, range)| ClipSource {
+      input_path: input_path.clone(),
+      start_time: Some(format_timecode_seconds(range.start_seconds)),
+      end_time: Some(format_timecode_seconds(range.end_seconds)),
+      order: index as i64 + 1,
+    })
+    .collect();
+  Ok(report)
+}
+
+pub fn detect_highlights(
+  jsonl_path: &Path,
+  segment_start: DateTime<Utc>,
+  duration_seconds: Option<f64>,
+  options: &HighlightOptions,
+) -> Result<HighlightReport, String> {
+  let buckets = read_density_buckets(jsonl_path, segment_start, duration_seconds)?;
+  let duration_seconds = duration_seconds
+    .filter(|value| *value > 0.0)
+    .unwrap_or(buckets.len() as f64);
+  if buckets.is_empty() {
+    return Ok(HighlightReport {
+      duration_seconds,
+      baseline: 0.0,
+      cutoff: 0.0,
+      density: Vec::new(),
+      ranges: Vec::new(),
+      sources: Vec::new(),
+    });
+  }
+
+  let density: Vec<f64> = buckets
+    .iter()
+    .map(|bucket| {
+      bucket.messages as f64 * options.message_weight
+        + bucket.gifts as f64 * options.gift_weight
+        + bucket.superchats as f64 * options.superchat_weight
+    })
+    .collect();
+  let windowed = window_sums(&density, options.window_seconds);
+  let baseline = windowed.iter().sum::<f64>() / windowed.len() as f64;
+  let cutoff = (baseline * options.threshold.max(1.0)).max(options.min_score.max(0.0));
+
+  let padding = options.padding_seconds.max(0.0);
+  let mut spans: Vec<(f64, f64)> = Vec::new();
+  let mut run_start: Option<usize> = None;
+  for (second, score) in windowed.iter().enumerate() {
+    match (run_start, *score >= cutoff) {
+      (None, true) => run_start = Some(second),
+      (Some(start), false) => {
+        spans.push(padded_span(start, second, padding, duration_seconds));
+        run_start = None;
+      }
+      _ => {}
+    }
+  }
+  if let Some(start) = run_start {
+    spans.push(padded_span(start, windowed.len(), padding, duration_seconds));
+  }
+
+  let merge_gap = options.merge_gap_seconds.max(0.0);
+  let mut merged: Vec<(f64, f64)> = Vec::new();
+  for (start, end) in spans {
+    match merged.last_mut() {
+      Some(last) if start - last.1 <= merge_gap => last.1 = last.1.max(end),
+      _ => merged.push((start, end)),
+    }
+  }
+
+  let mut ranges: Vec<HighlightRange> = merged
+    .into_iter()
+    .map(|(start, end)| summarize_range(&buckets, &windowed, start, end))
+    .collect();
+  if options.max_clips > 0 && ranges.len() > options.max_clips {
+    ranges.sort_by(|a, b| b.peak_score.partial_cmp(&a.peak_score).unwrap_or(std::cmp::Ordering::Equal));
+    ranges.truncate(options.max_clips);
+    ranges.sort_by(|a, b| {
+      a.start_seconds
+        .partial_cmp(&b.start_seconds)
+        .unwrap_or(std::cmp::Ordering::Equal)
+    });
+  }
+
+  Ok(HighlightReport {
+    duration_seconds,
+    baseline,
+    cutoff,
+    density,
+    ranges,
+    sources: Vec::new(),
+  })
+}
+
+fn read_density_buckets(
+  jsonl_path: &Path,
+  segment_start: DateTime<Utc>,
+  duration_seconds: Option<f64>,
+) -> Result<Vec<SecondBucket>, String> {
+  let file = File::open(jsonl_path).map_err(|err| format!("读取弹幕文件失败: {}", err))?;
+  let limit = duration_seconds
+    .filter(|value| *value > 0.0)
+    .map(|value| value.min(MAX_DENSITY_SECONDS));
+  let mut buckets: Vec<SecondBucket> = match limit {
+    Some(value) => vec![SecondBucket::default(); value.ceil() as usize],
+    None => Vec::new(),
+  };
+  for line in BufReader::new(file).lines().map_while(Result::ok) {
+    let value = match serde_json::from_str::<Value>(line.trim()) {
+      Ok(value) => value,
+      Err(_) => continue,
+    };
+    let received_at = match value
+      .get("timestamp")
+      .and_then(|value| value.as_str())
+      .and_then(parse_segment_start)
+    {
+      Some(value) => value,
+      None => continue,
+    };
+    let offset = (received_at - segment_start).num_milliseconds() as f64 / 1000.0;
+    if offset < 0.0 || offset >= limit.unwrap_or(MAX_DENSITY_SECONDS) {
+      continue;
+    }
+    let second = offset.floor() as usize;
+    if second >= buckets.len() {
+      if limit.is_some() {
+        continue;
+      }
+      buckets.resize(second + 1, SecondBucket::default());
+    }
+    let bucket = &mut buckets[second];
+    match value.get("cmd").and_then(|value| value.as_str()).unwrap_or("") {
+      "DANMU_MSG" => bucket.messages += 1,
+      "SEND_GIFT" | "COMBO_SEND" | "GUARD_BUY" => bucket.gifts += 1,
+      "SUPER_CHAT_MESSAGE" => bucket.superchats += 1,
+      _ => {}
+    }
+  }
+  Ok(buckets)
+}
+
+fn window_sums(density: &[f64], window_seconds: f64) -> Vec<f64> {
+  let half = (window_seconds.max(1.0) / 2.0).floor() as usize;
+  let mut prefix = Vec::with_capacity(density.len() + 1);
+  prefix.push(0.0);
+  for value in density {
+    let last = *prefix.last().unwrap_or(&0.0);
+    prefix.push(last + value);
+  }
+  (0..density.len())
+    .map(|second| {
+      let start = second.saturating_sub(half);
+      let end = (second + half + 1).min(density.len());
+      prefix[end] - prefix[start]
+    })
+    .collect()
+}
+
+fn padded_span(start: usize, end: usize, padding: f64, duration_seconds: f64) -> (f64, f64) {
+  let start = (start as f64 - padding).max(0.0);
+  let end = (end as f64 + padding).min(duration_seconds.max(0.0));
+  (start, end.max(start))
+}
+
+fn summarize_range(
+  buckets: &[SecondBucket],
+  windowed: &[f64],
+  start: f64,
+  end: f64,
+) -> HighlightRange {
+  let first = (start.floor() as usize).min(buckets.len());
+  let last = (end.ceil() as usize).min(buckets.len());
+  let mut range = HighlightRange {
+    start_seconds: start,
+    end_seconds: end,
+    peak_seconds: start,
+    peak_score: 0.0,
+    message_count: 0,
+    gift_count: 0,
+    superchat_count: 0,
+  };
+  for second in first..last {
+    let bucket = &buckets[second];
+    range.message_count += bucket.messages;
+    range.gift_count += bucket.gifts;
+    range.superchat_count += bucket.superchats;
+    if windowed[second] > range.peak_score {
+      range.peak_score = windowed[second];
+      range.peak_seconds = second as f64;
+    }
+  }
+  range
+}
@@ -4,6 +4,8 @@ use std::sync::Mutex;
 use rusqlite::Connection;
 use thiserror::Error;
 
+mod migrations;
+
 #[derive(Debug, Error)]
 pub enum DbError {
   #[error("database error: {0}")]
@@ -12,6 +14,14 @@ pub enum DbError {
   Io(#[from] std::io::Error),
   #[error("database lock poisoned")]
   Lock,
+  #[error("migration {version} ({name}) failed: {source}")]
+  Migration {
+    version: i64,
+    name: &'static str,
+    source: rusqlite::Error,
+  },
+  #[error("database schema version {0} is newer than supported version {1}")]
+  UnsupportedVersion(i64, i64),
 }
 
 pub struct Db {
@@ -24,97 +34,9 @@ impl Db {
       std::fs::create_dir_all(parent)?;
     }
 
-    let conn = Connection::open(db_path)?;
-    let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN merged_id INTEGER", []);
-    conn.execute_batch(include_str!("db/schema.sql"))?;
-    let _ = conn.execute(
-      "INSERT OR IGNORE INTO app_settings (key, value, updated_at) \
-       VALUES ('baidu_sync_concurrency', '3', datetime('now'))",
-      [],
-    );
-    let _ = conn.execute(
-      "UPDATE app_settings SET value = '3', updated_at = datetime('now') \
-       WHERE key = 'baidu_sync_concurrency' AND value = '1'",
-      [],
-    );
-    let _ = conn.execute("ALTER TABLE live_settings ADD COLUMN record_path TEXT", []);
-    let _ = conn.execute(
-      "ALTER TABLE live_settings ADD COLUMN baidu_sync_enabled INTEGER DEFAULT 0",
-      [],
-    );
-    let _ = conn.execute("ALTER TABLE live_settings ADD COLUMN baidu_sync_path TEXT", []);
-    let _ = conn.execute(
-      "ALTER TABLE live_settings ADD COLUMN title_split_min_seconds INTEGER DEFAULT 1800",
-      [],
-    );
-    let _ = conn.execute(
-      "ALTER TABLE live_settings ADD COLUMN stream_read_timeout_ms INTEGER DEFAULT 15000",
-      [],
-    );
-    let _ = conn.execute(
-      "ALTER TABLE live_settings ADD COLUMN flv_fix_adjust_timestamp_jump INTEGER DEFAULT 1",
-      [],
-    );
-    let _ = conn.execute(
-      "ALTER TABLE live_settings ADD COLUMN flv_fix_split_on_timestamp_jump INTEGER DEFAULT 1",
-      [],
-    );
-    let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN aid INTEGER", []);
-    let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN remote_state INTEGER", []);
-    let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN reject_reason TEXT", []);
-    let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN priority INTEGER DEFAULT 0", []);
-    let _ = conn.execute(
-      "ALTER TABLE submission_task ADD COLUMN baidu_sync_enabled INTEGER DEFAULT 0",
-      [],
-    );
-    let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN baidu_sync_path TEXT", []);
-    let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN baidu_sync_filename TEXT", []);
-    let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN topic_id INTEGER", []);
-    let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN mission_id INTEGER", []);
-    let _ = conn.execute("ALTER TABLE submission_task ADD COLUMN activity_title TEXT", []);
-    let _ = conn.execute("ALTER TABLE video_download ADD COLUMN cid INTEGER", []);
-    let _ = conn.execute("ALTER TABLE video_download ADD COLUMN content TEXT", []);
-    let _ = conn.execute(
-      "ALTER TABLE video_download ADD COLUMN source_type TEXT DEFAULT 'BILIBILI'",
-      [],
-    );
-    let _ = conn.execute(
-      "ALTER TABLE video_download ADD COLUMN progress_total INTEGER DEFAULT 0",
-      [],
-    );
-    let _ = conn.execute(
-      "ALTER TABLE video_download ADD COLUMN progress_done INTEGER DEFAULT 0",
-      [],
-    );
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_progress REAL DEFAULT 0.0", []);
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_uploaded_bytes INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_total_bytes INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_cid INTEGER", []);
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_file_name TEXT", []);
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_session_id TEXT", []);
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_biz_id INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_endpoint TEXT", []);
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_auth TEXT", []);
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_uri TEXT", []);
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_chunk_size INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN upload_last_part_index INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN remote_dir TEXT", []);
-    let _ = conn.execute("ALTER TABLE merged_video ADD COLUMN remote_name TEXT", []);
-    let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_progress REAL DEFAULT 0.0", []);
-    let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_uploaded_bytes INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_total_bytes INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_session_id TEXT", []);
-    let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_biz_id INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_endpoint TEXT", []);
-    let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_auth TEXT", []);
-    let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_uri TEXT", []);
-    let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_chunk_size INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE task_output_segment ADD COLUMN upload_last_part_index INTEGER DEFAULT 0", []);
-    let _ = conn.execute("ALTER TABLE live_room_settings ADD COLUMN baidu_sync_path TEXT", []);
-    let _ = conn.execute(
-      "ALTER TABLE live_room_settings ADD COLUMN baidu_sync_enabled INTEGER DEFAULT 0",
-      [],
-    );
+    let mut conn = Connection::open(db_path)?;
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    migrations::run_migrations(&mut conn)?;
 
     Ok(Self {
       conn: Mutex::new(conn),
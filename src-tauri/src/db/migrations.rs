@@ -0,0 +1,468 @@
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension, Transaction};
+
+use super::DbError;
+
+struct Migration {
+  version: i64,
+  name: &'static str,
+  apply: fn(&Transaction) -> Result<(), rusqlite::Error>,
+}
+
+const MIGRATIONS: &[Migration] = &[
+  Migration {
+    version: 1,
+    name: "baseline_schema",
+    apply: apply_baseline_schema,
+  },
+  Migration {
+    version: 2,
+    name: "seed_baidu_sync_concurrency",
+    apply: seed_baidu_sync_concurrency,
+  },
+  Migration {
+    version: 3,
+    name: "create_webhook_endpoint",
+    apply: create_webhook_endpoint,
+  },
+  Migration {
+    version: 4,
+    name: "add_account_user_id",
+    apply: add_account_user_id,
+  },
+  Migration {
+    version: 5,
+    name: "add_submission_schedule",
+    apply: add_submission_schedule,
+  },
+  Migration {
+    version: 6,
+    name: "add_live_stream_preferences",
+    apply: add_live_stream_preferences,
+  },
+  Migration {
+    version: 7,
+    name: "add_live_room_schedule",
+    apply: add_live_room_schedule,
+  },
+  Migration {
+    version: 8,
+    name: "add_live_room_settings_override",
+    apply: add_live_room_settings_override,
+  },
+  Migration {
+    version: 9,
+    name: "add_live_room_auto_submit",
+    apply: add_live_room_auto_submit,
+  },
+  Migration {
+    version: 10,
+    name: "create_live_session",
+    apply: create_live_session,
+  },
+  Migration {
+    version: 11,
+    name: "add_baidu_sync_verification",
+    apply: add_baidu_sync_verification,
+  },
+  Migration {
+    version: 12,
+    name: "add_download_sidecar",
+    apply: add_download_sidecar,
+  },
+  Migration {
+    version: 13,
+    name: "create_vod_subscription",
+    apply: create_vod_subscription,
+  },
+  Migration {
+    version: 14,
+    name: "add_vod_subscription_item_retry",
+    apply: add_vod_subscription_item_retry,
+  },
+  Migration {
+    version: 15,
+    name: "add_video_download_account",
+    apply: add_video_download_account,
+  },
+  Migration {
+    version: 16,
+    name: "add_live_session_submission",
+    apply: add_live_session_submission,
+  },
+  Migration {
+    version: 17,
+    name: "rename_download_connection_settings",
+    apply: rename_download_connection_settings,
+  },
+];
+
+const LEGACY_COLUMNS: &[(&str, &str, &str)] = &[
+  ("task_output_segment", "merged_id", "INTEGER"),
+  ("live_settings", "record_path", "TEXT"),
+  ("live_settings", "baidu_sync_enabled", "INTEGER DEFAULT 0"),
+  ("live_settings", "baidu_sync_path", "TEXT"),
+  ("live_settings", "title_split_min_seconds", "INTEGER DEFAULT 1800"),
+  ("live_settings", "stream_read_timeout_ms", "INTEGER DEFAULT 15000"),
+  ("live_settings", "flv_fix_adjust_timestamp_jump", "INTEGER DEFAULT 1"),
+  ("live_settings", "flv_fix_split_on_timestamp_jump", "INTEGER DEFAULT 1"),
+  ("submission_task", "aid", "INTEGER"),
+  ("submission_task", "remote_state", "INTEGER"),
+  ("submission_task", "reject_reason", "TEXT"),
+  ("submission_task", "priority", "INTEGER DEFAULT 0"),
+  ("submission_task", "baidu_sync_enabled", "INTEGER DEFAULT 0"),
+  ("submission_task", "baidu_sync_path", "TEXT"),
+  ("submission_task", "baidu_sync_filename", "TEXT"),
+  ("submission_task", "topic_id", "INTEGER"),
+  ("submission_task", "mission_id", "INTEGER"),
+  ("submission_task", "activity_title", "TEXT"),
+  ("video_download", "cid", "INTEGER"),
+  ("video_download", "content", "TEXT"),
+  ("video_download", "source_type", "TEXT DEFAULT 'BILIBILI'"),
+  ("video_download", "progress_total", "INTEGER DEFAULT 0"),
+  ("video_download", "progress_done", "INTEGER DEFAULT 0"),
+  ("merged_video", "upload_progress", "REAL DEFAULT 0.0"),
+  ("merged_video", "upload_uploaded_bytes", "INTEGER DEFAULT 0"),
+  ("merged_video", "upload_total_bytes", "INTEGER DEFAULT 0"),
+  ("merged_video", "upload_cid", "INTEGER"),
+  ("merged_video", "upload_file_name", "TEXT"),
+  ("merged_video", "upload_session_id", "TEXT"),
+  ("merged_video", "upload_biz_id", "INTEGER DEFAULT 0"),
+  ("merged_video", "upload_endpoint", "TEXT"),
+  ("merged_video", "upload_auth", "TEXT"),
+  ("merged_video", "upload_uri", "TEXT"),
+  ("merged_video", "upload_chunk_size", "INTEGER DEFAULT 0"),
+  ("merged_video", "upload_last_part_index", "INTEGER DEFAULT 0"),
+  ("merged_video", "remote_dir", "TEXT"),
+  ("merged_video", "remote_name", "TEXT"),
+  ("task_output_segment", "upload_progress", "REAL DEFAULT 0.0"),
+  ("task_output_segment", "upload_uploaded_bytes", "INTEGER DEFAULT 0"),
+  ("task_output_segment", "upload_total_bytes", "INTEGER DEFAULT 0"),
+  ("task_output_segment", "upload_session_id", "TEXT"),
+  ("task_output_segment", "upload_biz_id", "INTEGER DEFAULT 0"),
+  ("task_output_segment", "upload_endpoint", "TEXT"),
+  ("task_output_segment", "upload_auth", "TEXT"),
+  ("task_output_segment", "upload_uri", "TEXT"),
+  ("task_output_segment", "upload_chunk_size", "INTEGER DEFAULT 0"),
+  ("task_output_segment", "upload_last_part_index", "INTEGER DEFAULT 0"),
+  ("task_output_segment", "segment_start_seconds", "REAL"),
+  ("task_output_segment", "segment_end_seconds", "REAL"),
+  ("live_room_settings", "baidu_sync_path", "TEXT"),
+  ("live_room_settings", "baidu_sync_enabled", "INTEGER DEFAULT 0"),
+];
+
+pub fn run_migrations(conn: &mut Connection) -> Result<(), DbError> {
+  conn.execute_batch(
+    "CREATE TABLE IF NOT EXISTS schema_version (
+      version INTEGER PRIMARY KEY,
+      name TEXT NOT NULL,
+      applied_at TEXT NOT NULL
+    );",
+  )?;
+  let current: i64 = conn.query_row(
+    "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+    [],
+    |row| row.get(0),
+  )?;
+  let latest = MIGRATIONS.last().map(|migration| migration.version).unwrap_or(0);
+  if current > latest {
+    return Err(DbError::UnsupportedVersion(current, latest));
+  }
+
+  for migration in MIGRATIONS.iter().filter(|migration| migration.version > current) {
+    let tx = conn.transaction()?;
+    let result = (migration.apply)(&tx).and_then(|_| {
+      tx.execute(
+        "INSERT INTO schema_version (version, name, applied_at) VALUES (?1, ?2, ?3)",
+        (migration.version, migration.name, Utc::now().to_rfc3339()),
+      )
+      .map(|_| ())
+    });
+    match result {
+      Ok(()) => tx.commit().map_err(|source| DbError::Migration {
+        version: migration.version,
+        name: migration.name,
+        source,
+      })?,
+      Err(source) => {
+        let _ = tx.rollback();
+        return Err(DbError::Migration {
+          version: migration.version,
+          name: migration.name,
+          source,
+        });
+      }
+    }
+  }
+  Ok(())
+}
+
+fn apply_baseline_schema(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  for (table, column, definition) in LEGACY_COLUMNS {
+    add_column_if_missing(tx, table, column, definition)?;
+  }
+  tx.execute_batch(include_str!("schema.sql"))
+}
+
+fn seed_baidu_sync_concurrency(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  tx.execute(
+    "INSERT OR IGNORE INTO app_settings (key, value, updated_at) \
+     VALUES ('baidu_sync_concurrency', '3', datetime('now'))",
+    [],
+  )?;
+  tx.execute(
+    "UPDATE app_settings SET value = '3', updated_at = datetime('now') \
+     WHERE key = 'baidu_sync_concurrency' AND value = '1'",
+    [],
+  )?;
+  Ok(())
+}
+
+fn create_webhook_endpoint(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  tx.execute_batch(
+    "CREATE TABLE IF NOT EXISTS webhook_endpoint (
+      id INTEGER PRIMARY KEY AUTOINCREMENT,
+      name TEXT NOT NULL,
+      provider TEXT NOT NULL DEFAULT 'GENERIC',
+      url TEXT NOT NULL,
+      secret TEXT,
+      chat_id TEXT,
+      events TEXT,
+      enabled INTEGER DEFAULT 1,
+      max_retries INTEGER DEFAULT 3,
+      retry_backoff_seconds INTEGER DEFAULT 5,
+      created_at TEXT NOT NULL,
+      updated_at TEXT NOT NULL
+    );",
+  )
+}
+
+fn add_account_user_id(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  add_column_if_missing(tx, "submission_task", "account_user_id", "INTEGER")?;
+  add_column_if_missing(tx, "live_room_settings", "account_user_id", "INTEGER")
+}
+
+fn add_submission_schedule(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  add_column_if_missing(tx, "submission_task", "scheduled_publish_time", "INTEGER")?;
+  add_column_if_missing(tx, "submission_task", "scheduled_publish_mode", "TEXT")
+}
+
+fn add_live_stream_preferences(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  add_column_if_missing(tx, "live_settings", "stream_protocol_order", "TEXT")?;
+  add_column_if_missing(tx, "live_settings", "stream_format_order", "TEXT")?;
+  add_column_if_missing(tx, "live_settings", "stream_codec_order", "TEXT")?;
+  add_column_if_missing(tx, "live_settings", "stream_cdn_order", "TEXT")?;
+  add_column_if_missing(tx, "live_settings", "stream_cdn_failover", "INTEGER DEFAULT 1")
+}
+
+fn add_live_room_schedule(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  add_column_if_missing(tx, "live_room_settings", "record_weekdays", "TEXT")?;
+  add_column_if_missing(tx, "live_room_settings", "record_time_windows", "TEXT")?;
+  add_column_if_missing(tx, "live_room_settings", "max_record_minutes", "INTEGER")?;
+  add_column_if_missing(tx, "live_room_settings", "title_regex", "TEXT")
+}
+
+fn add_live_room_settings_override(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  add_column_if_missing(tx, "live_room_settings", "settings_override", "TEXT")
+}
+
+fn add_live_room_auto_submit(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  add_column_if_missing(tx, "live_room_settings", "auto_submit_enabled", "INTEGER DEFAULT 0")?;
+  add_column_if_missing(tx, "live_room_settings", "auto_submit_template", "TEXT")
+}
+
+fn create_live_session(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  tx.execute_batch(
+    "CREATE TABLE IF NOT EXISTS live_session (
+      id INTEGER PRIMARY KEY AUTOINCREMENT,
+      room_id TEXT NOT NULL,
+      live_time TEXT,
+      title TEXT,
+      titles TEXT,
+      status TEXT NOT NULL,
+      start_time TEXT NOT NULL,
+      end_time TEXT,
+      create_time TEXT NOT NULL,
+      update_time TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_live_session_room_id ON live_session (room_id);",
+  )?;
+  add_column_if_missing(tx, "live_record_task", "session_id", "INTEGER")?;
+  if !table_exists(tx, "live_record_task")? {
+    return Ok(());
+  }
+  tx.execute_batch(
+    "CREATE INDEX IF NOT EXISTS idx_live_record_task_session_id ON live_record_task (session_id);",
+  )?;
+  backfill_live_sessions(tx)
+}
+
+fn backfill_live_sessions(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  const SESSION_GAP_SECONDS: i64 = 30 * 60;
+  let rows = {
+    let mut stmt = tx.prepare(
+      "SELECT id, room_id, start_time, end_time, title, status FROM live_record_task \
+       WHERE session_id IS NULL ORDER BY room_id ASC, start_time ASC, id ASC",
+    )?;
+    let rows = stmt
+      .query_map([], |row| {
+        Ok((
+          row.get::<_, i64>(0)?,
+          row.get::<_, String>(1)?,
+          row.get::<_, String>(2)?,
+          row.get::<_, Option<String>>(3)?,
+          row.get::<_, Option<String>>(4)?,
+          row.get::<_, String>(5)?,
+        ))
+      })?
+      .collect::<Result<Vec<_>, _>>()?;
+    rows
+  };
+  let parse = |value: &str| chrono::DateTime::parse_from_rfc3339(value).ok();
+  let now = Utc::now().to_rfc3339();
+  let mut current: Option<(i64, String, Option<String>, Vec<String>)> = None;
+  for (record_id, room_id, start_time, end_time, title, status) in rows {
+    let continues = current.as_ref().is_some_and(|(_, current_room, last_end, _)| {
+      *current_room == room_id
+        && match (last_end.as_deref().and_then(parse), parse(&start_time)) {
+          (Some(last_end), Some(start)) => (start - last_end).num_seconds() <= SESSION_GAP_SECONDS,
+          _ => false,
+        }
+    });
+    if !continues {
+      tx.execute(
+        "INSERT INTO live_session (room_id, live_time, title, titles, status, start_time, end_time, create_time, update_time) \
+         VALUES (?1, NULL, ?2, NULL, 'COMPLETED', ?3, NULL, ?4, ?5)",
+        (&room_id, title.as_deref(), &start_time, &now, &now),
+      )?;
+      current = Some((tx.last_insert_rowid(), room_id.clone(), None, Vec::new()));
+    }
+    if let Some((session_id, _, last_end, titles)) = current.as_mut() {
+      tx.execute(
+        "UPDATE live_record_task SET session_id = ?1 WHERE id = ?2",
+        (*session_id, record_id),
+      )?;
+      if let Some(title) = title.filter(|value| !value.trim().is_empty()) {
+        if !titles.contains(&title) {
+          titles.push(title);
+        }
+      }
+      *last_end = end_time.clone().or(Some(start_time.clone()));
+      tx.execute(
+        "UPDATE live_session SET titles = ?1, end_time = ?2, status = ?3 WHERE id = ?4",
+        (
+          serde_json::to_string(titles).unwrap_or_default(),
+          last_end.as_deref(),
+          if status == "RECORDING" { "RECORDING" } else { "COMPLETED" },
+          *session_id,
+        ),
+      )?;
+    }
+  }
+  Ok(())
+}
+
+fn add_baidu_sync_verification(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  add_column_if_missing(tx, "baidu_sync_task", "verify_status", "TEXT")?;
+  add_column_if_missing(tx, "baidu_sync_task", "remote_size", "INTEGER")?;
+  add_column_if_missing(tx, "baidu_sync_task", "remote_md5", "TEXT")?;
+  add_column_if_missing(tx, "baidu_sync_task", "verified_at", "TEXT")
+}
+
+fn add_download_sidecar(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  add_column_if_missing(tx, "video_download", "sidecar_options", "TEXT")?;
+  add_column_if_missing(tx, "video_download", "sidecar_files", "TEXT")
+}
+
+fn create_vod_subscription(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  tx.execute_batch(
+    "CREATE TABLE IF NOT EXISTS vod_subscription (
+      id INTEGER PRIMARY KEY AUTOINCREMENT,
+      source_type TEXT NOT NULL,
+      source_id TEXT NOT NULL,
+      mid TEXT,
+      name TEXT,
+      enabled INTEGER DEFAULT 1,
+      keyword TEXT,
+      start_time INTEGER NOT NULL,
+      check_interval_minutes INTEGER DEFAULT 30,
+      download_config TEXT,
+      auto_submit_enabled INTEGER DEFAULT 0,
+      auto_submit_template TEXT,
+      last_check_time TEXT,
+      last_error TEXT,
+      create_time TEXT NOT NULL,
+      update_time TEXT NOT NULL,
+      UNIQUE (source_type, source_id)
+    );
+    CREATE TABLE IF NOT EXISTS vod_subscription_item (
+      id INTEGER PRIMARY KEY AUTOINCREMENT,
+      subscription_id INTEGER NOT NULL,
+      bvid TEXT NOT NULL,
+      title TEXT,
+      pubdate INTEGER,
+      status TEXT NOT NULL,
+      download_task_ids TEXT,
+      submission_task_id TEXT,
+      error TEXT,
+      create_time TEXT NOT NULL,
+      UNIQUE (subscription_id, bvid)
+    );",
+  )
+}
+
+fn add_vod_subscription_item_retry(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  add_column_if_missing(tx, "vod_subscription_item", "retry_count", "INTEGER DEFAULT 0")
+}
+
+fn add_video_download_account(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  add_column_if_missing(tx, "video_download", "account_user_id", "INTEGER")
+}
+
+fn add_live_session_submission(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  add_column_if_missing(tx, "live_session", "submission_task_id", "TEXT")
+}
+
+fn rename_download_connection_settings(tx: &Transaction) -> Result<(), rusqlite::Error> {
+  tx.execute_batch(
+    "UPDATE OR IGNORE app_settings SET key = 'download_connections' WHERE key = 'download_aria2c_connections';
+    UPDATE OR IGNORE app_settings SET key = 'download_split' WHERE key = 'download_aria2c_split';
+    DELETE FROM app_settings WHERE key IN ('download_enable_aria2c', 'download_aria2c_connections', 'download_aria2c_split');",
+  )
+}
+
+fn table_exists(conn: &Connection, table: &str) -> Result<bool, rusqlite::Error> {
+  conn
+    .query_row(
+      "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+      [table],
+      |_| Ok(()),
+    )
+    .optional()
+    .map(|value| value.is_some())
+}
+
+fn add_column_if_missing(
+  conn: &Connection,
+  table: &str,
+  column: &str,
+  definition: &str,
+) -> Result<(), rusqlite::Error> {
+  if !table_exists(conn, table)? {
+    return Ok(());
+  }
+  let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+  let exists = stmt
+    .query_map([], |row| row.get::<_, String>(1))?
+    .collect::<Result<Vec<_>, _>>()?
+    .iter()
+    .any(|name| name.eq_ignore_ascii_case(column));
+  if exists {
+    return Ok(());
+  }
+  conn.execute(
+    &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+    [],
+  )?;
+  Ok(())
+}
@@ -102,6 +102,8 @@ CREATE TABLE IF NOT EXISTS task_output_segment (
   upload_uri TEXT,
   upload_chunk_size INTEGER DEFAULT 0,
   upload_last_part_index INTEGER DEFAULT 0,
+  segment_start_seconds REAL,
+  segment_end_seconds REAL,
   FOREIGN KEY (task_id) REFERENCES submission_task (task_id) ON DELETE CASCADE
 );
 
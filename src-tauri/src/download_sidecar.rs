@@ -0,0 +1,595 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::bilibili::client::BilibiliClient;
+use crate::danmaku_ass::{
+  convert_video_danmaku_to_ass, escape_ass_text, format_ass_time, DanmakuAssOptions,
+  VideoDanmakuItem,
+};
+use crate::login_store::AuthInfo;
+use crate::utils::escape_xml;
+
+const DANMAKU_SEGMENT_SECONDS: i64 = 360;
+const MAX_DANMAKU_SEGMENTS: i64 = 240;
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DownloadSidecarOptions {
+  pub subtitle: bool,
+  pub subtitle_format: String,
+  pub danmaku: bool,
+  pub danmaku_format: String,
+  pub cover: bool,
+  pub metadata: bool,
+  pub metadata_format: String,
+}
+
+impl DownloadSidecarOptions {
+  pub fn normalize(mut self) -> Self {
+    self.subtitle_format = pick_format(&self.subtitle_format, &["srt", "ass"]);
+    self.danmaku_format = pick_format(&self.danmaku_format, &["xml", "ass"]);
+    self.metadata_format = pick_format(&self.metadata_format, &["json", "nfo"]);
+    self
+  }
+
+  pub fn is_enabled(&self) -> bool {
+    self.subtitle || self.danmaku || self.cover || self.metadata
+  }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DownloadSidecarFiles {
+  pub subtitles: Vec<String>,
+  pub danmaku: Option<String>,
+  pub danmaku_count: usize,
+  pub cover: Option<String>,
+  pub metadata: Option<String>,
+  pub errors: Vec<String>,
+}
+
+struct DanmakuElem {
+  progress_ms: i64,
+  mode: i64,
+  font_size: i64,
+  color: u32,
+  mid_hash: String,
+  content: String,
+  ctime: i64,
+  weight: i64,
+  pool: i64,
+  id_str: String,
+}
+
+enum ProtoValue<'a> {
+  Varint(u64),
+  Bytes(&'a [u8]),
+  Fixed,
+}
+
+pub async fn download_sidecars(
+  client: &BilibiliClient,
+  auth: Option<&AuthInfo>,
+  bvid: Option<&str>,
+  aid: Option<&str>,
+  cid: i64,
+  media_path: &Path,
+  options: &DownloadSidecarOptions,
+) -> DownloadSidecarFiles {
+  let mut files = DownloadSidecarFiles::default();
+  if !options.is_enabled() {
+    return files;
+  }
+  let id_params = video_id_params(bvid, aid);
+  let view = if options.cover || options.metadata || options.danmaku {
+    let url = format!("{}/x/web-interface/view", client.base_url());
+    match client.get_json(&url, &id_params, auth, false).await {
+      Ok(view) => Some(view),
+      Err(err) => {
+        files.errors.push(format!("视频信息获取失败: {}", err));
+        None
+      }
+    }
+  } else {
+    None
+  };
+
+  if options.subtitle {
+    match save_subtitles(client, auth, &id_params, cid, media_path, &options.subtitle_format).await
+    {
+      Ok(paths) => files.subtitles = paths,
+      Err(err) => files.errors.push(format!("字幕保存失败: {}", err)),
+    }
+  }
+
+  if options.danmaku {
+    let duration = view.as_ref().and_then(|view| part_duration(view, cid));
+    match save_danmaku(client, auth, cid, duration, media_path, &options.danmaku_format).await {
+      Ok((path, count)) => {
+        files.danmaku = Some(path);
+        files.danmaku_count = count;
+      }
+      Err(err) => files.errors.push(format!("弹幕保存失败: {}", err)),
+    }
+  }
+
+  if let Some(view) = view.as_ref() {
+    if options.cover {
+      match save_cover(client, view, media_path).await {
+        Ok(path) => files.cover = Some(path),
+        Err(err) => files.errors.push(format!("封面保存失败: {}", err)),
+      }
+    }
+    if options.metadata {
+      let tags = fetch_tags(client, auth, &id_params).await;
+      match save_metadata(view, cid, &tags, media_path, &options.metadata_format) {
+        Ok(path) => files.metadata = Some(path),
+        Err(err) => files.errors.push(format!("元数据保存失败: {}", err)),
+      }
+    }
+  }
+
+  files
+}
+
+pub fn sidecar_file_paths(files: &DownloadSidecarFiles) -> Vec<PathBuf> {
+  files
+    .subtitles
+    .iter()
+    .chain(files.danmaku.iter())
+    .chain(files.cover.iter())
+    .chain(files.metadata.iter())
+    .map(PathBuf::from)
+    .collect()
+}
+
+async fn save_subtitles(
+  client: &BilibiliClient,
+  auth: Option<&AuthInfo>,
+  id_params: &[(String, String)],
+  cid: i64,
+  media_path: &Path,
+  format: &str,
+) -> Result<Vec<String>, String> {
+  let mut params = id_params.to_vec();
+  params.push(("cid".to_string(), cid.to_string()));
+  let url = format!("{}/x/player/wbi/v2", client.base_url());
+  let player = client.get_json(&url, &params, auth, true).await?;
+  let subtitles = player
+    .get("subtitle")
+    .and_then(|value| value.get("subtitles"))
+    .and_then(|value| value.as_array())
+    .cloned()
+    .unwrap_or_default();
+  let mut paths = Vec::new();
+  for item in subtitles {
+    let Some(subtitle_url) = item
+      .get("subtitle_url")
+      .and_then(|value| value.as_str())
+      .filter(|value| !value.is_empty())
+    else {
+      continue;
+    };
+    let lan = item
+      .get("lan")
+      .and_then(|value| value.as_str())
+      .filter(|value| !value.is_empty())
+      .unwrap_or("und");
+    let bytes = client.get_bytes(&absolute_url(subtitle_url), &[], auth).await?;
+    let payload: Value =
+      serde_json::from_slice(&bytes).map_err(|err| format!("字幕解析失败: {}", err))?;
+    let cues: Vec<(f64, f64, String)> = payload
+      .get("body")
+      .and_then(|value| value.as_array())
+      .map(|items| {
+        items
+          .iter()
+          .filter_map(|cue| {
+            let from = cue.get("from")?.as_f64()?;
+            let to = cue.get("to")?.as_f64()?;
+            let content = cue.get("content")?.as_str()?.to_string();
+            Some((from, to, content))
+          })
+          .collect()
+      })
+      .unwrap_or_default();
+    let output = media_path.with_extension(format!("{}.{}", lan, format));
+    let text = if format == "ass" {
+      build_subtitle_ass(&cues)
+    } else {
+      build_subtitle_srt(&cues)
+    };
+    fs::write(&output, text).map_err(|err| format!("写入字幕失败: {}", err))?;
+    paths.push(output.to_string_lossy().to_string());
+  }
+  Ok(paths)
+}
+
+async fn save_danmaku(
+  client: &BilibiliClient,
+  auth: Option<&AuthInfo>,
+  cid: i64,
+  duration: Option<i64>,
+  media_path: &Path,
+  format: &str,
+) -> Result<(String, usize), String> {
+  let segment_count = duration
+    .filter(|value| *value > 0)
+    .map(|value| (value + DANMAKU_SEGMENT_SECONDS - 1) / DANMAKU_SEGMENT_SECONDS)
+    .unwrap_or(MAX_DANMAKU_SEGMENTS)
+    .clamp(1, MAX_DANMAKU_SEGMENTS);
+  let url = format!("{}/x/v2/dm/web/seg.so", client.base_url());
+  let mut elems = Vec::new();
+  for index in 1..=segment_count {
+    let params = vec![
+      ("type".to_string(), "1".to_string()),
+      ("oid".to_string(), cid.to_string()),
+      ("segment_index".to_string(), index.to_string()),
+    ];
+    let bytes = client.get_bytes(&url, &params, auth).await?;
+    let segment = decode_danmaku_segment(&bytes)?;
+    if segment.is_empty() && duration.is_none() {
+      break;
+    }
+    elems.extend(segment);
+  }
+  elems.sort_by_key(|elem| elem.progress_ms);
+  let count = elems.len();
+  let output = media_path.with_extension(format!("danmaku.{}", format));
+  if format == "ass" {
+    let items: Vec<VideoDanmakuItem> = elems
+      .iter()
+      .filter(|elem| elem.mode <= 6)
+      .map(|elem| VideoDanmakuItem {
+        offset: elem.progress_ms as f64 / 1000.0,
+        color: elem.color,
+        text: elem.content.clone(),
+      })
+      .collect();
+    convert_video_danmaku_to_ass(&items, &output, &DanmakuAssOptions::default())?;
+  } else {
+    fs::write(&output, build_danmaku_xml(cid, &elems))
+      .map_err(|err| format!("写入弹幕失败: {}", err))?;
+  }
+  Ok((output.to_string_lossy().to_string(), count))
+}
+
+async fn save_cover(
+  client: &BilibiliClient,
+  view: &Value,
+  media_path: &Path,
+) -> Result<String, String> {
+  let cover_url = view
+    .get("pic")
+    .and_then(|value| value.as_str())
+    .filter(|value| !value.is_empty())
+    .ok_or_else(|| "缺少封面地址".to_string())?;
+  let cover_url = absolute_url(cover_url);
+  let extension = cover_url
+    .rsplit('/')
+    .next()
+    .and_then(|name| name.split('?').next())
+    .and_then(|name| name.rsplit_once('.'))
+    .map(|(_, ext)| ext.to_ascii_lowercase())
+    .filter(|ext| matches!(ext.as_str(), "jpg" | "jpeg" | "png" | "webp" | "gif"))
+    .unwrap_or_else(|| "jpg".to_string());
+  let bytes = client.get_bytes(&cover_url, &[], None).await?;
+  let output = media_path.with_extension(extension);
+  fs::write(&output, bytes).map_err(|err| format!("写入封面失败: {}", err))?;
+  Ok(output.to_string_lossy().to_string())
+}
+
+async fn fetch_tags(
+  client: &BilibiliClient,
+  auth: Option<&AuthInfo>,
+  id_params: &[(String, String)],
+) -> Vec<String> {
+  let url = format!("{}/x/tag/archive/tags", client.base_url());
+  client
+    .get_json(&url, id_params, auth, false)
+    .await
+    .ok()
+    .and_then(|value| value.as_array().cloned())
+    .map(|items| {
+      items
+        .iter()
+        .filter_map(|item| item.get("tag_name").and_then(|value| value.as_str()))
+        .map(|value| value.to_string())
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn save_metadata(
+  view: &Value,
+  cid: i64,
+  tags: &[String],
+  media_path: &Path,
+  format: &str,
+) -> Result<String, String> {
+  let text_of = |key: &str| {
+    view
+      .get(key)
+      .and_then(|value| value.as_str())
+      .unwrap_or("")
+      .to_string()
+  };
+  let owner = view.get("owner").cloned().unwrap_or(Value::Null);
+  let uploader = owner
+    .get("name")
+    .and_then(|value| value.as_str())
+    .unwrap_or("")
+    .to_string();
+  let pubdate = view.get("pubdate").and_then(|value| value.as_i64()).unwrap_or(0);
+  let pubdate_text = DateTime::from_timestamp(pubdate, 0)
+    .map(|value| value.with_timezone(&Local).to_rfc3339())
+    .unwrap_or_default();
+  let part_title = view
+    .get("pages")
+    .and_then(|value| value.as_array())
+    .and_then(|pages| {
+      pages
+        .iter()
+        .find(|page| page.get("cid").and_then(|value| value.as_i64()) == Some(cid))
+    })
+    .and_then(|page| page.get("part"))
+    .and_then(|value| value.as_str())
+    .unwrap_or("")
+    .to_string();
+  let output = media_path.with_extension(format);
+  let content = if format == "nfo" {
+    let mut nfo = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<movie>\n");
+    nfo.push_str(&format!("  <title>{}</title>\n", escape_xml(&text_of("title"))));
+    if !part_title.is_empty() {
+      nfo.push_str(&format!("  <originaltitle>{}</originaltitle>\n", escape_xml(&part_title)));
+    }
+    nfo.push_str(&format!("  <plot>{}</plot>\n", escape_xml(&text_of("desc"))));
+    nfo.push_str(&format!("  <director>{}</director>\n", escape_xml(&uploader)));
+    nfo.push_str(&format!("  <studio>{}</studio>\n", escape_xml(&uploader)));
+    if let Some(date) = pubdate_text.get(..10) {
+      nfo.push_str(&format!("  <premiered>{}</premiered>\n", date));
+    }
+    nfo.push_str(&format!("  <genre>{}</genre>\n", escape_xml(&text_of("tname"))));
+    for tag in tags {
+      nfo.push_str(&format!("  <tag>{}</tag>\n", escape_xml(tag)));
+    }
+    nfo.push_str(&format!(
+      "  <uniqueid type=\"bilibili\" default=\"true\">{}</uniqueid>\n",
+      escape_xml(&text_of("bvid"))
+    ));
+    nfo.push_str("</movie>\n");
+    nfo
+  } else {
+    let metadata = json!({
+      "bvid": text_of("bvid"),
+      "aid": view.get("aid").cloned().unwrap_or(Value::Null),
+      "cid": cid,
+      "title": text_of("title"),
+      "partTitle": part_title,
+      "uploader": {
+        "mid": owner.get("mid").cloned().unwrap_or(Value::Null),
+        "name": uploader,
+      },
+      "pubdate": pubdate,
+      "pubdateText": pubdate_text,
+      "category": text_of("tname"),
+      "tags": tags,
+      "description": text_of("desc"),
+      "duration": part_duration(view, cid),
+      "cover": text_of("pic"),
+    });
+    serde_json::to_string_pretty(&metadata).map_err(|err| err.to_string())?
+  };
+  fs::write(&output, content).map_err(|err| format!("写入元数据失败: {}", err))?;
+  Ok(output.to_string_lossy().to_string())
+}
+
+fn part_duration(view: &Value, cid: i64) -> Option<i64> {
+  view
+    .get("pages")
+    .and_then(|value| value.as_array())
+    .and_then(|pages| {
+      pages
+        .iter()
+        .find(|page| page.get("cid").and_then(|value| value.as_i64()) == Some(cid))
+    })
+    .and_then(|page| page.get("duration"))
+    .or_else(|| view.get("duration"))
+    .and_then(|value| value.as_i64())
+}
+
+fn video_id_params(bvid: Option<&str>, aid: Option<&str>) -> Vec<(String, String)> {
+  let mut params = Vec::new();
+  if let Some(bvid) = bvid {
+    params.push(("bvid".to_string(), bvid.to_string()));
+  }
+  if let Some(aid) = aid {
+    params.push(("aid".to_string(), aid.to_string()));
+  }
+  params
+}
+
+fn absolute_url(url: &str) -> String {
+  if url.starts_with("//") {
+    format!("https:{}", url)
+  } else {
+    url.replacen("http://", "https://", 1)
+  }
+}
+
+fn pick_format(value: &str, allowed: &[&str]) -> String {
+  let value = value.trim().to_ascii_lowercase();
+  if allowed.contains(&value.as_str()) {
+    value
+  } else {
+    allowed[0].to_string()
+  }
+}
+
+fn build_subtitle_srt(cues: &[(f64, f64, String)]) -> String {
+  let mut output = String::new();
+  for (index, (from, to, content)) in cues.iter().enumerate() {
+    output.push_str(&format!(
+      "{}\n{} --> {}\n{}\n\n",
+      index + 1,
+      format_srt_time(*from),
+      format_srt_time(*to),
+      content
+    ));
+  }
+  output
+}
+
+fn build_subtitle_ass(cues: &[(f64, f64, String)]) -> String {
+  let mut output = String::from(
+    "[Script Info]\n\
+     ScriptType: v4.00+\n\
+     PlayResX: 1920\n\
+     PlayResY: 1080\n\
+     \n\
+     [V4+ Styles]\n\
+     Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n\
+     Style: Default,Microsoft YaHei,56,&H00FFFFFF,&H00FFFFFF,&H00000000,&H80000000,0,0,0,0,100,100,0,0,1,2,1,2,20,20,40,1\n\
+     \n\
+     [Events]\n\
+     Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n",
+  );
+  for (from, to, content) in cues {
+    output.push_str(&format!(
+      "Dialogue: 0,{},{},Default,,0,0,0,,{}\n",
+      format_ass_time(*from),
+      format_ass_time(*to),
+      escape_ass_text(content)
+    ));
+  }
+  output
+}
+
+fn format_srt_time(seconds: f64) -> String {
+  let total_ms = (seconds.max(0.0) * 1000.0).round() as i64;
+  format!(
+    "{:02}:{:02}:{:02},{:03}",
+    total_ms / 3_600_000,
+    (total_ms / 60_000) % 60,
+    (total_ms / 1000) % 60,
+    total_ms % 1000
+  )
+}
+
+fn build_danmaku_xml(cid: i64, elems: &[DanmakuElem]) -> String {
+  let mut output = format!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<i>\n  <chatserver>chat.bilibili.com</chatserver>\n  <chatid>{}</chatid>\n  <mission>0</mission>\n  <maxlimit>{}</maxlimit>\n  <state>0</state>\n  <real_name>0</real_name>\n  <source>k-v</source>\n",
+    cid,
+    elems.len()
+  );
+  for elem in elems {
+    output.push_str(&format!(
+      "  <d p=\"{:.5},{},{},{},{},{},{},{},{}\">{}</d>\n",
+      elem.progress_ms as f64 / 1000.0,
+      elem.mode,
+      elem.font_size,
+      elem.color,
+      elem.ctime,
+      elem.pool,
+      escape_xml(&elem.mid_hash),
+      escape_xml(&elem.id_str),
+      elem.weight,
+      escape_xml(&elem.content)
+    ));
+  }
+  output.push_str("</i>\n");
+  output
+}
+
+fn decode_danmaku_segment(data: &[u8]) -> Result<Vec<DanmakuElem>, String> {
+  let mut elems = Vec::new();
+  let mut pos = 0;
+  while pos < data.len() {
+    let (field, value) =
+      read_proto_field(data, &mut pos).ok_or_else(|| "弹幕数据格式无效".to_string())?;
+    if let (1, ProtoValue::Bytes(bytes)) = (field, value) {
+      elems.push(decode_danmaku_elem(bytes)?);
+    }
+  }
+  Ok(elems)
+}
+
+fn decode_danmaku_elem(data: &[u8]) -> Result<DanmakuElem, String> {
+  let mut elem = DanmakuElem {
+    progress_ms: 0,
+    mode: 1,
+    font_size: 25,
+    color: 0xffffff,
+    mid_hash: String::new(),
+    content: String::new(),
+    ctime: 0,
+    weight: 0,
+    pool: 0,
+    id_str: String::new(),
+  };
+  let mut pos = 0;
+  while pos < data.len() {
+    let (field, value) =
+      read_proto_field(data, &mut pos).ok_or_else(|| "弹幕数据格式无效".to_string())?;
+    match (field, value) {
+      (2, ProtoValue::Varint(value)) => elem.progress_ms = value as i64,
+      (3, ProtoValue::Varint(value)) => elem.mode = value as i64,
+      (4, ProtoValue::Varint(value)) => elem.font_size = value as i64,
+      (5, ProtoValue::Varint(value)) => elem.color = value as u32,
+      (6, ProtoValue::Bytes(bytes)) => elem.mid_hash = String::from_utf8_lossy(bytes).to_string(),
+      (7, ProtoValue::Bytes(bytes)) => elem.content = String::from_utf8_lossy(bytes).to_string(),
+      (8, ProtoValue::Varint(value)) => elem.ctime = value as i64,
+      (9, ProtoValue::Varint(value)) => elem.weight = value as i64,
+      (11, ProtoValue::Varint(value)) => elem.pool = value as i64,
+      (12, ProtoValue::Bytes(bytes)) => elem.id_str = String::from_utf8_lossy(bytes).to_string(),
+      _ => {}
+    }
+  }
+  Ok(elem)
+}
+
+fn read_proto_field<'a>(data: &'a [u8], pos: &mut usize) -> Option<(u64, ProtoValue<'a>)> {
+  let key = read_varint(data, pos)?;
+  let value = match key & 0x7 {
+    0 => ProtoValue::Varint(read_varint(data, pos)?),
+    1 => {
+      *pos = pos.checked_add(8).filter(|end| *end <= data.len())?;
+      ProtoValue::Fixed
+    }
+    2 => {
+      let len = read_varint(data, pos)? as usize;
+      let end = pos.checked_add(len).filter(|end| *end <= data.len())?;
+      let bytes = &data[*pos..end];
+      *pos = end;
+      ProtoValue::Bytes(bytes)
+    }
+    5 => {
+      *pos = pos.checked_add(4).filter(|end| *end <= data.len())?;
+      ProtoValue::Fixed
+    }
+    _ => return None,
+  };
+  Some((key >> 3, value))
+}
+
+fn read_varint(data: &[u8], pos: &mut usize) -> Option<u64> {
+  let mut result = 0u64;
+  let mut shift = 0;
+  while *pos < data.len() {
+    let byte = data[*pos];
+    *pos += 1;
+    result |= ((byte & 0x7f) as u64) << shift;
+    if byte & 0x80 == 0 {
+      return Some(result);
+    }
+    shift += 7;
+    if shift >= 64 {
+      return None;
+    }
+  }
+  None
+}
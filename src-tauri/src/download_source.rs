@@ -0,0 +1,340 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::bilibili::client::BilibiliClient;
+use crate::login_store::AuthInfo;
+
+pub const SOURCE_UPLOADER: &str = "uploader";
+pub const SOURCE_FAVORITE: &str = "favorite";
+pub const SOURCE_SEASON: &str = "season";
+pub const SOURCE_SERIES: &str = "series";
+pub const SOURCE_WATCH_LATER: &str = "watch_later";
+
+const DEFAULT_MAX_COUNT: usize = 50;
+const MAX_PAGES: i64 = 100;
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DownloadSourceQuery {
+  pub source_type: String,
+  pub source_id: Option<String>,
+  pub mid: Option<String>,
+  pub start_time: Option<i64>,
+  pub end_time: Option<i64>,
+  pub keyword: Option<String>,
+  pub max_count: Option<i64>,
+  pub account_user_id: Option<i64>,
+  #[serde(skip)]
+  pub exclude_bvids: HashSet<String>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadSourceVideo {
+  pub bvid: String,
+  pub title: String,
+  pub pubdate: i64,
+  pub cover: Option<String>,
+  pub duration: Option<i64>,
+}
+
+struct SourcePage {
+  videos: Vec<DownloadSourceVideo>,
+  has_more: bool,
+  ordered_by_pubdate: bool,
+}
+
+pub async fn resolve_download_source(
+  client: &BilibiliClient,
+  auth: Option<&AuthInfo>,
+  query: &DownloadSourceQuery,
+) -> Result<Vec<DownloadSourceVideo>, String> {
+  let source_type = query.source_type.trim().to_ascii_lowercase();
+  let source_id = query
+    .source_id
+    .as_deref()
+    .map(|value| value.trim().to_string())
+    .filter(|value| !value.is_empty());
+  if source_type != SOURCE_WATCH_LATER && source_id.is_none() {
+    return Err("请填写来源 ID".to_string());
+  }
+  if source_type == SOURCE_WATCH_LATER && auth.is_none() {
+    return Err("稍后再看需要先登录".to_string());
+  }
+  let source_id = source_id.unwrap_or_default();
+  let max_count = query
+    .max_count
+    .filter(|value| *value > 0)
+    .map(|value| value as usize)
+    .unwrap_or(DEFAULT_MAX_COUNT);
+  let keyword = query
+    .keyword
+    .as_deref()
+    .map(|value| value.trim().to_lowercase())
+    .filter(|value| !value.is_empty());
+
+  let mut result = Vec::new();
+  let mut page_num = 1;
+  while page_num <= MAX_PAGES {
+    let page = match source_type.as_str() {
+      SOURCE_UPLOADER => fetch_uploader_page(client, auth, &source_id, page_num).await?,
+      SOURCE_FAVORITE => fetch_favorite_page(client, auth, &source_id, page_num).await?,
+      SOURCE_SEASON => fetch_season_page(client, auth, &source_id, query, page_num).await?,
+      SOURCE_SERIES => fetch_series_page(client, auth, &source_id, query, page_num).await?,
+      SOURCE_WATCH_LATER => fetch_watch_later(client, auth).await?,
+      _ => return Err(format!("不支持的下载来源: {}", query.source_type)),
+    };
+    let mut reached_start = false;
+    for video in page.videos {
+      if let Some(end_time) = query.end_time {
+        if video.pubdate > end_time {
+          continue;
+        }
+      }
+      if let Some(start_time) = query.start_time {
+        if video.pubdate < start_time {
+          reached_start = page.ordered_by_pubdate;
+          continue;
+        }
+      }
+      if let Some(keyword) = keyword.as_ref() {
+        if !video.title.to_lowercase().contains(keyword) {
+          continue;
+        }
+      }
+      if query.exclude_bvids.contains(&video.bvid) {
+        continue;
+      }
+      if result
+        .iter()
+        .any(|item: &DownloadSourceVideo| item.bvid == video.bvid)
+      {
+        continue;
+      }
+      result.push(video);
+      if result.len() >= max_count {
+        return Ok(result);
+      }
+    }
+    if !page.has_more || reached_start {
+      break;
+    }
+    page_num += 1;
+  }
+  Ok(result)
+}
+
+async fn fetch_uploader_page(
+  client: &BilibiliClient,
+  auth: Option<&AuthInfo>,
+  mid: &str,
+  page_num: i64,
+) -> Result<SourcePage, String> {
+  let params = vec![
+    ("mid".to_string(), mid.to_string()),
+    ("pn".to_string(), page_num.to_string()),
+    ("ps".to_string(), "30".to_string()),
+    ("order".to_string(), "pubdate".to_string()),
+    ("dm_img_list".to_string(), "[]".to_string()),
+    (
+      "dm_img_str".to_string(),
+      "V2ViR0wgMS4wIChPcGVuR0wgRVMgMi4wIENocm9taXVtKQ".to_string(),
+    ),
+    (
+      "dm_cover_img_str".to_string(),
+      "QU5HTEUgKEludGVsLCBJbnRlbChSKSBVSEQgR3JhcGhpY3MgNjMwIERpcmVjdDNEMTEp".to_string(),
+    ),
+  ];
+  let url = format!("{}/x/space/wbi/arc/search", client.base_url());
+  let data = client.get_json(&url, &params, auth, true).await?;
+  let videos = collect_videos(
+    data.get("list").and_then(|value| value.get("vlist")),
+    "created",
+  );
+  let total = data
+    .get("page")
+    .and_then(|value| value.get("count"))
+    .and_then(|value| value.as_i64())
+    .unwrap_or(0);
+  Ok(SourcePage {
+    has_more: page_num * 30 < total && !videos.is_empty(),
+    videos,
+    ordered_by_pubdate: true,
+  })
+}
+
+async fn fetch_favorite_page(
+  client: &BilibiliClient,
+  auth: Option<&AuthInfo>,
+  media_id: &str,
+  page_num: i64,
+) -> Result<SourcePage, String> {
+  let params = vec![
+    ("media_id".to_string(), media_id.to_string()),
+    ("pn".to_string(), page_num.to_string()),
+    ("ps".to_string(), "20".to_string()),
+    ("platform".to_string(), "web".to_string()),
+  ];
+  let url = format!("{}/x/v3/fav/resource/list", client.base_url());
+  let data = client.get_json(&url, &params, auth, false).await?;
+  let medias = data
+    .get("medias")
+    .and_then(|value| value.as_array())
+    .cloned()
+    .unwrap_or_default();
+  let filtered: Vec<Value> = medias
+    .into_iter()
+    .filter(|item| item.get("type").and_then(|value| value.as_i64()) == Some(2))
+    .filter(|item| item.get("title").and_then(|value| value.as_str()) != Some("已失效视频"))
+    .collect();
+  Ok(SourcePage {
+    videos: collect_videos(Some(&Value::Array(filtered)), "pubtime"),
+    has_more: data
+      .get("has_more")
+      .and_then(|value| value.as_bool())
+      .unwrap_or(false),
+    ordered_by_pubdate: false,
+  })
+}
+
+async fn fetch_season_page(
+  client: &BilibiliClient,
+  auth: Option<&AuthInfo>,
+  season_id: &str,
+  query: &DownloadSourceQuery,
+  page_num: i64,
+) -> Result<SourcePage, String> {
+  let mut params = vec![
+    ("season_id".to_string(), season_id.to_string()),
+    ("page_num".to_string(), page_num.to_string()),
+    ("page_size".to_string(), "30".to_string()),
+    ("sort_reverse".to_string(), "false".to_string()),
+  ];
+  if let Some(mid) = query.mid.as_ref().filter(|value| !value.trim().is_empty()) {
+    params.push(("mid".to_string(), mid.trim().to_string()));
+  }
+  let url = format!(
+    "{}/x/polymer/web-space/seasons_archives_list",
+    client.base_url()
+  );
+  let data = client.get_json(&url, &params, auth, false).await?;
+  let videos = collect_videos(data.get("archives"), "pubdate");
+  let total = data
+    .get("page")
+    .and_then(|value| value.get("total"))
+    .and_then(|value| value.as_i64())
+    .unwrap_or(0);
+  Ok(SourcePage {
+    has_more: page_num * 30 < total && !videos.is_empty(),
+    videos,
+    ordered_by_pubdate: false,
+  })
+}
+
+async fn fetch_series_page(
+  client: &BilibiliClient,
+  auth: Option<&AuthInfo>,
+  series_id: &str,
+  query: &DownloadSourceQuery,
+  page_num: i64,
+) -> Result<SourcePage, String> {
+  let mid = query
+    .mid
+    .as_deref()
+    .map(|value| value.trim())
+    .filter(|value| !value.is_empty())
+    .ok_or_else(|| "视频列表需要填写 UP 主 UID".to_string())?;
+  let params = vec![
+    ("mid".to_string(), mid.to_string()),
+    ("series_id".to_string(), series_id.to_string()),
+    ("pn".to_string(), page_num.to_string()),
+    ("ps".to_string(), "30".to_string()),
+    ("sort".to_string(), "desc".to_string()),
+    ("only_normal".to_string(), "true".to_string()),
+  ];
+  let url = format!("{}/x/series/archives", client.base_url());
+  let data = client.get_json(&url, &params, auth, false).await?;
+  let videos = collect_videos(data.get("archives"), "pubdate");
+  let total = data
+    .get("page")
+    .and_then(|value| value.get("total"))
+    .and_then(|value| value.as_i64())
+    .unwrap_or(0);
+  Ok(SourcePage {
+    has_more: page_num * 30 < total && !videos.is_empty(),
+    videos,
+    ordered_by_pubdate: true,
+  })
+}
+
+async fn fetch_watch_later(
+  client: &BilibiliClient,
+  auth: Option<&AuthInfo>,
+) -> Result<SourcePage, String> {
+  let url = format!("{}/x/v2/history/toview", client.base_url());
+  let data = client.get_json(&url, &[], auth, false).await?;
+  Ok(SourcePage {
+    videos: collect_videos(data.get("list"), "pubdate"),
+    has_more: false,
+    ordered_by_pubdate: false,
+  })
+}
+
+fn collect_videos(list: Option<&Value>, time_key: &str) -> Vec<DownloadSourceVideo> {
+  list
+    .and_then(|value| value.as_array())
+    .map(|items| {
+      items
+        .iter()
+        .filter_map(|item| parse_video(item, time_key))
+        .collect()
+    })
+    .unwrap_or_default()
+}
+
+fn parse_video(item: &Value, time_key: &str) -> Option<DownloadSourceVideo> {
+  let bvid = item
+    .get("bvid")
+    .and_then(|value| value.as_str())
+    .filter(|value| !value.is_empty())?
+    .to_string();
+  let title = item
+    .get("title")
+    .and_then(|value| value.as_str())
+    .unwrap_or("")
+    .to_string();
+  let pubdate = item
+    .get(time_key)
+    .or_else(|| item.get("pubdate"))
+    .and_then(|value| value.as_i64())
+    .unwrap_or(0);
+  let cover = item
+    .get("pic")
+    .or_else(|| item.get("cover"))
+    .and_then(|value| value.as_str())
+    .map(|value| value.to_string());
+  let duration = item
+    .get("duration")
+    .and_then(|value| value.as_i64())
+    .or_else(|| {
+      item
+        .get("length")
+        .and_then(|value| value.as_str())
+        .and_then(parse_length)
+    });
+  Some(DownloadSourceVideo {
+    bvid,
+    title,
+    pubdate,
+    cover,
+    duration,
+  })
+}
+
+fn parse_length(value: &str) -> Option<i64> {
+  value.split(':').try_fold(0i64, |acc, part| {
+    part.trim().parse::<i64>().ok().map(|num| acc * 60 + num)
+  })
+}
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock};
+
+use crate::commands::settings::EncoderSettings;
+use crate::config::resolve_ffmpeg_path;
+
+pub const SUPPORTED_VIDEO_ENCODERS: [&str; 10] = [
+  "libx264",
+  "libx265",
+  "h264_nvenc",
+  "hevc_nvenc",
+  "h264_qsv",
+  "hevc_qsv",
+  "h264_vaapi",
+  "hevc_vaapi",
+  "h264_videotoolbox",
+  "hevc_videotoolbox",
+];
+
+const SOFTWARE_FALLBACK_ENCODERS: [&str; 3] = ["libx264", "libopenh264", "mpeg4"];
+const VAAPI_DEVICE: &str = "/dev/dri/renderD128";
+
+static ENCODER_PROBE_CACHE: OnceLock<Mutex<HashMap<String, bool>>> = OnceLock::new();
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EncoderFamily {
+  Software,
+  Nvenc,
+  Qsv,
+  Vaapi,
+  VideoToolbox,
+  Basic,
+}
+
+pub struct ResolvedEncoder {
+  pub name: String,
+  pub fallback: bool,
+}
+
+pub struct EncodePlan {
+  pub encoder: ResolvedEncoder,
+  pub input_args: Vec<String>,
+  pub upload_filter: Option<String>,
+  pub video_args: Vec<String>,
+  pub audio_args: Vec<String>,
+}
+
+pub fn detect_available_encoders() -> Vec<String> {
+  SUPPORTED_VIDEO_ENCODERS
+    .iter()
+    .filter(|name| probe_encoder(name))
+    .map(|name| name.to_string())
+    .collect()
+}
+
+pub fn resolve_video_encoder(preferred: &str) -> ResolvedEncoder {
+  let preferred = preferred.trim();
+  let mut candidates: Vec<&str> = Vec::new();
+  if !preferred.is_empty() {
+    candidates.push(preferred);
+  }
+  if preferred.contains("hevc") || preferred.contains("265") {
+    candidates.push("libx265");
+  }
+  candidates.extend(SOFTWARE_FALLBACK_ENCODERS);
+  candidates.dedup();
+
+  for candidate in &candidates {
+    if probe_encoder(candidate) {
+      return ResolvedEncoder {
+        name: candidate.to_string(),
+        fallback: *candidate != preferred,
+      };
+    }
+  }
+  ResolvedEncoder {
+    name: SOFTWARE_FALLBACK_ENCODERS[0].to_string(),
+    fallback: preferred != SOFTWARE_FALLBACK_ENCODERS[0],
+  }
+}
+
+pub fn build_encode_plan(settings: &EncoderSettings) -> EncodePlan {
+  let encoder = resolve_video_encoder(&settings.video_encoder);
+  let family = encoder_family(&encoder.name);
+  let mut input_args = Vec::new();
+  let mut upload_filter = None;
+  if family == EncoderFamily::Vaapi {
+    input_args.extend(["-vaapi_device".to_string(), VAAPI_DEVICE.to_string()]);
+    upload_filter = Some("format=nv12,hwupload".to_string());
+  }
+
+  let mut video_args = vec!["-c:v".to_string(), encoder.name.clone()];
+  let use_crf = settings.rate_control == "CRF" && family != EncoderFamily::Basic;
+  if use_crf {
+    let crf = settings.crf.clamp(0, 51);
+    match family {
+      EncoderFamily::Software => video_args.extend(["-crf".to_string(), crf.to_string()]),
+      EncoderFamily::Nvenc => video_args.extend([
+        "-rc".to_string(),
+        "vbr".to_string(),
+        "-cq".to_string(),
+        crf.to_string(),
+        "-b:v".to_string(),
+        "0".to_string(),
+      ]),
+      EncoderFamily::Qsv => video_args.extend(["-global_quality".to_string(), crf.to_string()]),
+      EncoderFamily::Vaapi => video_args.extend([
+        "-rc_mode".to_string(),
+        "CQP".to_string(),
+        "-qp".to_string(),
+        crf.to_string(),
+      ]),
+      EncoderFamily::VideoToolbox => {
+        let quality = ((51 - crf) * 100 / 51).clamp(1, 100);
+        video_args.extend(["-q:v".to_string(), quality.to_string()]);
+      }
+      EncoderFamily::Basic => {}
+    }
+  } else {
+    let bitrate = settings.video_bitrate_kbps.max(100);
+    video_args.extend([
+      "-b:v".to_string(),
+      format!("{}k", bitrate),
+      "-maxrate".to_string(),
+      format!("{}k", bitrate),
+      "-bufsize".to_string(),
+      format!("{}k", bitrate * 2),
+    ]);
+  }
+  let supports_preset = matches!(
+    family,
+    EncoderFamily::Software | EncoderFamily::Nvenc | EncoderFamily::Qsv
+  );
+  if supports_preset && !encoder.fallback && !settings.preset.trim().is_empty() {
+    video_args.extend(["-preset".to_string(), settings.preset.trim().to_string()]);
+  }
+  if matches!(family, EncoderFamily::Software | EncoderFamily::Basic) {
+    video_args.extend(["-pix_fmt".to_string(), "yuv420p".to_string()]);
+  }
+
+  let mut audio_args = vec!["-c:a".to_string(), "aac".to_string()];
+  if settings.audio_bitrate_kbps > 0 {
+    audio_args.extend(["-b:a".to_string(), format!("{}k", settings.audio_bitrate_kbps)]);
+  }
+  audio_args.extend(["-ar".to_string(), "48000".to_string()]);
+
+  EncodePlan {
+    encoder,
+    input_args,
+    upload_filter,
+    video_args,
+    audio_args,
+  }
+}
+
+pub fn resolve_fps_filter(settings: &EncoderSettings, source_fps: f64) -> Option<String> {
+  let target = settings.fps.max(1);
+  match settings.fps_mode.as_str() {
+    "SOURCE" => None,
+    "MAX" if source_fps > 0.0 && source_fps <= target as f64 + 0.01 => None,
+    _ => Some(format!("fps={}", target)),
+  }
+}
+
+fn encoder_family(name: &str) -> EncoderFamily {
+  if name == "libx264" || name == "libx265" {
+    EncoderFamily::Software
+  } else if name.ends_with("_nvenc") {
+    EncoderFamily::Nvenc
+  } else if name.ends_with("_qsv") {
+    EncoderFamily::Qsv
+  } else if name.ends_with("_vaapi") {
+    EncoderFamily::Vaapi
+  } else if name.ends_with("_videotoolbox") {
+    EncoderFamily::VideoToolbox
+  } else {
+    EncoderFamily::Basic
+  }
+}
+
+fn probe_encoder(name: &str) -> bool {
+  let cache = ENCODER_PROBE_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+  if let Ok(guard) = cache.lock() {
+    if let Some(result) = guard.get(name) {
+      return *result;
+    }
+  }
+
+  let mut command = Command::new(resolve_ffmpeg_path());
+  command.args(["-hide_banner", "-loglevel", "error"]);
+  let is_vaapi = encoder_family(name) == EncoderFamily::Vaapi;
+  if is_vaapi {
+    command.args(["-vaapi_device", VAAPI_DEVICE]);
+  }
+  command.args(["-f", "lavfi", "-i", "color=c=black:s=256x256:r=30:d=0.2"]);
+  if is_vaapi {
+    command.args(["-vf", "format=nv12,hwupload"]);
+  }
+  command.args(["-frames:v", "3", "-c:v", name, "-f", "null", "-"]);
+  let supported = command
+    .stdout(Stdio::null())
+    .stderr(Stdio::null())
+    .status()
+    .map(|status| status.success())
+    .unwrap_or(false);
+
+  if let Ok(mut guard) = cache.lock() {
+    guard.insert(name.to_string(), supported);
+  }
+  supported
+}
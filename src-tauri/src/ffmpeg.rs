@@ -20,6 +20,20 @@ pub fn run_ffmpeg(args: &[String]) -> Result<(), String> {
   Err(format!("FFmpeg failed: {}", stderr.trim()))
 }
 
+pub fn run_ffmpeg_capture_stderr(args: &[String]) -> Result<String, String> {
+  let ffmpeg_path = resolve_ffmpeg_path();
+  let output = Command::new(ffmpeg_path)
+    .args(args)
+    .output()
+    .map_err(|err| format!("Failed to start FFmpeg: {}", err))?;
+
+  let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+  if output.status.success() {
+    return Ok(stderr);
+  }
+  Err(format!("FFmpeg failed: {}", stderr.trim()))
+}
+
 pub fn run_ffmpeg_with_progress<F>(
   args: &[String],
   duration_ms: Option<i64>,
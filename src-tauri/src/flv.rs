@@ -0,0 +1,815 @@
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+const TIMESTAMP_JUMP_THRESHOLD_MS: i64 = 500;
+const TIMESTAMP_AUDIO_FALLBACK_MS: i64 = 22;
+const TIMESTAMP_AUDIO_MIN_STEP_MS: i64 = 20;
+const TIMESTAMP_AUDIO_MAX_STEP_MS: i64 = 24;
+const TIMESTAMP_VIDEO_FALLBACK_MS: i64 = 33;
+const TIMESTAMP_VIDEO_MIN_STEP_MS: i64 = 15;
+const TIMESTAMP_VIDEO_MAX_STEP_MS: i64 = 50;
+const TIMESTAMP_MIN_STEP_MS: i64 = 1;
+const FLV_FIX_READ_BUFFER: usize = 1024 * 1024;
+const FLV_FIX_MAX_JUMP_ENTRIES: usize = 200;
+
+pub struct FlvTag {
+  pub tag_type: u8,
+  pub bytes: Vec<u8>,
+  data_offset: usize,
+  data_len: usize,
+}
+
+impl FlvTag {
+  pub fn data(&self) -> &[u8] {
+    &self.bytes[self.data_offset..self.data_offset + self.data_len]
+  }
+}
+
+pub fn write_flv_timestamp(tag: &mut FlvTag, timestamp: u32) {
+  if tag.bytes.len() < 8 {
+    return;
+  }
+  tag.bytes[4] = ((timestamp >> 16) & 0xff) as u8;
+  tag.bytes[5] = ((timestamp >> 8) & 0xff) as u8;
+  tag.bytes[6] = (timestamp & 0xff) as u8;
+  tag.bytes[7] = ((timestamp >> 24) & 0xff) as u8;
+}
+
+pub fn is_video_keyframe(tag: &FlvTag) -> bool {
+  if tag.tag_type != 9 {
+    return false;
+  }
+  let data = tag.data();
+  if data.is_empty() {
+    return false;
+  }
+  let frame_type = data[0] >> 4;
+  frame_type == 1
+}
+
+pub fn is_audio_header_tag(data: &[u8]) -> bool {
+  if data.len() < 2 {
+    return false;
+  }
+  let sound_format = data[0] >> 4;
+  sound_format == 10 && data[1] == 0
+}
+
+pub fn is_video_header_tag(data: &[u8]) -> bool {
+  if data.len() < 2 {
+    return false;
+  }
+  let codec_id = data[0] & 0x0f;
+  let packet_type = data[1];
+  (codec_id == 7 || codec_id == 12) && packet_type == 0
+}
+
+fn clamp_timestamp(value: i64) -> u32 {
+  if value <= 0 {
+    return 0;
+  }
+  if value > u32::MAX as i64 {
+    return u32::MAX;
+  }
+  value as u32
+}
+
+pub struct TimestampJumpInfo {
+  pub diff: i64,
+  pub original: i64,
+  pub fixed: i64,
+  pub offset: i64,
+}
+
+struct TimestampChannelState {
+  last_original: Option<i64>,
+  last_fixed: Option<i64>,
+  last_step: i64,
+  fallback: i64,
+  min_step: i64,
+  max_step: i64,
+}
+
+impl TimestampChannelState {
+  fn new(fallback: i64, min_step: i64, max_step: i64) -> Self {
+    Self {
+      last_original: None,
+      last_fixed: None,
+      last_step: fallback,
+      fallback,
+      min_step,
+      max_step,
+    }
+  }
+
+  fn reset(&mut self) {
+    self.last_original = None;
+    self.last_fixed = None;
+    self.last_step = self.fallback;
+  }
+
+  fn update_step(&mut self, current: i64) -> i64 {
+    let step = match self.last_original {
+      Some(prev) => {
+        let diff = current - prev;
+        if diff >= self.min_step && diff <= self.max_step {
+          diff
+        } else {
+          self.fallback
+        }
+      }
+      None => self.fallback,
+    };
+    self.last_original = Some(current);
+    self.last_step = step;
+    step
+  }
+
+  fn update_fixed(&mut self, fixed: i64) {
+    self.last_fixed = Some(fixed);
+  }
+}
+
+pub struct TimestampFixer {
+  enabled: bool,
+  apply_fix: bool,
+  last_original: Option<i64>,
+  last_fixed: Option<i64>,
+  current_offset: i64,
+  next_target: i64,
+  audio: TimestampChannelState,
+  video: TimestampChannelState,
+}
+
+impl TimestampFixer {
+  pub fn new(enabled: bool, apply_fix: bool) -> Self {
+    Self {
+      enabled,
+      apply_fix,
+      last_original: None,
+      last_fixed: None,
+      current_offset: 0,
+      next_target: 0,
+      audio: TimestampChannelState::new(
+        TIMESTAMP_AUDIO_FALLBACK_MS,
+        TIMESTAMP_AUDIO_MIN_STEP_MS,
+        TIMESTAMP_AUDIO_MAX_STEP_MS,
+      ),
+      video: TimestampChannelState::new(
+        TIMESTAMP_VIDEO_FALLBACK_MS,
+        TIMESTAMP_VIDEO_MIN_STEP_MS,
+        TIMESTAMP_VIDEO_MAX_STEP_MS,
+      ),
+    }
+  }
+
+  pub fn reset(&mut self) {
+    self.last_original = None;
+    self.last_fixed = None;
+    self.current_offset = 0;
+    self.next_target = 0;
+    self.audio.reset();
+    self.video.reset();
+  }
+
+  pub fn fix_tag(&mut self, tag: &mut FlvTag, is_header: bool) -> Option<TimestampJumpInfo> {
+    if !self.enabled {
+      return None;
+    }
+    let original = parse_flv_timestamp(tag) as i64;
+    if !self.apply_fix {
+      if is_header {
+        return None;
+      }
+      let last_original = match self.last_original {
+        Some(value) => value,
+        None => {
+          self.last_original = Some(original);
+          self.last_fixed = Some(original);
+          return None;
+        }
+      };
+      let diff = original - last_original;
+      self.last_original = Some(original);
+      self.last_fixed = Some(original);
+      if diff < -TIMESTAMP_JUMP_THRESHOLD_MS || diff > TIMESTAMP_JUMP_THRESHOLD_MS {
+        return Some(TimestampJumpInfo {
+          diff,
+          original,
+          fixed: original,
+          offset: 0,
+        });
+      }
+      return None;
+    }
+    if is_header {
+      let stamp = if tag.tag_type == 18 {
+        self.next_target
+      } else {
+        self.last_fixed.unwrap_or(0)
+      };
+      write_flv_timestamp(tag, clamp_timestamp(stamp));
+      return None;
+    }
+
+    let step = match tag.tag_type {
+      8 => self.audio.update_step(original),
+      9 => self.video.update_step(original),
+      _ => TIMESTAMP_MIN_STEP_MS,
+    }
+    .max(TIMESTAMP_MIN_STEP_MS);
+
+    let last_original = match self.last_original {
+      Some(value) => value,
+      None => {
+        self.current_offset = original;
+        let fixed = 0;
+        self.last_original = Some(original);
+        self.last_fixed = Some(fixed);
+        match tag.tag_type {
+          8 => self.audio.update_fixed(fixed),
+          9 => self.video.update_fixed(fixed),
+          _ => {}
+        }
+        self.recalculate_next_target();
+        write_flv_timestamp(tag, fixed as u32);
+        return None;
+      }
+    };
+    let last_fixed = self.last_fixed.unwrap_or(0);
+    let diff = original - last_original;
+    let mut jump_info = None;
+
+    if diff < -TIMESTAMP_JUMP_THRESHOLD_MS || diff > TIMESTAMP_JUMP_THRESHOLD_MS {
+      self.current_offset = original - self.next_target;
+    }
+
+    let mut fixed = original - self.current_offset;
+    if fixed <= last_fixed {
+      fixed = last_fixed + step;
+      self.current_offset = original - fixed;
+    }
+    self.last_original = Some(original);
+    self.last_fixed = Some(fixed);
+    match tag.tag_type {
+      8 => self.audio.update_fixed(fixed),
+      9 => self.video.update_fixed(fixed),
+      _ => {}
+    }
+    self.recalculate_next_target();
+    write_flv_timestamp(tag, clamp_timestamp(fixed));
+    if diff < -TIMESTAMP_JUMP_THRESHOLD_MS || diff > TIMESTAMP_JUMP_THRESHOLD_MS {
+      jump_info = Some(TimestampJumpInfo {
+        diff,
+        original,
+        fixed,
+        offset: self.current_offset,
+      });
+    }
+    jump_info
+  }
+
+  fn recalculate_next_target(&mut self) {
+    let audio_next = self
+      .audio
+      .last_fixed
+      .map(|value| value + self.audio.last_step)
+      .unwrap_or(0);
+    let video_next = self
+      .video
+      .last_fixed
+      .map(|value| value + self.video.last_step)
+      .unwrap_or(0);
+    self.next_target = audio_next.max(video_next);
+  }
+}
+
+pub enum FlvParsedItem {
+  Header(Vec<u8>),
+  Tag(FlvTag),
+}
+
+pub struct FlvStreamParser {
+  buffer: Vec<u8>,
+  header_parsed: bool,
+}
+
+impl FlvStreamParser {
+  pub fn new() -> Self {
+    Self {
+      buffer: Vec::new(),
+      header_parsed: false,
+    }
+  }
+
+  pub fn push(&mut self, data: &[u8]) -> Result<Vec<FlvParsedItem>, String> {
+    if !data.is_empty() {
+      self.buffer.extend_from_slice(data);
+    }
+    let mut items = Vec::new();
+    let mut offset = 0;
+    if !self.header_parsed {
+      if self.buffer.len() < 3 {
+        return Ok(items);
+      }
+      if self.buffer[..3] != *b"FLV" {
+        return Err("FLV header mismatch".to_string());
+      }
+      if self.buffer.len() < 13 {
+        return Ok(items);
+      }
+      let header = self.buffer[offset..offset + 13].to_vec();
+      offset += 13;
+      self.header_parsed = true;
+      items.push(FlvParsedItem::Header(header));
+    }
+
+    loop {
+      if self.buffer.len().saturating_sub(offset) < 11 {
+        break;
+      }
+      let header_start = offset;
+      let data_size = read_u24_be(&self.buffer[header_start + 1..header_start + 4]);
+      let total = 11 + data_size + 4;
+      if self.buffer.len().saturating_sub(offset) < total {
+        break;
+      }
+      let bytes = self.buffer[offset..offset + total].to_vec();
+      let tag_type = bytes[0];
+      let data_offset = 11;
+      let data_len = data_size;
+      items.push(FlvParsedItem::Tag(FlvTag {
+        tag_type,
+        bytes,
+        data_offset,
+        data_len,
+      }));
+      offset += total;
+    }
+
+    if offset > 0 {
+      self.buffer.drain(0..offset);
+    }
+    Ok(items)
+  }
+
+  pub fn remaining(&self) -> usize {
+    self.buffer.len()
+  }
+}
+
+pub struct FlvHeaderCache {
+  header: Option<Vec<u8>>,
+  script_tag: Option<Vec<u8>>,
+  audio_header: Option<Vec<u8>>,
+  video_header: Option<Vec<u8>>,
+}
+
+impl FlvHeaderCache {
+  pub fn new() -> Self {
+    Self {
+      header: None,
+      script_tag: None,
+      audio_header: None,
+      video_header: None,
+    }
+  }
+
+  pub fn set_header(&mut self, header: Vec<u8>) {
+    self.header = Some(header);
+  }
+
+  pub fn has_header(&self) -> bool {
+    self.header.is_some()
+  }
+
+  pub fn update_from_tag(&mut self, tag: &FlvTag) {
+    match tag.tag_type {
+      18 => {
+        if self.script_tag.is_none() {
+          self.script_tag = Some(normalize_header_tag(&tag.bytes));
+        }
+      }
+      8 => {
+        if is_audio_header(tag.data(), self.audio_header.is_some()) {
+          self.audio_header = Some(normalize_header_tag(&tag.bytes));
+        }
+      }
+      9 => {
+        if is_video_header(tag.data(), self.video_header.is_some()) {
+          self.video_header = Some(normalize_header_tag(&tag.bytes));
+        }
+      }
+      _ => {}
+    }
+  }
+
+  pub fn preamble(&self) -> Result<Vec<u8>, String> {
+    let header = self
+      .header
+      .as_ref()
+      .ok_or_else(|| "缺少FLV头信息".to_string())?;
+    let mut bytes = header.clone();
+    if let Some(tag) = self.script_tag.as_ref() {
+      bytes.extend_from_slice(tag);
+    }
+    if let Some(tag) = self.video_header.as_ref() {
+      bytes.extend_from_slice(tag);
+    }
+    if let Some(tag) = self.audio_header.as_ref() {
+      bytes.extend_from_slice(tag);
+    }
+    Ok(bytes)
+  }
+}
+
+fn read_u24_be(slice: &[u8]) -> usize {
+  if slice.len() < 3 {
+    return 0;
+  }
+  ((slice[0] as usize) << 16) | ((slice[1] as usize) << 8) | slice[2] as usize
+}
+
+pub fn parse_flv_timestamp(tag: &FlvTag) -> u32 {
+  if tag.bytes.len() < 8 {
+    return 0;
+  }
+  let ts = ((tag.bytes[7] as u32) << 24)
+    | ((tag.bytes[4] as u32) << 16)
+    | ((tag.bytes[5] as u32) << 8)
+    | (tag.bytes[6] as u32);
+  ts
+}
+
+pub fn normalize_header_tag(tag: &[u8]) -> Vec<u8> {
+  let mut normalized = tag.to_vec();
+  if normalized.len() >= 11 {
+    normalized[4] = 0;
+    normalized[5] = 0;
+    normalized[6] = 0;
+    normalized[7] = 0;
+  }
+  normalized
+}
+
+fn is_audio_header(data: &[u8], has_header: bool) -> bool {
+  if data.len() < 2 {
+    return false;
+  }
+  let sound_format = data[0] >> 4;
+  if sound_format == 10 {
+    data[1] == 0
+  } else {
+    !has_header
+  }
+}
+
+fn is_video_header(data: &[u8], has_header: bool) -> bool {
+  if data.len() < 2 {
+    return false;
+  }
+  let codec_id = data[0] & 0x0f;
+  let packet_type = data[1];
+  if codec_id == 7 || codec_id == 12 {
+    packet_type == 0
+  } else {
+    !has_header
+  }
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FlvFixOptions {
+  pub fix_timestamp: bool,
+  pub split_on_timestamp_jump: bool,
+  pub split_on_header_change: bool,
+}
+
+impl Default for FlvFixOptions {
+  fn default() -> Self {
+    Self {
+      fix_timestamp: true,
+      split_on_timestamp_jump: false,
+      split_on_header_change: false,
+    }
+  }
+}
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlvFixReport {
+  pub source_path: String,
+  pub output_paths: Vec<String>,
+  pub report_path: String,
+  pub total_tags: u64,
+  pub audio_tags: u64,
+  pub video_tags: u64,
+  pub script_tags: u64,
+  pub timestamp_jump_count: u64,
+  pub timestamp_jumps: Vec<FlvFixTimestampJump>,
+  pub header_changes: u64,
+  pub injected_headers: u64,
+  pub duplicate_headers: u64,
+  pub dropped_tags: u64,
+  pub fixed_tag_sizes: u64,
+  pub truncated_bytes: u64,
+  pub split_count: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FlvFixTimestampJump {
+  pub output_index: usize,
+  pub tag_index: u64,
+  pub diff: i64,
+  pub original: i64,
+  pub fixed: i64,
+}
+
+struct FlvFixOutput {
+  target: PathBuf,
+  index: usize,
+  file: Option<BufWriter<File>>,
+  audio_header: Option<Vec<u8>>,
+  video_header: Option<Vec<u8>>,
+}
+
+impl FlvFixOutput {
+  fn is_open(&self) -> bool {
+    self.file.is_some()
+  }
+
+  fn open(&mut self, cache: &FlvHeaderCache, report: &mut FlvFixReport) -> Result<(), String> {
+    self.close()?;
+    self.index += 1;
+    let path = if self.index == 1 {
+      self.target.clone()
+    } else {
+      let stem = self
+        .target
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .unwrap_or("output");
+      self.target.with_file_name(format!("{}_part{}.flv", stem, self.index))
+    };
+    let header = cache
+      .header
+      .as_ref()
+      .ok_or_else(|| "缺少FLV头信息".to_string())?;
+    let file = File::create(&path).map_err(|err| format!("创建输出文件失败: {}", err))?;
+    self.file = Some(BufWriter::new(file));
+    self.audio_header = None;
+    self.video_header = None;
+    self.write(header)?;
+    if let Some(tag) = cache.script_tag.as_ref() {
+      self.write(tag)?;
+    }
+    report.output_paths.push(path.to_string_lossy().to_string());
+    Ok(())
+  }
+
+  fn write(&mut self, bytes: &[u8]) -> Result<(), String> {
+    let file = self.file.as_mut().ok_or_else(|| "输出文件未打开".to_string())?;
+    file.write_all(bytes).map_err(|err| format!("写入失败: {}", err))
+  }
+
+  fn close(&mut self) -> Result<(), String> {
+    if let Some(mut file) = self.file.take() {
+      file.flush().map_err(|err| format!("写入失败: {}", err))?;
+    }
+    Ok(())
+  }
+}
+
+pub fn repair_flv_file(
+  source: &Path,
+  target: &Path,
+  options: &FlvFixOptions,
+) -> Result<FlvFixReport, String> {
+  let mut cache = scan_flv_headers(source)?;
+  let mut report = FlvFixReport {
+    source_path: source.to_string_lossy().to_string(),
+    ..Default::default()
+  };
+  let mut output = FlvFixOutput {
+    target: target.to_path_buf(),
+    index: 0,
+    file: None,
+    audio_header: None,
+    video_header: None,
+  };
+  let mut fixer = TimestampFixer::new(true, options.fix_timestamp);
+  let mut parser = FlvStreamParser::new();
+  let mut pending_split = false;
+  let mut input = File::open(source).map_err(|err| format!("打开源文件失败: {}", err))?;
+  let mut buf = vec![0u8; FLV_FIX_READ_BUFFER];
+
+  loop {
+    let read = input
+      .read(&mut buf)
+      .map_err(|err| format!("读取源文件失败: {}", err))?;
+    if read == 0 {
+      break;
+    }
+    let items = parser
+      .push(&buf[..read])
+      .map_err(|_| "不是有效的 FLV 文件".to_string())?;
+    for item in items {
+      let mut tag = match item {
+        FlvParsedItem::Header(_) => continue,
+        FlvParsedItem::Tag(tag) => tag,
+      };
+      report.total_tags += 1;
+      let expected_size = (tag.bytes.len() - 4) as u32;
+      let size_offset = tag.bytes.len() - 4;
+      let actual_size = u32::from_be_bytes([
+        tag.bytes[size_offset],
+        tag.bytes[size_offset + 1],
+        tag.bytes[size_offset + 2],
+        tag.bytes[size_offset + 3],
+      ]);
+      if actual_size != expected_size {
+        tag.bytes[size_offset..].copy_from_slice(&expected_size.to_be_bytes());
+        report.fixed_tag_sizes += 1;
+      }
+
+      match tag.tag_type {
+        8 => report.audio_tags += 1,
+        9 => report.video_tags += 1,
+        18 => {
+          report.script_tags += 1;
+          if report.script_tags > 1 {
+            report.dropped_tags += 1;
+          }
+          continue;
+        }
+        _ => {
+          report.dropped_tags += 1;
+          continue;
+        }
+      }
+
+      let is_video = tag.tag_type == 9;
+      if !output.is_open() {
+        output.open(&cache, &mut report)?;
+      }
+
+      if is_audio_header_tag(tag.data()) || is_video_header_tag(tag.data()) {
+        let normalized = normalize_header_tag(&tag.bytes);
+        let written = if is_video {
+          output.video_header.as_ref()
+        } else {
+          output.audio_header.as_ref()
+        };
+        if written == Some(&normalized) {
+          report.duplicate_headers += 1;
+          continue;
+        }
+        if written.is_some() {
+          report.header_changes += 1;
+          if is_video && options.split_on_header_change {
+            output.open(&cache, &mut report)?;
+            fixer.reset();
+            report.split_count += 1;
+          }
+        }
+        if is_video {
+          cache.video_header = Some(normalized.clone());
+          output.video_header = Some(normalized);
+        } else {
+          cache.audio_header = Some(normalized.clone());
+          output.audio_header = Some(normalized);
+        }
+        fixer.fix_tag(&mut tag, true);
+        output.write(&tag.bytes)?;
+        continue;
+      }
+
+      let needs_header = {
+        let data = tag.data();
+        !data.is_empty()
+          && if is_video {
+            matches!(data[0] & 0x0f, 7 | 12)
+          } else {
+            data[0] >> 4 == 10
+          }
+      };
+      let cached_header = if is_video {
+        cache.video_header.clone()
+      } else {
+        cache.audio_header.clone()
+      };
+      if needs_header && cached_header.is_none() {
+        report.dropped_tags += 1;
+        continue;
+      }
+
+      let original = parse_flv_timestamp(&tag);
+      if let Some(jump) = fixer.fix_tag(&mut tag, false) {
+        report.timestamp_jump_count += 1;
+        if report.timestamp_jumps.len() < FLV_FIX_MAX_JUMP_ENTRIES {
+          report.timestamp_jumps.push(FlvFixTimestampJump {
+            output_index: output.index,
+            tag_index: report.total_tags,
+            diff: jump.diff,
+            original: jump.original,
+            fixed: jump.fixed,
+          });
+        }
+        if options.split_on_timestamp_jump {
+          pending_split = true;
+        }
+      }
+      if pending_split && is_video_keyframe(&tag) {
+        output.open(&cache, &mut report)?;
+        fixer.reset();
+        write_flv_timestamp(&mut tag, original);
+        fixer.fix_tag(&mut tag, false);
+        report.split_count += 1;
+        pending_split = false;
+      }
+
+      let written = if is_video {
+        output.video_header.is_some()
+      } else {
+        output.audio_header.is_some()
+      };
+      if needs_header && !written {
+        if let Some(header) = cached_header {
+          let mut header_tag = header.clone();
+          header_tag[4..8].copy_from_slice(&tag.bytes[4..8]);
+          output.write(&header_tag)?;
+          report.injected_headers += 1;
+          if is_video {
+            output.video_header = Some(header);
+          } else {
+            output.audio_header = Some(header);
+          }
+        }
+      }
+      output.write(&tag.bytes)?;
+    }
+  }
+  output.close()?;
+  report.truncated_bytes = parser.remaining() as u64;
+  if report.output_paths.is_empty() {
+    return Err("没有可修复的音视频数据".to_string());
+  }
+
+  let report_path = flv_fix_report_path(target);
+  report.report_path = report_path.to_string_lossy().to_string();
+  let content = serde_json::to_string_pretty(&report).map_err(|err| err.to_string())?;
+  fs::write(&report_path, content).map_err(|err| format!("写入修复报告失败: {}", err))?;
+  Ok(report)
+}
+
+pub fn flv_fix_report_path(target: &Path) -> PathBuf {
+  let stem = target
+    .file_stem()
+    .and_then(|value| value.to_str())
+    .unwrap_or("output");
+  target.with_file_name(format!("{}.report.json", stem))
+}
+
+fn scan_flv_headers(source: &Path) -> Result<FlvHeaderCache, String> {
+  let mut cache = FlvHeaderCache::new();
+  let mut parser = FlvStreamParser::new();
+  let mut input = File::open(source).map_err(|err| format!("打开源文件失败: {}", err))?;
+  let mut buf = vec![0u8; FLV_FIX_READ_BUFFER];
+  loop {
+    let read = input
+      .read(&mut buf)
+      .map_err(|err| format!("读取源文件失败: {}", err))?;
+    if read == 0 {
+      break;
+    }
+    let items = parser
+      .push(&buf[..read])
+      .map_err(|_| "不是有效的 FLV 文件".to_string())?;
+    for item in items {
+      match item {
+        FlvParsedItem::Header(header) => cache.set_header(header),
+        FlvParsedItem::Tag(tag) => match tag.tag_type {
+          18 if cache.script_tag.is_none() => cache.update_from_tag(&tag),
+          8 if cache.audio_header.is_none() && is_audio_header_tag(tag.data()) => {
+            cache.audio_header = Some(normalize_header_tag(&tag.bytes));
+          }
+          9 if cache.video_header.is_none() && is_video_header_tag(tag.data()) => {
+            cache.video_header = Some(normalize_header_tag(&tag.bytes));
+          }
+          _ => {}
+        },
+      }
+    }
+    if cache.script_tag.is_some() && cache.audio_header.is_some() && cache.video_header.is_some() {
+      break;
+    }
+  }
+  if !cache.has_header() {
+    return Err("不是有效的 FLV 文件".to_string());
+  }
+  Ok(cache)
+}
@@ -0,0 +1,120 @@
+use url::Url;
+
+pub struct HlsPlaylist {
+  pub target_duration: f64,
+  pub end_list: bool,
+  pub variants: Vec<String>,
+  pub segments: Vec<HlsSegment>,
+}
+
+pub struct HlsSegment {
+  pub sequence: u64,
+  pub uri: String,
+  pub duration: f64,
+  pub discontinuity: bool,
+  pub map_uri: Option<String>,
+}
+
+impl HlsPlaylist {
+  pub fn is_fmp4(&self) -> bool {
+    self.segments.iter().any(|segment| segment.map_uri.is_some())
+  }
+
+  pub fn last_sequence(&self) -> Option<u64> {
+    self.segments.last().map(|segment| segment.sequence)
+  }
+}
+
+pub fn parse_playlist(text: &str) -> Result<HlsPlaylist, String> {
+  let mut lines = text.lines().map(|line| line.trim()).filter(|line| !line.is_empty());
+  if lines.next() != Some("#EXTM3U") {
+    return Err("不是有效的 HLS 播放列表".to_string());
+  }
+
+  let mut playlist = HlsPlaylist {
+    target_duration: 0.0,
+    end_list: false,
+    variants: Vec::new(),
+    segments: Vec::new(),
+  };
+  let mut sequence: u64 = 0;
+  let mut map_uri: Option<String> = None;
+  let mut pending_duration = 0.0;
+  let mut pending_discontinuity = false;
+  let mut pending_variant = false;
+
+  for line in lines {
+    if let Some(value) = line.strip_prefix("#EXT-X-TARGETDURATION:") {
+      playlist.target_duration = value.trim().parse().unwrap_or(0.0);
+    } else if let Some(value) = line.strip_prefix("#EXT-X-MEDIA-SEQUENCE:") {
+      sequence = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("媒体序号无效: {}", value))?;
+    } else if let Some(value) = line.strip_prefix("#EXT-X-MAP:") {
+      map_uri = parse_attribute(value, "URI");
+    } else if let Some(value) = line.strip_prefix("#EXTINF:") {
+      pending_duration = value
+        .split(',')
+        .next()
+        .and_then(|item| item.trim().parse().ok())
+        .unwrap_or(0.0);
+    } else if line == "#EXT-X-DISCONTINUITY" {
+      pending_discontinuity = true;
+    } else if line == "#EXT-X-ENDLIST" {
+      playlist.end_list = true;
+    } else if line.starts_with("#EXT-X-STREAM-INF") {
+      pending_variant = true;
+    } else if line.starts_with('#') {
+      continue;
+    } else if pending_variant {
+      playlist.variants.push(line.to_string());
+      pending_variant = false;
+    } else {
+      playlist.segments.push(HlsSegment {
+        sequence,
+        uri: line.to_string(),
+        duration: pending_duration,
+        discontinuity: pending_discontinuity,
+        map_uri: map_uri.clone(),
+      });
+      sequence += 1;
+      pending_duration = 0.0;
+      pending_discontinuity = false;
+    }
+  }
+
+  if playlist.variants.is_empty() && playlist.segments.is_empty() && !playlist.end_list {
+    return Err("HLS 播放列表为空".to_string());
+  }
+  Ok(playlist)
+}
+
+pub fn resolve_uri(base: &str, uri: &str) -> Result<String, String> {
+  let base = Url::parse(base).map_err(|err| format!("播放列表地址无效: {}", err))?;
+  base
+    .join(uri)
+    .map(|url| url.to_string())
+    .map_err(|err| format!("分片地址无效: {}", err))
+}
+
+fn parse_attribute(value: &str, name: &str) -> Option<String> {
+  let mut rest = value;
+  while !rest.is_empty() {
+    let (key, tail) = rest.split_once('=')?;
+    let (item, next) = if let Some(quoted) = tail.strip_prefix('"') {
+      let end = quoted.find('"')?;
+      (&quoted[..end], quoted[end + 1..].trim_start_matches(','))
+    } else {
+      match tail.split_once(',') {
+        Some((item, next)) => (item, next),
+        None => (tail, ""),
+      }
+    };
+    if key.trim().eq_ignore_ascii_case(name) {
+      return Some(item.to_string());
+    }
+    rest = next;
+  }
+  None
+}
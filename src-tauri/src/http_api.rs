@@ -0,0 +1,530 @@
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::async_runtime::JoinHandle;
+use tokio::net::TcpListener;
+
+use crate::api::ApiResponse;
+use crate::commands::settings::HttpApiSettings;
+use crate::commands::{
+  anchor, auth, baidu_sync, download, file_scanner, live, process, settings, submission,
+  subscription, toolbox,
+};
+use crate::utils::append_log;
+use crate::AppState;
+
+const API_PREFIX: &str = "/api/";
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+#[derive(Default)]
+pub struct HttpApiRuntime {
+  server: Mutex<Option<JoinHandle<()>>>,
+}
+
+struct DispatchError {
+  status: StatusCode,
+  message: String,
+}
+
+impl DispatchError {
+  fn new(status: StatusCode, message: impl Into<String>) -> Self {
+    Self {
+      status,
+      message: message.into(),
+    }
+  }
+}
+
+pub fn restart_http_api(state: &AppState, settings: &HttpApiSettings) -> Result<(), String> {
+  stop_http_api(state);
+  if !settings.enabled {
+    return Ok(());
+  }
+  let token = settings.token.trim().to_string();
+  if token.is_empty() {
+    append_log(&state.app_log_path, "http_api_start_skip reason=empty_token");
+    return Err("未配置访问令牌".to_string());
+  }
+  let ip = settings
+    .bind_address
+    .trim()
+    .parse::<IpAddr>()
+    .map_err(|_| format!("监听地址无效: {}", settings.bind_address))?;
+  let port = u16::try_from(settings.port).map_err(|_| format!("端口无效: {}", settings.port))?;
+  let addr = SocketAddr::new(ip, port);
+  let listener = std::net::TcpListener::bind(addr).map_err(|err| {
+    append_log(
+      &state.app_log_path,
+      &format!("http_api_bind_fail addr={} err={}", addr, err),
+    );
+    format!("监听 {} 失败: {}", addr, err)
+  })?;
+  listener
+    .set_nonblocking(true)
+    .map_err(|err| format!("监听 {} 失败: {}", addr, err))?;
+  append_log(&state.app_log_path, &format!("http_api_start addr={}", addr));
+
+  let app_state = state.clone();
+  let handle = tauri::async_runtime::spawn(async move {
+    serve(app_state, listener, Arc::new(token)).await;
+  });
+  if let Ok(mut guard) = state.http_api_runtime.server.lock() {
+    *guard = Some(handle);
+  }
+  Ok(())
+}
+
+pub fn stop_http_api(state: &AppState) {
+  let handle = state
+    .http_api_runtime
+    .server
+    .lock()
+    .ok()
+    .and_then(|mut guard| guard.take());
+  if let Some(handle) = handle {
+    handle.abort();
+    append_log(&state.app_log_path, "http_api_stop");
+  }
+}
+
+async fn serve(state: AppState, listener: std::net::TcpListener, token: Arc<String>) {
+  let listener = match TcpListener::from_std(listener) {
+    Ok(listener) => listener,
+    Err(err) => {
+      append_log(&state.app_log_path, &format!("http_api_listen_fail err={}", err));
+      return;
+    }
+  };
+  loop {
+    let (stream, peer) = match listener.accept().await {
+      Ok(value) => value,
+      Err(err) => {
+        append_log(&state.app_log_path, &format!("http_api_accept_fail err={}", err));
+        continue;
+      }
+    };
+    let state = state.clone();
+    let token = Arc::clone(&token);
+    tauri::async_runtime::spawn(async move {
+      let log_path = Arc::clone(&state.app_log_path);
+      let service = service_fn(move |request| {
+        let state = state.clone();
+        let token = Arc::clone(&token);
+        async move { Ok::<_, Infallible>(handle_request(&state, &token, peer, request).await) }
+      });
+      if let Err(err) = http1::Builder::new()
+        .serve_connection(TokioIo::new(stream), service)
+        .await
+      {
+        append_log(
+          &log_path,
+          &format!("http_api_connection_error peer={} err={}", peer, err),
+        );
+      }
+    });
+  }
+}
+
+async fn handle_request(
+  state: &AppState,
+  token: &str,
+  peer: SocketAddr,
+  request: Request<Incoming>,
+) -> Response<Full<Bytes>> {
+  let command = match request.uri().path().strip_prefix(API_PREFIX) {
+    Some(command) if !command.is_empty() => command.to_string(),
+    _ => return error_response(StatusCode::NOT_FOUND, "接口不存在"),
+  };
+  if request.method() != Method::POST {
+    return error_response(StatusCode::METHOD_NOT_ALLOWED, "仅支持 POST 请求");
+  }
+  if !is_authorized(&request, token) {
+    append_log(
+      &state.app_log_path,
+      &format!("http_api_unauthorized peer={} command={}", peer, command),
+    );
+    return error_response(StatusCode::UNAUTHORIZED, "访问令牌无效");
+  }
+  let body = match Limited::new(request.into_body(), MAX_BODY_BYTES).collect().await {
+    Ok(collected) => collected.to_bytes(),
+    Err(_) => return error_response(StatusCode::PAYLOAD_TOO_LARGE, "请求体读取失败或过大"),
+  };
+  let args = if body.iter().all(|byte| byte.is_ascii_whitespace()) {
+    Value::Object(Default::default())
+  } else {
+    match serde_json::from_slice::<Value>(&body) {
+      Ok(value @ Value::Object(_)) => value,
+      _ => return error_response(StatusCode::BAD_REQUEST, "请求体必须是 JSON 对象"),
+    }
+  };
+  append_log(
+    &state.app_log_path,
+    &format!("http_api_request peer={} command={}", peer, command),
+  );
+  match dispatch(state, &command, &args).await {
+    Ok(value) => json_response(StatusCode::OK, &value),
+    Err(err) => {
+      append_log(
+        &state.app_log_path,
+        &format!(
+          "http_api_request_fail command={} status={} err={}",
+          command,
+          err.status.as_u16(),
+          err.message
+        ),
+      );
+      error_response(err.status, err.message)
+    }
+  }
+}
+
+async fn dispatch(state: &AppState, command: &str, args: &Value) -> Result<Value, DispatchError> {
+  match command {
+    "scan_path" => respond(file_scanner::scan_path(arg(args, "path")?)),
+    "validate_directory" => respond(file_scanner::validate_directory(arg(args, "path")?)),
+    "video_duration" => respond(file_scanner::video_duration(arg(args, "path")?)),
+    "auth_status" => respond(auth::auth_status_inner(state).await.map_err(internal)?),
+    "auth_account_list" => respond(auth::auth_account_list_inner(state)),
+    "auth_account_set_default" => respond(auth::auth_account_set_default_inner(
+      state,
+      arg(args, "userId")?,
+    )),
+    "auth_account_remove" => respond(auth::auth_account_remove_inner(state, arg(args, "userId")?)),
+    "get_download_settings" => respond(settings::get_download_settings_inner(state)),
+    "get_live_settings" => respond(settings::get_live_settings_inner(state)),
+    "update_live_settings" => respond(settings::update_live_settings_inner(
+      state,
+      arg(args, "payload")?,
+    )),
+    "get_encoder_settings" => respond(settings::get_encoder_settings_inner(state)),
+    "update_encoder_settings" => respond(settings::update_encoder_settings_inner(
+      state,
+      arg(args, "payload")?,
+    )),
+    "get_storage_settings" => respond(settings::get_storage_settings_inner(state)),
+    "update_storage_settings" => respond(settings::update_storage_settings_inner(
+      state,
+      arg(args, "payload")?,
+    )),
+    "get_bandwidth_settings" => respond(settings::get_bandwidth_settings_inner(state)),
+    "update_bandwidth_settings" => respond(settings::update_bandwidth_settings_inner(
+      state,
+      arg(args, "payload")?,
+    )),
+    "storage_retention_run" => respond(
+      settings::storage_retention_run_inner(state)
+        .await
+        .map_err(internal)?,
+    ),
+    "anchor_subscribe" => respond(
+      anchor::anchor_subscribe_inner(state, arg(args, "payload")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "anchor_list" => respond(anchor::anchor_list_inner(state)),
+    "anchor_unsubscribe" => respond(anchor::anchor_unsubscribe_inner(state, arg(args, "uid")?)),
+    "anchor_check" => respond(anchor::anchor_check_inner(state).await.map_err(internal)?),
+    "live_record_start" => respond(
+      live::live_record_start_inner(state, arg(args, "roomId")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "live_record_stop" => respond(live::live_record_stop_inner(state, arg(args, "roomId")?)),
+    "live_room_auto_record_update" => respond(
+      live::live_room_auto_record_update_inner(
+        state,
+        arg(args, "roomId")?,
+        arg(args, "autoRecord")?,
+      )
+      .await
+      .map_err(internal)?,
+    ),
+    "live_room_baidu_sync_update" => respond(live::live_room_baidu_sync_update_inner(
+      state,
+      arg(args, "roomId")?,
+      arg(args, "baiduSyncPath")?,
+    )),
+    "live_room_account_update" => respond(live::live_room_account_update_inner(
+      state,
+      arg(args, "roomId")?,
+      arg(args, "accountUserId")?,
+    )),
+    "live_room_schedule_update" => respond(live::live_room_schedule_update_inner(
+      state,
+      arg(args, "roomId")?,
+      arg(args, "payload")?,
+    )),
+    "live_room_settings_get" => respond(live::live_room_settings_get_inner(state, arg(args, "roomId")?)),
+    "live_room_settings_update" => respond(live::live_room_settings_update_inner(
+      state,
+      arg(args, "roomId")?,
+      arg(args, "overrides")?,
+    )),
+    "live_session_list" => respond(live::live_session_list_inner(
+      state,
+      arg(args, "roomId")?,
+      arg(args, "limit")?,
+    )),
+    "live_session_merge" => respond(live::live_session_merge_inner(state, arg(args, "sessionIds")?)),
+    "live_session_export" => respond(
+      live::live_session_export_inner(state, arg(args, "sessionId")?, arg(args, "targetDir")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "live_room_auto_submit_get" => respond(live::live_room_auto_submit_get_inner(state, arg(args, "roomId")?)),
+    "live_room_auto_submit_update" => respond(live::live_room_auto_submit_update_inner(
+      state,
+      arg(args, "roomId")?,
+      arg(args, "payload")?,
+    )),
+    "live_room_baidu_sync_toggle" => respond(live::live_room_baidu_sync_toggle_inner(
+      state,
+      arg(args, "roomId")?,
+      arg(args, "enabled")?,
+    )),
+    "download_video" => respond(
+      download::download_video_inner(state, arg(args, "payload")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "download_source_preview" => respond(
+      download::download_source_preview_inner(state, arg(args, "payload")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "download_source_enqueue" => respond(
+      download::download_source_enqueue_inner(state, arg(args, "payload")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "vod_subscription_list" => respond(subscription::vod_subscription_list_inner(state)),
+    "vod_subscription_save" => respond(subscription::vod_subscription_save_inner(
+      state,
+      arg(args, "payload")?,
+    )),
+    "vod_subscription_delete" => respond(subscription::vod_subscription_delete_inner(
+      state,
+      arg(args, "id")?,
+    )),
+    "vod_subscription_check" => respond(
+      subscription::vod_subscription_check_inner(state, arg(args, "id")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "vod_subscription_items" => respond(subscription::vod_subscription_items_inner(
+      state,
+      arg(args, "subscriptionId")?,
+    )),
+    "download_get" => respond(download::download_get_inner(state, arg(args, "taskId")?)),
+    "download_list_by_status" => respond(download::download_list_by_status_inner(
+      state,
+      arg(args, "status")?,
+    )),
+    "download_delete" => respond(download::download_delete_inner(
+      state,
+      arg(args, "taskId")?,
+      arg(args, "deleteFile")?,
+    )),
+    "download_retry" => respond(
+      download::download_retry_inner(state, arg(args, "taskId")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "download_resume" => respond(
+      download::download_resume_inner(state, arg(args, "taskId")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "process_create" => respond(
+      process::process_create_inner(state, arg(args, "request")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "process_status" => respond(process::process_status_inner(state, arg(args, "taskId")?)),
+    "toolbox_remux" => respond(
+      toolbox::toolbox_remux_inner(state, arg(args, "payload")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "toolbox_danmaku_ass" => respond(
+      toolbox::toolbox_danmaku_ass_inner(state, arg(args, "payload")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "toolbox_flv_fix" => respond(
+      toolbox::toolbox_flv_fix_inner(state, arg(args, "payload")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "baidu_sync_settings" => respond(baidu_sync::baidu_sync_settings_inner(state)),
+    "baidu_sync_status" => respond(baidu_sync::baidu_sync_status_inner(state)),
+    "baidu_sync_list" => respond(baidu_sync::baidu_sync_list_inner(state, arg(args, "request")?)),
+    "baidu_sync_retry" => respond(baidu_sync::baidu_sync_retry_inner(state, arg(args, "taskId")?)),
+    "baidu_sync_cancel" => respond(baidu_sync::baidu_sync_cancel_inner(state, arg(args, "taskId")?)),
+    "baidu_sync_pause" => respond(baidu_sync::baidu_sync_pause_inner(state, arg(args, "taskId")?)),
+    "baidu_sync_delete" => respond(baidu_sync::baidu_sync_delete_inner(state, arg(args, "taskId")?)),
+    "baidu_sync_update_settings" => respond(baidu_sync::baidu_sync_update_settings_inner(
+      state,
+      arg(args, "request")?,
+    )),
+    "sync_backend_settings" => respond(baidu_sync::sync_backend_settings_inner(state)),
+    "sync_backend_update_settings" => respond(baidu_sync::sync_backend_update_settings_inner(
+      state,
+      arg(args, "payload")?,
+    )),
+    "sync_backend_test" => respond(
+      baidu_sync::sync_backend_test_inner(state, arg(args, "payload")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "submission_create" => respond(
+      submission::submission_create_inner(state, arg(args, "request")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "submission_highlight_proposals" => respond(
+      submission::submission_highlight_proposals_inner(state, arg(args, "request")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "submission_update" => respond(
+      submission::submission_update_inner(state, arg(args, "request")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "submission_repost" => respond(
+      submission::submission_repost_inner(state, arg(args, "request")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "submission_resegment" => respond(
+      submission::submission_resegment_inner(state, arg(args, "request")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "submission_list" => respond(
+      submission::submission_list_inner(
+        state,
+        arg(args, "page")?,
+        arg(args, "pageSize")?,
+        arg(args, "refreshRemote")?,
+      )
+      .await
+      .map_err(internal)?,
+    ),
+    "submission_list_by_status" => respond(
+      submission::submission_list_by_status_inner(
+        state,
+        arg(args, "status")?,
+        arg(args, "page")?,
+        arg(args, "pageSize")?,
+        arg(args, "refreshRemote")?,
+      )
+      .await
+      .map_err(internal)?,
+    ),
+    "submission_detail" => respond(submission::submission_detail_inner(state, arg(args, "taskId")?)),
+    "submission_delete" => respond(submission::submission_delete_inner(state, arg(args, "request")?)),
+    "submission_execute" => respond(
+      submission::submission_execute_inner(state, arg(args, "taskId")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "submission_integrated_execute" => respond(
+      submission::submission_integrated_execute_inner(state, arg(args, "taskId")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "submission_upload_execute" => respond(
+      submission::submission_upload_execute_inner(state, arg(args, "taskId")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "submission_queue_prioritize" => respond(
+      submission::submission_queue_prioritize_inner(state, arg(args, "taskId")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "submission_schedule_update" => respond(submission::submission_schedule_update_inner(
+      state,
+      arg(args, "taskId")?,
+      arg(args, "scheduledPublishTime")?,
+      arg(args, "scheduledPublishMode")?,
+    )),
+    "submission_retry_segment_upload" => respond(
+      submission::submission_retry_segment_upload_inner(state, arg(args, "segmentId")?)
+        .await
+        .map_err(internal)?,
+    ),
+    "workflow_status" => respond(submission::workflow_status_inner(state, arg(args, "taskId")?)),
+    "workflow_pause" => respond(submission::workflow_pause_inner(state, arg(args, "taskId")?)),
+    "workflow_resume" => respond(submission::workflow_resume_inner(state, arg(args, "taskId")?)),
+    "workflow_cancel" => respond(submission::workflow_cancel_inner(state, arg(args, "taskId")?)),
+    _ => Err(DispatchError::new(
+      StatusCode::NOT_FOUND,
+      format!("未知命令: {}", command),
+    )),
+  }
+}
+
+fn arg<T: DeserializeOwned>(args: &Value, key: &str) -> Result<T, DispatchError> {
+  serde_json::from_value(args.get(key).cloned().unwrap_or(Value::Null)).map_err(|err| {
+    DispatchError::new(
+      StatusCode::BAD_REQUEST,
+      format!("参数 {} 无效: {}", key, err),
+    )
+  })
+}
+
+fn respond<T: Serialize>(response: ApiResponse<T>) -> Result<Value, DispatchError> {
+  serde_json::to_value(response)
+    .map_err(|err| DispatchError::new(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))
+}
+
+fn internal(message: String) -> DispatchError {
+  DispatchError::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+}
+
+fn is_authorized(request: &Request<Incoming>, token: &str) -> bool {
+  let provided = request
+    .headers()
+    .get(AUTHORIZATION)
+    .and_then(|value| value.to_str().ok())
+    .and_then(|value| value.strip_prefix("Bearer "))
+    .map(str::trim)
+    .unwrap_or("");
+  if provided.len() != token.len() {
+    return false;
+  }
+  provided
+    .bytes()
+    .zip(token.bytes())
+    .fold(0u8, |acc, (left, right)| acc | (left ^ right))
+    == 0
+}
+
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response<Full<Bytes>> {
+  let body = serde_json::to_value(ApiResponse::<Value>::error(message)).unwrap_or(Value::Null);
+  json_response(status, &body)
+}
+
+fn json_response(status: StatusCode, body: &Value) -> Response<Full<Bytes>> {
+  let mut response = Response::new(Full::new(Bytes::from(body.to_string())));
+  *response.status_mut() = status;
+  response.headers_mut().insert(
+    CONTENT_TYPE,
+    HeaderValue::from_static("application/json; charset=utf-8"),
+  );
+  response
+}
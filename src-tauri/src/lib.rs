@@ -9,17 +9,37 @@ use tauri::Manager;
 mod api;
 mod app_log;
 mod baidu_sync;
+mod bandwidth;
 mod bilibili;
 mod commands;
 mod config;
+mod daemon;
+mod danmaku_ass;
+mod danmaku_highlight;
 mod db;
+mod download_sidecar;
+mod download_source;
+mod encoder;
 mod ffmpeg;
+mod flv;
+mod hls;
+mod http_api;
 mod live_recorder;
+mod live_session;
+mod live_submission;
 mod login_refresh;
 mod login_store;
 mod processing;
+mod record_schedule;
+mod remote_storage;
+mod segment_download;
+mod storage;
 mod utils;
+mod webhook;
 
+pub use daemon::run_daemon;
+
+#[derive(Clone)]
 struct AppState {
     db: Arc<db::Db>,
     bilibili: Arc<bilibili::client::BilibiliClient>,
@@ -31,6 +51,7 @@ struct AppState {
     edit_upload_state: Arc<Mutex<commands::submission::EditUploadState>>,
     baidu_sync_runtime: Arc<baidu_sync::BaiduSyncRuntime>,
     baidu_login_runtime: Arc<Mutex<commands::baidu_sync::BaiduLoginRuntime>>,
+    http_api_runtime: Arc<http_api::HttpApiRuntime>,
 }
 
 struct DownloadRuntime {
@@ -62,6 +83,130 @@ fn init_panic_log(path: Arc<std::path::PathBuf>) {
     }));
 }
 
+struct BackgroundTasks {
+    downloads: bool,
+    live_recording: bool,
+    submissions: bool,
+    baidu_sync: bool,
+    cookie_refresh: bool,
+}
+
+impl BackgroundTasks {
+    fn all() -> Self {
+        Self {
+            downloads: true,
+            live_recording: true,
+            submissions: true,
+            baidu_sync: true,
+            cookie_refresh: true,
+        }
+    }
+}
+
+fn build_app_state(app_dir: &std::path::Path) -> Result<AppState, Box<dyn std::error::Error>> {
+    let db_path = app_dir.join("reaction-cut-rust.sqlite3");
+    let db = Arc::new(db::Db::new(db_path)?);
+    let login_path = app_dir.join("bilibili_login_info.json");
+    let download_dir = commands::settings::load_download_settings_from_db(&db)
+        .map(|settings| settings.download_path)
+        .unwrap_or_else(|_| config::default_download_dir().to_string_lossy().to_string());
+    let log_dir = commands::settings::ensure_log_dir(&db, std::path::Path::new(&download_dir));
+    let log_dir = std::path::PathBuf::from(log_dir);
+    let log_path = log_dir.join("auth_debug.log");
+    let app_log_path = log_dir.join("app_debug.log");
+    let panic_log_path = log_dir.join("panic_debug.log");
+    utils::append_log(&app_log_path, "app_start");
+    let ffmpeg_path = config::resolve_ffmpeg_path();
+    let ffprobe_path = config::resolve_ffprobe_path();
+    let baidu_pcs_candidates = config::resolve_baidu_pcs_candidates();
+    utils::append_log(
+        &app_log_path,
+        &format!(
+            "bin_paths ffmpeg={} ffprobe={} baidu_pcs={}",
+            ffmpeg_path.to_string_lossy(),
+            ffprobe_path.to_string_lossy(),
+            baidu_pcs_candidates.join(",")
+        ),
+    );
+    init_panic_log(Arc::new(panic_log_path));
+    let heartbeat_path = app_log_path.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            utils::append_log(&heartbeat_path, "heartbeat");
+            sleep(Duration::from_secs(30)).await;
+        }
+    });
+    Ok(AppState {
+        db,
+        bilibili: Arc::new(bilibili::client::BilibiliClient::new()),
+        login_store: Arc::new(login_store::LoginStore::new(login_path)),
+        log_path: Arc::new(log_path),
+        app_log_path: Arc::new(app_log_path),
+        download_runtime: Arc::new(DownloadRuntime::new()),
+        live_runtime: Arc::new(live_recorder::new_live_runtime()),
+        edit_upload_state: Arc::new(Mutex::new(
+            commands::submission::EditUploadState::default(),
+        )),
+        baidu_sync_runtime: Arc::new(baidu_sync::BaiduSyncRuntime::new()),
+        baidu_login_runtime: Arc::new(Mutex::new(
+            commands::baidu_sync::BaiduLoginRuntime::default(),
+        )),
+        http_api_runtime: Arc::new(http_api::HttpApiRuntime::default()),
+    })
+}
+
+fn build_live_context(state: &AppState) -> live_recorder::LiveContext {
+    live_recorder::LiveContext {
+        db: Arc::clone(&state.db),
+        bilibili: Arc::clone(&state.bilibili),
+        login_store: Arc::clone(&state.login_store),
+        app_log_path: Arc::clone(&state.app_log_path),
+        live_runtime: Arc::clone(&state.live_runtime),
+        edit_upload_state: Arc::clone(&state.edit_upload_state),
+    }
+}
+
+fn start_background_tasks(state: &AppState, tasks: &BackgroundTasks) {
+    commands::settings::apply_bandwidth_settings(&state.db);
+    if tasks.downloads {
+        commands::download::recover_stale_downloads(state);
+        commands::download::start_download_queue_loop(state);
+        commands::subscription::start_vod_subscription_loop(state);
+    }
+    if tasks.live_recording {
+        let live_context = build_live_context(state);
+        live_recorder::recover_stale_recordings(live_context.clone());
+        live_recorder::start_record_recovery_loop(live_context.clone());
+        live_recorder::start_auto_record_loop(live_context);
+    }
+    if tasks.cookie_refresh {
+        login_refresh::start_cookie_refresh_loop(
+            Arc::clone(&state.db),
+            Arc::clone(&state.bilibili),
+            Arc::clone(&state.login_store),
+            Arc::clone(&state.app_log_path),
+        );
+    }
+    if tasks.submissions {
+        commands::submission::start_submission_background_tasks(
+            Arc::clone(&state.db),
+            Arc::clone(&state.bilibili),
+            Arc::clone(&state.login_store),
+            Arc::clone(&state.app_log_path),
+            Arc::clone(&state.edit_upload_state),
+        );
+    }
+    if tasks.baidu_sync {
+        let baidu_context = baidu_sync::BaiduSyncContext {
+            db: Arc::clone(&state.db),
+            app_log_path: Arc::clone(&state.app_log_path),
+            runtime: Arc::clone(&state.baidu_sync_runtime),
+        };
+        baidu_sync::start_baidu_sync_loop(baidu_context);
+    }
+    storage::start_retention_loop(Arc::clone(&state.db), Arc::clone(&state.app_log_path));
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let app = tauri::Builder::default()
@@ -82,95 +227,21 @@ pub fn run() {
         .setup(|app| {
             config::init_resource_bins(&app.handle());
             let app_dir = app.path().app_data_dir()?;
-            let db_path = app_dir.join("reaction-cut-rust.sqlite3");
-            let db = Arc::new(db::Db::new(db_path)?);
-            let login_path = app_dir.join("bilibili_login_info.json");
-            let download_dir = commands::settings::load_download_settings_from_db(&db)
-                .map(|settings| settings.download_path)
-                .unwrap_or_else(|_| config::default_download_dir().to_string_lossy().to_string());
-            let log_dir = commands::settings::ensure_log_dir(&db, std::path::Path::new(&download_dir));
-            let log_dir = std::path::PathBuf::from(log_dir);
-            let log_path = log_dir.join("auth_debug.log");
-            let app_log_path = log_dir.join("app_debug.log");
-            let panic_log_path = log_dir.join("panic_debug.log");
-            utils::append_log(&app_log_path, "app_start");
+            let state = build_app_state(&app_dir)?;
             if let Some(resource_dir) = config::resolve_resource_bin_dir(&app.handle()) {
                 utils::append_log(
-                    &app_log_path,
+                    &state.app_log_path,
                     &format!("resource_bin_dir={}", resource_dir.to_string_lossy()),
                 );
             } else {
-                utils::append_log(&app_log_path, "resource_bin_dir_missing");
+                utils::append_log(&state.app_log_path, "resource_bin_dir_missing");
+            }
+            start_background_tasks(&state, &BackgroundTasks::all());
+            let http_api_settings = commands::settings::load_http_api_settings_from_db(&state.db)
+                .unwrap_or_else(|_| commands::settings::default_http_api_settings());
+            if let Err(err) = http_api::restart_http_api(&state, &http_api_settings) {
+                utils::append_log(&state.app_log_path, &format!("http_api_start_fail err={}", err));
             }
-            let ffmpeg_path = config::resolve_ffmpeg_path();
-            let ffprobe_path = config::resolve_ffprobe_path();
-            let aria2c_candidates = config::resolve_aria2c_candidates();
-            let baidu_pcs_candidates = config::resolve_baidu_pcs_candidates();
-            utils::append_log(
-                &app_log_path,
-                &format!(
-                    "bin_paths ffmpeg={} ffprobe={} aria2c={} baidu_pcs={}",
-                    ffmpeg_path.to_string_lossy(),
-                    ffprobe_path.to_string_lossy(),
-                    aria2c_candidates.join(","),
-                    baidu_pcs_candidates.join(",")
-                ),
-            );
-            init_panic_log(Arc::new(panic_log_path));
-            let heartbeat_path = app_log_path.clone();
-            tauri::async_runtime::spawn(async move {
-                loop {
-                    utils::append_log(&heartbeat_path, "heartbeat");
-                    sleep(Duration::from_secs(30)).await;
-                }
-            });
-            let state = AppState {
-                db,
-                bilibili: Arc::new(bilibili::client::BilibiliClient::new()),
-                login_store: Arc::new(login_store::LoginStore::new(login_path)),
-                log_path: Arc::new(log_path),
-                app_log_path: Arc::new(app_log_path),
-                download_runtime: Arc::new(DownloadRuntime::new()),
-                live_runtime: Arc::new(live_recorder::new_live_runtime()),
-                edit_upload_state: Arc::new(Mutex::new(
-                    commands::submission::EditUploadState::default(),
-                )),
-                baidu_sync_runtime: Arc::new(baidu_sync::BaiduSyncRuntime::new()),
-                baidu_login_runtime: Arc::new(Mutex::new(
-                    commands::baidu_sync::BaiduLoginRuntime::default(),
-                )),
-            };
-            commands::download::recover_stale_downloads(&state);
-            commands::download::start_download_queue_loop(&state);
-            let live_context = live_recorder::LiveContext {
-                db: Arc::clone(&state.db),
-                bilibili: Arc::clone(&state.bilibili),
-                login_store: Arc::clone(&state.login_store),
-                app_log_path: Arc::clone(&state.app_log_path),
-                live_runtime: Arc::clone(&state.live_runtime),
-            };
-            live_recorder::recover_stale_recordings(live_context.clone());
-            live_recorder::start_record_recovery_loop(live_context.clone());
-            live_recorder::start_auto_record_loop(live_context);
-            login_refresh::start_cookie_refresh_loop(
-                Arc::clone(&state.db),
-                Arc::clone(&state.bilibili),
-                Arc::clone(&state.login_store),
-                Arc::clone(&state.app_log_path),
-            );
-            commands::submission::start_submission_background_tasks(
-                Arc::clone(&state.db),
-                Arc::clone(&state.bilibili),
-                Arc::clone(&state.login_store),
-                Arc::clone(&state.app_log_path),
-                Arc::clone(&state.edit_upload_state),
-            );
-            let baidu_context = baidu_sync::BaiduSyncContext {
-                db: Arc::clone(&state.db),
-                app_log_path: Arc::clone(&state.app_log_path),
-                runtime: Arc::clone(&state.baidu_sync_runtime),
-            };
-            baidu_sync::start_baidu_sync_loop(baidu_context);
             app.manage(state);
             Ok(())
         })
@@ -186,11 +257,24 @@ pub fn run() {
             commands::auth::auth_refresh,
             commands::auth::auth_client_log,
             commands::auth::auth_logout,
+            commands::auth::auth_account_list,
+            commands::auth::auth_account_set_default,
+            commands::auth::auth_account_remove,
             commands::auth::auth_perform_qrcode_login,
             commands::settings::get_download_settings,
             commands::settings::update_download_settings,
             commands::settings::get_live_settings,
             commands::settings::update_live_settings,
+            commands::settings::get_encoder_settings,
+            commands::settings::update_encoder_settings,
+            commands::settings::get_storage_settings,
+            commands::settings::update_storage_settings,
+            commands::settings::get_bandwidth_settings,
+            commands::settings::update_bandwidth_settings,
+            commands::settings::storage_retention_run,
+            commands::settings::get_encoder_capabilities,
+            commands::settings::get_http_api_settings,
+            commands::settings::update_http_api_settings,
             commands::anchor::anchor_subscribe,
             commands::anchor::anchor_list,
             commands::anchor::anchor_unsubscribe,
@@ -200,6 +284,15 @@ pub fn run() {
             commands::live::live_room_auto_record_update,
             commands::live::live_room_baidu_sync_update,
             commands::live::live_room_baidu_sync_toggle,
+            commands::live::live_room_account_update,
+            commands::live::live_room_schedule_update,
+            commands::live::live_room_settings_get,
+            commands::live::live_room_settings_update,
+            commands::live::live_room_auto_submit_get,
+            commands::live::live_room_auto_submit_update,
+            commands::live::live_session_list,
+            commands::live::live_session_merge,
+            commands::live::live_session_export,
             commands::video::video_detail,
             commands::video::video_playurl,
             commands::video::video_playurl_by_aid,
@@ -208,6 +301,13 @@ pub fn run() {
             commands::video::bilibili_partitions,
             commands::video::bilibili_topics,
             commands::download::download_video,
+            commands::download::download_source_preview,
+            commands::download::download_source_enqueue,
+            commands::subscription::vod_subscription_list,
+            commands::subscription::vod_subscription_save,
+            commands::subscription::vod_subscription_delete,
+            commands::subscription::vod_subscription_check,
+            commands::subscription::vod_subscription_items,
             commands::download::download_get,
             commands::download::download_list_by_status,
             commands::download::download_delete,
@@ -216,7 +316,10 @@ pub fn run() {
             commands::process::process_create,
             commands::process::process_status,
             commands::toolbox::toolbox_remux,
+            commands::toolbox::toolbox_danmaku_ass,
+            commands::toolbox::toolbox_flv_fix,
             commands::submission::submission_queue_prioritize,
+            commands::submission::submission_schedule_update,
             commands::baidu_sync::baidu_sync_settings,
             commands::baidu_sync::baidu_sync_status,
             commands::baidu_sync::baidu_sync_login,
@@ -235,7 +338,11 @@ pub fn run() {
             commands::baidu_sync::baidu_sync_pause,
             commands::baidu_sync::baidu_sync_delete,
             commands::baidu_sync::baidu_sync_update_settings,
+            commands::baidu_sync::sync_backend_settings,
+            commands::baidu_sync::sync_backend_update_settings,
+            commands::baidu_sync::sync_backend_test,
             commands::submission::submission_create,
+            commands::submission::submission_highlight_proposals,
             commands::submission::submission_update,
             commands::submission::submission_repost,
             commands::submission::submission_resegment,
@@ -259,6 +366,11 @@ pub fn run() {
             commands::submission::workflow_pause,
             commands::submission::workflow_resume,
             commands::submission::workflow_cancel,
+            commands::webhook::webhook_list,
+            commands::webhook::webhook_events,
+            commands::webhook::webhook_save,
+            commands::webhook::webhook_delete,
+            commands::webhook::webhook_test,
         ])
         .build(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -1,15 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::{File, OpenOptions};
 use std::io::{ErrorKind, Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
 use std::sync::{
   atomic::{AtomicBool, Ordering},
-  mpsc, Arc, Mutex,
+  Arc, Mutex,
 };
 use std::time::{Duration, Instant, SystemTime};
 
-use chrono::Utc;
+use chrono::{DateTime, Local, Utc};
 use reqwest::blocking::Client;
 use reqwest::header::{
   HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, REFERER, USER_AGENT,
@@ -21,19 +20,47 @@ use tokio_tungstenite::tungstenite::Message;
 use futures_util::{SinkExt, StreamExt};
 use url::Url;
 
+use crate::bandwidth::{record_usage, TransferClass};
 use crate::bilibili::client::BilibiliClient;
 use crate::commands::settings::{
-  load_download_settings_from_db, load_live_settings_from_db, LiveSettings,
+  apply_room_live_settings, load_download_settings_from_db, load_live_settings_from_db, LiveSettings,
+  DEFAULT_STREAM_CODEC_ORDER, DEFAULT_STREAM_FORMAT_ORDER, DEFAULT_STREAM_PROTOCOL_ORDER,
+};
+use crate::commands::submission::EditUploadState;
+use crate::config::default_download_dir;
+use crate::danmaku_ass::{
+  convert_danmaku_to_ass, danmaku_ass_path, danmaku_jsonl_path, parse_segment_start,
+  DanmakuAssOptions,
 };
-use crate::config::{default_download_dir, resolve_ffmpeg_path};
 use crate::db::Db;
 use crate::ffmpeg::run_ffmpeg;
+use crate::flv::{
+  is_audio_header_tag, is_video_header_tag, is_video_keyframe, parse_flv_timestamp,
+  FlvHeaderCache, FlvParsedItem, FlvStreamParser, TimestampFixer,
+};
+use crate::hls::{parse_playlist, resolve_uri, HlsPlaylist};
+use crate::live_session::{close_live_session, open_live_session, touch_live_session_title};
+use crate::live_submission::create_live_submission;
 use crate::login_store::{AuthInfo, LoginStore};
+use crate::record_schedule::RecordSchedule;
+use crate::storage::ensure_free_space;
 use crate::baidu_sync;
 use crate::utils::{append_log, now_rfc3339, sanitize_filename};
+use crate::webhook::{
+  emit_webhook_event, EVENT_AUTO_RECORD_START, EVENT_LIVE_REMUX_DONE, EVENT_RECORD_STOP,
+};
 
 pub struct LiveRuntime {
   records: Mutex<HashMap<String, LiveRecordHandle>>,
+  capped_sessions: Mutex<HashMap<String, String>>,
+  pending_segment_tasks: Mutex<HashSet<i64>>,
+  pending_submissions: Mutex<HashMap<String, PendingLiveSubmission>>,
+}
+
+struct PendingLiveSubmission {
+  session_id: i64,
+  title: String,
+  start_time: DateTime<Utc>,
 }
 
 pub struct LiveRecordHandle {
@@ -44,6 +71,8 @@ pub struct LiveRecordHandle {
   pub current_file: Arc<Mutex<String>>,
   pub start_time: String,
   pub start_date: String,
+  pub manual: bool,
+  pub session_id: Option<i64>,
 }
 
 pub struct LiveRecordInfo {
@@ -58,6 +87,7 @@ pub struct LiveContext {
   pub login_store: Arc<LoginStore>,
   pub app_log_path: Arc<PathBuf>,
   pub live_runtime: Arc<LiveRuntime>,
+  pub edit_upload_state: Arc<Mutex<EditUploadState>>,
 }
 
 #[derive(Clone)]
@@ -71,24 +101,27 @@ pub struct LiveRoomInfo {
   pub area_name: Option<String>,
   #[allow(dead_code)]
   pub parent_area_name: Option<String>,
+  pub live_time: Option<String>,
 }
 
 const INVALID_STREAM_TAG_LIMIT: usize = 300;
 const INVALID_STREAM_STALL_SECS: u64 = 10;
 const STREAM_URL_REFRESH_LEAD_SECS: u64 = 30;
 const MISSING_SEGMENT_WINDOW_SECS: u64 = 60;
-const TIMESTAMP_JUMP_THRESHOLD_MS: i64 = 500;
-const TIMESTAMP_AUDIO_FALLBACK_MS: i64 = 22;
-const TIMESTAMP_AUDIO_MIN_STEP_MS: i64 = 20;
-const TIMESTAMP_AUDIO_MAX_STEP_MS: i64 = 24;
-const TIMESTAMP_VIDEO_FALLBACK_MS: i64 = 33;
-const TIMESTAMP_VIDEO_MIN_STEP_MS: i64 = 15;
-const TIMESTAMP_VIDEO_MAX_STEP_MS: i64 = 50;
-const TIMESTAMP_MIN_STEP_MS: i64 = 1;
+const HLS_MAX_PLAYLIST_DEPTH: usize = 3;
+const HLS_PLAYLIST_RETRY_LIMIT: usize = 5;
+const HLS_SEGMENT_RETRY_LIMIT: u64 = 3;
+const HLS_SEGMENT_RETRY_BACKOFF_MS: u64 = 500;
+const HLS_SEQUENCE_RESET_GAP: u64 = 30;
+const HLS_MIN_POLL_SECS: f64 = 1.0;
+const HLS_MAX_POLL_SECS: f64 = 5.0;
 
 pub fn new_live_runtime() -> LiveRuntime {
   LiveRuntime {
     records: Mutex::new(HashMap::new()),
+    capped_sessions: Mutex::new(HashMap::new()),
+    pending_segment_tasks: Mutex::new(HashSet::new()),
+    pending_submissions: Mutex::new(HashMap::new()),
   }
 }
 
@@ -116,6 +149,80 @@ impl LiveRuntime {
     }
   }
 
+  pub fn auto_session_start(&self, room_id: &str) -> Option<DateTime<Utc>> {
+    let map = self.records.lock().ok()?;
+    let handle = map.get(room_id)?;
+    if handle.manual {
+      return None;
+    }
+    DateTime::parse_from_rfc3339(&handle.start_time)
+      .ok()
+      .map(|value| value.with_timezone(&Utc))
+  }
+
+  fn session_id(&self, room_id: &str) -> Option<i64> {
+    let map = self.records.lock().ok()?;
+    map.get(room_id)?.session_id
+  }
+
+  fn is_session_capped(&self, room_id: &str, live_time: &str) -> bool {
+    self
+      .capped_sessions
+      .lock()
+      .map(|map| map.get(room_id).is_some_and(|value| value == live_time))
+      .unwrap_or(false)
+  }
+
+  fn cap_session(&self, room_id: &str, live_time: &str) {
+    if let Ok(mut map) = self.capped_sessions.lock() {
+      map.insert(room_id.to_string(), live_time.to_string());
+    }
+  }
+
+  fn clear_capped_session(&self, room_id: &str) {
+    if let Ok(mut map) = self.capped_sessions.lock() {
+      map.remove(room_id);
+    }
+  }
+
+  fn begin_segment_task(&self, record_id: i64) {
+    if let Ok(mut set) = self.pending_segment_tasks.lock() {
+      set.insert(record_id);
+    }
+  }
+
+  fn end_segment_task(&self, record_id: i64) {
+    if let Ok(mut set) = self.pending_segment_tasks.lock() {
+      set.remove(&record_id);
+    }
+  }
+
+  fn has_pending_segment_task(&self, record_ids: &[i64]) -> bool {
+    self
+      .pending_segment_tasks
+      .lock()
+      .map(|set| record_ids.iter().any(|id| set.contains(id)))
+      .unwrap_or(false)
+  }
+
+  fn defer_submission(&self, room_id: &str, pending: PendingLiveSubmission) {
+    if let Ok(mut map) = self.pending_submissions.lock() {
+      map.insert(room_id.to_string(), pending);
+    }
+  }
+
+  fn take_pending_submission(&self, room_id: &str) -> Option<PendingLiveSubmission> {
+    self.pending_submissions.lock().ok()?.remove(room_id)
+  }
+
+  pub fn active_room_ids(&self) -> Vec<String> {
+    self
+      .records
+      .lock()
+      .map(|map| map.keys().cloned().collect())
+      .unwrap_or_default()
+  }
+
   pub fn stop(&self, room_id: &str) {
     if let Ok(map) = self.records.lock() {
       if let Some(handle) = map.get(room_id) {
@@ -125,6 +232,8 @@ impl LiveRuntime {
   }
 }
 
+const SEGMENT_TASK_WAIT_TIMEOUT_SECS: u64 = 30 * 60;
+const SEGMENT_TASK_POLL_SECS: u64 = 2;
 const STALE_RECORD_REMUX_MAX_AGE_SECS: u64 = 36 * 60 * 60;
 const STALE_RECORD_IDLE_SECS: u64 = 30 * 60;
 const STALE_RECORD_RECOVERY_INTERVAL_SECS: u64 = 10 * 60;
@@ -459,10 +568,30 @@ pub fn start_auto_record_loop(context: LiveContext) {
               let _ = update_anchor_status(&context.db, &room_id, info.live_status);
               let auto_record = load_room_auto_record(&context.db, &room_id).unwrap_or(true);
               let recording = context.live_runtime.is_recording(&room_id);
+              let block_reason = if info.live_status == 1 {
+                auto_record_block_reason(&context, &room_id, &info)
+              } else {
+                context.live_runtime.clear_capped_session(&room_id);
+                None
+              };
               if info.live_status == 1 && auto_record && !recording {
-                match start_recording(context.clone(), &room_id, info.clone(), settings.clone()) {
+                if let Some(reason) = block_reason {
+                  append_log(
+                    &context.app_log_path,
+                    &format!("auto_record_skip room={} reason={}", room_id, reason),
+                  );
+                  continue;
+                }
+                match start_recording(context.clone(), &room_id, info.clone(), settings.clone(), false) {
                   Ok(()) => {
                     append_log(&context.app_log_path, &format!("auto_record_start room={}", room_id));
+                    emit_webhook_event(
+                      &context.db,
+                      &context.app_log_path,
+                      EVENT_AUTO_RECORD_START,
+                      &format!("直播间 {} 开始录制: {}", room_id, info.title),
+                      serde_json::json!({ "roomId": room_id, "title": info.title }),
+                    );
                   }
                   Err(err) => {
                     append_log(
@@ -473,14 +602,37 @@ pub fn start_auto_record_loop(context: LiveContext) {
                 }
               } else if info.live_status != 1 && recording {
                 stop_recording(context.clone(), &room_id, "直播结束自动停止");
+              } else if info.live_status != 1 {
+                if let Some(pending) = context.live_runtime.take_pending_submission(&room_id) {
+                  let context = context.clone();
+                  let room_id = room_id.clone();
+                  tauri::async_runtime::spawn_blocking(move || {
+                    submit_live_session(&context, &room_id, pending);
+                  });
+                }
+              } else if recording && context.live_runtime.auto_session_start(&room_id).is_some() {
+                if let Some(reason) = block_reason {
+                  stop_recording(context.clone(), &room_id, &format!("录制计划限制({})", reason));
+                }
               }
-              if recording && settings.cutting_by_title {
-                if let Ok(mut map) = context.live_runtime.records.lock() {
-                  if let Some(handle) = map.get_mut(&room_id) {
-                    let mut last_title = handle.last_title.lock().unwrap_or_else(|e| e.into_inner());
-                    if *last_title != info.title {
-                      *last_title = info.title.clone();
-                      handle.title_split_flag.store(true, Ordering::SeqCst);
+              if recording {
+                let room_settings = apply_room_live_settings(&context.db, &room_id, &settings)
+                  .unwrap_or_else(|_| settings.clone());
+                let base_dir = resolve_record_base_dir(&context.db, &room_settings);
+                if let Err(err) = ensure_free_space(&context.db, &base_dir) {
+                  append_log(
+                    &context.app_log_path,
+                    &format!("record_low_disk_space room={} err={}", room_id, err),
+                  );
+                  stop_recording(context.clone(), &room_id, "磁盘空间不足自动停止");
+                } else if room_settings.cutting_by_title {
+                  if let Ok(mut map) = context.live_runtime.records.lock() {
+                    if let Some(handle) = map.get_mut(&room_id) {
+                      let mut last_title = handle.last_title.lock().unwrap_or_else(|e| e.into_inner());
+                      if *last_title != info.title {
+                        *last_title = info.title.clone();
+                        handle.title_split_flag.store(true, Ordering::SeqCst);
+                      }
                     }
                   }
                 }
@@ -502,6 +654,7 @@ pub fn start_recording(
   room_id: &str,
   room_info: LiveRoomInfo,
   settings: LiveSettings,
+  manual: bool,
 ) -> Result<(), String> {
   if context.live_runtime.is_recording(room_id) {
     return Ok(());
@@ -511,12 +664,44 @@ pub fn start_recording(
     return Err("当前未开播".to_string());
   }
 
+  let settings = apply_room_live_settings(&context.db, room_id, &settings).unwrap_or_else(|err| {
+    append_log(
+      &context.app_log_path,
+      &format!("live_room_settings_error room={} err={}", room_id, err),
+    );
+    settings
+  });
+  ensure_free_space(&context.db, &resolve_record_base_dir(&context.db, &settings))?;
   let nickname = load_anchor_nickname(&context.db, room_id).ok().flatten();
   let stop_flag = Arc::new(AtomicBool::new(false));
   let split_flag = Arc::new(AtomicBool::new(false));
   let title_split_flag = Arc::new(AtomicBool::new(false));
   let current_title = room_info.title.clone();
   let start_time = Utc::now();
+  let session_id = match open_live_session(
+    &context.db,
+    room_id,
+    room_info.live_time.as_deref(),
+    &room_info.title,
+  ) {
+    Ok(session_id) => Some(session_id),
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!("live_session_open_error room={} err={}", room_id, err),
+      );
+      None
+    }
+  };
+  if let Some(pending) = context.live_runtime.take_pending_submission(room_id) {
+    if Some(pending.session_id) != session_id {
+      let context = context.clone();
+      let room_id = room_id.to_string();
+      tauri::async_runtime::spawn_blocking(move || {
+        submit_live_session(&context, &room_id, pending);
+      });
+    }
+  }
   let handle = LiveRecordHandle {
     stop_flag: Arc::clone(&stop_flag),
     split_flag: Arc::clone(&split_flag),
@@ -525,6 +710,8 @@ pub fn start_recording(
     current_file: Arc::new(Mutex::new(String::new())),
     start_time: start_time.to_rfc3339(),
     start_date: start_time.format("%Y%m%d").to_string(),
+    manual,
+    session_id,
   };
 
   if let Ok(mut map) = context.live_runtime.records.lock() {
@@ -612,11 +799,108 @@ pub fn start_recording(
     if let Ok(mut map) = runtime.records.lock() {
       map.remove(&room_id_owned);
     }
+    if let Some(session_id) = session_id {
+      if let Err(err) = close_live_session(&context.db, session_id) {
+        append_log(
+          &context.app_log_path,
+          &format!("live_session_close_error room={} err={}", room_id_owned, err),
+        );
+      }
+    }
+    emit_webhook_event(
+      &context.db,
+      &context.app_log_path,
+      EVENT_RECORD_STOP,
+      &format!("直播间 {} 录制结束: {}", room_id_owned, current_room_info.title),
+      serde_json::json!({
+        "roomId": room_id_owned,
+        "title": current_room_info.title,
+        "retryCount": retry_count,
+      }),
+    );
+    let Some(session_id) = session_id else {
+      append_log(
+        &context.app_log_path,
+        &format!("live_auto_submit_skip room={} reason=no_session", room_id_owned),
+      );
+      return;
+    };
+    let pending = PendingLiveSubmission {
+      session_id,
+      title: current_room_info.title.clone(),
+      start_time,
+    };
+    let still_live = match tauri::async_runtime::block_on(fetch_room_info(
+      &context.bilibili,
+      &room_id_owned,
+    )) {
+      Ok(info) => info.live_status == 1,
+      Err(err) => {
+        append_log(
+          &context.app_log_path,
+          &format!("live_auto_submit_status_error room={} err={}", room_id_owned, err),
+        );
+        true
+      }
+    };
+    if still_live {
+      append_log(
+        &context.app_log_path,
+        &format!(
+          "live_auto_submit_defer room={} session_id={} reason=still_live",
+          room_id_owned, session_id
+        ),
+      );
+      runtime.defer_submission(&room_id_owned, pending);
+      return;
+    }
+    submit_live_session(&context, &room_id_owned, pending);
   });
 
   Ok(())
 }
 
+pub fn auto_record_block_reason(
+  context: &LiveContext,
+  room_id: &str,
+  info: &LiveRoomInfo,
+) -> Option<&'static str> {
+  let schedule = match load_room_record_schedule(&context.db, room_id) {
+    Ok(schedule) => schedule,
+    Err(err) => {
+      append_log(
+        &context.app_log_path,
+        &format!("record_schedule_invalid room={} err={}", room_id, err),
+      );
+      return None;
+    }
+  };
+  let live_time = info.live_time.clone().unwrap_or_default();
+  if context.live_runtime.is_session_capped(room_id, &live_time) {
+    return Some("max_duration");
+  }
+  if schedule.max_record_minutes > 0 {
+    if let Some(start) = context.live_runtime.auto_session_start(room_id) {
+      if Utc::now() - start >= chrono::Duration::minutes(schedule.max_record_minutes) {
+        context.live_runtime.cap_session(room_id, &live_time);
+        return Some("max_duration");
+      }
+    }
+  }
+  schedule.check(Local::now(), &info.title).err()
+}
+
+pub fn resolve_record_base_dir(db: &Db, settings: &LiveSettings) -> PathBuf {
+  if settings.record_path.trim().is_empty() {
+    let download_dir = load_download_settings_from_db(db)
+      .map(|settings| settings.download_path)
+      .unwrap_or_else(|_| default_download_dir().to_string_lossy().to_string());
+    PathBuf::from(download_dir).join("live_recordings")
+  } else {
+    PathBuf::from(settings.record_path.trim())
+  }
+}
+
 pub fn stop_recording(context: LiveContext, room_id: &str, reason: &str) {
   append_log(
     &context.app_log_path,
@@ -652,14 +936,7 @@ fn run_record_loop(
       settings.flv_fix_disable_on_annexb
     ),
   );
-  let base_dir = if settings.record_path.trim().is_empty() {
-    let download_dir = load_download_settings_from_db(&context.db)
-      .map(|settings| settings.download_path)
-      .unwrap_or_else(|_| default_download_dir().to_string_lossy().to_string());
-    PathBuf::from(download_dir).join("live_recordings")
-  } else {
-    PathBuf::from(settings.record_path.trim())
-  };
+  let base_dir = resolve_record_base_dir(&context.db, &settings);
   let _ = std::fs::create_dir_all(&base_dir);
 
   let stop_flag = {
@@ -732,9 +1009,10 @@ fn run_record_loop(
     ))
     .build()
     .map_err(|err| format!("Failed to build client: {}", err))?;
-  let auth = context.login_store.load_auth_info(&context.db).ok().flatten();
+  let auth = load_room_auth(&context, &room_id);
   let mut stream_urls: Vec<String> = Vec::new();
   let mut stream_url_index: usize = 0;
+  let mut stream_url_failures: usize = 0;
   let mut force_no_qn_until: Option<i64> = None;
 
   loop {
@@ -797,6 +1075,7 @@ fn run_record_loop(
         }
       };
       stream_url_index = 0;
+      stream_url_failures = 0;
     }
 
     let stream_url = match stream_urls.get(stream_url_index) {
@@ -824,23 +1103,26 @@ fn run_record_loop(
     }
 
     if is_hls_url(&stream_url) {
-      let hls_file_path = normalize_hls_path(&current_file_path);
-      update_current_file(&context, &room_id, &hls_file_path);
-      append_log(
-        &context.app_log_path,
-        &format!("stream_hls_detected room={} path={}", room_id, hls_file_path),
-      );
+      let session = HlsRecordSession {
+        context: &context,
+        client: &client,
+        auth: auth.as_ref(),
+        room_id: &room_id,
+        room_info: &room_info,
+        nickname: nickname.as_deref(),
+        settings: &settings,
+        base_dir: &base_dir,
+        record_start_date: &record_start_date,
+        stop_flag: &stop_flag,
+        split_flag: &split_flag,
+        title_split_flag: &title_split_flag,
+      };
       if let Err(err) = record_hls_stream(
-        &context,
-        &room_id,
-        &room_info,
-        nickname.as_deref(),
-        &current_title,
-        &hls_file_path,
-        segment_index,
-        &settings,
-        &stop_flag,
+        &session,
         &stream_url,
+        &mut segment_index,
+        &mut current_title,
+        &mut current_file_path,
       ) {
         append_log(
           &context.app_log_path,
@@ -890,6 +1172,16 @@ fn run_record_loop(
       Ok(resp) => resp,
       Err(err) => {
         append_log(&context.app_log_path, &format!("stream_connect_error room={} err={}", room_id, err));
+        if try_next_stream_url(
+          &mut stream_url_failures,
+          stream_urls.len(),
+          &settings,
+          context.app_log_path.as_ref(),
+          room_id.as_str(),
+          "connect_error",
+        ) {
+          continue;
+        }
         stream_urls.clear();
         std::thread::sleep(Duration::from_millis(settings.stream_retry_ms.max(1000) as u64));
         continue;
@@ -930,6 +1222,16 @@ fn run_record_loop(
         room_id.as_str(),
         "response_status",
       );
+      if try_next_stream_url(
+        &mut stream_url_failures,
+        stream_urls.len(),
+        &settings,
+        context.app_log_path.as_ref(),
+        room_id.as_str(),
+        "response_status",
+      ) {
+        continue;
+      }
       stream_urls.clear();
       std::thread::sleep(Duration::from_millis(settings.stream_retry_ms.max(1000) as u64));
       continue;
@@ -968,11 +1270,22 @@ fn run_record_loop(
         room_id.as_str(),
         "response_unexpected",
       );
+      if try_next_stream_url(
+        &mut stream_url_failures,
+        stream_urls.len(),
+        &settings,
+        context.app_log_path.as_ref(),
+        room_id.as_str(),
+        "response_unexpected",
+      ) {
+        continue;
+      }
       stream_urls.clear();
       std::thread::sleep(Duration::from_millis(settings.stream_retry_ms.max(1000) as u64));
       continue;
     }
 
+    stream_url_failures = 0;
     let mut buf = vec![0u8; 8192];
     let mut parser = FlvStreamParser::new();
     let mut cache = FlvHeaderCache::new();
@@ -1039,6 +1352,7 @@ fn run_record_loop(
         }
         Ok(n) => {
           missing_started_at = None;
+          record_usage(TransferClass::Recording, n as u64);
           let items = match parser.push(&buf[..n]) {
             Ok(items) => items,
             Err(err) => {
@@ -1075,7 +1389,7 @@ fn run_record_loop(
                     &room_info,
                     nickname.as_deref(),
                   )?;
-                  cache.write_preamble(&mut new_segment)?;
+                  new_segment.write(&cache.preamble()?)?;
                   timestamp_fixer.reset();
                   segment_start = Instant::now();
                   segment = Some(new_segment);
@@ -1169,7 +1483,7 @@ fn run_record_loop(
                       &room_info,
                       nickname.as_deref(),
                     )?;
-                    cache.write_preamble(&mut new_segment)?;
+                    new_segment.write(&cache.preamble()?)?;
                     timestamp_fixer.reset();
                     segment_start = Instant::now();
                     segment = Some(new_segment);
@@ -1307,565 +1621,111 @@ fn run_record_loop(
   Ok(())
 }
 
-struct FlvTag {
-  tag_type: u8,
-  bytes: Vec<u8>,
-  data_offset: usize,
-  data_len: usize,
-}
-
-impl FlvTag {
-  fn data(&self) -> &[u8] {
-    &self.bytes[self.data_offset..self.data_offset + self.data_len]
-  }
-}
-
-fn write_flv_timestamp(tag: &mut FlvTag, timestamp: u32) {
-  if tag.bytes.len() < 8 {
-    return;
-  }
-  tag.bytes[4] = ((timestamp >> 16) & 0xff) as u8;
-  tag.bytes[5] = ((timestamp >> 8) & 0xff) as u8;
-  tag.bytes[6] = (timestamp & 0xff) as u8;
-  tag.bytes[7] = ((timestamp >> 24) & 0xff) as u8;
-}
-
-fn is_video_keyframe(tag: &FlvTag) -> bool {
-  if tag.tag_type != 9 {
-    return false;
-  }
-  let data = tag.data();
-  if data.is_empty() {
-    return false;
-  }
-  let frame_type = data[0] >> 4;
-  frame_type == 1
-}
-
-fn is_audio_header_tag(data: &[u8]) -> bool {
-  if data.len() < 2 {
-    return false;
-  }
-  let sound_format = data[0] >> 4;
-  sound_format == 10 && data[1] == 0
-}
-
-fn is_video_header_tag(data: &[u8]) -> bool {
-  if data.len() < 2 {
-    return false;
-  }
-  let codec_id = data[0] & 0x0f;
-  let packet_type = data[1];
-  (codec_id == 7 || codec_id == 12) && packet_type == 0
+struct SegmentWriter {
+  db: Arc<Db>,
+  log_path: Arc<PathBuf>,
+  record_id: i64,
+  file_path: String,
+  file: File,
+  bytes_written: u64,
+  #[allow(dead_code)]
+  title: String,
+  metadata_path: Option<String>,
 }
 
-fn clamp_timestamp(value: i64) -> u32 {
-  if value <= 0 {
-    return 0;
-  }
-  if value > u32::MAX as i64 {
-    return u32::MAX;
+impl SegmentWriter {
+  fn write(&mut self, buf: &[u8]) -> Result<(), String> {
+    self.file.write_all(buf).map_err(|err| format!("写入失败: {}", err))?;
+    self.bytes_written += buf.len() as u64;
+    Ok(())
   }
-  value as u32
-}
-
-struct TimestampJumpInfo {
-  diff: i64,
-  original: i64,
-  fixed: i64,
-  offset: i64,
-}
-
-struct TimestampChannelState {
-  last_original: Option<i64>,
-  last_fixed: Option<i64>,
-  last_step: i64,
-  fallback: i64,
-  min_step: i64,
-  max_step: i64,
-}
 
-impl TimestampChannelState {
-  fn new(fallback: i64, min_step: i64, max_step: i64) -> Self {
-    Self {
-      last_original: None,
-      last_fixed: None,
-      last_step: fallback,
-      fallback,
-      min_step,
-      max_step,
+  fn finish(&mut self, status: &str, error: Option<&str>) -> Result<(), String> {
+    let end_time = now_rfc3339();
+    update_record_task(
+      &self.db,
+      self.record_id,
+      status,
+      Some(end_time.clone()),
+      self.bytes_written,
+      error,
+    )?;
+    if let Some(path) = self.metadata_path.as_ref() {
+      if let Err(err) = update_metadata_file(path, &end_time, self.bytes_written) {
+        append_log(
+          self.log_path.as_ref(),
+          &format!(
+            "record_metadata_update_failed record_id={} err={}",
+            self.record_id, err
+          ),
+        );
+      }
     }
+    Ok(())
   }
+}
 
-  fn reset(&mut self) {
-    self.last_original = None;
-    self.last_fixed = None;
-    self.last_step = self.fallback;
+fn open_segment(
+  context: &LiveContext,
+  room_id: &str,
+  file_path: &str,
+  title: &str,
+  segment_index: i64,
+  settings: &LiveSettings,
+  room_info: &LiveRoomInfo,
+  nickname: Option<&str>,
+) -> Result<SegmentWriter, String> {
+  if let Some(parent) = Path::new(file_path).parent() {
+    std::fs::create_dir_all(parent).map_err(|err| format!("创建目录失败: {}", err))?;
   }
 
-  fn update_step(&mut self, current: i64) -> i64 {
-    let step = match self.last_original {
-      Some(prev) => {
-        let diff = current - prev;
-        if diff >= self.min_step && diff <= self.max_step {
-          diff
-        } else {
-          self.fallback
-        }
-      }
-      None => self.fallback,
-    };
-    self.last_original = Some(current);
-    self.last_step = step;
-    step
-  }
+  let file = OpenOptions::new()
+    .create(true)
+    .write(true)
+    .truncate(true)
+    .open(file_path)
+    .map_err(|err| format!("创建文件失败: {}", err))?;
 
-  fn update_fixed(&mut self, fixed: i64) {
-    self.last_fixed = Some(fixed);
+  let session_id = context.live_runtime.session_id(room_id);
+  let record_id =
+    insert_record_task(&context.db, room_id, session_id, file_path, segment_index, title)?;
+  if let Some(session_id) = session_id {
+    let _ = touch_live_session_title(&context.db, session_id, title);
   }
+  let metadata_path = if settings.write_metadata {
+    Some(write_metadata_file(file_path, room_info, nickname, title)?)
+  } else {
+    None
+  };
+  Ok(SegmentWriter {
+    db: Arc::clone(&context.db),
+    log_path: Arc::clone(&context.app_log_path),
+    record_id,
+    file_path: file_path.to_string(),
+    file,
+    bytes_written: 0,
+    title: title.to_string(),
+    metadata_path,
+  })
 }
 
-struct TimestampFixer {
-  enabled: bool,
-  apply_fix: bool,
-  last_original: Option<i64>,
-  last_fixed: Option<i64>,
-  current_offset: i64,
-  next_target: i64,
-  audio: TimestampChannelState,
-  video: TimestampChannelState,
-}
-
-impl TimestampFixer {
-  fn new(enabled: bool, apply_fix: bool) -> Self {
-    Self {
-      enabled,
-      apply_fix,
-      last_original: None,
-      last_fixed: None,
-      current_offset: 0,
-      next_target: 0,
-      audio: TimestampChannelState::new(
-        TIMESTAMP_AUDIO_FALLBACK_MS,
-        TIMESTAMP_AUDIO_MIN_STEP_MS,
-        TIMESTAMP_AUDIO_MAX_STEP_MS,
-      ),
-      video: TimestampChannelState::new(
-        TIMESTAMP_VIDEO_FALLBACK_MS,
-        TIMESTAMP_VIDEO_MIN_STEP_MS,
-        TIMESTAMP_VIDEO_MAX_STEP_MS,
-      ),
-    }
-  }
-
-  fn reset(&mut self) {
-    self.last_original = None;
-    self.last_fixed = None;
-    self.current_offset = 0;
-    self.next_target = 0;
-    self.audio.reset();
-    self.video.reset();
-  }
-
-  fn fix_tag(&mut self, tag: &mut FlvTag, is_header: bool) -> Option<TimestampJumpInfo> {
-    if !self.enabled {
-      return None;
-    }
-    let original = parse_flv_timestamp(tag) as i64;
-    if !self.apply_fix {
-      if is_header {
-        return None;
-      }
-      let last_original = match self.last_original {
-        Some(value) => value,
-        None => {
-          self.last_original = Some(original);
-          self.last_fixed = Some(original);
-          return None;
-        }
-      };
-      let diff = original - last_original;
-      self.last_original = Some(original);
-      self.last_fixed = Some(original);
-      if diff < -TIMESTAMP_JUMP_THRESHOLD_MS || diff > TIMESTAMP_JUMP_THRESHOLD_MS {
-        return Some(TimestampJumpInfo {
-          diff,
-          original,
-          fixed: original,
-          offset: 0,
-        });
-      }
-      return None;
-    }
-    if is_header {
-      let stamp = if tag.tag_type == 18 {
-        self.next_target
-      } else {
-        self.last_fixed.unwrap_or(0)
-      };
-      write_flv_timestamp(tag, clamp_timestamp(stamp));
-      return None;
-    }
-
-    let step = match tag.tag_type {
-      8 => self.audio.update_step(original),
-      9 => self.video.update_step(original),
-      _ => TIMESTAMP_MIN_STEP_MS,
-    }
-    .max(TIMESTAMP_MIN_STEP_MS);
-
-    let last_original = match self.last_original {
-      Some(value) => value,
-      None => {
-        self.current_offset = original;
-        let fixed = 0;
-        self.last_original = Some(original);
-        self.last_fixed = Some(fixed);
-        match tag.tag_type {
-          8 => self.audio.update_fixed(fixed),
-          9 => self.video.update_fixed(fixed),
-          _ => {}
-        }
-        self.recalculate_next_target();
-        write_flv_timestamp(tag, fixed as u32);
-        return None;
-      }
-    };
-    let last_fixed = self.last_fixed.unwrap_or(0);
-    let diff = original - last_original;
-    let mut jump_info = None;
-
-    if diff < -TIMESTAMP_JUMP_THRESHOLD_MS || diff > TIMESTAMP_JUMP_THRESHOLD_MS {
-      self.current_offset = original - self.next_target;
-    }
-
-    let mut fixed = original - self.current_offset;
-    if fixed <= last_fixed {
-      fixed = last_fixed + step;
-      self.current_offset = original - fixed;
-    }
-    self.last_original = Some(original);
-    self.last_fixed = Some(fixed);
-    match tag.tag_type {
-      8 => self.audio.update_fixed(fixed),
-      9 => self.video.update_fixed(fixed),
-      _ => {}
-    }
-    self.recalculate_next_target();
-    write_flv_timestamp(tag, clamp_timestamp(fixed));
-    if diff < -TIMESTAMP_JUMP_THRESHOLD_MS || diff > TIMESTAMP_JUMP_THRESHOLD_MS {
-      jump_info = Some(TimestampJumpInfo {
-        diff,
-        original,
-        fixed,
-        offset: self.current_offset,
-      });
-    }
-    jump_info
-  }
-
-  fn recalculate_next_target(&mut self) {
-    let audio_next = self
-      .audio
-      .last_fixed
-      .map(|value| value + self.audio.last_step)
-      .unwrap_or(0);
-    let video_next = self
-      .video
-      .last_fixed
-      .map(|value| value + self.video.last_step)
-      .unwrap_or(0);
-    self.next_target = audio_next.max(video_next);
-  }
-}
-
-enum FlvParsedItem {
-  Header(Vec<u8>),
-  Tag(FlvTag),
-}
-
-struct FlvStreamParser {
-  buffer: Vec<u8>,
-  header_parsed: bool,
-}
-
-impl FlvStreamParser {
-  fn new() -> Self {
-    Self {
-      buffer: Vec::new(),
-      header_parsed: false,
-    }
-  }
-
-  fn push(&mut self, data: &[u8]) -> Result<Vec<FlvParsedItem>, String> {
-    if !data.is_empty() {
-      self.buffer.extend_from_slice(data);
-    }
-    let mut items = Vec::new();
-    let mut offset = 0;
-    if !self.header_parsed {
-      if self.buffer.len() < 3 {
-        return Ok(items);
-      }
-      if self.buffer[..3] != *b"FLV" {
-        return Err("FLV header mismatch".to_string());
-      }
-      if self.buffer.len() < 13 {
-        return Ok(items);
-      }
-      let header = self.buffer[offset..offset + 13].to_vec();
-      offset += 13;
-      self.header_parsed = true;
-      items.push(FlvParsedItem::Header(header));
-    }
-
-    loop {
-      if self.buffer.len().saturating_sub(offset) < 11 {
-        break;
-      }
-      let header_start = offset;
-      let data_size = read_u24_be(&self.buffer[header_start + 1..header_start + 4]);
-      let total = 11 + data_size + 4;
-      if self.buffer.len().saturating_sub(offset) < total {
-        break;
-      }
-      let bytes = self.buffer[offset..offset + total].to_vec();
-      let tag_type = bytes[0];
-      let data_offset = 11;
-      let data_len = data_size;
-      items.push(FlvParsedItem::Tag(FlvTag {
-        tag_type,
-        bytes,
-        data_offset,
-        data_len,
-      }));
-      offset += total;
-    }
-
-    if offset > 0 {
-      self.buffer.drain(0..offset);
-    }
-    Ok(items)
-  }
-}
-
-struct FlvHeaderCache {
-  header: Option<Vec<u8>>,
-  script_tag: Option<Vec<u8>>,
-  audio_header: Option<Vec<u8>>,
-  video_header: Option<Vec<u8>>,
-}
-
-impl FlvHeaderCache {
-  fn new() -> Self {
-    Self {
-      header: None,
-      script_tag: None,
-      audio_header: None,
-      video_header: None,
-    }
-  }
-
-  fn set_header(&mut self, header: Vec<u8>) {
-    self.header = Some(header);
-  }
-
-  fn has_header(&self) -> bool {
-    self.header.is_some()
-  }
-
-  fn update_from_tag(&mut self, tag: &FlvTag) {
-    match tag.tag_type {
-      18 => {
-        if self.script_tag.is_none() {
-          self.script_tag = Some(normalize_header_tag(&tag.bytes));
-        }
-      }
-      8 => {
-        if is_audio_header(tag.data(), self.audio_header.is_some()) {
-          self.audio_header = Some(normalize_header_tag(&tag.bytes));
-        }
-      }
-      9 => {
-        if is_video_header(tag.data(), self.video_header.is_some()) {
-          self.video_header = Some(normalize_header_tag(&tag.bytes));
-        }
-      }
-      _ => {}
-    }
-  }
-
-  fn write_preamble(&self, segment: &mut SegmentWriter) -> Result<(), String> {
-    let header = self
-      .header
-      .as_ref()
-      .ok_or_else(|| "缺少FLV头信息".to_string())?;
-    segment.write(header)?;
-    if let Some(tag) = self.script_tag.as_ref() {
-      segment.write(tag)?;
-    }
-    if let Some(tag) = self.video_header.as_ref() {
-      segment.write(tag)?;
-    }
-    if let Some(tag) = self.audio_header.as_ref() {
-      segment.write(tag)?;
-    }
-    Ok(())
-  }
-}
-
-fn read_u24_be(slice: &[u8]) -> usize {
-  if slice.len() < 3 {
-    return 0;
-  }
-  ((slice[0] as usize) << 16) | ((slice[1] as usize) << 8) | slice[2] as usize
-}
-
-fn parse_flv_timestamp(tag: &FlvTag) -> u32 {
-  if tag.bytes.len() < 8 {
-    return 0;
-  }
-  let ts = ((tag.bytes[7] as u32) << 24)
-    | ((tag.bytes[4] as u32) << 16)
-    | ((tag.bytes[5] as u32) << 8)
-    | (tag.bytes[6] as u32);
-  ts
-}
-
-fn normalize_header_tag(tag: &[u8]) -> Vec<u8> {
-  let mut normalized = tag.to_vec();
-  if normalized.len() >= 11 {
-    normalized[4] = 0;
-    normalized[5] = 0;
-    normalized[6] = 0;
-    normalized[7] = 0;
-  }
-  normalized
-}
-
-fn is_audio_header(data: &[u8], has_header: bool) -> bool {
-  if data.len() < 2 {
-    return false;
-  }
-  let sound_format = data[0] >> 4;
-  if sound_format == 10 {
-    data[1] == 0
-  } else {
-    !has_header
-  }
-}
-
-fn is_video_header(data: &[u8], has_header: bool) -> bool {
-  if data.len() < 2 {
-    return false;
-  }
-  let codec_id = data[0] & 0x0f;
-  let packet_type = data[1];
-  if codec_id == 7 || codec_id == 12 {
-    packet_type == 0
-  } else {
-    !has_header
-  }
-}
-
-struct SegmentWriter {
-  db: Arc<Db>,
-  log_path: Arc<PathBuf>,
-  record_id: i64,
-  file_path: String,
-  file: File,
-  bytes_written: u64,
-  #[allow(dead_code)]
-  title: String,
-  metadata_path: Option<String>,
-}
-
-impl SegmentWriter {
-  fn write(&mut self, buf: &[u8]) -> Result<(), String> {
-    self.file.write_all(buf).map_err(|err| format!("写入失败: {}", err))?;
-    self.bytes_written += buf.len() as u64;
-    Ok(())
-  }
-
-  fn finish(&mut self, status: &str, error: Option<&str>) -> Result<(), String> {
-    let end_time = now_rfc3339();
-    update_record_task(
-      &self.db,
-      self.record_id,
-      status,
-      Some(end_time.clone()),
-      self.bytes_written,
-      error,
-    )?;
-    if let Some(path) = self.metadata_path.as_ref() {
-      if let Err(err) = update_metadata_file(path, &end_time, self.bytes_written) {
-        append_log(
-          self.log_path.as_ref(),
-          &format!(
-            "record_metadata_update_failed record_id={} err={}",
-            self.record_id, err
-          ),
-        );
-      }
-    }
-    Ok(())
-  }
-}
-
-fn open_segment(
-  context: &LiveContext,
-  room_id: &str,
-  file_path: &str,
-  title: &str,
-  segment_index: i64,
-  settings: &LiveSettings,
-  room_info: &LiveRoomInfo,
-  nickname: Option<&str>,
-) -> Result<SegmentWriter, String> {
-  if let Some(parent) = Path::new(file_path).parent() {
-    std::fs::create_dir_all(parent).map_err(|err| format!("创建目录失败: {}", err))?;
-  }
-
-  let file = OpenOptions::new()
-    .create(true)
-    .write(true)
-    .truncate(true)
-    .open(file_path)
-    .map_err(|err| format!("创建文件失败: {}", err))?;
-
-  let record_id = insert_record_task(&context.db, room_id, file_path, segment_index, title)?;
-  let metadata_path = if settings.write_metadata {
-    Some(write_metadata_file(file_path, room_info, nickname, title)?)
-  } else {
-    None
-  };
-  Ok(SegmentWriter {
-    db: Arc::clone(&context.db),
-    log_path: Arc::clone(&context.app_log_path),
-    record_id,
-    file_path: file_path.to_string(),
-    file,
-    bytes_written: 0,
-    title: title.to_string(),
-    metadata_path,
-  })
-}
-
-fn spawn_segment_remux(context: LiveContext, record_id: i64, file_path: String) {
-  let source_path = PathBuf::from(file_path);
-  let ext = source_path
-    .extension()
-    .and_then(|value| value.to_str())
-    .unwrap_or("")
-    .to_string();
-  if !ext.eq_ignore_ascii_case("flv") {
-    return;
+fn spawn_segment_remux(context: LiveContext, record_id: i64, file_path: String) {
+  let source_path = PathBuf::from(file_path);
+  let ext = source_path
+    .extension()
+    .and_then(|value| value.to_str())
+    .unwrap_or("")
+    .to_string();
+  if !ext.eq_ignore_ascii_case("flv") {
+    return;
   }
   let target_path = source_path.with_extension("mp4");
   let source = source_path.to_string_lossy().to_string();
   let target = target_path.to_string_lossy().to_string();
   let log_path = context.app_log_path.clone();
   let db = context.db.clone();
+  let runtime = Arc::clone(&context.live_runtime);
+  runtime.begin_segment_task(record_id);
   tauri::async_runtime::spawn(async move {
     append_log(
       log_path.as_ref(),
@@ -1901,6 +1761,25 @@ fn spawn_segment_remux(context: LiveContext, record_id: i64, file_path: String)
           log_path.as_ref(),
           &format!("live_remux_done record_id={} status=ok", record_id),
         );
+        emit_webhook_event(
+          &db,
+          log_path.as_ref(),
+          EVENT_LIVE_REMUX_DONE,
+          &format!("录制分段转封装完成: {}", target),
+          serde_json::json!({
+            "recordId": record_id,
+            "status": "ok",
+            "filePath": target,
+            "fileSize": file_size,
+          }),
+        );
+        let ass_db = db.clone();
+        let ass_log_path = log_path.clone();
+        let ass_target = target.clone();
+        let _ = tauri::async_runtime::spawn_blocking(move || {
+          generate_segment_danmaku_ass(&ass_db, ass_log_path.as_ref(), record_id, &ass_target)
+        })
+        .await;
         if let Err(err) = baidu_sync::enqueue_live_sync(&db, log_path.as_ref(), record_id) {
           append_log(
             log_path.as_ref(),
@@ -1908,25 +1787,150 @@ fn spawn_segment_remux(context: LiveContext, record_id: i64, file_path: String)
           );
         }
       }
-      Ok(Err(err)) => {
+      Ok(Err(err)) | Err(err) => {
         append_log(
           log_path.as_ref(),
           &format!("live_remux_done record_id={} status=err err={}", record_id, err),
         );
-      }
-      Err(err) => {
-        append_log(
+        emit_webhook_event(
+          &db,
           log_path.as_ref(),
-          &format!("live_remux_done record_id={} status=err err={}", record_id, err),
+          EVENT_LIVE_REMUX_DONE,
+          &format!("录制分段转封装失败: {}", source),
+          serde_json::json!({
+            "recordId": record_id,
+            "status": "error",
+            "filePath": source,
+            "error": err,
+          }),
         );
       }
     }
+    runtime.end_segment_task(record_id);
   });
 }
 
+fn spawn_segment_finalize(context: LiveContext, record_id: i64, file_path: String) {
+  let log_path = context.app_log_path.clone();
+  let db = context.db.clone();
+  let runtime = Arc::clone(&context.live_runtime);
+  runtime.begin_segment_task(record_id);
+  tauri::async_runtime::spawn(async move {
+    let ass_db = db.clone();
+    let ass_log_path = log_path.clone();
+    let _ = tauri::async_runtime::spawn_blocking(move || {
+      generate_segment_danmaku_ass(&ass_db, ass_log_path.as_ref(), record_id, &file_path)
+    })
+    .await;
+    if let Err(err) = baidu_sync::enqueue_live_sync(&db, log_path.as_ref(), record_id) {
+      append_log(
+        log_path.as_ref(),
+        &format!("baidu_sync_enqueue_fail record_id={} err={}", record_id, err),
+      );
+    }
+    runtime.end_segment_task(record_id);
+  });
+}
+
+fn submit_live_session(context: &LiveContext, room_id: &str, pending: PendingLiveSubmission) {
+  wait_for_session_segment_tasks(context, pending.session_id);
+  if let Err(err) = create_live_submission(
+    context,
+    room_id,
+    pending.session_id,
+    &pending.title,
+    pending.start_time,
+  ) {
+    append_log(
+      &context.app_log_path,
+      &format!("live_auto_submit_error room={} err={}", room_id, err),
+    );
+  }
+}
+
+fn wait_for_session_segment_tasks(context: &LiveContext, session_id: i64) {
+  let record_ids = context
+    .db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare("SELECT id FROM live_record_task WHERE session_id = ?1")?;
+      let ids = stmt
+        .query_map([session_id], |row| row.get::<_, i64>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+      Ok(ids)
+    })
+    .unwrap_or_default();
+  let started_at = Instant::now();
+  while context.live_runtime.has_pending_segment_task(&record_ids) {
+    if started_at.elapsed().as_secs() >= SEGMENT_TASK_WAIT_TIMEOUT_SECS {
+      append_log(
+        &context.app_log_path,
+        &format!("live_segment_task_wait_timeout session_id={}", session_id),
+      );
+      return;
+    }
+    std::thread::sleep(Duration::from_secs(SEGMENT_TASK_POLL_SECS));
+  }
+}
+
+fn generate_segment_danmaku_ass(db: &Db, log_path: &Path, record_id: i64, media_path: &str) {
+  let media_path = Path::new(media_path);
+  let jsonl_path = danmaku_jsonl_path(media_path);
+  if !jsonl_path.exists() {
+    return;
+  }
+  let start_time = db
+    .with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT start_time FROM live_record_task WHERE id = ?1",
+          [record_id],
+          |row| row.get::<_, String>(0),
+        )
+        .optional()
+    })
+    .ok()
+    .flatten();
+  let segment_start = match start_time.as_deref().and_then(parse_segment_start) {
+    Some(value) => value,
+    None => {
+      append_log(
+        log_path,
+        &format!("live_danmaku_ass_skip record_id={} reason=start_time_missing", record_id),
+      );
+      return;
+    }
+  };
+  let output_path = danmaku_ass_path(media_path);
+  match convert_danmaku_to_ass(
+    &jsonl_path,
+    &output_path,
+    segment_start,
+    None,
+    &DanmakuAssOptions::default(),
+  ) {
+    Ok(summary) => append_log(
+      log_path,
+      &format!(
+        "live_danmaku_ass_done record_id={} path={} scroll={} superchat={} gift={} dropped={}",
+        record_id,
+        summary.output_path,
+        summary.scroll_count,
+        summary.superchat_count,
+        summary.gift_count,
+        summary.dropped_count
+      ),
+    ),
+    Err(err) => append_log(
+      log_path,
+      &format!("live_danmaku_ass_fail record_id={} err={}", record_id, err),
+    ),
+  }
+}
+
 fn insert_record_task(
   db: &Db,
   room_id: &str,
+  session_id: Option<i64>,
   file_path: &str,
   segment_index: i64,
   title: &str,
@@ -1934,9 +1938,9 @@ fn insert_record_task(
   let now = now_rfc3339();
   db.with_conn(|conn| {
     conn.execute(
-      "INSERT INTO live_record_task (room_id, status, file_path, segment_index, start_time, title, create_time, update_time) \
-       VALUES (?1, 'RECORDING', ?2, ?3, ?4, ?5, ?6, ?7)",
-      (room_id, file_path, segment_index, &now, title, &now, &now),
+      "INSERT INTO live_record_task (room_id, session_id, status, file_path, segment_index, start_time, title, create_time, update_time) \
+       VALUES (?1, ?2, 'RECORDING', ?3, ?4, ?5, ?6, ?7, ?8)",
+      (room_id, session_id, file_path, segment_index, &now, title, &now, &now),
     )?;
     Ok(conn.last_insert_rowid())
   })
@@ -2019,6 +2023,56 @@ fn load_room_auto_record(db: &Db, room_id: &str) -> Result<bool, String> {
   .map_err(|err| err.to_string())
 }
 
+fn load_room_record_schedule(db: &Db, room_id: &str) -> Result<RecordSchedule, String> {
+  let row = db
+    .with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT record_weekdays, record_time_windows, max_record_minutes, title_regex \
+           FROM live_room_settings WHERE room_id = ?1",
+          [room_id],
+          |row| {
+            Ok((
+              row.get::<_, Option<String>>(0)?,
+              row.get::<_, Option<String>>(1)?,
+              row.get::<_, Option<i64>>(2)?,
+              row.get::<_, Option<String>>(3)?,
+            ))
+          },
+        )
+        .optional()
+    })
+    .map_err(|err| err.to_string())?;
+  let (weekdays, windows, max_minutes, title_regex) = row.unwrap_or_default();
+  RecordSchedule::parse(
+    weekdays.as_deref(),
+    windows.as_deref(),
+    max_minutes,
+    title_regex.as_deref(),
+  )
+}
+
+fn load_room_account_user_id(db: &Db, room_id: &str) -> Result<Option<i64>, String> {
+  db.with_conn(|conn| {
+    conn
+      .query_row(
+        "SELECT account_user_id FROM live_room_settings WHERE room_id = ?1",
+        [room_id],
+        |row| row.get::<_, Option<i64>>(0),
+      )
+      .optional()
+      .map(|value| value.flatten())
+  })
+  .map_err(|err| err.to_string())
+}
+
+fn load_room_auth(context: &LiveContext, room_id: &str) -> Option<AuthInfo> {
+  let account_user_id = load_room_account_user_id(&context.db, room_id).unwrap_or(None);
+  context
+    .login_store
+    .load_auth_info_or_default(&context.db, account_user_id, &context.app_log_path)
+}
+
 fn update_anchor_status(db: &Db, room_id: &str, live_status: i64) -> Result<(), String> {
   let now = now_rfc3339();
   db.with_conn(|conn| {
@@ -2205,6 +2259,202 @@ fn fetch_stream_urls(
   settings: &LiveSettings,
   auth: Option<&AuthInfo>,
   with_quality: bool,
+) -> Result<Vec<String>, String> {
+  let mut urls = match fetch_play_info_urls(client, room_id, settings, auth, with_quality) {
+    Ok(urls) => urls,
+    Err(play_info_err) => fetch_legacy_stream_urls(client, room_id, settings, auth, with_quality)
+      .map_err(|err| format!("{}; 旧版接口: {}", play_info_err, err))?,
+  };
+  if !settings.stream_cdn_failover {
+    urls.truncate(1);
+  }
+  Ok(urls)
+}
+
+struct StreamCandidate {
+  url: String,
+  protocol_rank: usize,
+  format_rank: usize,
+  codec_rank: usize,
+  cdn_rank: usize,
+}
+
+fn fetch_play_info_urls(
+  client: &BilibiliClient,
+  room_id: &str,
+  settings: &LiveSettings,
+  auth: Option<&AuthInfo>,
+  with_quality: bool,
+) -> Result<Vec<String>, String> {
+  let protocols = parse_preference_list(&settings.stream_protocol_order, DEFAULT_STREAM_PROTOCOL_ORDER);
+  let formats = parse_preference_list(&settings.stream_format_order, DEFAULT_STREAM_FORMAT_ORDER);
+  let codecs = parse_preference_list(&settings.stream_codec_order, DEFAULT_STREAM_CODEC_ORDER);
+  let cdn_order = parse_preference_list(&settings.stream_cdn_order, "");
+  let protocol_ids = protocols
+    .iter()
+    .filter_map(|name| match name.as_str() {
+      "http_stream" => Some("0"),
+      "http_hls" => Some("1"),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+  let format_ids = formats
+    .iter()
+    .filter_map(|name| match name.as_str() {
+      "flv" => Some("0"),
+      "ts" => Some("1"),
+      "fmp4" => Some("2"),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+  let codec_ids = codecs
+    .iter()
+    .filter_map(|name| match name.as_str() {
+      "avc" => Some("0"),
+      "hevc" => Some("1"),
+      _ => None,
+    })
+    .collect::<Vec<_>>();
+  let mut params = vec![
+    ("room_id".to_string(), room_id.to_string()),
+    ("protocol".to_string(), protocol_ids.join(",")),
+    ("format".to_string(), format_ids.join(",")),
+    ("codec".to_string(), codec_ids.join(",")),
+    ("platform".to_string(), "web".to_string()),
+    ("ptype".to_string(), "8".to_string()),
+    ("dolby".to_string(), "5".to_string()),
+    ("panorama".to_string(), "1".to_string()),
+  ];
+  if with_quality {
+    let qn = parse_quality(&settings.recording_quality);
+    params.push(("qn".to_string(), qn.to_string()));
+  }
+
+  let data = tauri::async_runtime::block_on(client.get_json(
+    "https://api.live.bilibili.com/xlive/web-room/v2/index/getRoomPlayInfo",
+    &params,
+    auth,
+    false,
+  ))?;
+  let streams = data
+    .get("playurl_info")
+    .and_then(|value| value.get("playurl"))
+    .and_then(|value| value.get("stream"))
+    .and_then(|value| value.as_array())
+    .ok_or("缺少直播流信息")?;
+
+  let mut candidates = Vec::new();
+  for stream in streams {
+    let protocol_name = stream
+      .get("protocol_name")
+      .and_then(|value| value.as_str())
+      .unwrap_or_default();
+    let Some(protocol_rank) = protocols.iter().position(|name| name == protocol_name) else {
+      continue;
+    };
+    let stream_formats = stream
+      .get("format")
+      .and_then(|value| value.as_array())
+      .cloned()
+      .unwrap_or_default();
+    for format in stream_formats {
+      let format_name = format
+        .get("format_name")
+        .and_then(|value| value.as_str())
+        .unwrap_or_default();
+      let Some(format_rank) = formats.iter().position(|name| name == format_name) else {
+        continue;
+      };
+      let format_codecs = format
+        .get("codec")
+        .and_then(|value| value.as_array())
+        .cloned()
+        .unwrap_or_default();
+      for codec in format_codecs {
+        let codec_name = codec
+          .get("codec_name")
+          .and_then(|value| value.as_str())
+          .unwrap_or_default();
+        let Some(codec_rank) = codecs.iter().position(|name| name == codec_name) else {
+          continue;
+        };
+        let base_url = codec
+          .get("base_url")
+          .and_then(|value| value.as_str())
+          .unwrap_or_default();
+        if base_url.is_empty() {
+          continue;
+        }
+        let url_infos = codec
+          .get("url_info")
+          .and_then(|value| value.as_array())
+          .cloned()
+          .unwrap_or_default();
+        for url_info in url_infos {
+          let host = url_info
+            .get("host")
+            .and_then(|value| value.as_str())
+            .unwrap_or_default();
+          if host.is_empty() {
+            continue;
+          }
+          let extra = url_info
+            .get("extra")
+            .and_then(|value| value.as_str())
+            .unwrap_or_default();
+          let cdn_rank = cdn_order
+            .iter()
+            .position(|keyword| host.contains(keyword.as_str()))
+            .unwrap_or(cdn_order.len());
+          candidates.push(StreamCandidate {
+            url: format!("{}{}{}", host, base_url, extra),
+            protocol_rank,
+            format_rank,
+            codec_rank,
+            cdn_rank,
+          });
+        }
+      }
+    }
+  }
+  candidates.sort_by_key(|candidate| {
+    (
+      candidate.protocol_rank,
+      candidate.format_rank,
+      candidate.codec_rank,
+      candidate.cdn_rank,
+    )
+  });
+  let mut urls: Vec<String> = Vec::new();
+  for candidate in candidates {
+    if !urls.contains(&candidate.url) {
+      urls.push(candidate.url);
+    }
+  }
+  if urls.is_empty() {
+    return Err("没有符合偏好的直播流".to_string());
+  }
+  Ok(urls)
+}
+
+fn parse_preference_list(value: &str, fallback: &str) -> Vec<String> {
+  let list = value
+    .split(',')
+    .map(|item| item.trim().to_ascii_lowercase())
+    .filter(|item| !item.is_empty())
+    .collect::<Vec<_>>();
+  if list.is_empty() && !fallback.is_empty() {
+    return parse_preference_list(fallback, "");
+  }
+  list
+}
+
+fn fetch_legacy_stream_urls(
+  client: &BilibiliClient,
+  room_id: &str,
+  settings: &LiveSettings,
+  auth: Option<&AuthInfo>,
+  with_quality: bool,
 ) -> Result<Vec<String>, String> {
   let mut params = vec![
     ("cid".to_string(), room_id.to_string()),
@@ -2244,168 +2494,364 @@ fn is_hls_url(url: &str) -> bool {
   url.contains(".m3u8")
 }
 
-fn normalize_hls_path(path: &str) -> String {
+fn normalize_hls_path(path: &str, is_fmp4: bool) -> String {
   let mut target = PathBuf::from(path);
-  let ext = target
-    .extension()
-    .and_then(|value| value.to_str())
-    .unwrap_or("")
-    .to_ascii_lowercase();
-  if ext == "ts" || ext == "m4s" || ext == "mp4" {
-    return target.to_string_lossy().to_string();
-  }
-  target.set_extension("ts");
+  target.set_extension(if is_fmp4 { "mp4" } else { "ts" });
   target.to_string_lossy().to_string()
 }
 
-fn record_hls_stream(
-  context: &LiveContext,
-  room_id: &str,
-  room_info: &LiveRoomInfo,
-  nickname: Option<&str>,
-  title: &str,
-  file_path: &str,
-  segment_index: i64,
-  settings: &LiveSettings,
-  stop_flag: &Arc<AtomicBool>,
-  stream_url: &str,
-) -> Result<(), String> {
-  if let Some(parent) = Path::new(file_path).parent() {
-    std::fs::create_dir_all(parent).map_err(|err| format!("创建目录失败: {}", err))?;
+struct HlsRecordSession<'a> {
+  context: &'a LiveContext,
+  client: &'a Client,
+  auth: Option<&'a AuthInfo>,
+  room_id: &'a str,
+  room_info: &'a LiveRoomInfo,
+  nickname: Option<&'a str>,
+  settings: &'a LiveSettings,
+  base_dir: &'a Path,
+  record_start_date: &'a str,
+  stop_flag: &'a AtomicBool,
+  split_flag: &'a AtomicBool,
+  title_split_flag: &'a AtomicBool,
+}
+
+impl HlsRecordSession<'_> {
+  fn fetch(&self, url: &str) -> Result<Vec<u8>, String> {
+    let mut request = self
+      .client
+      .get(url)
+      .timeout(Duration::from_millis(
+        self.settings.stream_read_timeout_ms.max(1000) as u64,
+      ))
+      .header(
+        USER_AGENT,
+        HeaderValue::from_static("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36"),
+      );
+    let referer_value = format!("https://live.bilibili.com/{}", self.room_info.room_id);
+    if let Ok(value) = HeaderValue::from_str(&referer_value) {
+      request = request.header(REFERER, value);
+    }
+    if let Some(auth) = self.auth {
+      if let Ok(value) = HeaderValue::from_str(&auth.cookie) {
+        request = request.header("Cookie", value);
+      }
+    }
+    let response = request.send().map_err(|err| format!("请求失败: {}", err))?;
+    if !response.status().is_success() {
+      return Err(format!("请求失败: HTTP {}", response.status().as_u16()));
+    }
+    let bytes = response
+      .bytes()
+      .map_err(|err| format!("读取响应失败: {}", err))?;
+    record_usage(TransferClass::Recording, bytes.len() as u64);
+    Ok(bytes.to_vec())
   }
 
-  let record_id = insert_record_task(&context.db, room_id, file_path, segment_index, title)?;
-  let metadata_path = if settings.write_metadata {
-    Some(write_metadata_file(file_path, room_info, nickname, title)?)
-  } else {
-    None
-  };
+  fn fetch_segment(&self, url: &str, sequence: u64) -> Result<Vec<u8>, String> {
+    let mut attempt = 1;
+    loop {
+      match self.fetch(url) {
+        Ok(data) => return Ok(data),
+        Err(err) => {
+          append_log(
+            &self.context.app_log_path,
+            &format!(
+              "stream_hls_segment_error room={} sequence={} attempt={} err={}",
+              self.room_id, sequence, attempt, err
+            ),
+          );
+          if attempt >= HLS_SEGMENT_RETRY_LIMIT || self.stop_flag.load(Ordering::SeqCst) {
+            return Err(err);
+          }
+        }
+      }
+      self.sleep(Duration::from_millis(HLS_SEGMENT_RETRY_BACKOFF_MS * attempt));
+      attempt += 1;
+    }
+  }
 
-  let referer_value = format!("Referer:https://live.bilibili.com/{}\r\n", room_info.room_id);
-  let args = vec![
-    "-hide_banner".to_string(),
-    "-loglevel".to_string(),
-    "error".to_string(),
-    "-rw_timeout".to_string(),
-    "10000000".to_string(),
-    "-timeout".to_string(),
-    "10000000".to_string(),
-    "-reconnect".to_string(),
-    "1".to_string(),
-    "-reconnect_streamed".to_string(),
-    "1".to_string(),
-    "-reconnect_delay_max".to_string(),
-    "3".to_string(),
-    "-user_agent".to_string(),
-    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36".to_string(),
-    "-headers".to_string(),
-    referer_value,
-    "-i".to_string(),
-    stream_url.to_string(),
-    "-c".to_string(),
-    "copy".to_string(),
-    "-f".to_string(),
-    "mpegts".to_string(),
-    file_path.to_string(),
-  ];
+  fn fetch_playlist(&self, url: &str) -> Result<(String, HlsPlaylist), String> {
+    let mut playlist_url = url.to_string();
+    for _ in 0..HLS_MAX_PLAYLIST_DEPTH {
+      let body = self.fetch(&playlist_url)?;
+      let playlist = parse_playlist(&String::from_utf8_lossy(&body))?;
+      if let Some(variant) = playlist.variants.first() {
+        playlist_url = resolve_uri(&playlist_url, variant)?;
+        continue;
+      }
+      return Ok((playlist_url, playlist));
+    }
+    Err("HLS 播放列表嵌套过深".to_string())
+  }
 
-  let mut child = Command::new(resolve_ffmpeg_path())
-    .args(&args)
-    .stdin(Stdio::piped())
-    .stderr(Stdio::piped())
-    .spawn()
-    .map_err(|err| format!("启动FFmpeg失败: {}", err))?;
-
-  let stderr = child.stderr.take();
-  let (stderr_tx, stderr_rx) = mpsc::channel();
-  std::thread::spawn(move || {
-    if let Some(mut stderr) = stderr {
-      let mut buffer = String::new();
-      let _ = stderr.read_to_string(&mut buffer);
-      let _ = stderr_tx.send(buffer);
+  fn sleep(&self, duration: Duration) {
+    let deadline = Instant::now() + duration;
+    while Instant::now() < deadline && !self.stop_flag.load(Ordering::SeqCst) {
+      std::thread::sleep(Duration::from_millis(200));
     }
-  });
+  }
+}
 
-  let mut stdin = child.stdin.take();
-  #[allow(unused_assignments)]
-  let mut exit_status = None;
-  loop {
-    if stop_flag.load(Ordering::SeqCst) {
-      if let Some(mut input) = stdin.take() {
-        let _ = input.write_all(b"q");
+fn record_hls_stream(
+  session: &HlsRecordSession,
+  stream_url: &str,
+  segment_index: &mut i64,
+  current_title: &mut String,
+  current_file_path: &mut String,
+) -> Result<(), String> {
+  let context = session.context;
+  let settings = session.settings;
+  let room_id = session.room_id;
+  let (mut playlist_url, mut playlist) = session.fetch_playlist(stream_url)?;
+  let is_fmp4 = playlist.is_fmp4();
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "stream_hls_detected room={} format={}",
+      room_id,
+      if is_fmp4 { "fmp4" } else { "ts" }
+    ),
+  );
+
+  let title_split_min = settings.title_split_min_seconds.max(0) as u64;
+  let stall_limit = Duration::from_millis(settings.stream_read_timeout_ms.max(1000) as u64)
+    .max(Duration::from_secs_f64(playlist.target_duration.max(1.0) * 3.0));
+  let mut writer: Option<SegmentWriter> = None;
+  let mut segment_start = Instant::now();
+  let mut pending_title: Option<String> = None;
+  let mut init_uri: Option<String> = None;
+  let mut init_data: Option<Vec<u8>> = None;
+  let mut last_sequence: Option<u64> = None;
+  let mut last_progress_at = Instant::now();
+  let mut playlist_failures: usize = 0;
+
+  let result: Result<&str, String> = 'record: loop {
+    if let (Some(last), Some(newest)) = (last_sequence, playlist.last_sequence()) {
+      if newest + HLS_SEQUENCE_RESET_GAP < last {
+        append_log(
+          &context.app_log_path,
+          &format!("stream_hls_sequence_reset room={} last={} newest={}", room_id, last, newest),
+        );
+        last_sequence = None;
       }
     }
 
-    match child.try_wait() {
-      Ok(Some(status)) => {
-        exit_status = Some(status);
-        break;
+    for segment in &playlist.segments {
+      if session.stop_flag.load(Ordering::SeqCst) {
+        break 'record Ok("STOPPED");
       }
-      Ok(None) => {}
-      Err(err) => {
-        return Err(format!("FFmpeg运行失败: {}", err));
+      if last_sequence.is_some_and(|last| segment.sequence <= last) {
+        continue;
       }
-    }
 
-    std::thread::sleep(Duration::from_millis(500));
-  }
+      let mut split_reason: Option<&str> = None;
+      if let Some(last) = last_sequence {
+        if segment.sequence > last + 1 {
+          append_log(
+            &context.app_log_path,
+            &format!(
+              "stream_hls_gap room={} last={} next={} missing={}",
+              room_id,
+              last,
+              segment.sequence,
+              segment.sequence - last - 1
+            ),
+          );
+          if settings.flv_fix_split_on_missing {
+            split_reason = Some("missing");
+          }
+        }
+      }
+      if is_fmp4 {
+        let Some(map_uri) = segment.map_uri.as_deref() else {
+          break 'record Err("fMP4 分片缺少初始化段".to_string());
+        };
+        if init_data.is_none() || segment.discontinuity || init_uri.as_deref() != Some(map_uri) {
+          let data = match resolve_uri(&playlist_url, map_uri).and_then(|url| session.fetch(&url)) {
+            Ok(data) => data,
+            Err(err) => break 'record Err(format!("下载初始化段失败: {}", err)),
+          };
+          if init_data.as_ref().is_some_and(|prev| prev != &data) {
+            split_reason = Some("init_changed");
+          }
+          init_uri = Some(map_uri.to_string());
+          init_data = Some(data);
+        }
+      } else if segment.discontinuity && writer.is_some() {
+        split_reason = Some("discontinuity");
+      }
 
-  let status = match exit_status {
-    Some(status) => status,
-    None => child
-      .wait()
-      .map_err(|err| format!("等待FFmpeg退出失败: {}", err))?,
-  };
-  let stderr_output = stderr_rx.recv_timeout(Duration::from_secs(1)).unwrap_or_default();
+      let mut pending_split = split_reason.is_some();
+      if let Some(reason) = split_reason {
+        append_log(
+          &context.app_log_path,
+          &format!("stream_hls_split room={} reason={} sequence={}", room_id, reason, segment.sequence),
+        );
+      }
+      let request_split = session.split_flag.swap(false, Ordering::SeqCst);
+      let title_split_requested = session.title_split_flag.swap(false, Ordering::SeqCst);
+      if title_split_requested {
+        let latest_title = load_current_title(context, room_id, current_title);
+        if latest_title != *current_title {
+          pending_title = Some(latest_title);
+          if title_split_min == 0 || segment_start.elapsed().as_secs() >= title_split_min {
+            pending_split = true;
+          }
+        }
+      }
+      if request_split {
+        if pending_title.is_none() && settings.cutting_by_title {
+          let latest_title = load_current_title(context, room_id, current_title);
+          if latest_title != *current_title {
+            pending_title = Some(latest_title);
+          }
+        }
+        pending_split = true;
+      }
+      if pending_title.is_some()
+        && title_split_min > 0
+        && segment_start.elapsed().as_secs() >= title_split_min
+      {
+        pending_split = true;
+      }
 
-  let file_size = std::fs::metadata(file_path)
-    .map(|meta| meta.len())
-    .unwrap_or(0);
-  let end_time = now_rfc3339();
-  let mut record_status = if stop_flag.load(Ordering::SeqCst) {
-    "STOPPED"
-  } else if status.success() {
-    "COMPLETED"
-  } else {
-    "FAILED"
-  };
-  let mut error_message = stderr_output.trim().to_string();
-  if !stop_flag.load(Ordering::SeqCst) && !status.success() {
-    if let Ok(info) = tauri::async_runtime::block_on(fetch_room_info(&context.bilibili, room_id)) {
-      if info.live_status != 1 {
-        record_status = "COMPLETED";
+      if pending_split {
+        if let Some(mut seg) = writer.take() {
+          let record_id = seg.record_id;
+          let file_path = seg.file_path.clone();
+          seg.finish("COMPLETED", Some("分段切换"))?;
+          drop(seg);
+          spawn_segment_finalize(context.clone(), record_id, file_path);
+          *segment_index += 1;
+          *current_title = pending_title
+            .take()
+            .unwrap_or_else(|| load_current_title(context, room_id, current_title));
+          *current_file_path = build_record_path(
+            &settings.file_name_template,
+            session.base_dir,
+            session.room_info,
+            session.nickname,
+            session.record_start_date,
+            *segment_index,
+          );
+        }
+      }
+
+      if writer.is_none() {
+        *current_file_path = normalize_hls_path(current_file_path, is_fmp4);
+        update_current_file(context, room_id, current_file_path);
+        let mut seg = open_segment(
+          context,
+          room_id,
+          current_file_path,
+          current_title,
+          *segment_index,
+          settings,
+          session.room_info,
+          session.nickname,
+        )?;
+        if let Some(data) = init_data.as_ref() {
+          seg.write(data)?;
+        }
+        segment_start = Instant::now();
+        writer = Some(seg);
+      }
+
+      let data = match resolve_uri(&playlist_url, &segment.uri)
+        .and_then(|url| session.fetch_segment(&url, segment.sequence))
+      {
+        Ok(data) => data,
+        Err(err) => {
+          append_log(
+            &context.app_log_path,
+            &format!(
+              "stream_hls_segment_dropped room={} sequence={} err={}",
+              room_id, segment.sequence, err
+            ),
+          );
+          continue;
+        }
+      };
+      last_sequence = Some(segment.sequence);
+      last_progress_at = Instant::now();
+      if let Some(seg) = writer.as_mut() {
+        seg.write(&data)?;
+        if settings.cutting_mode == 1 {
+          let limit = settings.cutting_number.max(1) as u64;
+          if segment_start.elapsed().as_secs() >= limit {
+            session.split_flag.store(true, Ordering::SeqCst);
+          }
+        } else if settings.cutting_mode == 2 {
+          let limit = settings.cutting_number.max(1) as u64 * 1024 * 1024;
+          if seg.bytes_written >= limit {
+            session.split_flag.store(true, Ordering::SeqCst);
+          }
+        }
       }
     }
-  }
-  if record_status != "FAILED" {
-    error_message.clear();
-  }
 
-  update_record_task(
-    &context.db,
-    record_id,
-    record_status,
-    Some(end_time.clone()),
-    file_size,
-    if error_message.is_empty() { None } else { Some(error_message.as_str()) },
-  )?;
-  if let Some(path) = metadata_path.as_ref() {
-    if let Err(err) = update_metadata_file(path, &end_time, file_size) {
-      append_log(
-        context.app_log_path.as_ref(),
-        &format!("record_metadata_update_failed record_id={} err={}", record_id, err),
-      );
+    if playlist.end_list {
+      break Ok("COMPLETED");
     }
-  }
-  if record_status == "COMPLETED" {
-    if let Err(err) = baidu_sync::enqueue_live_sync(&context.db, context.app_log_path.as_ref(), record_id) {
-      append_log(
-        context.app_log_path.as_ref(),
-        &format!("baidu_sync_enqueue_fail record_id={} err={}", record_id, err),
-      );
+    if last_progress_at.elapsed() >= stall_limit {
+      break Err("直播流长时间没有新分片".to_string());
+    }
+    let poll_secs = playlist
+      .segments
+      .last()
+      .map(|segment| segment.duration)
+      .unwrap_or(playlist.target_duration)
+      .clamp(HLS_MIN_POLL_SECS, HLS_MAX_POLL_SECS);
+    session.sleep(Duration::from_secs_f64(poll_secs));
+    if session.stop_flag.load(Ordering::SeqCst) {
+      break Ok("STOPPED");
+    }
+    match session.fetch_playlist(&playlist_url) {
+      Ok((next_url, next_playlist)) => {
+        if next_playlist.is_fmp4() != is_fmp4 {
+          break Err("直播流分片格式发生变化".to_string());
+        }
+        playlist_url = next_url;
+        playlist = next_playlist;
+        playlist_failures = 0;
+      }
+      Err(err) => {
+        playlist_failures += 1;
+        append_log(
+          &context.app_log_path,
+          &format!(
+            "stream_hls_playlist_error room={} attempt={} err={}",
+            room_id, playlist_failures, err
+          ),
+        );
+        if playlist_failures >= HLS_PLAYLIST_RETRY_LIMIT {
+          break Err(err);
+        }
+      }
+    }
+  };
+
+  let Some(mut seg) = writer.take() else {
+    return result.map(|_| ());
+  };
+  let record_id = seg.record_id;
+  let file_path = seg.file_path.clone();
+  match result.as_ref() {
+    Ok(status) => seg.finish(status, None)?,
+    Err(err) => {
+      let still_live = tauri::async_runtime::block_on(fetch_room_info(&context.bilibili, room_id))
+        .map(|info| info.live_status == 1)
+        .unwrap_or(true);
+      if still_live {
+        seg.finish("FAILED", Some(err.as_str()))?;
+      } else {
+        seg.finish("COMPLETED", None)?;
+      }
     }
   }
-  Ok(())
+  drop(seg);
+  spawn_segment_finalize(context.clone(), record_id, file_path);
+  result.map(|_| ())
 }
 
 fn summarize_stream_url(url: &str) -> String {
@@ -2471,6 +2917,28 @@ fn should_refresh_stream_url(url: &str, lead_secs: u64) -> Option<(u64, u64)> {
   }
 }
 
+fn try_next_stream_url(
+  failures: &mut usize,
+  url_count: usize,
+  settings: &LiveSettings,
+  log_path: &Path,
+  room_id: &str,
+  reason: &str,
+) -> bool {
+  *failures += 1;
+  if !settings.stream_cdn_failover || *failures >= url_count {
+    return false;
+  }
+  append_log(
+    log_path,
+    &format!(
+      "stream_cdn_failover room={} reason={} attempt={}/{}",
+      room_id, reason, failures, url_count
+    ),
+  );
+  true
+}
+
 fn mark_force_no_qn(
   force_no_qn_until: &mut Option<i64>,
   settings: &LiveSettings,
@@ -2550,6 +3018,11 @@ pub async fn fetch_room_info(
     .get("parent_area_name")
     .and_then(|value| value.as_str())
     .map(|value| value.to_string());
+  let live_time = data
+    .get("live_time")
+    .and_then(|value| value.as_str())
+    .filter(|value| !value.starts_with("0000"))
+    .map(|value| value.to_string());
 
   Ok(LiveRoomInfo {
     room_id,
@@ -2559,6 +3032,7 @@ pub async fn fetch_room_info(
     cover,
     area_name,
     parent_area_name,
+    live_time,
   })
 }
 
@@ -2665,7 +3139,7 @@ async fn run_danmaku_loop(
     }
   }
 
-  let auth = context.login_store.load_auth_info(&context.db).ok().flatten();
+  let auth = load_room_auth(&context, &runtime_room_id);
   let uid = auth.as_ref().and_then(|info| info.user_id).unwrap_or(0);
   loop {
     if stop_flag.load(Ordering::SeqCst) {
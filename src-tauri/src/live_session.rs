@@ -0,0 +1,391 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, OptionalExtension};
+use serde::Serialize;
+
+use crate::danmaku_ass::{danmaku_ass_path, danmaku_jsonl_path};
+use crate::db::Db;
+use crate::utils::{now_rfc3339, sanitize_filename};
+
+const SESSION_REUSE_GAP_SECONDS: i64 = 10 * 60;
+const COVER_EXTENSIONS: &[&str] = &["jpg", "png", "webp"];
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveSessionSegment {
+  pub id: i64,
+  pub status: String,
+  pub file_path: String,
+  pub segment_index: i64,
+  pub title: Option<String>,
+  pub start_time: String,
+  pub end_time: Option<String>,
+  pub file_size: i64,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveSessionView {
+  pub id: i64,
+  pub room_id: String,
+  pub nickname: Option<String>,
+  pub live_time: Option<String>,
+  pub title: Option<String>,
+  pub titles: Vec<String>,
+  pub status: String,
+  pub start_time: String,
+  pub end_time: Option<String>,
+  pub segment_count: usize,
+  pub total_size: i64,
+  pub duration_seconds: i64,
+  pub danmaku_files: Vec<String>,
+  pub cover_path: Option<String>,
+  pub segments: Vec<LiveSessionSegment>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveSessionExportResult {
+  pub output_dir: String,
+  pub files: Vec<String>,
+}
+
+pub fn open_live_session(
+  db: &Db,
+  room_id: &str,
+  live_time: Option<&str>,
+  title: &str,
+) -> Result<i64, String> {
+  let now = now_rfc3339();
+  let live_time = live_time.map(str::trim).filter(|value| !value.is_empty());
+  db.with_conn(|conn| {
+    let latest = conn
+      .query_row(
+        "SELECT id, live_time, status, end_time FROM live_session WHERE room_id = ?1 ORDER BY start_time DESC, id DESC LIMIT 1",
+        [room_id],
+        |row| {
+          Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, Option<String>>(3)?,
+          ))
+        },
+      )
+      .optional()?;
+    if let Some((session_id, latest_live_time, status, end_time)) = latest {
+      let same_broadcast = match (live_time, latest_live_time.as_deref()) {
+        (Some(current), Some(previous)) => current == previous,
+        _ => {
+          status == "RECORDING"
+            || end_time
+              .as_deref()
+              .and_then(parse_time)
+              .is_some_and(|end| (Utc::now() - end).num_seconds() <= SESSION_REUSE_GAP_SECONDS)
+        }
+      };
+      if same_broadcast {
+        conn.execute(
+          "UPDATE live_session SET status = 'RECORDING', end_time = NULL, update_time = ?1 WHERE id = ?2",
+          (&now, session_id),
+        )?;
+        append_session_title(conn, session_id, title)?;
+        return Ok(session_id);
+      }
+      if status == "RECORDING" {
+        conn.execute(
+          "UPDATE live_session SET status = 'COMPLETED', end_time = COALESCE(end_time, ?1), update_time = ?1 WHERE id = ?2",
+          (&now, session_id),
+        )?;
+      }
+    }
+    conn.execute(
+      "INSERT INTO live_session (room_id, live_time, title, titles, status, start_time, end_time, create_time, update_time) \
+       VALUES (?1, ?2, ?3, NULL, 'RECORDING', ?4, NULL, ?5, ?6)",
+      (room_id, live_time, title, &now, &now, &now),
+    )?;
+    let session_id = conn.last_insert_rowid();
+    append_session_title(conn, session_id, title)?;
+    Ok(session_id)
+  })
+  .map_err(|err| format!("写入直播场次失败: {}", err))
+}
+
+pub fn touch_live_session_title(db: &Db, session_id: i64, title: &str) -> Result<(), String> {
+  db.with_conn(|conn| append_session_title(conn, session_id, title))
+    .map_err(|err| err.to_string())
+}
+
+pub fn close_live_session(db: &Db, session_id: i64) -> Result<(), String> {
+  let now = now_rfc3339();
+  db.with_conn(|conn| {
+    conn.execute(
+      "UPDATE live_session SET status = 'COMPLETED', end_time = ?1, update_time = ?2 WHERE id = ?3",
+      (&now, &now, session_id),
+    )?;
+    Ok(())
+  })
+  .map_err(|err| err.to_string())
+}
+
+pub fn list_live_sessions(
+  db: &Db,
+  room_id: Option<&str>,
+  limit: i64,
+) -> Result<Vec<LiveSessionView>, String> {
+  let ids = db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT id FROM live_session WHERE (?1 IS NULL OR room_id = ?1) ORDER BY start_time DESC, id DESC LIMIT ?2",
+      )?;
+      let ids = stmt
+        .query_map((room_id, limit.max(1)), |row| row.get::<_, i64>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+      Ok(ids)
+    })
+    .map_err(|err| err.to_string())?;
+  ids
+    .into_iter()
+    .map(|id| load_live_session(db, id))
+    .collect()
+}
+
+pub fn load_live_session(db: &Db, session_id: i64) -> Result<LiveSessionView, String> {
+  let (session, segments) = db
+    .with_conn(|conn| {
+      let session = conn
+        .query_row(
+          "SELECT s.id, s.room_id, a.nickname, s.live_time, s.title, s.titles, s.status, s.start_time, s.end_time \
+           FROM live_session s LEFT JOIN anchor a ON a.uid = s.room_id WHERE s.id = ?1",
+          [session_id],
+          |row| {
+            Ok(LiveSessionView {
+              id: row.get(0)?,
+              room_id: row.get(1)?,
+              nickname: row.get(2)?,
+              live_time: row.get(3)?,
+              title: row.get(4)?,
+              titles: row
+                .get::<_, Option<String>>(5)?
+                .and_then(|value| serde_json::from_str(&value).ok())
+                .unwrap_or_default(),
+              status: row.get(6)?,
+              start_time: row.get(7)?,
+              end_time: row.get(8)?,
+              segment_count: 0,
+              total_size: 0,
+              duration_seconds: 0,
+              danmaku_files: Vec::new(),
+              cover_path: None,
+              segments: Vec::new(),
+            })
+          },
+        )
+        .optional()?;
+      let mut stmt = conn.prepare(
+        "SELECT id, status, file_path, segment_index, title, start_time, end_time, file_size \
+         FROM live_record_task WHERE session_id = ?1 ORDER BY start_time ASC, segment_index ASC, id ASC",
+      )?;
+      let segments = stmt
+        .query_map([session_id], |row| {
+          Ok(LiveSessionSegment {
+            id: row.get(0)?,
+            status: row.get(1)?,
+            file_path: row.get(2)?,
+            segment_index: row.get(3)?,
+            title: row.get(4)?,
+            start_time: row.get(5)?,
+            end_time: row.get(6)?,
+            file_size: row.get::<_, Option<i64>>(7)?.unwrap_or(0),
+          })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+      Ok((session, segments))
+    })
+    .map_err(|err| err.to_string())?;
+  let mut session = session.ok_or_else(|| "直播场次不存在".to_string())?;
+  for segment in &segments {
+    let media_path = Path::new(&segment.file_path);
+    for path in [danmaku_jsonl_path(media_path), danmaku_ass_path(media_path)] {
+      if path.exists() {
+        session.danmaku_files.push(path.to_string_lossy().to_string());
+      }
+    }
+    if session.cover_path.is_none() {
+      session.cover_path = COVER_EXTENSIONS
+        .iter()
+        .map(|ext| media_path.with_extension(format!("cover.{}", ext)))
+        .find(|path| path.exists())
+        .map(|path| path.to_string_lossy().to_string());
+    }
+    session.total_size += segment.file_size;
+    if let (Some(start), Some(end)) = (
+      parse_time(&segment.start_time),
+      segment.end_time.as_deref().and_then(parse_time),
+    ) {
+      session.duration_seconds += (end - start).num_seconds().max(0);
+    }
+  }
+  session.segment_count = segments.len();
+  session.segments = segments;
+  Ok(session)
+}
+
+pub fn merge_live_sessions(db: &Db, session_ids: &[i64]) -> Result<LiveSessionView, String> {
+  let unique_ids: BTreeSet<i64> = session_ids.iter().copied().collect();
+  if unique_ids.len() != session_ids.len() {
+    return Err("不能重复选择同一直播场次".to_string());
+  }
+  let mut sessions = unique_ids
+    .iter()
+    .map(|id| load_live_session(db, *id))
+    .collect::<Result<Vec<_>, _>>()?;
+  if sessions.len() < 2 {
+    return Err("请至少选择两个直播场次".to_string());
+  }
+  if sessions
+    .iter()
+    .any(|session| session.room_id != sessions[0].room_id)
+  {
+    return Err("只能合并同一直播间的场次".to_string());
+  }
+  if sessions.iter().any(|session| session.status == "RECORDING") {
+    return Err("录制中的场次不能合并".to_string());
+  }
+  sessions.sort_by(|a, b| a.start_time.cmp(&b.start_time));
+  let target_id = sessions[0].id;
+  let mut titles: Vec<String> = Vec::new();
+  for title in sessions.iter().flat_map(|session| session.titles.iter()) {
+    if !titles.contains(title) {
+      titles.push(title.clone());
+    }
+  }
+  let end_time = sessions
+    .iter()
+    .filter_map(|session| session.end_time.clone())
+    .max();
+  let now = now_rfc3339();
+  db.with_conn_mut(|conn| {
+    let tx = conn.transaction()?;
+    for session in sessions.iter().skip(1) {
+      tx.execute(
+        "UPDATE live_record_task SET session_id = ?1 WHERE session_id = ?2",
+        (target_id, session.id),
+      )?;
+      tx.execute("DELETE FROM live_session WHERE id = ?1", [session.id])?;
+    }
+    tx.execute(
+      "UPDATE live_session SET titles = ?1, end_time = ?2, update_time = ?3 WHERE id = ?4",
+      (
+        serde_json::to_string(&titles).unwrap_or_default(),
+        end_time.as_deref(),
+        &now,
+        target_id,
+      ),
+    )?;
+    tx.commit()
+  })
+  .map_err(|err| format!("合并直播场次失败: {}", err))?;
+  load_live_session(db, target_id)
+}
+
+pub fn export_live_session(
+  session: &LiveSessionView,
+  target_dir: &Path,
+) -> Result<LiveSessionExportResult, String> {
+  let folder_name = sanitize_filename(&format!(
+    "{}_{}_{}",
+    session.nickname.as_deref().unwrap_or(&session.room_id),
+    session
+      .start_time
+      .get(..10)
+      .unwrap_or(&session.start_time),
+    session.title.as_deref().unwrap_or("live"),
+  ));
+  let output_dir = target_dir.join(folder_name);
+  std::fs::create_dir_all(&output_dir).map_err(|err| format!("创建导出目录失败: {}", err))?;
+
+  let mut sources: Vec<PathBuf> = session
+    .segments
+    .iter()
+    .map(|segment| PathBuf::from(&segment.file_path))
+    .filter(|path| path.exists())
+    .collect();
+  if sources.is_empty() {
+    return Err("场次没有可导出的录制文件".to_string());
+  }
+  sources.extend(session.danmaku_files.iter().map(PathBuf::from));
+  sources.extend(session.cover_path.iter().map(PathBuf::from));
+
+  let mut files = Vec::new();
+  for source in sources {
+    let file_name = source
+      .file_name()
+      .ok_or_else(|| format!("文件名无效: {}", source.to_string_lossy()))?;
+    let target = output_dir.join(file_name);
+    if target.exists() {
+      files.push(target.to_string_lossy().to_string());
+      continue;
+    }
+    if std::fs::hard_link(&source, &target).is_err() {
+      std::fs::copy(&source, &target).map_err(|err| {
+        format!("导出文件失败: {} ({})", source.to_string_lossy(), err)
+      })?;
+    }
+    files.push(target.to_string_lossy().to_string());
+  }
+
+  let manifest_path = output_dir.join("session.json");
+  let manifest = serde_json::to_string_pretty(session).map_err(|err| err.to_string())?;
+  std::fs::write(&manifest_path, manifest).map_err(|err| format!("写入场次信息失败: {}", err))?;
+  files.push(manifest_path.to_string_lossy().to_string());
+
+  Ok(LiveSessionExportResult {
+    output_dir: output_dir.to_string_lossy().to_string(),
+    files,
+  })
+}
+
+fn append_session_title(
+  conn: &Connection,
+  session_id: i64,
+  title: &str,
+) -> Result<(), rusqlite::Error> {
+  let title = title.trim();
+  if title.is_empty() {
+    return Ok(());
+  }
+  let raw = conn
+    .query_row(
+      "SELECT titles FROM live_session WHERE id = ?1",
+      [session_id],
+      |row| row.get::<_, Option<String>>(0),
+    )
+    .optional()?
+    .flatten();
+  let mut titles: Vec<String> = raw
+    .and_then(|value| serde_json::from_str(&value).ok())
+    .unwrap_or_default();
+  if titles.iter().any(|item| item == title) {
+    return Ok(());
+  }
+  titles.push(title.to_string());
+  conn.execute(
+    "UPDATE live_session SET title = COALESCE(title, ?1), titles = ?2, update_time = ?3 WHERE id = ?4",
+    (
+      title,
+      serde_json::to_string(&titles).unwrap_or_default(),
+      now_rfc3339(),
+      session_id,
+    ),
+  )?;
+  Ok(())
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+  DateTime::parse_from_rfc3339(value)
+    .ok()
+    .map(|value| value.with_timezone(&Utc))
+}
@@ -0,0 +1,364 @@
+use std::path::Path;
+
+use chrono::{DateTime, Local, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::submission::{create_workflow_instance_for_task, start_submission_workflow};
+use crate::db::Db;
+use crate::live_recorder::LiveContext;
+use crate::utils::{append_log, now_rfc3339};
+
+const MAX_TITLE_CHARS: usize = 80;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveSubmissionTemplate {
+  pub title_template: String,
+  #[serde(default)]
+  pub description_template: String,
+  #[serde(default)]
+  pub tags_template: String,
+  pub partition_id: i64,
+  #[serde(default)]
+  pub collection_id: Option<i64>,
+  #[serde(default = "default_video_type")]
+  pub video_type: String,
+  #[serde(default)]
+  pub segment_prefix: Option<String>,
+  #[serde(default)]
+  pub account_user_id: Option<i64>,
+  #[serde(default)]
+  pub priority: bool,
+  #[serde(default = "default_segmentation_enabled")]
+  pub segmentation_enabled: bool,
+  #[serde(default = "default_segment_duration_seconds")]
+  pub segment_duration_seconds: i64,
+  #[serde(default = "default_preserve_original")]
+  pub preserve_original: bool,
+  #[serde(default)]
+  pub min_total_seconds: i64,
+}
+
+pub struct LiveSubmissionRule {
+  pub enabled: bool,
+  pub template: Option<LiveSubmissionTemplate>,
+}
+
+struct SessionSegment {
+  file_path: String,
+  start_time: DateTime<Utc>,
+  end_time: Option<DateTime<Utc>>,
+}
+
+fn default_video_type() -> String {
+  "ORIGINAL".to_string()
+}
+
+fn default_segmentation_enabled() -> bool {
+  true
+}
+
+fn default_segment_duration_seconds() -> i64 {
+  133
+}
+
+fn default_preserve_original() -> bool {
+  true
+}
+
+impl LiveSubmissionTemplate {
+  pub fn validate(&self) -> Result<(), String> {
+    if self.title_template.trim().is_empty() {
+      return Err("投稿标题模板不能为空".to_string());
+    }
+    if self.partition_id <= 0 {
+      return Err("请选择投稿分区".to_string());
+    }
+    if self.video_type.trim().is_empty() {
+      return Err("请选择投稿类型".to_string());
+    }
+    if self.segmentation_enabled && self.segment_duration_seconds <= 0 {
+      return Err("分段时长必须大于0".to_string());
+    }
+    Ok(())
+  }
+
+  pub fn workflow_config(&self) -> serde_json::Value {
+    serde_json::json!({
+      "enableSegmentation": self.segmentation_enabled,
+      "segmentationConfig": {
+        "enabled": self.segmentation_enabled,
+        "segmentDurationSeconds": self.segment_duration_seconds,
+        "preserveOriginal": self.preserve_original,
+      },
+    })
+  }
+}
+
+pub fn load_live_submission_rule(db: &Db, room_id: &str) -> Result<LiveSubmissionRule, String> {
+  let row = db
+    .with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT auto_submit_enabled, auto_submit_template FROM live_room_settings WHERE room_id = ?1",
+          [room_id],
+          |row| Ok((row.get::<_, Option<i64>>(0)?, row.get::<_, Option<String>>(1)?)),
+        )
+        .optional()
+    })
+    .map_err(|err| err.to_string())?;
+  let (enabled, template) = row.unwrap_or((None, None));
+  let template = match template.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+    Some(value) => Some(
+      serde_json::from_str::<LiveSubmissionTemplate>(value)
+        .map_err(|err| format!("自动投稿模板解析失败: {}", err))?,
+    ),
+    None => None,
+  };
+  Ok(LiveSubmissionRule {
+    enabled: enabled.unwrap_or(0) != 0,
+    template,
+  })
+}
+
+pub fn render_submission_template(
+  template: &str,
+  room_id: &str,
+  title: &str,
+  nickname: &str,
+  session_start: DateTime<Utc>,
+) -> String {
+  let local = session_start.with_timezone(&Local);
+  template
+    .replace("{{ roomId }}", room_id)
+    .replace("{{ title }}", title)
+    .replace("{{ name }}", nickname)
+    .replace("{{ date }}", &local.format("%Y-%m-%d").to_string())
+    .replace("{{ liveDate }}", &local.format("%Y%m%d").to_string())
+    .replace("{{ time }}", &local.format("%H:%M").to_string())
+    .trim()
+    .to_string()
+}
+
+pub fn create_live_submission(
+  context: &LiveContext,
+  room_id: &str,
+  session_id: i64,
+  title: &str,
+  session_start: DateTime<Utc>,
+) -> Result<Option<String>, String> {
+  let rule = load_live_submission_rule(&context.db, room_id)?;
+  let template = match rule.template {
+    Some(template) if rule.enabled => template,
+    _ => return Ok(None),
+  };
+  template.validate()?;
+  if let Some(existing) = load_session_submission(&context.db, session_id)? {
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "live_auto_submit_skip room={} session_id={} reason=already_submitted task_id={}",
+        room_id, session_id, existing
+      ),
+    );
+    return Ok(None);
+  }
+
+  let segments = load_session_segments(&context.db, session_id)?;
+  if segments.is_empty() {
+    append_log(
+      &context.app_log_path,
+      &format!("live_auto_submit_skip room={} reason=no_segments", room_id),
+    );
+    return Ok(None);
+  }
+  let total_seconds: i64 = segments
+    .iter()
+    .filter_map(|segment| {
+      segment
+        .end_time
+        .map(|end| (end - segment.start_time).num_seconds().max(0))
+    })
+    .sum();
+  if template.min_total_seconds > 0 && total_seconds < template.min_total_seconds {
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "live_auto_submit_skip room={} reason=too_short seconds={}",
+        room_id, total_seconds
+      ),
+    );
+    return Ok(None);
+  }
+
+  let (nickname, room_account_user_id) = load_room_profile(&context.db, room_id)?;
+  let nickname = nickname.unwrap_or_else(|| "主播".to_string());
+  let render = |value: &str| render_submission_template(value, room_id, title, &nickname, session_start);
+  let submission_title: String = render(&template.title_template)
+    .chars()
+    .take(MAX_TITLE_CHARS)
+    .collect();
+  if submission_title.is_empty() {
+    return Err("投稿标题为空".to_string());
+  }
+  let description = Some(render(&template.description_template)).filter(|value| !value.is_empty());
+  let tags = Some(render(&template.tags_template)).filter(|value| !value.is_empty());
+  let account_user_id = template.account_user_id.or(room_account_user_id);
+
+  let task_id = uuid::Uuid::new_v4().to_string();
+  let now = now_rfc3339();
+  let claimed = context
+    .db
+    .with_conn_mut(|conn| {
+      let tx = conn.transaction()?;
+      let claimed = tx.execute(
+        "UPDATE live_session SET submission_task_id = ?1, update_time = ?2 WHERE id = ?3 AND submission_task_id IS NULL",
+        (&task_id, &now, session_id),
+      )?;
+      if claimed == 0 {
+        return Ok(false);
+      }
+      tx.execute(
+        "INSERT INTO submission_task (task_id, status, priority, title, description, cover_url, partition_id, tags, topic_id, mission_id, activity_title, video_type, collection_id, bvid, aid, created_at, updated_at, segment_prefix, baidu_sync_enabled, baidu_sync_path, baidu_sync_filename, account_user_id, scheduled_publish_time, scheduled_publish_mode) \
+         VALUES (?1, 'PENDING', ?2, ?3, ?4, NULL, ?5, ?6, NULL, NULL, NULL, ?7, ?8, NULL, NULL, ?9, ?10, ?11, 0, NULL, NULL, ?12, NULL, NULL)",
+        params![
+          &task_id,
+          if template.priority { 1 } else { 0 },
+          &submission_title,
+          description.as_deref(),
+          template.partition_id,
+          tags.as_deref(),
+          &template.video_type,
+          template.collection_id,
+          &now,
+          &now,
+          template.segment_prefix.as_deref(),
+          account_user_id,
+        ],
+      )?;
+      for (index, segment) in segments.iter().enumerate() {
+        tx.execute(
+          "INSERT INTO task_source_video (id, task_id, source_file_path, sort_order, start_time, end_time) \
+           VALUES (?1, ?2, ?3, ?4, NULL, NULL)",
+          (
+            uuid::Uuid::new_v4().to_string(),
+            &task_id,
+            &segment.file_path,
+            (index + 1) as i64,
+          ),
+        )?;
+      }
+      tx.commit()?;
+      Ok(true)
+    })
+    .map_err(|err| format!("创建投稿任务失败: {}", err))?;
+  if !claimed {
+    append_log(
+      &context.app_log_path,
+      &format!(
+        "live_auto_submit_skip room={} session_id={} reason=already_submitted",
+        room_id, session_id
+      ),
+    );
+    return Ok(None);
+  }
+
+  create_workflow_instance_for_task(&context.db, &task_id, &template.workflow_config())?;
+  append_log(
+    &context.app_log_path,
+    &format!(
+      "live_auto_submit_created room={} session_id={} task_id={} segments={}",
+      room_id,
+      session_id,
+      task_id,
+      segments.len()
+    ),
+  );
+  start_submission_workflow(
+    context.db.clone(),
+    context.app_log_path.clone(),
+    context.edit_upload_state.clone(),
+    task_id.clone(),
+  );
+  Ok(Some(task_id))
+}
+
+fn load_session_submission(db: &Db, session_id: i64) -> Result<Option<String>, String> {
+  db.with_conn(|conn| {
+    conn
+      .query_row(
+        "SELECT submission_task_id FROM live_session WHERE id = ?1",
+        [session_id],
+        |row| row.get::<_, Option<String>>(0),
+      )
+      .optional()
+      .map(Option::flatten)
+  })
+  .map_err(|err| err.to_string())
+}
+
+fn load_session_segments(db: &Db, session_id: i64) -> Result<Vec<SessionSegment>, String> {
+  let rows = db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT file_path, start_time, end_time FROM live_record_task \
+         WHERE session_id = ?1 AND status = 'COMPLETED' ORDER BY start_time ASC, segment_index ASC, id ASC",
+      )?;
+      let rows = stmt
+        .query_map([session_id], |row| {
+          Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<String>>(2)?,
+          ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+      Ok(rows)
+    })
+    .map_err(|err| err.to_string())?;
+  let parse = |value: &str| {
+    DateTime::parse_from_rfc3339(value)
+      .ok()
+      .map(|value| value.with_timezone(&Utc))
+  };
+  Ok(
+    rows
+      .into_iter()
+      .filter_map(|(file_path, start_time, end_time)| {
+        let start_time = parse(&start_time)?;
+        if !Path::new(&file_path).exists() {
+          return None;
+        }
+        Some(SessionSegment {
+          file_path,
+          start_time,
+          end_time: end_time.as_deref().and_then(parse),
+        })
+      })
+      .collect(),
+  )
+}
+
+fn load_room_profile(db: &Db, room_id: &str) -> Result<(Option<String>, Option<i64>), String> {
+  db.with_conn(|conn| {
+    let nickname = conn
+      .query_row(
+        "SELECT nickname FROM anchor WHERE uid = ?1",
+        [room_id],
+        |row| row.get::<_, Option<String>>(0),
+      )
+      .optional()?
+      .flatten();
+    let account_user_id = conn
+      .query_row(
+        "SELECT account_user_id FROM live_room_settings WHERE room_id = ?1",
+        [room_id],
+        |row| row.get::<_, Option<i64>>(0),
+      )
+      .optional()?
+      .flatten();
+    Ok((nickname, account_user_id))
+  })
+  .map_err(|err| err.to_string())
+}
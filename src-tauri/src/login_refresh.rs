@@ -52,18 +52,39 @@ pub fn start_cookie_refresh_loop(
 ) {
   tauri::async_runtime::spawn(async move {
     loop {
-      let result = refresh_cookie_if_needed(
-        bilibili.as_ref(),
-        login_store.as_ref(),
-        db.as_ref(),
-        log_path.as_ref(),
-      )
-      .await;
-      if let Err(err) = result {
-        append_log(
+      let user_ids = match login_store.list_accounts(db.as_ref()) {
+        Ok(accounts) if !accounts.is_empty() => accounts
+          .into_iter()
+          .map(|account| Some(account.user_id))
+          .collect::<Vec<_>>(),
+        Ok(_) => vec![None],
+        Err(err) => {
+          append_log(
+            log_path.as_ref(),
+            &format!("cookie_refresh_loop_fail err={}", err),
+          );
+          vec![None]
+        }
+      };
+      for user_id in user_ids {
+        let result = refresh_cookie_if_needed(
+          bilibili.as_ref(),
+          login_store.as_ref(),
+          db.as_ref(),
           log_path.as_ref(),
-          &format!("cookie_refresh_loop_fail err={}", err),
-        );
+          user_id,
+        )
+        .await;
+        if let Err(err) = result {
+          append_log(
+            log_path.as_ref(),
+            &format!(
+              "cookie_refresh_loop_fail user_id={} err={}",
+              user_id.unwrap_or(0),
+              err
+            ),
+          );
+        }
       }
       tokio::time::sleep(std::time::Duration::from_secs(
         (DEFAULT_COOKIE_REFRESH_MINUTES.max(1) as u64) * 60,
@@ -78,17 +99,21 @@ pub async fn refresh_cookie(
   login_store: &LoginStore,
   db: &Db,
   log_path: &Path,
+  user_id: Option<i64>,
 ) -> Result<AuthInfo, String> {
   let _guard = refresh_lock().lock().await;
-  append_log(log_path, "cookie_refresh_start");
+  append_log(
+    log_path,
+    &format!("cookie_refresh_start user_id={}", user_id.unwrap_or(0)),
+  );
   let login_data = login_store
-    .load_login_data(db)
+    .load_login_data_for(db, user_id)
     .map_err(|err| format!("读取登录信息失败: {}", err))?
     .ok_or_else(|| "请先登录".to_string())?;
   let cookie = extract_cookie(&login_data).ok_or_else(|| "登录信息缺少Cookie".to_string())?;
   let csrf = extract_csrf(&cookie).ok_or_else(|| "登录信息缺少CSRF".to_string())?;
   let refresh_token = login_store
-    .load_refresh_token(db)
+    .load_refresh_token_for(db, user_id)
     .map_err(|err| format!("读取refresh_token失败: {}", err))?
     .filter(|token| !token.trim().is_empty())
     .ok_or_else(|| "登录信息缺少refresh_token".to_string())?;
@@ -119,10 +144,10 @@ pub async fn refresh_cookie(
   .await?;
   let new_login_data = build_refreshed_login_data(&login_data, new_cookie, new_refresh_token);
   login_store
-    .save_login_info(db, &new_login_data)
+    .update_account_login_info(db, &new_login_data)
     .map_err(|err| format!("保存刷新Cookie失败: {}", err))?;
   let auth_info = login_store
-    .load_auth_info(db)
+    .load_auth_info_for(db, user_id)
     .map_err(|err| format!("读取刷新登录信息失败: {}", err))?
     .ok_or_else(|| "刷新后登录信息无效".to_string())?;
   append_log(log_path, "cookie_refresh_ok");
@@ -134,10 +159,11 @@ pub async fn refresh_cookie_if_needed(
   login_store: &LoginStore,
   db: &Db,
   log_path: &Path,
+  user_id: Option<i64>,
 ) -> Result<bool, String> {
   let _guard = refresh_lock().lock().await;
   let login_data = match login_store
-    .load_login_data(db)
+    .load_login_data_for(db, user_id)
     .map_err(|err| format!("读取登录信息失败: {}", err))?
   {
     Some(data) => data,
@@ -146,13 +172,13 @@ pub async fn refresh_cookie_if_needed(
   let cookie = extract_cookie(&login_data).ok_or_else(|| "登录信息缺少Cookie".to_string())?;
   let csrf = extract_csrf(&cookie).ok_or_else(|| "登录信息缺少CSRF".to_string())?;
   let refresh_token = login_store
-    .load_refresh_token(db)
+    .load_refresh_token_for(db, user_id)
     .map_err(|err| format!("读取refresh_token失败: {}", err))?
     .filter(|token| !token.trim().is_empty())
     .ok_or_else(|| "登录信息缺少refresh_token".to_string())?;
 
   let client = Client::new();
-  let expired = load_login_expire_time(db)?
+  let expired = load_login_expire_time(db, user_id)?
     .map(|expire_time| expire_time <= Utc::now())
     .unwrap_or(false);
   let login_invalid = match check_login_status(&client, bilibili, &cookie).await {
@@ -197,18 +223,18 @@ pub async fn refresh_cookie_if_needed(
   .await?;
   let new_login_data = build_refreshed_login_data(&login_data, new_cookie, new_refresh_token);
   login_store
-    .save_login_info(db, &new_login_data)
+    .update_account_login_info(db, &new_login_data)
     .map_err(|err| format!("保存刷新Cookie失败: {}", err))?;
   append_log(log_path, "cookie_refresh_check_ok");
   Ok(true)
 }
 
-fn load_login_expire_time(db: &Db) -> Result<Option<DateTime<Utc>>, String> {
+fn load_login_expire_time(db: &Db, user_id: Option<i64>) -> Result<Option<DateTime<Utc>>, String> {
   db.with_conn(|conn| {
     let mut stmt = conn.prepare(
-      "SELECT expire_time FROM login_info ORDER BY login_time DESC LIMIT 1",
+      "SELECT expire_time FROM login_info WHERE ?1 IS NULL OR user_id = ?1 ORDER BY login_time DESC LIMIT 1",
     )?;
-    let mut rows = stmt.query([])?;
+    let mut rows = stmt.query([user_id])?;
     if let Some(row) = rows.next()? {
       let expire_time: Option<String> = row.get(0)?;
       if let Some(expire_time) = expire_time {
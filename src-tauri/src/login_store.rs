@@ -1,13 +1,16 @@
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Duration, TimeZone, Utc};
+use rusqlite::OptionalExtension;
+use serde::Serialize;
 use serde_json::{json, Value};
 use thiserror::Error;
 use url::Url;
 
 use crate::db::Db;
+use crate::utils::append_log;
 
 #[derive(Debug, Error)]
 pub enum LoginStoreError {
@@ -28,6 +31,23 @@ pub struct AuthInfo {
   pub data: Value,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginAccount {
+  pub user_id: i64,
+  pub username: Option<String>,
+  pub nickname: Option<String>,
+  pub avatar_url: Option<String>,
+  pub login_time: String,
+  pub expire_time: Option<String>,
+  pub is_default: bool,
+}
+
+pub const DEFAULT_ACCOUNT_SETTING_KEY: &str = "bilibili_default_user_id";
+
+const DEFAULT_ACCOUNT_ORDER: &str =
+  "ORDER BY (user_id = (SELECT CAST(value AS INTEGER) FROM app_settings WHERE key = 'bilibili_default_user_id')) DESC, login_time DESC";
+
 pub struct LoginStore {
   file_path: PathBuf,
 }
@@ -60,6 +80,180 @@ impl LoginStore {
     Ok(auth_info)
   }
 
+  pub fn load_auth_info_for(
+    &self,
+    db: &Db,
+    user_id: Option<i64>,
+  ) -> Result<Option<AuthInfo>, LoginStoreError> {
+    let user_id = match user_id {
+      Some(user_id) => user_id,
+      None => return self.load_auth_info(db),
+    };
+    if let Ok(Some(auth_info)) = self.load_from_file() {
+      if auth_info.user_id == Some(user_id) {
+        return Ok(Some(auth_info));
+      }
+    }
+    self.load_account_from_db(db, user_id)
+  }
+
+  pub fn load_auth_info_or_default(
+    &self,
+    db: &Db,
+    user_id: Option<i64>,
+    log_path: &Path,
+  ) -> Option<AuthInfo> {
+    if let Some(user_id) = user_id {
+      match self.load_auth_info_for(db, Some(user_id)) {
+        Ok(Some(auth_info)) => return Some(auth_info),
+        Ok(None) => append_log(
+          log_path,
+          &format!("auth_account_fallback user_id={} reason=not_found", user_id),
+        ),
+        Err(err) => append_log(
+          log_path,
+          &format!("auth_account_fallback user_id={} err={}", user_id, err),
+        ),
+      }
+    }
+    self.load_auth_info(db).ok().flatten()
+  }
+
+  pub fn load_login_data_for(
+    &self,
+    db: &Db,
+    user_id: Option<i64>,
+  ) -> Result<Option<Value>, LoginStoreError> {
+    let user_id = match user_id {
+      Some(user_id) => user_id,
+      None => return self.load_login_data(db),
+    };
+    let cookie_info = db.with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT cookie_info FROM login_info WHERE user_id = ?1",
+          [user_id],
+          |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()
+    })?;
+    match cookie_info.flatten() {
+      Some(info) => Ok(Some(serde_json::from_str(&info)?)),
+      None => Ok(None),
+    }
+  }
+
+  pub fn load_refresh_token_for(
+    &self,
+    db: &Db,
+    user_id: Option<i64>,
+  ) -> Result<Option<String>, LoginStoreError> {
+    let user_id = match user_id {
+      Some(user_id) => user_id,
+      None => return self.load_refresh_token(db),
+    };
+    if let Some(data) = self.load_login_data_for(db, Some(user_id))? {
+      if let Some(token) = extract_refresh_token(&data) {
+        return Ok(Some(token));
+      }
+    }
+    let token = db.with_conn(|conn| {
+      conn
+        .query_row(
+          "SELECT refresh_token FROM login_info WHERE user_id = ?1",
+          [user_id],
+          |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()
+    })?;
+    Ok(token.flatten())
+  }
+
+  pub fn list_accounts(&self, db: &Db) -> Result<Vec<LoginAccount>, LoginStoreError> {
+    let default_user_id = self.default_user_id(db)?;
+    let accounts = db.with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT user_id, username, nickname, avatar_url, login_time, expire_time FROM login_info ORDER BY create_time ASC",
+      )?;
+      let rows = stmt.query_map([], |row| {
+        let user_id: i64 = row.get(0)?;
+        Ok(LoginAccount {
+          user_id,
+          username: row.get(1)?,
+          nickname: row.get(2)?,
+          avatar_url: row.get(3)?,
+          login_time: row.get(4)?,
+          expire_time: row.get(5)?,
+          is_default: Some(user_id) == default_user_id,
+        })
+      })?;
+      rows.collect::<Result<Vec<_>, _>>()
+    })?;
+    Ok(accounts)
+  }
+
+  pub fn default_user_id(&self, db: &Db) -> Result<Option<i64>, LoginStoreError> {
+    let user_id = db.with_conn(|conn| {
+      conn
+        .query_row(
+          &format!("SELECT user_id FROM login_info {} LIMIT 1", DEFAULT_ACCOUNT_ORDER),
+          [],
+          |row| row.get::<_, i64>(0),
+        )
+        .optional()
+    })?;
+    Ok(user_id)
+  }
+
+  pub fn set_default_account(&self, db: &Db, user_id: i64) -> Result<bool, LoginStoreError> {
+    let data = match self.load_login_data_for(db, Some(user_id))? {
+      Some(data) => data,
+      None => return Ok(false),
+    };
+    self.write_default_account(db, user_id, &data)?;
+    Ok(true)
+  }
+
+  pub fn remove_account(&self, db: &Db, user_id: i64) -> Result<(), LoginStoreError> {
+    let was_default = self.default_user_id(db)? == Some(user_id);
+    db.with_conn(|conn| {
+      conn.execute("DELETE FROM login_info WHERE user_id = ?1", [user_id])?;
+      if was_default {
+        conn.execute(
+          "DELETE FROM app_settings WHERE key = ?1",
+          [DEFAULT_ACCOUNT_SETTING_KEY],
+        )?;
+      }
+      Ok(())
+    })?;
+    if was_default {
+      if self.file_path.exists() {
+        fs::remove_file(&self.file_path)?;
+      }
+      if let Some(next_user_id) = self.default_user_id(db)? {
+        self.set_default_account(db, next_user_id)?;
+      }
+    }
+    Ok(())
+  }
+
+  pub fn update_account_login_info(
+    &self,
+    db: &Db,
+    login_data: &Value,
+  ) -> Result<Option<i64>, LoginStoreError> {
+    let user_id = match extract_user_id(login_data) {
+      Some(user_id) => user_id,
+      None => return self.save_login_info(db, login_data),
+    };
+    let default_user_id = self.default_user_id(db)?;
+    if default_user_id.is_none() || default_user_id == Some(user_id) {
+      return self.save_login_info(db, login_data);
+    }
+    self.upsert_login_info(db, user_id, login_data)?;
+    Ok(Some(user_id))
+  }
+
   pub fn load_login_data(&self, db: &Db) -> Result<Option<Value>, LoginStoreError> {
     if let Some(data) = self.load_login_data_from_file()? {
       return Ok(Some(data));
@@ -67,7 +261,7 @@ impl LoginStore {
 
     let record = db.with_conn(|conn| {
       let mut stmt =
-        conn.prepare("SELECT cookie_info FROM login_info ORDER BY login_time DESC LIMIT 1")?;
+        conn.prepare(&format!("SELECT cookie_info FROM login_info {} LIMIT 1", DEFAULT_ACCOUNT_ORDER))?;
       let mut rows = stmt.query([])?;
       if let Some(row) = rows.next()? {
         let cookie_info: String = row.get(0)?;
@@ -94,7 +288,7 @@ impl LoginStore {
 
     let record = db.with_conn(|conn| {
       let mut stmt =
-        conn.prepare("SELECT refresh_token FROM login_info ORDER BY login_time DESC LIMIT 1")?;
+        conn.prepare(&format!("SELECT refresh_token FROM login_info {} LIMIT 1", DEFAULT_ACCOUNT_ORDER))?;
       let mut rows = stmt.query([])?;
       if let Some(row) = rows.next()? {
         let refresh_token: Option<String> = row.get(0)?;
@@ -108,18 +302,53 @@ impl LoginStore {
   }
 
   pub fn save_login_info(&self, db: &Db, login_data: &Value) -> Result<Option<i64>, LoginStoreError> {
+    let user_id = match extract_user_id(login_data) {
+      Some(user_id) => user_id,
+      None => {
+        self.write_login_file(login_data)?;
+        return Ok(None);
+      }
+    };
+    self.upsert_login_info(db, user_id, login_data)?;
+    self.write_default_account(db, user_id, login_data)?;
+    Ok(Some(user_id))
+  }
+
+  fn write_login_file(&self, login_data: &Value) -> Result<(), LoginStoreError> {
     let login_time_ms = Utc::now().timestamp_millis();
     let file_value = json!({
       "loginTime": login_time_ms,
       "data": login_data,
     });
     fs::write(&self.file_path, serde_json::to_string(&file_value)?)?;
+    Ok(())
+  }
 
-    let user_id = extract_user_id(login_data);
-    if user_id.is_none() {
-      return Ok(None);
-    }
+  fn write_default_account(
+    &self,
+    db: &Db,
+    user_id: i64,
+    login_data: &Value,
+  ) -> Result<(), LoginStoreError> {
+    self.write_login_file(login_data)?;
+    let now = Utc::now().to_rfc3339();
+    db.with_conn(|conn| {
+      conn.execute(
+        "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, ?3) \
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        (DEFAULT_ACCOUNT_SETTING_KEY, user_id.to_string(), &now),
+      )?;
+      Ok(())
+    })?;
+    Ok(())
+  }
 
+  fn upsert_login_info(
+    &self,
+    db: &Db,
+    user_id_value: i64,
+    login_data: &Value,
+  ) -> Result<(), LoginStoreError> {
     let now = Utc::now();
     let expire_time = extract_expire_time(login_data)
       .unwrap_or_else(|| now + Duration::hours(24));
@@ -133,7 +362,6 @@ impl LoginStore {
 
     let cookie_info = serde_json::to_string(login_data)?;
 
-    let user_id_value = user_id.unwrap();
     let login_time_str = now.to_rfc3339();
     let expire_time_str = expire_time.to_rfc3339();
     let create_time_str = now.to_rfc3339();
@@ -170,18 +398,20 @@ impl LoginStore {
       Ok(())
     })?;
 
-    Ok(Some(user_id_value))
+    Ok(())
   }
 
   pub fn logout(&self, db: &Db) -> Result<(), LoginStoreError> {
-    if self.file_path.exists() {
-      fs::remove_file(&self.file_path)?;
-    }
-    db.with_conn(|conn| {
-      conn.execute("DELETE FROM login_info", [])?;
-      Ok(())
-    })?;
-    Ok(())
+    let user_id = match self.default_user_id(db)? {
+      Some(user_id) => user_id,
+      None => {
+        if self.file_path.exists() {
+          fs::remove_file(&self.file_path)?;
+        }
+        return Ok(());
+      }
+    };
+    self.remove_account(db, user_id)
   }
 
   fn load_from_file(&self) -> Result<Option<AuthInfo>, LoginStoreError> {
@@ -213,11 +443,32 @@ impl LoginStore {
   }
 
   fn load_from_db(&self, db: &Db) -> Result<Option<AuthInfo>, LoginStoreError> {
+    self.load_auth_record(db, None)
+  }
+
+  fn load_account_from_db(&self, db: &Db, user_id: i64) -> Result<Option<AuthInfo>, LoginStoreError> {
+    self.load_auth_record(db, Some(user_id))
+  }
+
+  fn load_auth_record(
+    &self,
+    db: &Db,
+    user_id: Option<i64>,
+  ) -> Result<Option<AuthInfo>, LoginStoreError> {
     let record = db.with_conn(|conn| {
-      let mut stmt = conn.prepare(
-        "SELECT user_id, cookie_info, expire_time FROM login_info ORDER BY login_time DESC LIMIT 1",
-      )?;
-      let mut rows = stmt.query([])?;
+      let mut stmt = match user_id {
+        Some(_) => conn.prepare(
+          "SELECT user_id, cookie_info, expire_time FROM login_info WHERE user_id = ?1",
+        )?,
+        None => conn.prepare(&format!(
+          "SELECT user_id, cookie_info, expire_time FROM login_info {} LIMIT 1",
+          DEFAULT_ACCOUNT_ORDER
+        ))?,
+      };
+      let mut rows = match user_id {
+        Some(user_id) => stmt.query([user_id])?,
+        None => stmt.query([])?,
+      };
       if let Some(row) = rows.next()? {
         let user_id: i64 = row.get(0)?;
         let cookie_info: String = row.get(1)?;
@@ -3,14 +3,23 @@ use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
+use serde::Serialize;
+
+use crate::commands::settings::EncoderSettings;
 use crate::config::resolve_ffprobe_path;
-use crate::ffmpeg::{run_ffmpeg, run_ffprobe_json};
+use crate::danmaku_ass::{escape_filter_path, resolve_burn_subtitle, DanmakuAssOptions};
+use crate::encoder::{build_encode_plan, resolve_fps_filter, EncodePlan};
+use crate::ffmpeg::{run_ffmpeg, run_ffmpeg_capture_stderr, run_ffprobe_json};
+use crate::utils::append_log;
 
 const START_DIFF_THRESHOLD_SECONDS: f64 = 1.0;
 const TIMESTAMP_GAP_THRESHOLD_SECONDS: f64 = 2.0;
 const NEGATIVE_JUMP_THRESHOLD_SECONDS: f64 = -0.5;
+const SEGMENT_MIN_TAIL_SECONDS: f64 = 10.0;
+const SILENCE_KEYFRAME_TOLERANCE_SECONDS: f64 = 1.0;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ClipSource {
   pub input_path: String,
   pub start_time: Option<String>,
@@ -18,6 +27,54 @@ pub struct ClipSource {
   pub order: i64,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SegmentMode {
+  Fixed,
+  Silence,
+  Scene,
+}
+
+impl SegmentMode {
+  pub fn parse(value: Option<&str>) -> Self {
+    match value.map(|value| value.trim().to_ascii_uppercase()).as_deref() {
+      Some("SILENCE") => SegmentMode::Silence,
+      Some("SCENE") => SegmentMode::Scene,
+      _ => SegmentMode::Fixed,
+    }
+  }
+
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      SegmentMode::Fixed => "FIXED",
+      SegmentMode::Silence => "SILENCE",
+      SegmentMode::Scene => "SCENE",
+    }
+  }
+}
+
+#[derive(Clone, Copy)]
+pub struct SegmentOptions {
+  pub segment_seconds: i64,
+  pub mode: SegmentMode,
+  pub search_window_seconds: f64,
+  pub silence_noise_db: f64,
+  pub silence_min_seconds: f64,
+  pub scene_threshold: f64,
+}
+
+impl Default for SegmentOptions {
+  fn default() -> Self {
+    Self {
+      segment_seconds: 133,
+      mode: SegmentMode::Fixed,
+      search_window_seconds: 20.0,
+      silence_noise_db: -35.0,
+      silence_min_seconds: 0.4,
+      scene_threshold: 0.4,
+    }
+  }
+}
+
 pub struct ClipCopyDecision {
   pub use_copy: bool,
   pub reason: Option<String>,
@@ -27,18 +84,33 @@ pub fn clip_sources(
   sources: &[ClipSource],
   output_dir: &Path,
   use_copy: bool,
+  burn_danmaku: bool,
+  encoder_settings: &EncoderSettings,
 ) -> Result<Vec<PathBuf>, String> {
   fs::create_dir_all(output_dir).map_err(|err| format!("Failed to create output dir: {}", err))?;
 
+  let use_copy = use_copy && !burn_danmaku;
   let transcode_profile = if use_copy {
     None
   } else {
-    build_transcode_profile(sources)?
+    build_transcode_profile(sources, encoder_settings)?
   };
+  let danmaku_options = DanmakuAssOptions::default();
   let mut outputs = Vec::new();
   for source in sources {
     let output_path = output_dir.join(format!("clip_{:03}.mp4", source.order));
-    clip_single(source, &output_path, use_copy, transcode_profile.as_ref())?;
+    let subtitle_path = if burn_danmaku {
+      resolve_burn_subtitle(Path::new(&source.input_path), &danmaku_options)?
+    } else {
+      None
+    };
+    clip_single(
+      source,
+      &output_path,
+      use_copy,
+      transcode_profile.as_ref(),
+      subtitle_path.as_deref(),
+    )?;
     outputs.push(output_path);
   }
 
@@ -101,6 +173,8 @@ struct ClipTranscodeProfile {
   width: i64,
   height: i64,
   normalize_video: bool,
+  fps_filter: Option<String>,
+  encode_plan: EncodePlan,
 }
 
 fn parse_fraction(value: &str) -> Option<f64> {
@@ -203,7 +277,10 @@ fn probe_media_info(path: &Path) -> Result<MediaProbeInfo, String> {
   Ok(MediaProbeInfo { video, audio })
 }
 
-fn build_transcode_profile(sources: &[ClipSource]) -> Result<Option<ClipTranscodeProfile>, String> {
+fn build_transcode_profile(
+  sources: &[ClipSource],
+  encoder_settings: &EncoderSettings,
+) -> Result<Option<ClipTranscodeProfile>, String> {
   if sources.is_empty() {
     return Ok(None);
   }
@@ -228,10 +305,14 @@ fn build_transcode_profile(sources: &[ClipSource]) -> Result<Option<ClipTranscod
   let normalize_video = sources.len() > 1
     && infos.iter().any(|info| info.video.width != target.0 || info.video.height != target.1);
 
+  let max_fps = infos.iter().map(|info| info.video.fps).fold(0.0, f64::max);
+
   Ok(Some(ClipTranscodeProfile {
     width: target.0,
     height: target.1,
     normalize_video,
+    fps_filter: resolve_fps_filter(encoder_settings, max_fps),
+    encode_plan: build_encode_plan(encoder_settings),
   }))
 }
 
@@ -398,6 +479,18 @@ fn build_read_intervals(source: &ClipSource) -> Option<String> {
   }
 }
 
+pub fn format_timecode_seconds(seconds: f64) -> String {
+  let total = if seconds.is_finite() { seconds.max(0.0) } else { 0.0 };
+  let hours = (total / 3600.0).floor() as i64;
+  let minutes = ((total - (hours as f64 * 3600.0)) / 60.0).floor() as i64;
+  let secs = total - (hours as f64 * 3600.0) - (minutes as f64 * 60.0);
+  if secs.fract().abs() < 0.001 {
+    format!("{:02}:{:02}:{:02}", hours, minutes, secs.floor() as i64)
+  } else {
+    format!("{:02}:{:02}:{:06.3}", hours, minutes, secs)
+  }
+}
+
 pub fn parse_time_to_seconds(value: &str) -> Option<f64> {
   let trimmed = value.trim();
   if trimmed.is_empty() || trimmed == "00:00:00" {
@@ -586,24 +679,51 @@ fn merge_last_short_segment(outputs: &mut Vec<PathBuf>, min_seconds: f64) -> Res
 pub fn segment_file(
   input_path: &Path,
   output_dir: &Path,
-  segment_seconds: i64,
-) -> Result<Vec<PathBuf>, String> {
+  options: &SegmentOptions,
+  log_path: &Path,
+) -> Result<Vec<(PathBuf, f64, f64)>, String> {
   fs::create_dir_all(output_dir).map_err(|err| format!("Failed to create segment dir: {}", err))?;
 
+  let (cut_points, duration) = match options.mode {
+    SegmentMode::Fixed => (Vec::new(), 0.0),
+    _ => match plan_segment_cut_points(input_path, options) {
+      Ok(plan) => plan,
+      Err(err) => {
+        append_log(
+          log_path,
+          &format!(
+            "segment_plan_fail input={} mode={} fallback=FIXED err={}",
+            input_path.to_string_lossy(),
+            options.mode.as_str(),
+            err
+          ),
+        );
+        (Vec::new(), 0.0)
+      }
+    },
+  };
   let output_pattern = output_dir.join("part_%03d.mp4");
-  let args = vec![
+  let mut args = vec![
     "-i".to_string(),
     input_path.to_string_lossy().to_string(),
     "-c".to_string(),
     "copy".to_string(),
     "-f".to_string(),
     "segment".to_string(),
-    "-segment_time".to_string(),
-    segment_seconds.to_string(),
+  ];
+  if cut_points.is_empty() {
+    args.push("-segment_time".to_string());
+    args.push(options.segment_seconds.max(1).to_string());
+  } else {
+    let times: Vec<String> = cut_points.iter().map(|value| format!("{:.3}", value)).collect();
+    args.push("-segment_times".to_string());
+    args.push(times.join(","));
+  }
+  args.extend([
     "-reset_timestamps".to_string(),
     "1".to_string(),
     output_pattern.to_string_lossy().to_string(),
-  ];
+  ]);
 
   run_ffmpeg(&args)?;
 
@@ -615,8 +735,250 @@ pub fn segment_file(
     .collect();
 
   outputs.sort();
-  merge_last_short_segment(&mut outputs, 10.0)?;
-  Ok(outputs)
+  merge_last_short_segment(&mut outputs, SEGMENT_MIN_TAIL_SECONDS)?;
+  if !cut_points.is_empty() {
+    if let Some(bounds) = planned_segment_bounds(&outputs, &cut_points, duration) {
+      return Ok(bounds);
+    }
+    append_log(
+      log_path,
+      &format!(
+        "segment_plan_mismatch input={} mode={} planned={} outputs={}",
+        input_path.to_string_lossy(),
+        options.mode.as_str(),
+        cut_points.len() + 1,
+        outputs.len()
+      ),
+    );
+  }
+  probed_segment_bounds(outputs)
+}
+
+pub fn whole_segment_bounds(path: &Path) -> Result<(PathBuf, f64, f64), String> {
+  let duration = probe_duration_seconds(path)?;
+  Ok((path.to_path_buf(), 0.0, duration))
+}
+
+fn planned_segment_bounds(
+  outputs: &[PathBuf],
+  cut_points: &[f64],
+  duration: f64,
+) -> Option<Vec<(PathBuf, f64, f64)>> {
+  if outputs.len() != cut_points.len() + 1 && outputs.len() != cut_points.len() {
+    return None;
+  }
+  let last = outputs.len() - 1;
+  Some(
+    outputs
+      .iter()
+      .enumerate()
+      .map(|(index, output)| {
+        let start = if index == 0 { 0.0 } else { cut_points[index - 1] };
+        let end = if index == last { duration } else { cut_points[index] };
+        (output.clone(), start, end)
+      })
+      .collect(),
+  )
+}
+
+fn probed_segment_bounds(outputs: Vec<PathBuf>) -> Result<Vec<(PathBuf, f64, f64)>, String> {
+  let mut offset = 0.0;
+  let mut bounds = Vec::with_capacity(outputs.len());
+  for output in outputs {
+    let start = offset;
+    offset += probe_duration_seconds(&output)?;
+    bounds.push((output, start, offset));
+  }
+  Ok(bounds)
+}
+
+fn plan_segment_cut_points(
+  input_path: &Path,
+  options: &SegmentOptions,
+) -> Result<(Vec<f64>, f64), String> {
+  let segment_seconds = options.segment_seconds.max(1) as f64;
+  let duration = probe_duration_seconds(input_path)?;
+  if duration <= segment_seconds + SEGMENT_MIN_TAIL_SECONDS {
+    return Ok((Vec::new(), duration));
+  }
+  let keyframes = probe_keyframe_times(input_path)?;
+  let windows = match options.mode {
+    SegmentMode::Silence => detect_silence_windows(input_path, options)?,
+    SegmentMode::Scene => detect_scene_changes(input_path, options)?
+      .into_iter()
+      .map(|time| (time, time))
+      .collect(),
+    SegmentMode::Fixed => Vec::new(),
+  };
+
+  let window = options.search_window_seconds.max(0.0).min(segment_seconds / 2.0);
+  let mut points = Vec::new();
+  let mut last = 0.0;
+  while last + segment_seconds < duration - SEGMENT_MIN_TAIL_SECONDS {
+    let target = last + segment_seconds;
+    let lower = (target - window).max(last + 1.0);
+    let upper = (target + window).min(duration - SEGMENT_MIN_TAIL_SECONDS);
+    let chosen = pick_cut_point(&keyframes, &windows, lower, upper, target)
+      .or_else(|| nearest_keyframe(&keyframes, target, lower, upper))
+      .unwrap_or(target);
+    if chosen <= last {
+      break;
+    }
+    points.push(chosen);
+    last = chosen;
+  }
+  Ok((points, duration))
+}
+
+fn pick_cut_point(
+  keyframes: &[f64],
+  windows: &[(f64, f64)],
+  lower: f64,
+  upper: f64,
+  target: f64,
+) -> Option<f64> {
+  let mut best: Option<f64> = None;
+  for (start, end) in windows {
+    let range_start = (start - SILENCE_KEYFRAME_TOLERANCE_SECONDS).max(lower);
+    let range_end = (end + SILENCE_KEYFRAME_TOLERANCE_SECONDS).min(upper);
+    if range_start > range_end {
+      continue;
+    }
+    let middle = ((start + end) / 2.0).clamp(range_start, range_end);
+    let candidate = if keyframes.is_empty() {
+      Some(middle)
+    } else {
+      nearest_keyframe(keyframes, middle, range_start, range_end)
+    };
+    if let Some(candidate) = candidate {
+      let closer = best
+        .map(|current| (candidate - target).abs() < (current - target).abs())
+        .unwrap_or(true);
+      if closer {
+        best = Some(candidate);
+      }
+    }
+  }
+  best
+}
+
+fn nearest_keyframe(keyframes: &[f64], time: f64, lower: f64, upper: f64) -> Option<f64> {
+  keyframes
+    .iter()
+    .copied()
+    .filter(|value| *value >= lower && *value <= upper)
+    .min_by(|a, b| {
+      (a - time)
+        .abs()
+        .partial_cmp(&(b - time).abs())
+        .unwrap_or(std::cmp::Ordering::Equal)
+    })
+}
+
+fn probe_keyframe_times(path: &Path) -> Result<Vec<f64>, String> {
+  let ffprobe_path = resolve_ffprobe_path();
+  let mut child = Command::new(ffprobe_path)
+    .arg("-v")
+    .arg("error")
+    .arg("-select_streams")
+    .arg("v:0")
+    .arg("-show_entries")
+    .arg("packet=pts_time,flags")
+    .arg("-of")
+    .arg("compact=p=0:nk=1")
+    .arg(path)
+    .stdout(Stdio::piped())
+    .stderr(Stdio::null())
+    .spawn()
+    .map_err(|err| format!("Failed to start FFprobe: {}", err))?;
+
+  let stdout = child
+    .stdout
+    .take()
+    .ok_or_else(|| "Failed to capture FFprobe stdout".to_string())?;
+  let mut keyframes = Vec::new();
+  for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+    let mut parts = line.trim().split('|');
+    let pts = parts.next().and_then(|item| item.parse::<f64>().ok());
+    let is_key = parts.next().map(|flags| flags.contains('K')).unwrap_or(false);
+    if let (Some(pts), true) = (pts, is_key) {
+      keyframes.push(pts);
+    }
+  }
+
+  let status = child
+    .wait()
+    .map_err(|err| format!("Failed to wait FFprobe: {}", err))?;
+  if !status.success() {
+    return Err("FFprobe failed".to_string());
+  }
+  keyframes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+  Ok(keyframes)
+}
+
+fn detect_silence_windows(path: &Path, options: &SegmentOptions) -> Result<Vec<(f64, f64)>, String> {
+  let args = vec![
+    "-hide_banner".to_string(),
+    "-nostats".to_string(),
+    "-i".to_string(),
+    path.to_string_lossy().to_string(),
+    "-vn".to_string(),
+    "-af".to_string(),
+    format!(
+      "silencedetect=noise={}dB:d={}",
+      options.silence_noise_db,
+      options.silence_min_seconds.max(0.1)
+    ),
+    "-f".to_string(),
+    "null".to_string(),
+    "-".to_string(),
+  ];
+  let output = run_ffmpeg_capture_stderr(&args)?;
+  let mut windows = Vec::new();
+  let mut pending_start: Option<f64> = None;
+  for line in output.lines() {
+    if let Some(value) = extract_float_after(line, "silence_start:") {
+      pending_start = Some(value);
+    } else if let Some(value) = extract_float_after(line, "silence_end:") {
+      if let Some(start) = pending_start.take() {
+        windows.push((start.max(0.0), value));
+      }
+    }
+  }
+  Ok(windows)
+}
+
+fn detect_scene_changes(path: &Path, options: &SegmentOptions) -> Result<Vec<f64>, String> {
+  let args = vec![
+    "-hide_banner".to_string(),
+    "-nostats".to_string(),
+    "-skip_frame".to_string(),
+    "nokey".to_string(),
+    "-i".to_string(),
+    path.to_string_lossy().to_string(),
+    "-an".to_string(),
+    "-vf".to_string(),
+    format!("select='gt(scene,{})',showinfo", options.scene_threshold.clamp(0.01, 1.0)),
+    "-f".to_string(),
+    "null".to_string(),
+    "-".to_string(),
+  ];
+  let output = run_ffmpeg_capture_stderr(&args)?;
+  Ok(
+    output
+      .lines()
+      .filter(|line| line.contains("showinfo"))
+      .filter_map(|line| extract_float_after(line, "pts_time:"))
+      .collect(),
+  )
+}
+
+fn extract_float_after(line: &str, key: &str) -> Option<f64> {
+  let index = line.find(key)?;
+  line[index + key.len()..]
+    .split_whitespace()
+    .next()
+    .and_then(|value| value.parse::<f64>().ok())
 }
 
 fn clip_single(
@@ -624,8 +986,13 @@ fn clip_single(
   output_path: &Path,
   use_copy: bool,
   profile: Option<&ClipTranscodeProfile>,
+  subtitle_path: Option<&Path>,
 ) -> Result<(), String> {
-  let mut args = vec!["-i".to_string(), source.input_path.clone()];
+  let mut args = Vec::new();
+  if let (false, Some(profile)) = (use_copy, profile) {
+    args.extend(profile.encode_plan.input_args.iter().cloned());
+  }
+  args.extend(["-i".to_string(), source.input_path.clone()]);
 
   if let Some(start) = source.start_time.as_deref() {
     if !start.is_empty() && start != "00:00:00" {
@@ -646,7 +1013,9 @@ fn clip_single(
   } else {
     if let Some(profile) = profile {
       let mut filters = Vec::new();
-      filters.push("fps=60".to_string());
+      if let Some(fps_filter) = profile.fps_filter.as_ref() {
+        filters.push(fps_filter.clone());
+      }
       if profile.normalize_video && profile.width > 0 && profile.height > 0 {
         filters.push(format!(
           "scale={}:{}:force_original_aspect_ratio=decrease",
@@ -657,32 +1026,38 @@ fn clip_single(
           profile.width, profile.height
         ));
       }
+      if let Some(subtitle_path) = subtitle_path {
+        filters.push(format!("subtitles='{}'", escape_filter_path(subtitle_path)));
+      }
+      if let Some(upload_filter) = profile.encode_plan.upload_filter.as_ref() {
+        filters.push(upload_filter.clone());
+      }
       if !filters.is_empty() {
         args.push("-vf".to_string());
         args.push(filters.join(","));
       }
       args.push("-af".to_string());
       args.push("aresample=48000:async=1:first_pts=0".to_string());
-      args.extend([
-        "-c:v".to_string(),
-        "h264_videotoolbox".to_string(),
-        "-b:v".to_string(),
-        "5M".to_string(),
-        "-c:a".to_string(),
-        "aac".to_string(),
-        "-ar".to_string(),
-        "48000".to_string(),
-      ]);
+      args.extend(profile.encode_plan.video_args.iter().cloned());
+      args.extend(profile.encode_plan.audio_args.iter().cloned());
     }
   }
   args.push(output_path.to_string_lossy().to_string());
 
   let args_line = args.join(" ");
+  let encoder = match (use_copy, profile) {
+    (false, Some(profile)) => format!(
+      "{} fallback={}",
+      profile.encode_plan.encoder.name, profile.encode_plan.encoder.fallback
+    ),
+    _ => "copy".to_string(),
+  };
   run_ffmpeg(&args).map_err(|err| {
     format!(
-      "clip_ffmpeg_fail input={} output={} args={} err={}",
+      "clip_ffmpeg_fail input={} output={} encoder={} args={} err={}",
       source.input_path,
       output_path.to_string_lossy(),
+      encoder,
       args_line,
       err
     )
@@ -0,0 +1,133 @@
+use chrono::{DateTime, Datelike, Local, Timelike};
+use regex::Regex;
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+pub struct RecordSchedule {
+  weekdays: Vec<u32>,
+  windows: Vec<(u32, u32)>,
+  pub max_record_minutes: i64,
+  title_regex: Option<Regex>,
+}
+
+impl RecordSchedule {
+  pub fn parse(
+    weekdays: Option<&str>,
+    windows: Option<&str>,
+    max_record_minutes: Option<i64>,
+    title_regex: Option<&str>,
+  ) -> Result<Self, String> {
+    let title_regex = match title_regex.map(str::trim).filter(|value| !value.is_empty()) {
+      Some(pattern) => Some(Regex::new(pattern).map_err(|err| format!("标题正则无效: {}", err))?),
+      None => None,
+    };
+    Ok(Self {
+      weekdays: parse_weekdays(weekdays.unwrap_or_default())?,
+      windows: parse_time_windows(windows.unwrap_or_default())?,
+      max_record_minutes: max_record_minutes.unwrap_or(0).max(0),
+      title_regex,
+    })
+  }
+
+  pub fn check(&self, now: DateTime<Local>, title: &str) -> Result<(), &'static str> {
+    if !self.in_window(now) {
+      return Err("out_of_schedule");
+    }
+    if let Some(regex) = self.title_regex.as_ref() {
+      if !regex.is_match(title) {
+        return Err("title_mismatch");
+      }
+    }
+    Ok(())
+  }
+
+  fn in_window(&self, now: DateTime<Local>) -> bool {
+    let today = now.weekday().number_from_monday();
+    let yesterday = now.weekday().pred().number_from_monday();
+    let minute = now.hour() * 60 + now.minute();
+    let day_allowed = |day: u32| self.weekdays.is_empty() || self.weekdays.contains(&day);
+    if self.windows.is_empty() {
+      return day_allowed(today);
+    }
+    self.windows.iter().any(|&(start, end)| {
+      if start < end {
+        day_allowed(today) && minute >= start && minute < end
+      } else {
+        (day_allowed(today) && minute >= start) || (day_allowed(yesterday) && minute < end)
+      }
+    })
+  }
+}
+
+pub fn normalize_weekdays(value: &str) -> Result<Option<String>, String> {
+  let days = parse_weekdays(value)?;
+  if days.is_empty() {
+    return Ok(None);
+  }
+  Ok(Some(
+    days.iter().map(|day| day.to_string()).collect::<Vec<_>>().join(","),
+  ))
+}
+
+pub fn normalize_time_windows(value: &str) -> Result<Option<String>, String> {
+  let windows = parse_time_windows(value)?;
+  if windows.is_empty() {
+    return Ok(None);
+  }
+  Ok(Some(
+    windows
+      .iter()
+      .map(|&(start, end)| format!("{}-{}", format_minute(start), format_minute(end)))
+      .collect::<Vec<_>>()
+      .join(","),
+  ))
+}
+
+fn parse_weekdays(value: &str) -> Result<Vec<u32>, String> {
+  let mut days = Vec::new();
+  for item in value.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+    let day = item
+      .parse::<u32>()
+      .ok()
+      .filter(|day| (1..=7).contains(day))
+      .ok_or_else(|| format!("星期取值无效: {}", item))?;
+    if !days.contains(&day) {
+      days.push(day);
+    }
+  }
+  days.sort_unstable();
+  if days.len() == 7 {
+    days.clear();
+  }
+  Ok(days)
+}
+
+pub(crate) fn parse_time_windows(value: &str) -> Result<Vec<(u32, u32)>, String> {
+  let mut windows = Vec::new();
+  for item in value.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+    let (start, end) = item
+      .split_once('-')
+      .ok_or_else(|| format!("时间段格式无效: {}", item))?;
+    let start = parse_minute(start).ok_or_else(|| format!("时间段格式无效: {}", item))?;
+    let end = parse_minute(end).ok_or_else(|| format!("时间段格式无效: {}", item))?;
+    if start == end {
+      return Err(format!("时间段起止时间不能相同: {}", item));
+    }
+    windows.push((start, end % MINUTES_PER_DAY));
+  }
+  Ok(windows)
+}
+
+fn parse_minute(value: &str) -> Option<u32> {
+  let (hour, minute) = value.trim().split_once(':')?;
+  let hour = hour.trim().parse::<u32>().ok()?;
+  let minute = minute.trim().parse::<u32>().ok()?;
+  if minute >= 60 || hour > 24 || (hour == 24 && minute > 0) {
+    return None;
+  }
+  Some(hour * 60 + minute)
+}
+
+fn format_minute(value: u32) -> String {
+  format!("{:02}:{:02}", value / 60, value % 60)
+}
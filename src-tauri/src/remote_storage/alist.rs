@@ -0,0 +1,226 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::blocking::Client;
+use serde_json::{json, Value};
+
+use crate::baidu_sync::{join_baidu_path, normalize_baidu_path};
+
+use super::{
+  build_http_client, counting_body, describe_http_error, finish_part_file, open_local_file,
+  parent_remote_path, prepare_local_target, remote_file_name, run_with_progress, should_skip_upload,
+  throttled_copy, RemoteDir, RemoteFileMeta, RemoteStorage, BACKEND_ALIST,
+};
+
+const FILE_PATH_ENCODE: &AsciiSet = &NON_ALPHANUMERIC
+  .remove(b'-')
+  .remove(b'_')
+  .remove(b'.')
+  .remove(b'~')
+  .remove(b'/');
+
+pub struct AlistStorage {
+  client: Client,
+  base_url: String,
+  token: String,
+}
+
+impl AlistStorage {
+  pub fn new(url: &str, token: &str) -> Result<Self, String> {
+    let base_url = url.trim().trim_end_matches('/').to_string();
+    if base_url.is_empty() {
+      return Err("请先配置 Alist 地址".to_string());
+    }
+    url::Url::parse(&base_url).map_err(|_| "Alist 地址无效".to_string())?;
+    Ok(Self {
+      client: build_http_client()?,
+      base_url,
+      token: token.trim().to_string(),
+    })
+  }
+
+  fn call(&self, api: &str, body: Value) -> Result<Value, String> {
+    let response = self
+      .client
+      .post(format!("{}{}", self.base_url, api))
+      .header("Authorization", &self.token)
+      .json(&body)
+      .send()
+      .map_err(|err| format!("Alist 请求失败: {}", err))?;
+    parse_alist_response(response)
+  }
+
+  fn get(&self, remote_path: &str) -> Result<Option<Value>, String> {
+    match self.call(
+      "/api/fs/get",
+      json!({ "path": normalize_baidu_path(remote_path) }),
+    ) {
+      Ok(data) => Ok(Some(data)),
+      Err(err) if is_alist_not_found(&err) => Ok(None),
+      Err(err) => Err(err),
+    }
+  }
+}
+
+impl RemoteStorage for AlistStorage {
+  fn kind(&self) -> &'static str {
+    BACKEND_ALIST
+  }
+
+  fn upload(
+    &self,
+    local_path: &Path,
+    remote_dir: &str,
+    remote_name: &str,
+    policy: &str,
+    on_progress: &mut dyn FnMut(f64),
+  ) -> Result<(), String> {
+    let (file, total) = open_local_file(local_path)?;
+    let remote_path = join_baidu_path(remote_dir, remote_name);
+    if should_skip_upload(self, &remote_path, total, policy)? {
+      return Ok(());
+    }
+    self.mkdir(remote_dir)?;
+    let file_path = utf8_percent_encode(&remote_path, FILE_PATH_ENCODE).to_string();
+    let response = run_with_progress(total, on_progress, |counter| {
+      self
+        .client
+        .put(format!("{}/api/fs/put", self.base_url))
+        .header("Authorization", &self.token)
+        .header("File-Path", file_path)
+        .header("Content-Type", "application/octet-stream")
+        .body(counting_body(file, total, &counter))
+        .send()
+        .map_err(|err| format!("Alist 上传失败: {}", err))
+    })?;
+    parse_alist_response(response).map(|_| ())
+  }
+
+  fn download(
+    &self,
+    remote_path: &str,
+    local_path: &Path,
+    _on_spawn: &mut dyn FnMut(Arc<Mutex<Child>>),
+  ) -> Result<PathBuf, String> {
+    let data = self
+      .get(remote_path)?
+      .ok_or_else(|| format!("远程文件不存在: {}", remote_path))?;
+    let raw_url = data
+      .get("raw_url")
+      .and_then(|value| value.as_str())
+      .filter(|value| !value.is_empty())
+      .ok_or_else(|| "Alist 未返回下载地址".to_string())?;
+    let part = prepare_local_target(local_path)?;
+    let response = self
+      .client
+      .get(raw_url)
+      .send()
+      .map_err(|err| format!("Alist 下载失败: {}", err))?;
+    if !response.status().is_success() {
+      return Err(describe_http_error("Alist 下载", response));
+    }
+    let mut output = fs::File::create(&part).map_err(|err| format!("创建文件失败: {}", err))?;
+    throttled_copy(response, &mut output)
+      .map_err(|err| format!("Alist 下载失败: {}", err))?;
+    finish_part_file(&part, local_path)
+  }
+
+  fn exists(&self, remote_path: &str) -> Result<bool, String> {
+    Ok(self.get(remote_path)?.is_some())
+  }
+
+  fn meta(&self, remote_path: &str) -> Result<RemoteFileMeta, String> {
+    let data = self
+      .get(remote_path)?
+      .ok_or_else(|| format!("远程文件不存在: {}", remote_path))?;
+    let size = data.get("size").and_then(|value| value.as_u64()).unwrap_or(0);
+    let md5 = data
+      .get("hash_info")
+      .and_then(|value| value.get("md5"))
+      .and_then(|value| value.as_str())
+      .map(|value| value.trim().to_ascii_lowercase())
+      .filter(|value| value.len() == 32);
+    Ok(RemoteFileMeta { size, md5 })
+  }
+
+  fn list_dirs(&self, path: &str) -> Result<Vec<RemoteDir>, String> {
+    let data = self.call(
+      "/api/fs/list",
+      json!({
+        "path": normalize_baidu_path(path),
+        "page": 1,
+        "per_page": 0,
+        "refresh": false,
+      }),
+    )?;
+    let mut dirs: Vec<RemoteDir> = data
+      .get("content")
+      .and_then(|value| value.as_array())
+      .map(|items| {
+        items
+          .iter()
+          .filter(|item| item.get("is_dir").and_then(|value| value.as_bool()) == Some(true))
+          .filter_map(|item| item.get("name").and_then(|value| value.as_str()))
+          .map(|name| RemoteDir {
+            name: name.to_string(),
+            path: join_baidu_path(path, name),
+          })
+          .collect()
+      })
+      .unwrap_or_default();
+    dirs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(dirs)
+  }
+
+  fn mkdir(&self, path: &str) -> Result<(), String> {
+    self
+      .call("/api/fs/mkdir", json!({ "path": normalize_baidu_path(path) }))
+      .map(|_| ())
+  }
+
+  fn rename(&self, from_path: &str, to_path: &str) -> Result<(), String> {
+    let from_dir = parent_remote_path(from_path);
+    let to_dir = parent_remote_path(to_path);
+    let from_name = remote_file_name(from_path);
+    let to_name = remote_file_name(to_path);
+    if from_dir != to_dir {
+      self.call(
+        "/api/fs/move",
+        json!({ "src_dir": from_dir, "dst_dir": to_dir, "names": [from_name] }),
+      )?;
+    }
+    if from_name != to_name {
+      self.call(
+        "/api/fs/rename",
+        json!({ "path": join_baidu_path(&to_dir, &from_name), "name": to_name }),
+      )?;
+    }
+    Ok(())
+  }
+}
+
+fn parse_alist_response(response: reqwest::blocking::Response) -> Result<Value, String> {
+  if !response.status().is_success() {
+    return Err(describe_http_error("Alist 请求", response));
+  }
+  let payload: Value = response
+    .json()
+    .map_err(|err| format!("Alist 响应解析失败: {}", err))?;
+  let code = payload.get("code").and_then(|value| value.as_i64()).unwrap_or(0);
+  if code != 200 {
+    let message = payload
+      .get("message")
+      .and_then(|value| value.as_str())
+      .unwrap_or("未知错误");
+    return Err(format!("Alist 请求失败: {}", message));
+  }
+  Ok(payload.get("data").cloned().unwrap_or(Value::Null))
+}
+
+fn is_alist_not_found(err: &str) -> bool {
+  let lower = err.to_ascii_lowercase();
+  lower.contains("not found") || lower.contains("not exist")
+}
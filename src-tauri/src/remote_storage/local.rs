@@ -0,0 +1,155 @@
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+
+use crate::baidu_sync::join_baidu_path;
+
+use super::{
+  finish_part_file, open_local_file, prepare_local_target, should_skip_upload, split_remote_path,
+  RemoteDir, RemoteFileMeta, RemoteStorage, BACKEND_LOCAL,
+};
+
+const COPY_BUFFER_SIZE: usize = 1024 * 1024;
+
+pub struct LocalStorage {
+  root: PathBuf,
+}
+
+impl LocalStorage {
+  pub fn new(root: &str) -> Result<Self, String> {
+    let root = root.trim();
+    if root.is_empty() {
+      return Err("请先配置本地/NAS 同步目录".to_string());
+    }
+    let root = PathBuf::from(root);
+    if !root.is_dir() {
+      return Err(format!("同步目录不存在: {}", root.to_string_lossy()));
+    }
+    Ok(Self { root })
+  }
+
+  fn resolve(&self, remote_path: &str) -> Result<PathBuf, String> {
+    let mut path = self.root.clone();
+    for segment in split_remote_path(remote_path) {
+      if segment == "." || segment == ".." {
+        return Err(format!("远程路径无效: {}", remote_path));
+      }
+      path.push(segment);
+    }
+    Ok(path)
+  }
+}
+
+impl RemoteStorage for LocalStorage {
+  fn kind(&self) -> &'static str {
+    BACKEND_LOCAL
+  }
+
+  fn upload(
+    &self,
+    local_path: &Path,
+    remote_dir: &str,
+    remote_name: &str,
+    policy: &str,
+    on_progress: &mut dyn FnMut(f64),
+  ) -> Result<(), String> {
+    let (source, total) = open_local_file(local_path)?;
+    let remote_path = join_baidu_path(remote_dir, remote_name);
+    if should_skip_upload(self, &remote_path, total, policy)? {
+      return Ok(());
+    }
+    let target = self.resolve(&remote_path)?;
+    let part = prepare_local_target(&target)?;
+    copy_with_progress(source, &part, total, on_progress)?;
+    finish_part_file(&part, &target)?;
+    Ok(())
+  }
+
+  fn download(
+    &self,
+    remote_path: &str,
+    local_path: &Path,
+    _on_spawn: &mut dyn FnMut(Arc<Mutex<Child>>),
+  ) -> Result<PathBuf, String> {
+    let source_path = self.resolve(remote_path)?;
+    let (source, total) = open_local_file(&source_path)?;
+    let part = prepare_local_target(local_path)?;
+    copy_with_progress(source, &part, total, &mut |_| {})?;
+    finish_part_file(&part, local_path)
+  }
+
+  fn exists(&self, remote_path: &str) -> Result<bool, String> {
+    Ok(self.resolve(remote_path)?.exists())
+  }
+
+  fn meta(&self, remote_path: &str) -> Result<RemoteFileMeta, String> {
+    let metadata = fs::metadata(self.resolve(remote_path)?)
+      .map_err(|err| format!("读取远程文件失败: {}", err))?;
+    Ok(RemoteFileMeta {
+      size: metadata.len(),
+      md5: None,
+    })
+  }
+
+  fn list_dirs(&self, path: &str) -> Result<Vec<RemoteDir>, String> {
+    let dir = self.resolve(path)?;
+    let entries = fs::read_dir(&dir).map_err(|err| format!("读取目录失败: {}", err))?;
+    let mut dirs: Vec<RemoteDir> = entries
+      .flatten()
+      .filter(|entry| entry.file_type().map(|kind| kind.is_dir()).unwrap_or(false))
+      .filter_map(|entry| entry.file_name().to_str().map(|name| name.to_string()))
+      .map(|name| RemoteDir {
+        path: join_baidu_path(path, &name),
+        name,
+      })
+      .collect();
+    dirs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(dirs)
+  }
+
+  fn mkdir(&self, path: &str) -> Result<(), String> {
+    fs::create_dir_all(self.resolve(path)?).map_err(|err| format!("创建目录失败: {}", err))
+  }
+
+  fn rename(&self, from_path: &str, to_path: &str) -> Result<(), String> {
+    let target = self.resolve(to_path)?;
+    if let Some(parent) = target.parent() {
+      fs::create_dir_all(parent).map_err(|err| format!("创建目录失败: {}", err))?;
+    }
+    fs::rename(self.resolve(from_path)?, target).map_err(|err| format!("重命名失败: {}", err))
+  }
+}
+
+fn copy_with_progress(
+  mut source: fs::File,
+  target: &Path,
+  total: u64,
+  on_progress: &mut dyn FnMut(f64),
+) -> Result<(), String> {
+  let mut output = fs::File::create(target).map_err(|err| format!("创建文件失败: {}", err))?;
+  let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+  let mut copied = 0u64;
+  let mut reported = 0.0;
+  loop {
+    let read = source
+      .read(&mut buffer)
+      .map_err(|err| format!("读取文件失败: {}", err))?;
+    if read == 0 {
+      break;
+    }
+    output
+      .write_all(&buffer[..read])
+      .map_err(|err| format!("写入文件失败: {}", err))?;
+    copied += read as u64;
+    if total > 0 {
+      let progress = copied as f64 * 100.0 / total as f64;
+      if progress - reported >= 1.0 {
+        reported = progress;
+        on_progress(progress);
+      }
+    }
+  }
+  output.flush().map_err(|err| format!("写入文件失败: {}", err))
+}
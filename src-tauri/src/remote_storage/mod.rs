@@ -0,0 +1,433 @@
+mod alist;
+mod local;
+mod s3;
+mod webdav;
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::baidu_sync::{self, join_baidu_path, normalize_baidu_path, read_setting, upsert_setting};
+use crate::bandwidth::{acquire_blocking, TransferClass};
+use crate::db::Db;
+use crate::utils::now_rfc3339;
+
+pub const BACKEND_BAIDU: &str = "baidu";
+pub const BACKEND_LOCAL: &str = "local";
+pub const BACKEND_WEBDAV: &str = "webdav";
+pub const BACKEND_S3: &str = "s3";
+pub const BACKEND_ALIST: &str = "alist";
+const MASKED_SECRET: &str = "********";
+
+const SUPPORTED_BACKENDS: &[&str] = &[
+  BACKEND_BAIDU,
+  BACKEND_LOCAL,
+  BACKEND_WEBDAV,
+  BACKEND_S3,
+  BACKEND_ALIST,
+];
+const PART_SUFFIX: &str = ".part";
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteDir {
+  pub name: String,
+  pub path: String,
+}
+
+pub struct RemoteFileMeta {
+  pub size: u64,
+  pub md5: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteStorageSettings {
+  pub backend: String,
+  pub local_root: String,
+  pub webdav_url: String,
+  pub webdav_username: String,
+  pub webdav_password: String,
+  pub s3_endpoint: String,
+  pub s3_region: String,
+  pub s3_bucket: String,
+  pub s3_access_key: String,
+  pub s3_secret_key: String,
+  pub s3_path_style: bool,
+  pub alist_url: String,
+  pub alist_token: String,
+}
+
+pub trait RemoteStorage: Send + Sync {
+  fn kind(&self) -> &'static str;
+
+  fn upload(
+    &self,
+    local_path: &Path,
+    remote_dir: &str,
+    remote_name: &str,
+    policy: &str,
+    on_progress: &mut dyn FnMut(f64),
+  ) -> Result<(), String>;
+
+  fn download(
+    &self,
+    remote_path: &str,
+    local_path: &Path,
+    on_spawn: &mut dyn FnMut(Arc<Mutex<Child>>),
+  ) -> Result<PathBuf, String>;
+
+  fn exists(&self, remote_path: &str) -> Result<bool, String>;
+
+  fn meta(&self, remote_path: &str) -> Result<RemoteFileMeta, String>;
+
+  fn size(&self, remote_path: &str) -> Result<u64, String> {
+    self.meta(remote_path).map(|meta| meta.size)
+  }
+
+  fn fix_md5(&self, _remote_path: &str) -> Result<bool, String> {
+    Ok(false)
+  }
+
+  fn list_dirs(&self, path: &str) -> Result<Vec<RemoteDir>, String>;
+
+  fn mkdir(&self, path: &str) -> Result<(), String>;
+
+  fn rename(&self, from_path: &str, to_path: &str) -> Result<(), String>;
+}
+
+pub fn open_remote_storage(db: &Db) -> Result<Box<dyn RemoteStorage>, String> {
+  let settings = load_remote_storage_settings(db)?;
+  build_remote_storage(db, &settings)
+}
+
+pub fn build_remote_storage(
+  db: &Db,
+  settings: &RemoteStorageSettings,
+) -> Result<Box<dyn RemoteStorage>, String> {
+  match settings.backend.as_str() {
+    BACKEND_LOCAL => Ok(Box::new(local::LocalStorage::new(&settings.local_root)?)),
+    BACKEND_WEBDAV => Ok(Box::new(webdav::WebDavStorage::new(
+      &settings.webdav_url,
+      &settings.webdav_username,
+      &settings.webdav_password,
+    )?)),
+    BACKEND_S3 => Ok(Box::new(s3::S3Storage::new(
+      &settings.s3_endpoint,
+      &settings.s3_region,
+      &settings.s3_bucket,
+      &settings.s3_access_key,
+      &settings.s3_secret_key,
+      settings.s3_path_style,
+    )?)),
+    BACKEND_ALIST => Ok(Box::new(alist::AlistStorage::new(
+      &settings.alist_url,
+      &settings.alist_token,
+    )?)),
+    _ => Ok(Box::new(baidu_sync::BaiduPcsStorage::from_db(db)?)),
+  }
+}
+
+pub fn load_remote_storage_settings(db: &Db) -> Result<RemoteStorageSettings, String> {
+  db.with_conn(|conn| {
+    let read = |key: &str| read_setting(conn, key).unwrap_or_default();
+    Ok(normalize_remote_storage_settings(RemoteStorageSettings {
+      backend: read("sync_backend"),
+      local_root: read("sync_local_root"),
+      webdav_url: read("sync_webdav_url"),
+      webdav_username: read("sync_webdav_username"),
+      webdav_password: read("sync_webdav_password"),
+      s3_endpoint: read("sync_s3_endpoint"),
+      s3_region: read("sync_s3_region"),
+      s3_bucket: read("sync_s3_bucket"),
+      s3_access_key: read("sync_s3_access_key"),
+      s3_secret_key: read("sync_s3_secret_key"),
+      s3_path_style: read_setting(conn, "sync_s3_path_style")
+        .and_then(|value| value.parse::<bool>().ok())
+        .unwrap_or(true),
+      alist_url: read("sync_alist_url"),
+      alist_token: read("sync_alist_token"),
+    }))
+  })
+  .map_err(|err| err.to_string())
+}
+
+pub fn mask_remote_storage_secrets(settings: RemoteStorageSettings) -> RemoteStorageSettings {
+  let mask = |value: String| {
+    if value.is_empty() {
+      value
+    } else {
+      MASKED_SECRET.to_string()
+    }
+  };
+  RemoteStorageSettings {
+    webdav_password: mask(settings.webdav_password),
+    s3_secret_key: mask(settings.s3_secret_key),
+    alist_token: mask(settings.alist_token),
+    ..settings
+  }
+}
+
+pub fn restore_masked_secrets(
+  db: &Db,
+  settings: RemoteStorageSettings,
+) -> Result<RemoteStorageSettings, String> {
+  let is_masked = |value: &str| value == MASKED_SECRET;
+  if !is_masked(&settings.webdav_password)
+    && !is_masked(&settings.s3_secret_key)
+    && !is_masked(&settings.alist_token)
+  {
+    return Ok(settings);
+  }
+  let stored = load_remote_storage_settings(db)?;
+  let keep = |value: String, stored: String| if is_masked(&value) { stored } else { value };
+  Ok(RemoteStorageSettings {
+    webdav_password: keep(settings.webdav_password, stored.webdav_password),
+    s3_secret_key: keep(settings.s3_secret_key, stored.s3_secret_key),
+    alist_token: keep(settings.alist_token, stored.alist_token),
+    ..settings
+  })
+}
+
+pub fn update_remote_storage_settings(
+  db: &Db,
+  settings: RemoteStorageSettings,
+) -> Result<RemoteStorageSettings, String> {
+  let settings = normalize_remote_storage_settings(restore_masked_secrets(db, settings)?);
+  let now = now_rfc3339();
+  let path_style = settings.s3_path_style.to_string();
+  let entries = [
+    ("sync_backend", settings.backend.as_str()),
+    ("sync_local_root", settings.local_root.as_str()),
+    ("sync_webdav_url", settings.webdav_url.as_str()),
+    ("sync_webdav_username", settings.webdav_username.as_str()),
+    ("sync_webdav_password", settings.webdav_password.as_str()),
+    ("sync_s3_endpoint", settings.s3_endpoint.as_str()),
+    ("sync_s3_region", settings.s3_region.as_str()),
+    ("sync_s3_bucket", settings.s3_bucket.as_str()),
+    ("sync_s3_access_key", settings.s3_access_key.as_str()),
+    ("sync_s3_secret_key", settings.s3_secret_key.as_str()),
+    ("sync_s3_path_style", path_style.as_str()),
+    ("sync_alist_url", settings.alist_url.as_str()),
+    ("sync_alist_token", settings.alist_token.as_str()),
+  ];
+  db.with_conn(|conn| {
+    for (key, value) in entries {
+      upsert_setting(conn, key, value, &now)?;
+    }
+    Ok(())
+  })
+  .map_err(|err| err.to_string())?;
+  Ok(settings)
+}
+
+fn normalize_remote_storage_settings(settings: RemoteStorageSettings) -> RemoteStorageSettings {
+  let backend = settings.backend.trim().to_lowercase();
+  let backend = if SUPPORTED_BACKENDS.contains(&backend.as_str()) {
+    backend
+  } else {
+    BACKEND_BAIDU.to_string()
+  };
+  let s3_region = settings.s3_region.trim();
+  RemoteStorageSettings {
+    backend,
+    local_root: settings.local_root.trim().to_string(),
+    webdav_url: settings.webdav_url.trim().trim_end_matches('/').to_string(),
+    webdav_username: settings.webdav_username.trim().to_string(),
+    webdav_password: settings.webdav_password,
+    s3_endpoint: settings.s3_endpoint.trim().trim_end_matches('/').to_string(),
+    s3_region: if s3_region.is_empty() {
+      "us-east-1".to_string()
+    } else {
+      s3_region.to_string()
+    },
+    s3_bucket: settings.s3_bucket.trim().to_string(),
+    s3_access_key: settings.s3_access_key.trim().to_string(),
+    s3_secret_key: settings.s3_secret_key.trim().to_string(),
+    s3_path_style: settings.s3_path_style,
+    alist_url: settings.alist_url.trim().trim_end_matches('/').to_string(),
+    alist_token: settings.alist_token.trim().to_string(),
+  }
+}
+
+pub fn should_skip_upload(
+  storage: &dyn RemoteStorage,
+  remote_path: &str,
+  local_size: u64,
+  policy: &str,
+) -> Result<bool, String> {
+  match policy {
+    "skip" => storage.exists(remote_path),
+    "rsync" => {
+      if !storage.exists(remote_path)? {
+        return Ok(false);
+      }
+      Ok(storage.size(remote_path)? == local_size)
+    }
+    _ => Ok(false),
+  }
+}
+
+fn split_remote_path(remote_path: &str) -> Vec<String> {
+  normalize_baidu_path(remote_path)
+    .split('/')
+    .filter(|segment| !segment.is_empty())
+    .map(|segment| segment.to_string())
+    .collect()
+}
+
+fn parent_remote_path(remote_path: &str) -> String {
+  let mut segments = split_remote_path(remote_path);
+  segments.pop();
+  if segments.is_empty() {
+    "/".to_string()
+  } else {
+    format!("/{}", segments.join("/"))
+  }
+}
+
+fn remote_file_name(remote_path: &str) -> String {
+  split_remote_path(remote_path).pop().unwrap_or_default()
+}
+
+fn remote_ancestors(remote_path: &str) -> Vec<String> {
+  let mut current = String::from("/");
+  split_remote_path(remote_path)
+    .into_iter()
+    .map(|segment| {
+      current = join_baidu_path(&current, &segment);
+      current.clone()
+    })
+    .collect()
+}
+
+fn build_http_client() -> Result<reqwest::blocking::Client, String> {
+  reqwest::blocking::Client::builder()
+    .connect_timeout(Duration::from_secs(15))
+    .timeout(None)
+    .build()
+    .map_err(|err| format!("创建 HTTP 客户端失败: {}", err))
+}
+
+fn open_local_file(local_path: &Path) -> Result<(fs::File, u64), String> {
+  let file = fs::File::open(local_path).map_err(|err| format!("读取本地文件失败: {}", err))?;
+  let size = file
+    .metadata()
+    .map_err(|err| format!("读取本地文件失败: {}", err))?
+    .len();
+  Ok((file, size))
+}
+
+fn part_path(local_path: &Path) -> PathBuf {
+  let mut value = local_path.as_os_str().to_os_string();
+  value.push(PART_SUFFIX);
+  PathBuf::from(value)
+}
+
+fn finish_part_file(part: &Path, local_path: &Path) -> Result<PathBuf, String> {
+  if local_path.exists() {
+    let _ = fs::remove_file(local_path);
+  }
+  fs::rename(part, local_path).map_err(|err| format!("重命名下载文件失败: {}", err))?;
+  Ok(local_path.to_path_buf())
+}
+
+fn prepare_local_target(local_path: &Path) -> Result<PathBuf, String> {
+  let parent = local_path
+    .parent()
+    .ok_or_else(|| "下载目标目录无效".to_string())?;
+  fs::create_dir_all(parent).map_err(|err| format!("创建下载目录失败: {}", err))?;
+  Ok(part_path(local_path))
+}
+
+struct CountingReader<R> {
+  inner: R,
+  counter: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    let read = self.inner.read(buf)?;
+    acquire_blocking(TransferClass::Sync, read as u64);
+    self.counter.fetch_add(read as u64, Ordering::Relaxed);
+    Ok(read)
+  }
+}
+
+fn counting_body<R: Read + Send + 'static>(
+  inner: R,
+  len: u64,
+  counter: &Arc<AtomicU64>,
+) -> reqwest::blocking::Body {
+  reqwest::blocking::Body::sized(
+    CountingReader {
+      inner,
+      counter: Arc::clone(counter),
+    },
+    len,
+  )
+}
+
+struct ThrottledReader<R> {
+  inner: R,
+  class: TransferClass,
+}
+
+impl<R: Read> Read for ThrottledReader<R> {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    let read = self.inner.read(buf)?;
+    acquire_blocking(self.class, read as u64);
+    Ok(read)
+  }
+}
+
+fn throttled_copy<R: Read, W: Write>(inner: R, output: &mut W) -> io::Result<u64> {
+  let mut reader = ThrottledReader {
+    inner,
+    class: TransferClass::Download,
+  };
+  io::copy(&mut reader, output)
+}
+
+fn run_with_progress<T, F>(
+  total: u64,
+  on_progress: &mut dyn FnMut(f64),
+  task: F,
+) -> Result<T, String>
+where
+  T: Send,
+  F: FnOnce(Arc<AtomicU64>) -> Result<T, String> + Send,
+{
+  let counter = Arc::new(AtomicU64::new(0));
+  let task_counter = Arc::clone(&counter);
+  std::thread::scope(|scope| {
+    let handle = scope.spawn(move || task(task_counter));
+    while !handle.is_finished() {
+      if total > 0 {
+        let sent = counter.load(Ordering::Relaxed).min(total);
+        on_progress(sent as f64 * 100.0 / total as f64);
+      }
+      std::thread::sleep(Duration::from_millis(500));
+    }
+    handle
+      .join()
+      .unwrap_or_else(|_| Err("上传线程异常退出".to_string()))
+  })
+}
+
+fn describe_http_error(action: &str, response: reqwest::blocking::Response) -> String {
+  let status = response.status();
+  let body = response.text().unwrap_or_default();
+  let body: String = body.trim().chars().take(300).collect();
+  if body.is_empty() {
+    format!("{}失败: HTTP {}", action, status)
+  } else {
+    format!("{}失败: HTTP {} {}", action, status, body)
+  }
+}
@@ -0,0 +1,687 @@
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::blocking::{Body, Client, Response};
+use reqwest::{Method, StatusCode};
+use sha2::{Digest, Sha256};
+
+use crate::baidu_sync::join_baidu_path;
+use crate::utils::{escape_xml, hmac_sha256};
+
+use super::{
+  build_http_client, counting_body, describe_http_error, finish_part_file, open_local_file,
+  prepare_local_target, run_with_progress, should_skip_upload, split_remote_path, throttled_copy,
+  RemoteDir, RemoteFileMeta, RemoteStorage, BACKEND_S3,
+};
+
+const URI_UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC
+  .remove(b'-')
+  .remove(b'_')
+  .remove(b'.')
+  .remove(b'~');
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+const MULTIPART_THRESHOLD: u64 = 64 * 1024 * 1024;
+const MULTIPART_PART_SIZE: u64 = 64 * 1024 * 1024;
+const MAX_MULTIPART_PARTS: u64 = 10000;
+const MAX_COPY_OBJECT_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+const MULTIPART_COPY_PART_SIZE: u64 = 512 * 1024 * 1024;
+
+enum Payload {
+  Empty,
+  Bytes(Vec<u8>),
+  Stream(Body),
+}
+
+struct ListResult {
+  prefixes: Vec<String>,
+  keys: Vec<String>,
+  next_token: Option<String>,
+}
+
+pub struct S3Storage {
+  client: Client,
+  scheme: String,
+  host: String,
+  bucket: String,
+  region: String,
+  access_key: String,
+  secret_key: String,
+  path_style: bool,
+}
+
+impl S3Storage {
+  pub fn new(
+    endpoint: &str,
+    region: &str,
+    bucket: &str,
+    access_key: &str,
+    secret_key: &str,
+    path_style: bool,
+  ) -> Result<Self, String> {
+    if bucket.trim().is_empty() {
+      return Err("请先配置 S3 存储桶".to_string());
+    }
+    if access_key.trim().is_empty() || secret_key.trim().is_empty() {
+      return Err("请先配置 S3 访问密钥".to_string());
+    }
+    let endpoint = if endpoint.trim().is_empty() {
+      format!("https://s3.{}.amazonaws.com", region.trim())
+    } else {
+      endpoint.trim().to_string()
+    };
+    let parsed = url::Url::parse(&endpoint).map_err(|_| "S3 地址无效".to_string())?;
+    let host = parsed
+      .host_str()
+      .ok_or_else(|| "S3 地址无效".to_string())?
+      .to_string();
+    let host = match parsed.port() {
+      Some(port) => format!("{}:{}", host, port),
+      None => host,
+    };
+    Ok(Self {
+      client: build_http_client()?,
+      scheme: parsed.scheme().to_string(),
+      host,
+      bucket: bucket.trim().to_string(),
+      region: region.trim().to_string(),
+      access_key: access_key.trim().to_string(),
+      secret_key: secret_key.trim().to_string(),
+      path_style,
+    })
+  }
+
+  fn request_host(&self) -> String {
+    if self.path_style {
+      self.host.clone()
+    } else {
+      format!("{}.{}", self.bucket, self.host)
+    }
+  }
+
+  fn canonical_uri(&self, key: &str) -> String {
+    let encoded_key = key
+      .split('/')
+      .map(|segment| utf8_percent_encode(segment, URI_UNRESERVED).to_string())
+      .collect::<Vec<_>>()
+      .join("/");
+    if self.path_style {
+      format!("/{}/{}", self.bucket, encoded_key)
+    } else {
+      format!("/{}", encoded_key)
+    }
+  }
+
+  fn send(
+    &self,
+    method: Method,
+    key: &str,
+    query: &[(&str, String)],
+    extra_headers: &[(&str, String)],
+    payload: Payload,
+  ) -> Result<Response, String> {
+    let now = Utc::now();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let short_date = now.format("%Y%m%d").to_string();
+    let host = self.request_host();
+    let uri = self.canonical_uri(key);
+    let mut query_pairs: Vec<(String, String)> = query
+      .iter()
+      .map(|(name, value)| {
+        (
+          utf8_percent_encode(name, URI_UNRESERVED).to_string(),
+          utf8_percent_encode(value, URI_UNRESERVED).to_string(),
+        )
+      })
+      .collect();
+    query_pairs.sort();
+    let canonical_query = query_pairs
+      .iter()
+      .map(|(name, value)| format!("{}={}", name, value))
+      .collect::<Vec<_>>()
+      .join("&");
+    let payload_hash = match &payload {
+      Payload::Empty => hex_sha256(b""),
+      Payload::Bytes(bytes) => hex_sha256(bytes),
+      Payload::Stream(_) => UNSIGNED_PAYLOAD.to_string(),
+    };
+    let mut headers: Vec<(String, String)> = vec![
+      ("host".to_string(), host.clone()),
+      ("x-amz-content-sha256".to_string(), payload_hash.clone()),
+      ("x-amz-date".to_string(), amz_date.clone()),
+    ];
+    for (name, value) in extra_headers {
+      headers.push((name.to_ascii_lowercase(), value.trim().to_string()));
+    }
+    headers.sort();
+    let canonical_headers: String = headers
+      .iter()
+      .map(|(name, value)| format!("{}:{}\n", name, value))
+      .collect();
+    let signed_headers = headers
+      .iter()
+      .map(|(name, _)| name.as_str())
+      .collect::<Vec<_>>()
+      .join(";");
+    let canonical_request = format!(
+      "{}\n{}\n{}\n{}\n{}\n{}",
+      method.as_str(),
+      uri,
+      canonical_query,
+      canonical_headers,
+      signed_headers,
+      payload_hash
+    );
+    let scope = format!("{}/{}/s3/aws4_request", short_date, self.region);
+    let string_to_sign = format!(
+      "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+      amz_date,
+      scope,
+      hex_sha256(canonical_request.as_bytes())
+    );
+    let signing_key = [self.region.as_str(), "s3", "aws4_request"].iter().fold(
+      hmac_sha256(format!("AWS4{}", self.secret_key).as_bytes(), short_date.as_bytes()),
+      |key, part| hmac_sha256(&key, part.as_bytes()),
+    );
+    let signature = hex_encode(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+    let authorization = format!(
+      "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+      self.access_key, scope, signed_headers, signature
+    );
+
+    let mut url = format!("{}://{}{}", self.scheme, host, uri);
+    if !canonical_query.is_empty() {
+      url.push('?');
+      url.push_str(&canonical_query);
+    }
+    let mut builder = self
+      .client
+      .request(method, url)
+      .header("Authorization", authorization);
+    for (name, value) in headers.iter().filter(|(name, _)| name != "host") {
+      builder = builder.header(name.as_str(), value.as_str());
+    }
+    builder = match payload {
+      Payload::Empty => builder,
+      Payload::Bytes(bytes) => builder.body(bytes),
+      Payload::Stream(body) => builder.body(body),
+    };
+    builder.send().map_err(|err| format!("S3 请求失败: {}", err))
+  }
+
+  fn head(&self, key: &str) -> Result<Option<RemoteFileMeta>, String> {
+    let response = self.send(Method::HEAD, key, &[], &[], Payload::Empty)?;
+    if response.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+    if !response.status().is_success() {
+      return Err(describe_http_error("S3 查询", response));
+    }
+    let size = response
+      .headers()
+      .get(reqwest::header::CONTENT_LENGTH)
+      .and_then(|value| value.to_str().ok())
+      .and_then(|value| value.parse::<u64>().ok())
+      .unwrap_or(0);
+    let md5 = response
+      .headers()
+      .get(reqwest::header::ETAG)
+      .and_then(|value| value.to_str().ok())
+      .map(|value| value.trim_matches('"').to_ascii_lowercase())
+      .filter(|value| value.len() == 32 && value.chars().all(|ch| ch.is_ascii_hexdigit()));
+    Ok(Some(RemoteFileMeta { size, md5 }))
+  }
+
+  fn list(
+    &self,
+    prefix: &str,
+    delimiter: bool,
+    token: Option<&str>,
+  ) -> Result<ListResult, String> {
+    let mut query = vec![("list-type", "2".to_string()), ("prefix", prefix.to_string())];
+    if delimiter {
+      query.push(("delimiter", "/".to_string()));
+    }
+    if let Some(token) = token {
+      query.push(("continuation-token", token.to_string()));
+    }
+    let response = self.send(Method::GET, "", &query, &[], Payload::Empty)?;
+    if !response.status().is_success() {
+      return Err(describe_http_error("S3 列表", response));
+    }
+    let body = response
+      .text()
+      .map_err(|err| format!("S3 响应读取失败: {}", err))?;
+    parse_list_result(&body)
+  }
+
+  fn put_object(
+    &self,
+    key: &str,
+    file: fs::File,
+    total: u64,
+    on_progress: &mut dyn FnMut(f64),
+  ) -> Result<(), String> {
+    let response = run_with_progress(total, on_progress, |counter| {
+      self.send(
+        Method::PUT,
+        key,
+        &[],
+        &[],
+        Payload::Stream(counting_body(file, total, &counter)),
+      )
+    })?;
+    if !response.status().is_success() {
+      return Err(describe_http_error("S3 上传", response));
+    }
+    Ok(())
+  }
+
+  fn multipart_upload(
+    &self,
+    key: &str,
+    local_path: &Path,
+    total: u64,
+    on_progress: &mut dyn FnMut(f64),
+  ) -> Result<(), String> {
+    let upload_id = self.create_multipart_upload(key)?;
+    let part_size = MULTIPART_PART_SIZE.max(total.div_ceil(MAX_MULTIPART_PARTS));
+    let result = run_with_progress(total, on_progress, |counter| {
+      let mut etags = Vec::new();
+      let mut offset = 0u64;
+      let mut part_number = 1u64;
+      while offset < total {
+        let length = part_size.min(total - offset);
+        let mut file =
+          fs::File::open(local_path).map_err(|err| format!("读取本地文件失败: {}", err))?;
+        file
+          .seek(SeekFrom::Start(offset))
+          .map_err(|err| format!("读取本地文件失败: {}", err))?;
+        let response = self.send(
+          Method::PUT,
+          key,
+          &[
+            ("partNumber", part_number.to_string()),
+            ("uploadId", upload_id.clone()),
+          ],
+          &[],
+          Payload::Stream(counting_body(file.take(length), length, &counter)),
+        )?;
+        if !response.status().is_success() {
+          return Err(describe_http_error("S3 分片上传", response));
+        }
+        let etag = response
+          .headers()
+          .get(reqwest::header::ETAG)
+          .and_then(|value| value.to_str().ok())
+          .map(|value| value.to_string())
+          .ok_or_else(|| "S3 分片上传失败: 缺少 ETag".to_string())?;
+        etags.push((part_number, etag));
+        offset += length;
+        part_number += 1;
+      }
+      Ok(etags)
+    });
+    match result {
+      Ok(etags) => self.complete_multipart_upload(key, &upload_id, &etags),
+      Err(err) => {
+        self.abort_multipart_upload(key, &upload_id);
+        Err(err)
+      }
+    }
+  }
+
+  fn create_multipart_upload(&self, key: &str) -> Result<String, String> {
+    let response = self.send(
+      Method::POST,
+      key,
+      &[("uploads", String::new())],
+      &[],
+      Payload::Empty,
+    )?;
+    if !response.status().is_success() {
+      return Err(describe_http_error("S3 分片上传初始化", response));
+    }
+    let body = response
+      .text()
+      .map_err(|err| format!("S3 响应读取失败: {}", err))?;
+    find_xml_text(&body, "UploadId")?
+      .ok_or_else(|| "S3 分片上传初始化失败: 缺少 UploadId".to_string())
+  }
+
+  fn abort_multipart_upload(&self, key: &str, upload_id: &str) {
+    let _ = self.send(
+      Method::DELETE,
+      key,
+      &[("uploadId", upload_id.to_string())],
+      &[],
+      Payload::Empty,
+    );
+  }
+
+  fn complete_multipart_upload(
+    &self,
+    key: &str,
+    upload_id: &str,
+    etags: &[(u64, String)],
+  ) -> Result<(), String> {
+    let parts: String = etags
+      .iter()
+      .map(|(number, etag)| {
+        format!(
+          "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+          number,
+          escape_xml(etag)
+        )
+      })
+      .collect();
+    let complete = format!(
+      "<CompleteMultipartUpload>{}</CompleteMultipartUpload>",
+      parts
+    );
+    let response = self.send(
+      Method::POST,
+      key,
+      &[("uploadId", upload_id.to_string())],
+      &[],
+      Payload::Bytes(complete.into_bytes()),
+    )?;
+    if !response.status().is_success() {
+      return Err(describe_http_error("S3 分片合并", response));
+    }
+    let body = response.text().unwrap_or_default();
+    if let Some(code) = find_xml_text(&body, "Code").ok().flatten() {
+      return Err(format!("S3 分片合并失败: {}", code));
+    }
+    Ok(())
+  }
+
+  fn copy_object(&self, from_key: &str, to_key: &str) -> Result<(), String> {
+    let source = format!(
+      "/{}/{}",
+      self.bucket,
+      from_key
+        .split('/')
+        .map(|segment| utf8_percent_encode(segment, URI_UNRESERVED).to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+    );
+    let size = self.head(from_key)?.map(|meta| meta.size).unwrap_or(0);
+    if size > MAX_COPY_OBJECT_SIZE {
+      return self.multipart_copy(&source, to_key, size);
+    }
+    let response = self.send(
+      Method::PUT,
+      to_key,
+      &[],
+      &[("x-amz-copy-source", source)],
+      Payload::Empty,
+    )?;
+    if !response.status().is_success() {
+      return Err(describe_http_error("S3 复制", response));
+    }
+    Ok(())
+  }
+
+  fn multipart_copy(&self, source: &str, to_key: &str, total: u64) -> Result<(), String> {
+    let upload_id = self.create_multipart_upload(to_key)?;
+    let part_size = MULTIPART_COPY_PART_SIZE.max(total.div_ceil(MAX_MULTIPART_PARTS));
+    let mut etags = Vec::new();
+    let mut offset = 0u64;
+    let mut part_number = 1u64;
+    while offset < total {
+      let length = part_size.min(total - offset);
+      let result = self
+        .send(
+          Method::PUT,
+          to_key,
+          &[
+            ("partNumber", part_number.to_string()),
+            ("uploadId", upload_id.clone()),
+          ],
+          &[
+            ("x-amz-copy-source", source.to_string()),
+            (
+              "x-amz-copy-source-range",
+              format!("bytes={}-{}", offset, offset + length - 1),
+            ),
+          ],
+          Payload::Empty,
+        )
+        .and_then(|response| {
+          if !response.status().is_success() {
+            return Err(describe_http_error("S3 分片复制", response));
+          }
+          let body = response
+            .text()
+            .map_err(|err| format!("S3 响应读取失败: {}", err))?;
+          find_xml_text(&body, "ETag")?.ok_or_else(|| "S3 分片复制失败: 缺少 ETag".to_string())
+        });
+      match result {
+        Ok(etag) => etags.push((part_number, etag)),
+        Err(err) => {
+          self.abort_multipart_upload(to_key, &upload_id);
+          return Err(err);
+        }
+      }
+      offset += length;
+      part_number += 1;
+    }
+    self.complete_multipart_upload(to_key, &upload_id, &etags)
+  }
+
+  fn delete_object(&self, key: &str) -> Result<(), String> {
+    let response = self.send(Method::DELETE, key, &[], &[], Payload::Empty)?;
+    if !response.status().is_success() && response.status() != StatusCode::NOT_FOUND {
+      return Err(describe_http_error("S3 删除", response));
+    }
+    Ok(())
+  }
+
+  fn move_object(&self, from_key: &str, to_key: &str) -> Result<(), String> {
+    self.copy_object(from_key, to_key)?;
+    self.delete_object(from_key)
+  }
+}
+
+impl RemoteStorage for S3Storage {
+  fn kind(&self) -> &'static str {
+    BACKEND_S3
+  }
+
+  fn upload(
+    &self,
+    local_path: &Path,
+    remote_dir: &str,
+    remote_name: &str,
+    policy: &str,
+    on_progress: &mut dyn FnMut(f64),
+  ) -> Result<(), String> {
+    let (file, total) = open_local_file(local_path)?;
+    let remote_path = join_baidu_path(remote_dir, remote_name);
+    if should_skip_upload(self, &remote_path, total, policy)? {
+      return Ok(());
+    }
+    let key = object_key(&remote_path);
+    if total > MULTIPART_THRESHOLD {
+      drop(file);
+      self.multipart_upload(&key, local_path, total, on_progress)
+    } else {
+      self.put_object(&key, file, total, on_progress)
+    }
+  }
+
+  fn download(
+    &self,
+    remote_path: &str,
+    local_path: &Path,
+    _on_spawn: &mut dyn FnMut(Arc<Mutex<Child>>),
+  ) -> Result<PathBuf, String> {
+    let part = prepare_local_target(local_path)?;
+    let response = self.send(
+      Method::GET,
+      &object_key(remote_path),
+      &[],
+      &[],
+      Payload::Empty,
+    )?;
+    if !response.status().is_success() {
+      return Err(describe_http_error("S3 下载", response));
+    }
+    let mut output = fs::File::create(&part).map_err(|err| format!("创建文件失败: {}", err))?;
+    throttled_copy(response, &mut output)
+      .map_err(|err| format!("S3 下载失败: {}", err))?;
+    finish_part_file(&part, local_path)
+  }
+
+  fn exists(&self, remote_path: &str) -> Result<bool, String> {
+    if self.head(&object_key(remote_path))?.is_some() {
+      return Ok(true);
+    }
+    let result = self.list(&dir_prefix(remote_path), true, None)?;
+    Ok(!result.prefixes.is_empty() || !result.keys.is_empty())
+  }
+
+  fn meta(&self, remote_path: &str) -> Result<RemoteFileMeta, String> {
+    self
+      .head(&object_key(remote_path))?
+      .ok_or_else(|| format!("远程文件不存在: {}", remote_path))
+  }
+
+  fn list_dirs(&self, path: &str) -> Result<Vec<RemoteDir>, String> {
+    let prefix = dir_prefix(path);
+    let mut dirs = Vec::new();
+    let mut token: Option<String> = None;
+    loop {
+      let result = self.list(&prefix, true, token.as_deref())?;
+      for item in result.prefixes {
+        let name = item
+          .strip_prefix(&prefix)
+          .unwrap_or(&item)
+          .trim_end_matches('/')
+          .to_string();
+        if name.is_empty() {
+          continue;
+        }
+        dirs.push(RemoteDir {
+          path: join_baidu_path(path, &name),
+          name,
+        });
+      }
+      token = result.next_token;
+      if token.is_none() {
+        break;
+      }
+    }
+    dirs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(dirs)
+  }
+
+  fn mkdir(&self, path: &str) -> Result<(), String> {
+    let response = self.send(Method::PUT, &dir_prefix(path), &[], &[], Payload::Empty)?;
+    if !response.status().is_success() {
+      return Err(describe_http_error("S3 创建目录", response));
+    }
+    Ok(())
+  }
+
+  fn rename(&self, from_path: &str, to_path: &str) -> Result<(), String> {
+    if self.head(&object_key(from_path))?.is_some() {
+      return self.move_object(&object_key(from_path), &object_key(to_path));
+    }
+    let from_prefix = dir_prefix(from_path);
+    let to_prefix = dir_prefix(to_path);
+    let mut keys = Vec::new();
+    let mut token: Option<String> = None;
+    loop {
+      let result = self.list(&from_prefix, false, token.as_deref())?;
+      keys.extend(result.keys);
+      token = result.next_token;
+      if token.is_none() {
+        break;
+      }
+    }
+    if keys.is_empty() {
+      return Err(format!("远程路径不存在: {}", from_path));
+    }
+    for key in keys {
+      let suffix = key.strip_prefix(&from_prefix).unwrap_or(&key);
+      self.move_object(&key, &format!("{}{}", to_prefix, suffix))?;
+    }
+    Ok(())
+  }
+}
+
+fn object_key(remote_path: &str) -> String {
+  split_remote_path(remote_path).join("/")
+}
+
+fn dir_prefix(remote_path: &str) -> String {
+  let key = object_key(remote_path);
+  if key.is_empty() {
+    key
+  } else {
+    format!("{}/", key)
+  }
+}
+
+fn hex_sha256(data: &[u8]) -> String {
+  hex_encode(&Sha256::digest(data))
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+  bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn find_xml_text(body: &str, tag: &str) -> Result<Option<String>, String> {
+  if body.trim().is_empty() {
+    return Ok(None);
+  }
+  let document =
+    roxmltree::Document::parse(body).map_err(|err| format!("S3 响应解析失败: {}", err))?;
+  Ok(
+    document
+      .descendants()
+      .find(|node| node.tag_name().name() == tag)
+      .and_then(|node| node.text())
+      .map(|value| value.to_string()),
+  )
+}
+
+fn parse_list_result(body: &str) -> Result<ListResult, String> {
+  let document =
+    roxmltree::Document::parse(body).map_err(|err| format!("S3 响应解析失败: {}", err))?;
+  let child_text = |node: roxmltree::Node, tag: &str| {
+    node
+      .children()
+      .find(|child| child.tag_name().name() == tag)
+      .and_then(|child| child.text())
+      .map(|value| value.to_string())
+  };
+  let root = document.root_element();
+  let prefixes = root
+    .children()
+    .filter(|node| node.tag_name().name() == "CommonPrefixes")
+    .filter_map(|node| child_text(node, "Prefix"))
+    .collect();
+  let keys = root
+    .children()
+    .filter(|node| node.tag_name().name() == "Contents")
+    .filter_map(|node| child_text(node, "Key"))
+    .collect();
+  let truncated = child_text(root, "IsTruncated").as_deref() == Some("true");
+  let next_token = if truncated {
+    child_text(root, "NextContinuationToken")
+  } else {
+    None
+  };
+  Ok(ListResult {
+    prefixes,
+    keys,
+    next_token,
+  })
+}
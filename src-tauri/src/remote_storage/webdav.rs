@@ -0,0 +1,268 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::{Method, StatusCode};
+
+use crate::baidu_sync::join_baidu_path;
+
+use super::{
+  build_http_client, counting_body, describe_http_error, finish_part_file, open_local_file,
+  parent_remote_path, prepare_local_target, remote_ancestors, run_with_progress, should_skip_upload,
+  split_remote_path, throttled_copy, RemoteDir, RemoteFileMeta, RemoteStorage, BACKEND_WEBDAV,
+};
+
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+  .remove(b'-')
+  .remove(b'_')
+  .remove(b'.')
+  .remove(b'~');
+const PROPFIND_BODY: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+<d:propfind xmlns:d=\"DAV:\"><d:prop><d:resourcetype/><d:getcontentlength/></d:prop></d:propfind>";
+
+struct PropEntry {
+  href: String,
+  is_dir: bool,
+  size: u64,
+}
+
+pub struct WebDavStorage {
+  client: Client,
+  base_url: String,
+  base_path: String,
+  username: String,
+  password: String,
+}
+
+impl WebDavStorage {
+  pub fn new(url: &str, username: &str, password: &str) -> Result<Self, String> {
+    let base_url = url.trim().trim_end_matches('/').to_string();
+    let parsed = url::Url::parse(&base_url).map_err(|_| "WebDAV 地址无效".to_string())?;
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+      return Err("WebDAV 地址仅支持 http/https".to_string());
+    }
+    Ok(Self {
+      client: build_http_client()?,
+      base_path: percent_decode_str(parsed.path())
+        .decode_utf8_lossy()
+        .trim_end_matches('/')
+        .to_string(),
+      base_url,
+      username: username.to_string(),
+      password: password.to_string(),
+    })
+  }
+
+  fn url(&self, remote_path: &str, is_dir: bool) -> String {
+    let mut url = self.base_url.clone();
+    for segment in split_remote_path(remote_path) {
+      url.push('/');
+      url.push_str(&utf8_percent_encode(&segment, PATH_SEGMENT).to_string());
+    }
+    if is_dir {
+      url.push('/');
+    }
+    url
+  }
+
+  fn request(&self, method: Method, url: &str) -> RequestBuilder {
+    let builder = self.client.request(method, url);
+    if self.username.is_empty() {
+      builder
+    } else {
+      builder.basic_auth(&self.username, Some(&self.password))
+    }
+  }
+
+  fn propfind(&self, remote_path: &str, depth: &str) -> Result<Option<Vec<PropEntry>>, String> {
+    let method = Method::from_bytes(b"PROPFIND").map_err(|err| err.to_string())?;
+    let response = self
+      .request(method, &self.url(remote_path, depth != "0"))
+      .header("Depth", depth)
+      .header("Content-Type", "application/xml; charset=utf-8")
+      .body(PROPFIND_BODY)
+      .send()
+      .map_err(|err| format!("WebDAV 请求失败: {}", err))?;
+    if response.status() == StatusCode::NOT_FOUND {
+      return Ok(None);
+    }
+    if !response.status().is_success() {
+      return Err(describe_http_error("WebDAV 查询", response));
+    }
+    let body = response
+      .text()
+      .map_err(|err| format!("WebDAV 响应读取失败: {}", err))?;
+    parse_multistatus(&body).map(Some)
+  }
+
+  fn ensure_dir(&self, remote_path: &str) -> Result<(), String> {
+    let method = Method::from_bytes(b"MKCOL").map_err(|err| err.to_string())?;
+    for path in remote_ancestors(remote_path) {
+      let response = self
+        .request(method.clone(), &self.url(&path, true))
+        .send()
+        .map_err(|err| format!("WebDAV 请求失败: {}", err))?;
+      let status = response.status();
+      if status.is_success() || status == StatusCode::METHOD_NOT_ALLOWED {
+        continue;
+      }
+      return Err(describe_http_error("WebDAV 创建目录", response));
+    }
+    Ok(())
+  }
+
+  fn entry_path(&self, href: &str) -> String {
+    let path = match url::Url::parse(href) {
+      Ok(parsed) => parsed.path().to_string(),
+      Err(_) => href.to_string(),
+    };
+    let decoded = percent_decode_str(&path).decode_utf8_lossy().to_string();
+    let relative = decoded
+      .strip_prefix(&self.base_path)
+      .unwrap_or(&decoded)
+      .trim_end_matches('/');
+    if relative.is_empty() {
+      "/".to_string()
+    } else {
+      relative.to_string()
+    }
+  }
+}
+
+impl RemoteStorage for WebDavStorage {
+  fn kind(&self) -> &'static str {
+    BACKEND_WEBDAV
+  }
+
+  fn upload(
+    &self,
+    local_path: &Path,
+    remote_dir: &str,
+    remote_name: &str,
+    policy: &str,
+    on_progress: &mut dyn FnMut(f64),
+  ) -> Result<(), String> {
+    let (file, total) = open_local_file(local_path)?;
+    let remote_path = join_baidu_path(remote_dir, remote_name);
+    if should_skip_upload(self, &remote_path, total, policy)? {
+      return Ok(());
+    }
+    self.ensure_dir(remote_dir)?;
+    let url = self.url(&remote_path, false);
+    let response: Response = run_with_progress(total, on_progress, |counter| {
+      self
+        .request(Method::PUT, &url)
+        .body(counting_body(file, total, &counter))
+        .send()
+        .map_err(|err| format!("WebDAV 上传失败: {}", err))
+    })?;
+    if !response.status().is_success() {
+      return Err(describe_http_error("WebDAV 上传", response));
+    }
+    Ok(())
+  }
+
+  fn download(
+    &self,
+    remote_path: &str,
+    local_path: &Path,
+    _on_spawn: &mut dyn FnMut(Arc<Mutex<Child>>),
+  ) -> Result<PathBuf, String> {
+    let part = prepare_local_target(local_path)?;
+    let response = self
+      .request(Method::GET, &self.url(remote_path, false))
+      .send()
+      .map_err(|err| format!("WebDAV 下载失败: {}", err))?;
+    if !response.status().is_success() {
+      return Err(describe_http_error("WebDAV 下载", response));
+    }
+    let mut output = fs::File::create(&part).map_err(|err| format!("创建文件失败: {}", err))?;
+    throttled_copy(response, &mut output)
+      .map_err(|err| format!("WebDAV 下载失败: {}", err))?;
+    finish_part_file(&part, local_path)
+  }
+
+  fn exists(&self, remote_path: &str) -> Result<bool, String> {
+    Ok(self.propfind(remote_path, "0")?.is_some())
+  }
+
+  fn meta(&self, remote_path: &str) -> Result<RemoteFileMeta, String> {
+    let entries = self
+      .propfind(remote_path, "0")?
+      .ok_or_else(|| format!("远程文件不存在: {}", remote_path))?;
+    let size = entries.first().map(|entry| entry.size).unwrap_or(0);
+    Ok(RemoteFileMeta { size, md5: None })
+  }
+
+  fn list_dirs(&self, path: &str) -> Result<Vec<RemoteDir>, String> {
+    let entries = self
+      .propfind(path, "1")?
+      .ok_or_else(|| format!("远程目录不存在: {}", path))?;
+    let current = self.entry_path(&self.url(path, false));
+    let mut dirs: Vec<RemoteDir> = entries
+      .into_iter()
+      .filter(|entry| entry.is_dir)
+      .map(|entry| self.entry_path(&entry.href))
+      .filter(|entry_path| *entry_path != current && parent_remote_path(entry_path) == current)
+      .filter_map(|entry_path| {
+        let name = entry_path.rsplit('/').next()?.to_string();
+        Some(RemoteDir {
+          path: join_baidu_path(path, &name),
+          name,
+        })
+      })
+      .collect();
+    dirs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(dirs)
+  }
+
+  fn mkdir(&self, path: &str) -> Result<(), String> {
+    self.ensure_dir(path)
+  }
+
+  fn rename(&self, from_path: &str, to_path: &str) -> Result<(), String> {
+    self.ensure_dir(&parent_remote_path(to_path))?;
+    let method = Method::from_bytes(b"MOVE").map_err(|err| err.to_string())?;
+    let response = self
+      .request(method, &self.url(from_path, false))
+      .header("Destination", self.url(to_path, false))
+      .header("Overwrite", "F")
+      .send()
+      .map_err(|err| format!("WebDAV 请求失败: {}", err))?;
+    if !response.status().is_success() {
+      return Err(describe_http_error("WebDAV 重命名", response));
+    }
+    Ok(())
+  }
+}
+
+fn parse_multistatus(body: &str) -> Result<Vec<PropEntry>, String> {
+  let document =
+    roxmltree::Document::parse(body).map_err(|err| format!("WebDAV 响应解析失败: {}", err))?;
+  let entries = document
+    .descendants()
+    .filter(|node| node.tag_name().name() == "response")
+    .filter_map(|node| {
+      let href = node
+        .descendants()
+        .find(|child| child.tag_name().name() == "href")?
+        .text()?
+        .trim()
+        .to_string();
+      let is_dir = node
+        .descendants()
+        .any(|child| child.tag_name().name() == "collection");
+      let size = node
+        .descendants()
+        .find(|child| child.tag_name().name() == "getcontentlength")
+        .and_then(|child| child.text())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(0);
+      Some(PropEntry { href, is_dir, size })
+    })
+    .collect();
+  Ok(entries)
+}
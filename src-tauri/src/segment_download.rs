@@ -0,0 +1,437 @@
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use futures_util::future::join_all;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_RANGE, RANGE, REFERER, USER_AGENT};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::bandwidth::{acquire, TransferClass};
+
+const STATE_SUFFIX: &str = ".dlstate";
+const MIN_CHUNK_SIZE: u64 = 1024 * 1024;
+const MAX_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+const MAX_CHUNK_RETRIES: u32 = 5;
+const RETRY_BASE_DELAY_MS: u64 = 500;
+const CONNECT_TIMEOUT_SECS: u64 = 15;
+const READ_TIMEOUT_SECS: u64 = 30;
+const PROGRESS_INTERVAL_SECS: u64 = 1;
+const DEFAULT_REFERER: &str = "https://www.bilibili.com/";
+const DEFAULT_USER_AGENT: &str =
+  "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/132.0.0.0 Safari/537.36";
+
+static DOWNLOAD_CLIENT: OnceLock<Client> = OnceLock::new();
+
+pub struct SegmentDownloadOptions {
+  pub connections: usize,
+  pub split: usize,
+  pub header: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SegmentState {
+  total: u64,
+  chunk_size: u64,
+  completed: Vec<bool>,
+}
+
+impl SegmentState {
+  fn chunk_range(&self, index: usize) -> (u64, u64) {
+    let start = index as u64 * self.chunk_size;
+    let end = (start + self.chunk_size).min(self.total) - 1;
+    (start, end)
+  }
+
+  fn completed_bytes(&self) -> u64 {
+    self
+      .completed
+      .iter()
+      .enumerate()
+      .filter(|(_, done)| **done)
+      .map(|(index, _)| {
+        let (start, end) = self.chunk_range(index);
+        end - start + 1
+      })
+      .sum()
+  }
+}
+
+struct ProbeResult {
+  total: u64,
+  supports_range: bool,
+}
+
+struct SegmentJob<'a> {
+  client: &'a Client,
+  urls: &'a [String],
+  headers: HeaderMap,
+  output_path: &'a Path,
+  state_path: PathBuf,
+  state: Mutex<SegmentState>,
+  pending: Mutex<VecDeque<usize>>,
+  supports_range: bool,
+  downloaded: AtomicU64,
+  preferred_url: AtomicUsize,
+  aborted: AtomicBool,
+}
+
+pub fn state_path(output_path: &Path) -> PathBuf {
+  PathBuf::from(format!("{}{}", output_path.to_string_lossy(), STATE_SUFFIX))
+}
+
+pub fn has_resume_state(output_path: &Path) -> bool {
+  state_path(output_path).exists() && output_path.exists()
+}
+
+pub fn cleanup_segment_files(output_path: &Path) {
+  let _ = fs::remove_file(output_path);
+  let _ = fs::remove_file(state_path(output_path));
+}
+
+pub async fn download_segmented<F>(
+  urls: &[String],
+  output_path: &Path,
+  options: &SegmentDownloadOptions,
+  mut on_progress: F,
+) -> Result<(), String>
+where
+  F: FnMut(u64, u64),
+{
+  if urls.is_empty() {
+    return Err("Missing stream url".to_string());
+  }
+  if let Some(parent) = output_path.parent() {
+    fs::create_dir_all(parent).map_err(|err| format!("Failed to create directory: {}", err))?;
+  }
+  let client = download_client()?;
+  let headers = build_headers(&options.header);
+  let probe = probe_target(client, urls, &headers).await?;
+  let state = prepare_state(output_path, &probe, options.split.max(1))?;
+  let pending: VecDeque<usize> = state
+    .completed
+    .iter()
+    .enumerate()
+    .filter(|(_, done)| !**done)
+    .map(|(index, _)| index)
+    .collect();
+  let job = SegmentJob {
+    client,
+    urls,
+    headers,
+    output_path,
+    state_path: state_path(output_path),
+    downloaded: AtomicU64::new(state.completed_bytes()),
+    state: Mutex::new(state),
+    pending: Mutex::new(pending),
+    supports_range: probe.supports_range,
+    preferred_url: AtomicUsize::new(0),
+    aborted: AtomicBool::new(false),
+  };
+  let workers = if probe.supports_range {
+    options.connections.max(1)
+  } else {
+    1
+  };
+
+  let work = join_all((0..workers).map(|_| run_worker(&job)));
+  tokio::pin!(work);
+  let mut ticker = tokio::time::interval(Duration::from_secs(PROGRESS_INTERVAL_SECS));
+  let results = loop {
+    tokio::select! {
+      results = &mut work => break results,
+      _ = ticker.tick() => on_progress(probe.total, job.downloaded.load(Ordering::Relaxed)),
+    }
+  };
+  on_progress(probe.total, job.downloaded.load(Ordering::Relaxed));
+  if let Some(err) = results.into_iter().find_map(|result| result.err()) {
+    return Err(err);
+  }
+  let _ = fs::remove_file(&job.state_path);
+  Ok(())
+}
+
+async fn run_worker(job: &SegmentJob<'_>) -> Result<(), String> {
+  loop {
+    if job.aborted.load(Ordering::Relaxed) {
+      return Ok(());
+    }
+    let next = job
+      .pending
+      .lock()
+      .map_err(|_| "Download queue lock failed".to_string())?
+      .pop_front();
+    let Some(index) = next else {
+      return Ok(());
+    };
+    if let Err(err) = download_chunk(job, index).await {
+      job.aborted.store(true, Ordering::Relaxed);
+      return Err(err);
+    }
+    let snapshot = {
+      let mut state = job
+        .state
+        .lock()
+        .map_err(|_| "Download state lock failed".to_string())?;
+      state.completed[index] = true;
+      serde_json::to_vec(&*state).map_err(|err| err.to_string())?
+    };
+    fs::write(&job.state_path, snapshot)
+      .map_err(|err| format!("Failed to write download state: {}", err))?;
+  }
+}
+
+async fn download_chunk(job: &SegmentJob<'_>, index: usize) -> Result<(), String> {
+  let (start, end) = job
+    .state
+    .lock()
+    .map_err(|_| "Download state lock failed".to_string())?
+    .chunk_range(index);
+  let mut written = 0u64;
+  let mut last_error = String::new();
+  for attempt in 0..MAX_CHUNK_RETRIES {
+    if job.aborted.load(Ordering::Relaxed) {
+      return Err("Download aborted".to_string());
+    }
+    if attempt > 0 {
+      let delay = RETRY_BASE_DELAY_MS.saturating_mul(1u64 << (attempt - 1).min(4));
+      tokio::time::sleep(Duration::from_millis(delay)).await;
+    }
+    if !job.supports_range && written > 0 {
+      job.downloaded.fetch_sub(written, Ordering::Relaxed);
+      written = 0;
+    }
+    let url_index = job.preferred_url.load(Ordering::Relaxed) % job.urls.len();
+    match fetch_range(job, &job.urls[url_index], start, end, &mut written).await {
+      Ok(()) => return Ok(()),
+      Err(err) => {
+        last_error = err;
+        let _ = job.preferred_url.compare_exchange(
+          url_index,
+          (url_index + 1) % job.urls.len(),
+          Ordering::Relaxed,
+          Ordering::Relaxed,
+        );
+      }
+    }
+  }
+  Err(format!(
+    "Chunk {} failed after {} attempts: {}",
+    index, MAX_CHUNK_RETRIES, last_error
+  ))
+}
+
+async fn fetch_range(
+  job: &SegmentJob<'_>,
+  url: &str,
+  start: u64,
+  end: u64,
+  written: &mut u64,
+) -> Result<(), String> {
+  let offset = start + *written;
+  let mut request = job.client.get(url).headers(job.headers.clone());
+  if job.supports_range {
+    request = request.header(RANGE, format!("bytes={}-{}", offset, end));
+  }
+  let mut response = request
+    .send()
+    .await
+    .map_err(|err| format!("Request failed: {}", err))?;
+  let status = response.status();
+  if job.supports_range {
+    if status != StatusCode::PARTIAL_CONTENT {
+      return Err(format!("Unexpected status: HTTP {}", status));
+    }
+    let range_start = response
+      .headers()
+      .get(CONTENT_RANGE)
+      .and_then(|value| value.to_str().ok())
+      .and_then(parse_content_range)
+      .map(|(range_start, _)| range_start);
+    if range_start != Some(offset) {
+      return Err("Unexpected content range".to_string());
+    }
+  } else if !status.is_success() {
+    return Err(format!("Unexpected status: HTTP {}", status));
+  }
+
+  let mut file = OpenOptions::new()
+    .write(true)
+    .open(job.output_path)
+    .map_err(|err| format!("Failed to open output file: {}", err))?;
+  file
+    .seek(SeekFrom::Start(offset))
+    .map_err(|err| format!("Failed to seek output file: {}", err))?;
+  let expected = end - start + 1;
+  while let Some(bytes) = response
+    .chunk()
+    .await
+    .map_err(|err| format!("Failed to read response: {}", err))?
+  {
+    let remaining = expected - *written;
+    let data = &bytes[..bytes.len().min(remaining as usize)];
+    acquire(TransferClass::Download, data.len() as u64).await;
+    file
+      .write_all(data)
+      .map_err(|err| format!("Failed to write output file: {}", err))?;
+    *written += data.len() as u64;
+    job.downloaded.fetch_add(data.len() as u64, Ordering::Relaxed);
+    if *written >= expected {
+      break;
+    }
+  }
+  file
+    .flush()
+    .map_err(|err| format!("Failed to flush output file: {}", err))?;
+  if *written < expected {
+    return Err(format!(
+      "Connection closed early: {}/{} bytes",
+      written, expected
+    ));
+  }
+  Ok(())
+}
+
+async fn probe_target(
+  client: &Client,
+  urls: &[String],
+  headers: &HeaderMap,
+) -> Result<ProbeResult, String> {
+  let mut last_error = String::new();
+  for url in urls {
+    let response = match client
+      .get(url)
+      .headers(headers.clone())
+      .header(RANGE, "bytes=0-0")
+      .send()
+      .await
+    {
+      Ok(response) => response,
+      Err(err) => {
+        last_error = format!("Request failed: {}", err);
+        continue;
+      }
+    };
+    let status = response.status();
+    if status == StatusCode::PARTIAL_CONTENT {
+      if let Some(total) = response
+        .headers()
+        .get(CONTENT_RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_content_range)
+        .and_then(|(_, total)| total)
+        .filter(|total| *total > 0)
+      {
+        return Ok(ProbeResult {
+          total,
+          supports_range: true,
+        });
+      }
+      last_error = "Missing content range".to_string();
+    } else if status.is_success() {
+      if let Some(total) = response.content_length().filter(|total| *total > 0) {
+        return Ok(ProbeResult {
+          total,
+          supports_range: false,
+        });
+      }
+      last_error = "Missing content length".to_string();
+    } else {
+      last_error = format!("Unexpected status: HTTP {}", status);
+    }
+  }
+  Err(last_error)
+}
+
+fn prepare_state(
+  output_path: &Path,
+  probe: &ProbeResult,
+  split: usize,
+) -> Result<SegmentState, String> {
+  let state_path = state_path(output_path);
+  let file_len = fs::metadata(output_path).map(|meta| meta.len()).ok();
+  if probe.supports_range && file_len == Some(probe.total) {
+    if let Some(state) = fs::read(&state_path)
+      .ok()
+      .and_then(|bytes| serde_json::from_slice::<SegmentState>(&bytes).ok())
+      .filter(|state| state.total == probe.total && state.chunk_size > 0)
+    {
+      return Ok(state);
+    }
+  }
+
+  let chunk_size = if probe.supports_range {
+    probe
+      .total
+      .div_ceil(split as u64)
+      .clamp(MIN_CHUNK_SIZE, MAX_CHUNK_SIZE)
+  } else {
+    probe.total
+  };
+  let chunk_count = probe.total.div_ceil(chunk_size) as usize;
+  let state = SegmentState {
+    total: probe.total,
+    chunk_size,
+    completed: vec![false; chunk_count],
+  };
+  let file = OpenOptions::new()
+    .create(true)
+    .write(true)
+    .truncate(true)
+    .open(output_path)
+    .map_err(|err| format!("Failed to create output file: {}", err))?;
+  file
+    .set_len(probe.total)
+    .map_err(|err| format!("Failed to allocate output file: {}", err))?;
+  let snapshot = serde_json::to_vec(&state).map_err(|err| err.to_string())?;
+  fs::write(&state_path, snapshot).map_err(|err| format!("Failed to write download state: {}", err))?;
+  Ok(state)
+}
+
+fn parse_content_range(value: &str) -> Option<(u64, Option<u64>)> {
+  let value = value.trim().strip_prefix("bytes")?.trim();
+  let (range, total) = value.split_once('/')?;
+  let (start, _) = range.split_once('-')?;
+  let start = start.trim().parse::<u64>().ok()?;
+  let total = total.trim().parse::<u64>().ok();
+  Some((start, total))
+}
+
+fn build_headers(header: &str) -> HeaderMap {
+  let mut headers = HeaderMap::new();
+  for line in header.split("\r\n") {
+    let Some((name, value)) = line.split_once(':') else {
+      continue;
+    };
+    if let (Ok(name), Ok(value)) = (
+      HeaderName::from_bytes(name.trim().as_bytes()),
+      HeaderValue::from_str(value.trim()),
+    ) {
+      headers.insert(name, value);
+    }
+  }
+  if !headers.contains_key(REFERER) {
+    headers.insert(REFERER, HeaderValue::from_static(DEFAULT_REFERER));
+  }
+  if !headers.contains_key(USER_AGENT) {
+    headers.insert(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
+  }
+  headers
+}
+
+fn download_client() -> Result<&'static Client, String> {
+  if let Some(client) = DOWNLOAD_CLIENT.get() {
+    return Ok(client);
+  }
+  let client = Client::builder()
+    .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+    .read_timeout(Duration::from_secs(READ_TIMEOUT_SECS))
+    .build()
+    .map_err(|err| format!("Failed to create download client: {}", err))?;
+  Ok(DOWNLOAD_CLIENT.get_or_init(|| client))
+}
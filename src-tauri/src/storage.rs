@@ -0,0 +1,259 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::commands::settings::load_storage_settings_from_db;
+use crate::commands::submission::{cleanup_submission_workdir, find_active_references};
+use crate::db::Db;
+use crate::utils::append_log;
+
+const RETENTION_INTERVAL_SECS: u64 = 3600;
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionSummary {
+  pub deleted_files: Vec<String>,
+  pub cleaned_task_ids: Vec<String>,
+  pub freed_bytes: u64,
+}
+
+pub fn available_space(path: &Path) -> Result<u64, String> {
+  let existing = path
+    .ancestors()
+    .find(|candidate| candidate.exists())
+    .ok_or_else(|| format!("路径不存在: {}", path.to_string_lossy()))?;
+  query_available_space(existing)
+    .map_err(|err| format!("读取磁盘空间失败: {} ({})", existing.to_string_lossy(), err))
+}
+
+pub fn ensure_free_space(db: &Db, path: &Path) -> Result<(), String> {
+  let settings = load_storage_settings_from_db(db).map_err(|err| err.to_string())?;
+  if settings.min_free_space_mb <= 0 {
+    return Ok(());
+  }
+  let available = available_space(path)?;
+  let threshold = settings.min_free_space_mb as u64 * BYTES_PER_MB;
+  if available < threshold {
+    return Err(format!(
+      "磁盘剩余空间不足: {} 可用 {} MB，低于阈值 {} MB",
+      path.to_string_lossy(),
+      available / BYTES_PER_MB,
+      settings.min_free_space_mb
+    ));
+  }
+  Ok(())
+}
+
+pub fn start_retention_loop(db: Arc<Db>, app_log_path: Arc<PathBuf>) {
+  tauri::async_runtime::spawn(async move {
+    loop {
+      tokio::time::sleep(Duration::from_secs(RETENTION_INTERVAL_SECS)).await;
+      let db = db.clone();
+      let log_path = app_log_path.clone();
+      let result =
+        tauri::async_runtime::spawn_blocking(move || run_retention(db.as_ref(), &log_path)).await;
+      if let Ok(Err(err)) = result {
+        append_log(app_log_path.as_ref(), &format!("storage_retention_error err={}", err));
+      }
+    }
+  });
+}
+
+pub fn run_retention(db: &Db, log_path: &PathBuf) -> Result<RetentionSummary, String> {
+  let settings = load_storage_settings_from_db(db).map_err(|err| err.to_string())?;
+  let mut summary = RetentionSummary::default();
+  if !settings.retention_enabled {
+    return Ok(summary);
+  }
+  if settings.retention_baidu_sync_days > 0 {
+    let cutoff = Utc::now() - chrono::Duration::days(settings.retention_baidu_sync_days);
+    for path in load_synced_recordings(db, cutoff)? {
+      delete_unreferenced_file(db, log_path, "", &path, "baidu_sync", &mut summary);
+    }
+  }
+  if settings.retention_submission_days > 0 {
+    let cutoff = Utc::now() - chrono::Duration::days(settings.retention_submission_days);
+    for (task_id, paths) in load_completed_submissions(db, cutoff)? {
+      for path in paths {
+        delete_unreferenced_file(db, log_path, &task_id, &path, "submission", &mut summary);
+      }
+      match cleanup_submission_workdir(db, log_path, &task_id) {
+        Ok(true) => summary.cleaned_task_ids.push(task_id),
+        Ok(false) => {}
+        Err(err) => append_log(
+          log_path,
+          &format!("storage_retention_workdir_fail task_id={} err={}", task_id, err),
+        ),
+      }
+    }
+  }
+  if !summary.deleted_files.is_empty() || !summary.cleaned_task_ids.is_empty() {
+    append_log(
+      log_path,
+      &format!(
+        "storage_retention_done files={} tasks={} freed_mb={}",
+        summary.deleted_files.len(),
+        summary.cleaned_task_ids.len(),
+        summary.freed_bytes / BYTES_PER_MB
+      ),
+    );
+  }
+  Ok(summary)
+}
+
+fn delete_unreferenced_file(
+  db: &Db,
+  log_path: &Path,
+  task_id: &str,
+  path: &str,
+  reason: &str,
+  summary: &mut RetentionSummary,
+) {
+  let target = Path::new(path);
+  let size = match std::fs::metadata(target) {
+    Ok(metadata) if metadata.is_file() => metadata.len(),
+    _ => return,
+  };
+  match find_active_references(db, task_id, path) {
+    Ok(conflicts) if conflicts.is_empty() => {}
+    Ok(conflicts) => {
+      append_log(
+        log_path,
+        &format!(
+          "storage_retention_skip path={} reason=referenced tasks={}",
+          path,
+          conflicts.len()
+        ),
+      );
+      return;
+    }
+    Err(err) => {
+      append_log(
+        log_path,
+        &format!("storage_retention_skip path={} err={}", path, err),
+      );
+      return;
+    }
+  }
+  match std::fs::remove_file(target) {
+    Ok(()) => {
+      append_log(
+        log_path,
+        &format!("storage_retention_delete reason={} path={} size={}", reason, path, size),
+      );
+      summary.deleted_files.push(path.to_string());
+      summary.freed_bytes += size;
+    }
+    Err(err) => append_log(
+      log_path,
+      &format!("storage_retention_delete_fail path={} err={}", path, err),
+    ),
+  }
+}
+
+fn load_synced_recordings(db: &Db, cutoff: DateTime<Utc>) -> Result<Vec<String>, String> {
+  let rows = db
+    .with_conn(|conn| {
+      let mut stmt = conn.prepare(
+        "SELECT DISTINCT b.local_path, b.updated_at FROM baidu_sync_task b \
+         JOIN live_record_task r ON r.file_path = b.local_path \
+         WHERE b.source_type = 'live_segment' AND b.status = 'SUCCESS' AND b.verify_status = 'VERIFIED' \
+         AND r.status != 'RECORDING'",
+      )?;
+      let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+      Ok(rows)
+    })
+    .map_err(|err| err.to_string())?;
+  Ok(
+    rows
+      .into_iter()
+      .filter(|(_, updated_at)| is_before(updated_at, cutoff))
+      .map(|(path, _)| path)
+      .collect(),
+  )
+}
+
+fn load_completed_submissions(
+  db: &Db,
+  cutoff: DateTime<Utc>,
+) -> Result<Vec<(String, Vec<String>)>, String> {
+  db.with_conn(|conn| {
+    let mut stmt = conn.prepare(
+      "SELECT task_id, updated_at FROM submission_task WHERE status = 'COMPLETED'",
+    )?;
+    let tasks = stmt
+      .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+      .collect::<Result<Vec<_>, _>>()?;
+    let mut source_stmt = conn.prepare(
+      "SELECT DISTINCT tsv.source_file_path FROM task_source_video tsv \
+       WHERE tsv.task_id = ?1 AND (\
+         EXISTS (SELECT 1 FROM live_record_task r WHERE r.file_path = tsv.source_file_path AND r.status != 'RECORDING') \
+         OR EXISTS (SELECT 1 FROM video_download d WHERE d.local_path = tsv.source_file_path))",
+    )?;
+    let mut result = Vec::new();
+    for (task_id, updated_at) in tasks {
+      if !is_before(&updated_at, cutoff) {
+        continue;
+      }
+      let paths = source_stmt
+        .query_map([&task_id], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+      result.push((task_id, paths));
+    }
+    Ok(result)
+  })
+  .map_err(|err| err.to_string())
+}
+
+fn is_before(value: &str, cutoff: DateTime<Utc>) -> bool {
+  DateTime::parse_from_rfc3339(value)
+    .map(|value| value.with_timezone(&Utc) < cutoff)
+    .unwrap_or(false)
+}
+
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)]
+fn query_available_space(path: &Path) -> io::Result<u64> {
+  use std::ffi::CString;
+  use std::os::unix::ffi::OsStrExt;
+
+  let c_path = CString::new(path.as_os_str().as_bytes())
+    .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+  let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+  if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+    return Err(io::Error::last_os_error());
+  }
+  Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(windows)]
+fn query_available_space(path: &Path) -> io::Result<u64> {
+  use std::os::windows::ffi::OsStrExt;
+  use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+  let wide: Vec<u16> = path
+    .as_os_str()
+    .encode_wide()
+    .chain(std::iter::once(0))
+    .collect();
+  let mut available: u64 = 0;
+  let ok = unsafe {
+    GetDiskFreeSpaceExW(
+      wide.as_ptr(),
+      &mut available,
+      std::ptr::null_mut(),
+      std::ptr::null_mut(),
+    )
+  };
+  if ok == 0 {
+    return Err(io::Error::last_os_error());
+  }
+  Ok(available)
+}
@@ -3,11 +3,19 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
 
 pub fn now_rfc3339() -> String {
   Utc::now().to_rfc3339()
 }
 
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+  let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac accepts any key length");
+  mac.update(data);
+  mac.finalize().into_bytes().to_vec()
+}
+
 pub fn sanitize_filename(name: &str) -> String {
   let mut sanitized = String::with_capacity(name.len());
   for ch in name.chars() {
@@ -17,6 +25,14 @@ pub fn sanitize_filename(name: &str) -> String {
   sanitized
 }
 
+pub fn escape_xml(value: &str) -> String {
+  value
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
 pub fn build_output_path(base_dir: &str, folder: &str, file_name: &str) -> PathBuf {
   let mut path = PathBuf::from(base_dir);
   path.push(folder);
@@ -0,0 +1,375 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::Utc;
+use reqwest::Client;
+use rusqlite::{params, Row};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::db::Db;
+use crate::utils::{append_log, hmac_sha256, now_rfc3339};
+
+pub const EVENT_AUTO_RECORD_START: &str = "auto_record_start";
+pub const EVENT_RECORD_STOP: &str = "record_stop";
+pub const EVENT_LIVE_REMUX_DONE: &str = "live_remux_done";
+pub const EVENT_DOWNLOAD_COMPLETED: &str = "download_completed";
+pub const EVENT_DOWNLOAD_FAILED: &str = "download_failed";
+pub const EVENT_SUBMISSION_STATUS: &str = "submission_status_changed";
+pub const EVENT_SUBMISSION_REJECTED: &str = "submission_rejected";
+pub const EVENT_BAIDU_SYNC_FAILED: &str = "baidu_sync_failed";
+pub const EVENT_VOD_NEW_UPLOAD: &str = "vod_new_upload";
+pub const EVENT_TEST: &str = "webhook_test";
+
+pub const WEBHOOK_EVENTS: [&str; 9] = [
+  EVENT_AUTO_RECORD_START,
+  EVENT_RECORD_STOP,
+  EVENT_LIVE_REMUX_DONE,
+  EVENT_DOWNLOAD_COMPLETED,
+  EVENT_DOWNLOAD_FAILED,
+  EVENT_SUBMISSION_STATUS,
+  EVENT_SUBMISSION_REJECTED,
+  EVENT_BAIDU_SYNC_FAILED,
+  EVENT_VOD_NEW_UPLOAD,
+];
+
+pub const WEBHOOK_PROVIDERS: [&str; 5] = ["GENERIC", "DISCORD", "FEISHU", "DINGTALK", "TELEGRAM"];
+
+const REQUEST_TIMEOUT_SECS: u64 = 15;
+const MAX_RETRIES: i64 = 10;
+const MAX_BACKOFF_SECS: u64 = 600;
+const TELEGRAM_API_BASE: &str = "https://api.telegram.org";
+
+static WEBHOOK_CLIENT: OnceLock<Client> = OnceLock::new();
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEndpointRecord {
+  pub id: i64,
+  pub name: String,
+  pub provider: String,
+  pub url: String,
+  pub secret: String,
+  pub chat_id: String,
+  pub events: Vec<String>,
+  pub enabled: bool,
+  pub max_retries: i64,
+  pub retry_backoff_seconds: i64,
+  pub created_at: String,
+  pub updated_at: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEndpointInput {
+  pub id: Option<i64>,
+  pub name: String,
+  pub provider: String,
+  pub url: String,
+  pub secret: Option<String>,
+  pub chat_id: Option<String>,
+  pub events: Option<Vec<String>>,
+  pub enabled: Option<bool>,
+  pub max_retries: Option<i64>,
+  pub retry_backoff_seconds: Option<i64>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookPayload {
+  event: String,
+  summary: String,
+  timestamp: String,
+  data: Value,
+}
+
+pub fn list_webhook_endpoints(db: &Db) -> Result<Vec<WebhookEndpointRecord>, String> {
+  db.with_conn(|conn| {
+    let mut stmt = conn.prepare(
+      "SELECT id, name, provider, url, secret, chat_id, events, enabled, max_retries, retry_backoff_seconds, created_at, updated_at \
+       FROM webhook_endpoint ORDER BY id ASC",
+    )?;
+    let rows = stmt.query_map([], map_endpoint_row)?;
+    rows.collect::<Result<Vec<_>, _>>()
+  })
+  .map_err(|err| err.to_string())
+}
+
+pub fn load_webhook_endpoint(db: &Db, id: i64) -> Result<Option<WebhookEndpointRecord>, String> {
+  Ok(list_webhook_endpoints(db)?.into_iter().find(|endpoint| endpoint.id == id))
+}
+
+pub fn save_webhook_endpoint(db: &Db, input: WebhookEndpointInput) -> Result<i64, String> {
+  let name = input.name.trim().to_string();
+  let url = input.url.trim().to_string();
+  let provider = input.provider.trim().to_ascii_uppercase();
+  if !WEBHOOK_PROVIDERS.contains(&provider.as_str()) {
+    return Err(format!("不支持的推送类型: {}", input.provider));
+  }
+  if url.is_empty() {
+    return Err("推送地址不能为空".to_string());
+  }
+  let chat_id = input.chat_id.unwrap_or_default().trim().to_string();
+  if provider == "TELEGRAM" && chat_id.is_empty() {
+    return Err("Telegram 推送需要填写 chat_id".to_string());
+  }
+  let events = input.events.unwrap_or_default();
+  if let Some(unknown) = events
+    .iter()
+    .find(|event| !WEBHOOK_EVENTS.contains(&event.as_str()))
+  {
+    return Err(format!("未知事件: {}", unknown));
+  }
+  let events = events.join(",");
+  let secret = input.secret.unwrap_or_default().trim().to_string();
+  let enabled = input.enabled.unwrap_or(true);
+  let max_retries = input.max_retries.unwrap_or(3).clamp(0, MAX_RETRIES);
+  let backoff = input.retry_backoff_seconds.unwrap_or(5).clamp(1, 300);
+  let now = now_rfc3339();
+  db.with_conn(|conn| match input.id {
+    Some(id) => {
+      let updated = conn.execute(
+        "UPDATE webhook_endpoint SET name = ?1, provider = ?2, url = ?3, secret = ?4, chat_id = ?5, events = ?6, \
+         enabled = ?7, max_retries = ?8, retry_backoff_seconds = ?9, updated_at = ?10 WHERE id = ?11",
+        params![
+          name,
+          provider,
+          url,
+          secret,
+          chat_id,
+          events,
+          enabled as i64,
+          max_retries,
+          backoff,
+          now,
+          id
+        ],
+      )?;
+      if updated == 0 {
+        return Err(rusqlite::Error::QueryReturnedNoRows);
+      }
+      Ok(id)
+    }
+    None => {
+      conn.execute(
+        "INSERT INTO webhook_endpoint (name, provider, url, secret, chat_id, events, enabled, max_retries, retry_backoff_seconds, created_at, updated_at) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?10)",
+        params![
+          name,
+          provider,
+          url,
+          secret,
+          chat_id,
+          events,
+          enabled as i64,
+          max_retries,
+          backoff,
+          now
+        ],
+      )?;
+      Ok(conn.last_insert_rowid())
+    }
+  })
+  .map_err(|err| match err {
+    crate::db::DbError::Sql(rusqlite::Error::QueryReturnedNoRows) => "推送配置不存在".to_string(),
+    other => other.to_string(),
+  })
+}
+
+pub fn delete_webhook_endpoint(db: &Db, id: i64) -> Result<(), String> {
+  db.with_conn(|conn| {
+    conn.execute("DELETE FROM webhook_endpoint WHERE id = ?1", [id])?;
+    Ok(())
+  })
+  .map_err(|err| err.to_string())
+}
+
+pub fn emit_webhook_event(db: &Db, log_path: &Path, event: &str, summary: &str, data: Value) {
+  let endpoints = match list_webhook_endpoints(db) {
+    Ok(list) => list,
+    Err(err) => {
+      append_log(
+        log_path,
+        &format!("webhook_load_fail event={} err={}", event, err),
+      );
+      return;
+    }
+  };
+  let payload = WebhookPayload {
+    event: event.to_string(),
+    summary: summary.to_string(),
+    timestamp: now_rfc3339(),
+    data,
+  };
+  for endpoint in endpoints
+    .into_iter()
+    .filter(|endpoint| endpoint.enabled && subscribes(endpoint, event))
+  {
+    let payload = payload.clone();
+    let log_path = log_path.to_path_buf();
+    tauri::async_runtime::spawn(async move {
+      deliver_with_retry(endpoint, payload, log_path).await;
+    });
+  }
+}
+
+pub async fn send_test_webhook(endpoint: &WebhookEndpointRecord) -> Result<(), String> {
+  let payload = WebhookPayload {
+    event: EVENT_TEST.to_string(),
+    summary: format!("推送配置「{}」测试消息", endpoint.name),
+    timestamp: now_rfc3339(),
+    data: json!({ "endpointId": endpoint.id }),
+  };
+  send_once(endpoint, &payload).await
+}
+
+fn subscribes(endpoint: &WebhookEndpointRecord, event: &str) -> bool {
+  endpoint.events.is_empty() || endpoint.events.iter().any(|value| value == event)
+}
+
+async fn deliver_with_retry(endpoint: WebhookEndpointRecord, payload: WebhookPayload, log_path: PathBuf) {
+  let attempts = endpoint.max_retries.clamp(0, MAX_RETRIES) + 1;
+  let mut backoff = endpoint.retry_backoff_seconds.max(1) as u64;
+  for attempt in 1..=attempts {
+    match send_once(&endpoint, &payload).await {
+      Ok(()) => {
+        append_log(
+          &log_path,
+          &format!(
+            "webhook_delivered id={} event={} attempt={}",
+            endpoint.id, payload.event, attempt
+          ),
+        );
+        return;
+      }
+      Err(err) => {
+        append_log(
+          &log_path,
+          &format!(
+            "webhook_delivery_fail id={} event={} attempt={}/{} err={}",
+            endpoint.id, payload.event, attempt, attempts, err
+          ),
+        );
+        if attempt < attempts {
+          tokio::time::sleep(Duration::from_secs(backoff)).await;
+          backoff = (backoff * 2).min(MAX_BACKOFF_SECS);
+        }
+      }
+    }
+  }
+}
+
+async fn send_once(endpoint: &WebhookEndpointRecord, payload: &WebhookPayload) -> Result<(), String> {
+  let client = WEBHOOK_CLIENT.get_or_init(|| {
+    Client::builder()
+      .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+      .build()
+      .unwrap_or_else(|_| Client::new())
+  });
+  let text = format_message(payload);
+  let request = match endpoint.provider.as_str() {
+    "DISCORD" => client.post(&endpoint.url).json(&json!({ "content": text })),
+    "FEISHU" => {
+      let mut body = json!({ "msg_type": "text", "content": { "text": text } });
+      if !endpoint.secret.is_empty() {
+        let timestamp = Utc::now().timestamp().to_string();
+        let key = format!("{}\n{}", timestamp, endpoint.secret);
+        body["timestamp"] = json!(timestamp);
+        body["sign"] = json!(STANDARD.encode(hmac_sha256(key.as_bytes(), b"")));
+      }
+      client.post(&endpoint.url).json(&body)
+    }
+    "DINGTALK" => {
+      let mut request = client.post(&endpoint.url);
+      if !endpoint.secret.is_empty() {
+        let timestamp = Utc::now().timestamp_millis().to_string();
+        let message = format!("{}\n{}", timestamp, endpoint.secret);
+        let sign = STANDARD.encode(hmac_sha256(endpoint.secret.as_bytes(), message.as_bytes()));
+        request = request.query(&[("timestamp", timestamp), ("sign", sign)]);
+      }
+      request.json(&json!({ "msgtype": "text", "text": { "content": text } }))
+    }
+    "TELEGRAM" => {
+      let url = if endpoint.url.starts_with("http://") || endpoint.url.starts_with("https://") {
+        endpoint.url.clone()
+      } else {
+        format!("{}/bot{}/sendMessage", TELEGRAM_API_BASE, endpoint.url)
+      };
+      client
+        .post(url)
+        .json(&json!({ "chat_id": endpoint.chat_id, "text": text }))
+    }
+    _ => client.post(&endpoint.url).json(payload),
+  };
+  let response = request.send().await.map_err(|err| err.to_string())?;
+  let status = response.status();
+  let body = response.text().await.unwrap_or_default();
+  if !status.is_success() {
+    return Err(format!("status={} body={}", status.as_u16(), truncate(&body)));
+  }
+  check_provider_response(&endpoint.provider, &body)
+}
+
+fn check_provider_response(provider: &str, body: &str) -> Result<(), String> {
+  let value = match serde_json::from_str::<Value>(body) {
+    Ok(value) => value,
+    Err(_) => return Ok(()),
+  };
+  let failed = match provider {
+    "FEISHU" => value
+      .get("code")
+      .or_else(|| value.get("StatusCode"))
+      .and_then(|code| code.as_i64())
+      .map(|code| code != 0)
+      .unwrap_or(false),
+    "DINGTALK" => value
+      .get("errcode")
+      .and_then(|code| code.as_i64())
+      .map(|code| code != 0)
+      .unwrap_or(false),
+    "TELEGRAM" => !value.get("ok").and_then(|ok| ok.as_bool()).unwrap_or(true),
+    _ => false,
+  };
+  if failed {
+    return Err(format!("body={}", truncate(body)));
+  }
+  Ok(())
+}
+
+fn format_message(payload: &WebhookPayload) -> String {
+  format!(
+    "[reaction-cut] {}\n事件: {}\n时间: {}",
+    payload.summary, payload.event, payload.timestamp
+  )
+}
+
+fn truncate(value: &str) -> String {
+  value.chars().take(200).collect()
+}
+
+fn map_endpoint_row(row: &Row) -> Result<WebhookEndpointRecord, rusqlite::Error> {
+  let events: Option<String> = row.get(6)?;
+  Ok(WebhookEndpointRecord {
+    id: row.get(0)?,
+    name: row.get(1)?,
+    provider: row.get(2)?,
+    url: row.get(3)?,
+    secret: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+    chat_id: row.get::<_, Option<String>>(5)?.unwrap_or_default(),
+    events: events
+      .unwrap_or_default()
+      .split(',')
+      .map(str::trim)
+      .filter(|value| !value.is_empty())
+      .map(str::to_string)
+      .collect(),
+    enabled: row.get::<_, i64>(7)? != 0,
+    max_retries: row.get(8)?,
+    retry_backoff_seconds: row.get(9)?,
+    created_at: row.get(10)?,
+    updated_at: row.get(11)?,
+  })
+}
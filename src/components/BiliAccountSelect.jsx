@@ -0,0 +1,56 @@
+import { useEffect, useState } from "react";
+import { invokeCommand } from "../lib/tauri";
+
+const accountLabel = (account) => {
+  const name = account.nickname || account.username || "";
+  const suffix = account.isDefault ? "（默认）" : "";
+  return name ? `${name} (${account.userId})${suffix}` : `${account.userId}${suffix}`;
+};
+
+export default function BiliAccountSelect({
+  value,
+  onChange,
+  disabled,
+  emptyLabel = "跟随默认账号",
+  className = "mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none",
+}) {
+  const [accounts, setAccounts] = useState([]);
+
+  useEffect(() => {
+    let active = true;
+    invokeCommand("auth_account_list")
+      .then((data) => {
+        if (active) {
+          setAccounts(Array.isArray(data) ? data : []);
+        }
+      })
+      .catch(() => {
+        if (active) {
+          setAccounts([]);
+        }
+      });
+    return () => {
+      active = false;
+    };
+  }, []);
+
+  const selected = value ? String(value) : "";
+  const missing = selected && !accounts.some((account) => String(account.userId) === selected);
+
+  return (
+    <select
+      value={selected}
+      disabled={disabled}
+      onChange={(event) => onChange(event.target.value ? Number(event.target.value) : null)}
+      className={className}
+    >
+      <option value="">{emptyLabel}</option>
+      {accounts.map((account) => (
+        <option key={account.userId} value={String(account.userId)}>
+          {accountLabel(account)}
+        </option>
+      ))}
+      {missing ? <option value={selected}>{`${selected}（账号已移除）`}</option> : null}
+    </select>
+  );
+}
@@ -0,0 +1,151 @@
+const defaultInputClassName =
+  "w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none";
+
+export const segmentModeOptions = [
+  { value: "FIXED", label: "固定时长" },
+  { value: "SILENCE", label: "静音处切分" },
+  { value: "SCENE", label: "场景切换处切分" },
+];
+
+export const defaultSegmentModeConfig = {
+  mode: "FIXED",
+  searchWindowSeconds: 20,
+  silenceNoiseDb: -35,
+  silenceMinSeconds: 0.4,
+  sceneThreshold: 0.4,
+};
+
+const readNumber = (value, fallback) => {
+  const parsed = Number(value);
+  return value !== null && value !== undefined && value !== "" && Number.isFinite(parsed)
+    ? parsed
+    : fallback;
+};
+
+export const readSegmentModeConfig = (segmentation) => {
+  const source = segmentation || {};
+  const mode = String(source.mode || "").toUpperCase();
+  return {
+    mode: segmentModeOptions.some((option) => option.value === mode) ? mode : "FIXED",
+    searchWindowSeconds: readNumber(
+      source.searchWindowSeconds,
+      defaultSegmentModeConfig.searchWindowSeconds,
+    ),
+    silenceNoiseDb: readNumber(source.silenceNoiseDb, defaultSegmentModeConfig.silenceNoiseDb),
+    silenceMinSeconds: readNumber(
+      source.silenceMinSeconds,
+      defaultSegmentModeConfig.silenceMinSeconds,
+    ),
+    sceneThreshold: readNumber(source.sceneThreshold, defaultSegmentModeConfig.sceneThreshold),
+  };
+};
+
+export const buildSegmentModeConfig = (segmentation) => {
+  const config = readSegmentModeConfig(segmentation);
+  if (config.mode === "FIXED") {
+    return { mode: config.mode };
+  }
+  if (config.mode === "SILENCE") {
+    return {
+      mode: config.mode,
+      searchWindowSeconds: config.searchWindowSeconds,
+      silenceNoiseDb: config.silenceNoiseDb,
+      silenceMinSeconds: config.silenceMinSeconds,
+    };
+  }
+  return {
+    mode: config.mode,
+    searchWindowSeconds: config.searchWindowSeconds,
+    sceneThreshold: config.sceneThreshold,
+  };
+};
+
+export const segmentModeLabel = (mode) =>
+  segmentModeOptions.find((option) => option.value === String(mode || "").toUpperCase())
+    ?.label || "固定时长";
+
+export default function SegmentModeFields({
+  value,
+  onChange,
+  disabled,
+  inputClassName = defaultInputClassName,
+  labelClassName = "space-y-1 text-xs text-[var(--muted)]",
+}) {
+  const config = readSegmentModeConfig(value);
+  const updateNumber = (key) => (event) => onChange({ [key]: event.target.value });
+
+  return (
+    <div className="grid gap-2 lg:grid-cols-2">
+      <label className={labelClassName}>
+        <span>分段方式</span>
+        <select
+          value={config.mode}
+          disabled={disabled}
+          onChange={(event) => onChange({ mode: event.target.value })}
+          className={inputClassName}
+        >
+          {segmentModeOptions.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+      </label>
+      {config.mode !== "FIXED" ? (
+        <label className={labelClassName}>
+          <span>切点搜索范围（秒）</span>
+          <input
+            type="number"
+            min={1}
+            value={value?.searchWindowSeconds ?? config.searchWindowSeconds}
+            onChange={updateNumber("searchWindowSeconds")}
+            readOnly={disabled}
+            className={inputClassName}
+          />
+        </label>
+      ) : null}
+      {config.mode === "SILENCE" ? (
+        <>
+          <label className={labelClassName}>
+            <span>静音阈值（dB）</span>
+            <input
+              type="number"
+              max={0}
+              value={value?.silenceNoiseDb ?? config.silenceNoiseDb}
+              onChange={updateNumber("silenceNoiseDb")}
+              readOnly={disabled}
+              className={inputClassName}
+            />
+          </label>
+          <label className={labelClassName}>
+            <span>最短静音时长（秒）</span>
+            <input
+              type="number"
+              min={0}
+              step={0.1}
+              value={value?.silenceMinSeconds ?? config.silenceMinSeconds}
+              onChange={updateNumber("silenceMinSeconds")}
+              readOnly={disabled}
+              className={inputClassName}
+            />
+          </label>
+        </>
+      ) : null}
+      {config.mode === "SCENE" ? (
+        <label className={labelClassName}>
+          <span>场景变化阈值（0-1）</span>
+          <input
+            type="number"
+            min={0}
+            max={1}
+            step={0.05}
+            value={value?.sceneThreshold ?? config.sceneThreshold}
+            onChange={updateNumber("sceneThreshold")}
+            readOnly={disabled}
+            className={inputClassName}
+          />
+        </label>
+      ) : null}
+    </div>
+  );
+}
@@ -1,6 +1,39 @@
 import { useEffect, useState } from "react";
+import { open } from "@tauri-apps/plugin-dialog";
+import BiliAccountSelect from "../components/BiliAccountSelect";
 import { invokeCommand } from "../lib/tauri";
-import { formatDateTime } from "../lib/format";
+import { formatDateTime, formatDuration } from "../lib/format";
+
+const weekdayOptions = [
+  { value: 1, label: "一" },
+  { value: 2, label: "二" },
+  { value: 3, label: "三" },
+  { value: 4, label: "四" },
+  { value: 5, label: "五" },
+  { value: 6, label: "六" },
+  { value: 7, label: "日" },
+];
+
+const defaultAutoSubmitForm = {
+  enabled: false,
+  titleTemplate: "【{{ name }}】{{ title }} {{ date }}",
+  descriptionTemplate: "",
+  tagsTemplate: "",
+  partitionId: "",
+  videoType: "ORIGINAL",
+  segmentationEnabled: true,
+  segmentDurationSeconds: "133",
+  minTotalSeconds: "",
+  accountUserId: null,
+};
+
+const hasRecordSchedule = (anchor) =>
+  Boolean(
+    anchor.recordWeekdays ||
+      anchor.recordTimeWindows ||
+      anchor.maxRecordMinutes ||
+      anchor.titleRegex,
+  );
 
 export default function AnchorSection() {
   const [newAnchorUid, setNewAnchorUid] = useState("");
@@ -17,6 +50,30 @@ export default function AnchorSection() {
   const [syncFolders, setSyncFolders] = useState([]);
   const [syncBrowseLoading, setSyncBrowseLoading] = useState(false);
   const [syncBrowseError, setSyncBrowseError] = useState("");
+  const [scheduleAnchor, setScheduleAnchor] = useState(null);
+  const [scheduleForm, setScheduleForm] = useState({
+    recordWeekdays: [],
+    recordTimeWindows: "",
+    maxRecordMinutes: "",
+    titleRegex: "",
+    accountUserId: null,
+  });
+  const [scheduleMessage, setScheduleMessage] = useState("");
+  const [scheduleSaving, setScheduleSaving] = useState(false);
+  const [overrideAnchor, setOverrideAnchor] = useState(null);
+  const [overrideText, setOverrideText] = useState("");
+  const [overrideEffective, setOverrideEffective] = useState(null);
+  const [overrideMessage, setOverrideMessage] = useState("");
+  const [overrideSaving, setOverrideSaving] = useState(false);
+  const [autoSubmitAnchor, setAutoSubmitAnchor] = useState(null);
+  const [autoSubmitForm, setAutoSubmitForm] = useState(defaultAutoSubmitForm);
+  const [autoSubmitMessage, setAutoSubmitMessage] = useState("");
+  const [autoSubmitSaving, setAutoSubmitSaving] = useState(false);
+  const [sessionAnchor, setSessionAnchor] = useState(null);
+  const [sessions, setSessions] = useState([]);
+  const [selectedSessionIds, setSelectedSessionIds] = useState([]);
+  const [sessionMessage, setSessionMessage] = useState("");
+  const [sessionBusy, setSessionBusy] = useState(false);
 
   const logClient = async (text) => {
     try {
@@ -232,6 +289,259 @@ export default function AnchorSection() {
     }
   };
 
+  const handleOpenSchedule = (anchor) => {
+    setScheduleAnchor(anchor);
+    setScheduleMessage("");
+    setScheduleForm({
+      recordWeekdays: String(anchor.recordWeekdays || "")
+        .split(",")
+        .map((item) => Number(item))
+        .filter((item) => item >= 1 && item <= 7),
+      recordTimeWindows: anchor.recordTimeWindows || "",
+      maxRecordMinutes: anchor.maxRecordMinutes ? String(anchor.maxRecordMinutes) : "",
+      titleRegex: anchor.titleRegex || "",
+      accountUserId: anchor.accountUserId || null,
+    });
+  };
+
+  const handleCloseSchedule = () => {
+    if (scheduleSaving) {
+      return;
+    }
+    setScheduleAnchor(null);
+    setScheduleMessage("");
+  };
+
+  const toggleScheduleWeekday = (day) => {
+    setScheduleForm((prev) => ({
+      ...prev,
+      recordWeekdays: prev.recordWeekdays.includes(day)
+        ? prev.recordWeekdays.filter((item) => item !== day)
+        : [...prev.recordWeekdays, day].sort(),
+    }));
+  };
+
+  const handleSaveSchedule = async () => {
+    if (!scheduleAnchor) {
+      return;
+    }
+    setScheduleSaving(true);
+    setScheduleMessage("");
+    try {
+      await invokeCommand("live_room_schedule_update", {
+        roomId: scheduleAnchor.uid,
+        payload: {
+          recordWeekdays: scheduleForm.recordWeekdays.join(","),
+          recordTimeWindows: scheduleForm.recordTimeWindows,
+          maxRecordMinutes: Number(scheduleForm.maxRecordMinutes || 0),
+          titleRegex: scheduleForm.titleRegex,
+        },
+      });
+      await invokeCommand("live_room_account_update", {
+        roomId: scheduleAnchor.uid,
+        accountUserId: scheduleForm.accountUserId,
+      });
+      await loadAnchors();
+      setMessage("录制计划已保存");
+      setScheduleAnchor(null);
+    } catch (error) {
+      setScheduleMessage(error?.message || "保存失败");
+    } finally {
+      setScheduleSaving(false);
+    }
+  };
+
+  const handleOpenOverride = async (anchor) => {
+    setOverrideAnchor(anchor);
+    setOverrideMessage("");
+    setOverrideText("");
+    setOverrideEffective(null);
+    try {
+      const data = await invokeCommand("live_room_settings_get", { roomId: anchor.uid });
+      const overrides = data?.overrides || {};
+      setOverrideText(Object.keys(overrides).length ? JSON.stringify(overrides, null, 2) : "");
+      setOverrideEffective(data?.effective || null);
+    } catch (error) {
+      setOverrideMessage(error?.message || "读取失败");
+    }
+  };
+
+  const handleCloseOverride = () => {
+    if (overrideSaving) {
+      return;
+    }
+    setOverrideAnchor(null);
+    setOverrideMessage("");
+  };
+
+  const handleSaveOverride = async () => {
+    if (!overrideAnchor) {
+      return;
+    }
+    let overrides = {};
+    if (overrideText.trim()) {
+      try {
+        overrides = JSON.parse(overrideText);
+      } catch (error) {
+        setOverrideMessage("JSON 格式错误");
+        return;
+      }
+    }
+    setOverrideSaving(true);
+    setOverrideMessage("");
+    try {
+      await invokeCommand("live_room_settings_update", {
+        roomId: overrideAnchor.uid,
+        overrides,
+      });
+      setMessage("独立设置已保存");
+      setOverrideAnchor(null);
+    } catch (error) {
+      setOverrideMessage(error?.message || "保存失败");
+    } finally {
+      setOverrideSaving(false);
+    }
+  };
+
+  const handleOpenAutoSubmit = async (anchor) => {
+    setAutoSubmitAnchor(anchor);
+    setAutoSubmitMessage("");
+    setAutoSubmitForm(defaultAutoSubmitForm);
+    try {
+      const data = await invokeCommand("live_room_auto_submit_get", { roomId: anchor.uid });
+      const template = data?.template;
+      setAutoSubmitForm({
+        enabled: Boolean(data?.enabled),
+        titleTemplate: template?.titleTemplate || defaultAutoSubmitForm.titleTemplate,
+        descriptionTemplate: template?.descriptionTemplate || "",
+        tagsTemplate: template?.tagsTemplate || "",
+        partitionId: template?.partitionId ? String(template.partitionId) : "",
+        videoType: template?.videoType || "ORIGINAL",
+        segmentationEnabled: template ? Boolean(template.segmentationEnabled) : true,
+        segmentDurationSeconds: String(template?.segmentDurationSeconds || 133),
+        minTotalSeconds: template?.minTotalSeconds ? String(template.minTotalSeconds) : "",
+        accountUserId: template?.accountUserId || null,
+      });
+    } catch (error) {
+      setAutoSubmitMessage(error?.message || "读取失败");
+    }
+  };
+
+  const handleCloseAutoSubmit = () => {
+    if (autoSubmitSaving) {
+      return;
+    }
+    setAutoSubmitAnchor(null);
+    setAutoSubmitMessage("");
+  };
+
+  const handleSaveAutoSubmit = async () => {
+    if (!autoSubmitAnchor) {
+      return;
+    }
+    setAutoSubmitSaving(true);
+    setAutoSubmitMessage("");
+    try {
+      await invokeCommand("live_room_auto_submit_update", {
+        roomId: autoSubmitAnchor.uid,
+        payload: {
+          enabled: autoSubmitForm.enabled,
+          template: {
+            titleTemplate: autoSubmitForm.titleTemplate,
+            descriptionTemplate: autoSubmitForm.descriptionTemplate,
+            tagsTemplate: autoSubmitForm.tagsTemplate,
+            partitionId: Number(autoSubmitForm.partitionId || 0),
+            videoType: autoSubmitForm.videoType,
+            segmentationEnabled: autoSubmitForm.segmentationEnabled,
+            segmentDurationSeconds: Number(autoSubmitForm.segmentDurationSeconds || 0),
+            preserveOriginal: true,
+            minTotalSeconds: Number(autoSubmitForm.minTotalSeconds || 0),
+            accountUserId: autoSubmitForm.accountUserId,
+          },
+        },
+      });
+      setMessage("自动投稿已保存");
+      setAutoSubmitAnchor(null);
+    } catch (error) {
+      setAutoSubmitMessage(error?.message || "保存失败");
+    } finally {
+      setAutoSubmitSaving(false);
+    }
+  };
+
+  const loadSessions = async (anchor) => {
+    const data = await invokeCommand("live_session_list", { roomId: anchor.uid, limit: 50 });
+    setSessions(Array.isArray(data) ? data : []);
+  };
+
+  const handleOpenSessions = async (anchor) => {
+    setSessionAnchor(anchor);
+    setSessions([]);
+    setSelectedSessionIds([]);
+    setSessionMessage("");
+    try {
+      await loadSessions(anchor);
+    } catch (error) {
+      setSessionMessage(error?.message || "读取失败");
+    }
+  };
+
+  const handleCloseSessions = () => {
+    if (sessionBusy) {
+      return;
+    }
+    setSessionAnchor(null);
+    setSessionMessage("");
+  };
+
+  const toggleSessionSelected = (id) => {
+    setSelectedSessionIds((prev) =>
+      prev.includes(id) ? prev.filter((item) => item !== id) : [...prev, id],
+    );
+  };
+
+  const handleMergeSessions = async () => {
+    if (!sessionAnchor || selectedSessionIds.length < 2) {
+      setSessionMessage("请至少选择两个场次");
+      return;
+    }
+    setSessionBusy(true);
+    setSessionMessage("");
+    try {
+      await invokeCommand("live_session_merge", { sessionIds: selectedSessionIds });
+      setSelectedSessionIds([]);
+      await loadSessions(sessionAnchor);
+      setSessionMessage("场次已合并");
+    } catch (error) {
+      setSessionMessage(error?.message || "合并失败");
+    } finally {
+      setSessionBusy(false);
+    }
+  };
+
+  const handleExportSession = async (session) => {
+    setSessionMessage("");
+    try {
+      const selected = await open({
+        directory: true,
+        multiple: false,
+      });
+      if (typeof selected !== "string") {
+        return;
+      }
+      setSessionBusy(true);
+      const data = await invokeCommand("live_session_export", {
+        sessionId: session.id,
+        targetDir: selected,
+      });
+      setSessionMessage(`已导出到 ${data?.outputDir || selected}`);
+    } catch (error) {
+      setSessionMessage(error?.message || "导出失败");
+    } finally {
+      setSessionBusy(false);
+    }
+  };
+
   const handleSyncSelectCurrent = () => {
     setSyncPath(syncBrowserPath);
   };
@@ -430,6 +740,7 @@ export default function AnchorSection() {
                 {anchor.baiduSyncEnabled && anchor.baiduSyncPath ? (
                   <span>同步路径：{anchor.baiduSyncPath}</span>
                 ) : null}
+                {hasRecordSchedule(anchor) ? <span>录制计划：已配置</span> : null}
                 <span>上次检查：{formatDateTime(anchor.lastCheckTime)}</span>
               </div>
               <div className="mt-3 flex flex-wrap gap-2">
@@ -454,6 +765,30 @@ export default function AnchorSection() {
                 >
                   取消订阅
                 </button>
+                <button
+                  className="rounded-full border border-black/10 bg-white px-3 py-1.5 text-xs font-semibold text-[var(--ink)]"
+                  onClick={() => handleOpenSchedule(anchor)}
+                >
+                  录制计划
+                </button>
+                <button
+                  className="rounded-full border border-black/10 bg-white px-3 py-1.5 text-xs font-semibold text-[var(--ink)]"
+                  onClick={() => handleOpenOverride(anchor)}
+                >
+                  独立设置
+                </button>
+                <button
+                  className="rounded-full border border-black/10 bg-white px-3 py-1.5 text-xs font-semibold text-[var(--ink)]"
+                  onClick={() => handleOpenAutoSubmit(anchor)}
+                >
+                  自动投稿
+                </button>
+                <button
+                  className="rounded-full border border-black/10 bg-white px-3 py-1.5 text-xs font-semibold text-[var(--ink)]"
+                  onClick={() => handleOpenSessions(anchor)}
+                >
+                  录制场次
+                </button>
                 {anchor.baiduSyncEnabled ? (
                   <button
                     className="rounded-full border border-black/10 bg-white px-3 py-1.5 text-xs font-semibold text-[var(--ink)]"
@@ -519,6 +854,312 @@ export default function AnchorSection() {
           </div>
         </div>
       ) : null}
+      {scheduleAnchor ? (
+        <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50">
+          <div className="w-[460px] rounded-2xl bg-[var(--block-color)] p-5 text-sm text-[var(--content-color)] shadow-xl">
+            <div className="text-base font-semibold">录制计划</div>
+            <div className="mt-2 text-xs text-[var(--desc-color)]">
+              主播：{scheduleAnchor.nickname || scheduleAnchor.uid}，仅对自动录制生效
+            </div>
+            <div className="mt-3 text-xs text-[var(--desc-color)]">允许录制的星期（不选表示每天）</div>
+            <div className="mt-2 flex flex-wrap gap-2">
+              {weekdayOptions.map((option) => (
+                <label key={option.value} className="flex items-center gap-1 text-xs">
+                  <input
+                    type="checkbox"
+                    checked={scheduleForm.recordWeekdays.includes(option.value)}
+                    onChange={() => toggleScheduleWeekday(option.value)}
+                  />
+                  周{option.label}
+                </label>
+              ))}
+            </div>
+            <div className="mt-3 text-xs text-[var(--desc-color)]">
+              允许录制的时间段，多个用逗号分隔，可跨零点，如 20:00-23:30,23:30-02:00
+            </div>
+            <input
+              value={scheduleForm.recordTimeWindows}
+              onChange={(event) =>
+                setScheduleForm((prev) => ({ ...prev, recordTimeWindows: event.target.value }))
+              }
+              placeholder="留空表示全天"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+            <div className="mt-3 text-xs text-[var(--desc-color)]">单场最长录制时长（分钟，0 表示不限制）</div>
+            <input
+              type="number"
+              value={scheduleForm.maxRecordMinutes}
+              onChange={(event) =>
+                setScheduleForm((prev) => ({ ...prev, maxRecordMinutes: event.target.value }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+            <div className="mt-3 text-xs text-[var(--desc-color)]">仅当直播标题匹配正则时录制</div>
+            <input
+              value={scheduleForm.titleRegex}
+              onChange={(event) =>
+                setScheduleForm((prev) => ({ ...prev, titleRegex: event.target.value }))
+              }
+              placeholder="留空表示不限制，如 (歌回|杂谈)"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+            <div className="mt-3 text-xs text-[var(--desc-color)]">录制与投稿使用的账号</div>
+            <BiliAccountSelect
+              value={scheduleForm.accountUserId}
+              onChange={(accountUserId) =>
+                setScheduleForm((prev) => ({ ...prev, accountUserId }))
+              }
+            />
+            {scheduleMessage ? (
+              <div className="mt-3 text-xs text-amber-600">{scheduleMessage}</div>
+            ) : null}
+            <div className="mt-4 flex justify-end gap-2">
+              <button className="h-9 rounded-lg px-4" onClick={handleCloseSchedule}>
+                取消
+              </button>
+              <button
+                className="h-9 rounded-lg px-4"
+                onClick={handleSaveSchedule}
+                disabled={scheduleSaving}
+              >
+                保存
+              </button>
+            </div>
+          </div>
+        </div>
+      ) : null}
+      {overrideAnchor ? (
+        <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50">
+          <div className="w-[560px] rounded-2xl bg-[var(--block-color)] p-5 text-sm text-[var(--content-color)] shadow-xl">
+            <div className="text-base font-semibold">独立设置</div>
+            <div className="mt-2 text-xs text-[var(--desc-color)]">
+              主播：{overrideAnchor.nickname || overrideAnchor.uid}，填写需要覆盖的录制设置字段（JSON），未填写的字段沿用全局设置
+            </div>
+            <textarea
+              value={overrideText}
+              onChange={(event) => setOverrideText(event.target.value)}
+              placeholder={'如 {"recordPath": "/data/live", "cuttingByTitle": true}'}
+              className="mt-3 h-40 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 font-mono text-xs focus:border-[var(--accent)] focus:outline-none"
+            />
+            {overrideEffective ? (
+              <>
+                <div className="mt-3 text-xs text-[var(--desc-color)]">当前生效配置</div>
+                <pre className="mt-2 max-h-48 overflow-auto rounded-lg border border-black/10 bg-white/60 px-3 py-2 text-xs">
+                  {JSON.stringify(overrideEffective, null, 2)}
+                </pre>
+              </>
+            ) : null}
+            {overrideMessage ? (
+              <div className="mt-3 text-xs text-amber-600">{overrideMessage}</div>
+            ) : null}
+            <div className="mt-4 flex justify-end gap-2">
+              <button className="h-9 rounded-lg px-4" onClick={handleCloseOverride}>
+                取消
+              </button>
+              <button
+                className="h-9 rounded-lg px-4"
+                onClick={handleSaveOverride}
+                disabled={overrideSaving}
+              >
+                保存
+              </button>
+            </div>
+          </div>
+        </div>
+      ) : null}
+      {autoSubmitAnchor ? (
+        <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50">
+          <div className="w-[500px] rounded-2xl bg-[var(--block-color)] p-5 text-sm text-[var(--content-color)] shadow-xl">
+            <div className="text-base font-semibold">自动投稿</div>
+            <div className="mt-2 text-xs text-[var(--desc-color)]">
+              主播：{autoSubmitAnchor.nickname || autoSubmitAnchor.uid}，录制结束后将本场分段按模板创建投稿任务
+            </div>
+            <label className="mt-3 flex items-center gap-2 text-xs">
+              <input
+                type="checkbox"
+                checked={autoSubmitForm.enabled}
+                onChange={(event) =>
+                  setAutoSubmitForm((prev) => ({ ...prev, enabled: event.target.checked }))
+                }
+              />
+              启用自动投稿
+            </label>
+            <div className="mt-3 text-xs text-[var(--desc-color)]">
+              标题模板，可用 {"{{ name }}"}、{"{{ title }}"}、{"{{ date }}"}、{"{{ time }}"}、{"{{ roomId }}"}
+            </div>
+            <input
+              value={autoSubmitForm.titleTemplate}
+              onChange={(event) =>
+                setAutoSubmitForm((prev) => ({ ...prev, titleTemplate: event.target.value }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+            <div className="mt-3 text-xs text-[var(--desc-color)]">简介模板</div>
+            <textarea
+              value={autoSubmitForm.descriptionTemplate}
+              onChange={(event) =>
+                setAutoSubmitForm((prev) => ({ ...prev, descriptionTemplate: event.target.value }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none h-20"
+            />
+            <div className="mt-3 text-xs text-[var(--desc-color)]">标签模板（逗号分隔）</div>
+            <input
+              value={autoSubmitForm.tagsTemplate}
+              onChange={(event) =>
+                setAutoSubmitForm((prev) => ({ ...prev, tagsTemplate: event.target.value }))
+              }
+              placeholder="如 {{ name }},直播录像"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+            <div className="mt-3 text-xs text-[var(--desc-color)]">投稿账号</div>
+            <BiliAccountSelect
+              value={autoSubmitForm.accountUserId}
+              emptyLabel="跟随直播间账号"
+              onChange={(accountUserId) =>
+                setAutoSubmitForm((prev) => ({ ...prev, accountUserId }))
+              }
+            />
+            <div className="mt-3 grid grid-cols-2 gap-3">
+              <div>
+                <div className="text-xs text-[var(--desc-color)]">分区 ID</div>
+                <input
+                  type="number"
+                  value={autoSubmitForm.partitionId}
+                  onChange={(event) =>
+                    setAutoSubmitForm((prev) => ({ ...prev, partitionId: event.target.value }))
+                  }
+                  className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+                />
+              </div>
+              <div>
+                <div className="text-xs text-[var(--desc-color)]">类型</div>
+                <select
+                  value={autoSubmitForm.videoType}
+                  onChange={(event) =>
+                    setAutoSubmitForm((prev) => ({ ...prev, videoType: event.target.value }))
+                  }
+                  className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+                >
+                  <option value="ORIGINAL">原创</option>
+                  <option value="REPOST">转载</option>
+                </select>
+              </div>
+              <div>
+                <div className="text-xs text-[var(--desc-color)]">分段时长（秒）</div>
+                <input
+                  type="number"
+                  value={autoSubmitForm.segmentDurationSeconds}
+                  disabled={!autoSubmitForm.segmentationEnabled}
+                  onChange={(event) =>
+                    setAutoSubmitForm((prev) => ({ ...prev, segmentDurationSeconds: event.target.value }))
+                  }
+                  className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+                />
+              </div>
+              <div>
+                <div className="text-xs text-[var(--desc-color)]">最短录制时长（秒，0 不限制）</div>
+                <input
+                  type="number"
+                  value={autoSubmitForm.minTotalSeconds}
+                  onChange={(event) =>
+                    setAutoSubmitForm((prev) => ({ ...prev, minTotalSeconds: event.target.value }))
+                  }
+                  className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+                />
+              </div>
+            </div>
+            <label className="mt-3 flex items-center gap-2 text-xs">
+              <input
+                type="checkbox"
+                checked={autoSubmitForm.segmentationEnabled}
+                onChange={(event) =>
+                  setAutoSubmitForm((prev) => ({ ...prev, segmentationEnabled: event.target.checked }))
+                }
+              />
+              合并后自动分段
+            </label>
+            {autoSubmitMessage ? (
+              <div className="mt-3 text-xs text-amber-600">{autoSubmitMessage}</div>
+            ) : null}
+            <div className="mt-4 flex justify-end gap-2">
+              <button className="h-9 rounded-lg px-4" onClick={handleCloseAutoSubmit}>
+                取消
+              </button>
+              <button
+                className="h-9 rounded-lg px-4"
+                onClick={handleSaveAutoSubmit}
+                disabled={autoSubmitSaving}
+              >
+                保存
+              </button>
+            </div>
+          </div>
+        </div>
+      ) : null}
+      {sessionAnchor ? (
+        <div className="fixed inset-0 z-50 flex items-center justify-center bg-black/50">
+          <div className="w-[640px] rounded-2xl bg-[var(--block-color)] p-5 text-sm text-[var(--content-color)] shadow-xl">
+            <div className="text-base font-semibold">录制场次</div>
+            <div className="mt-2 text-xs text-[var(--desc-color)]">
+              主播：{sessionAnchor.nickname || sessionAnchor.uid}，同一场直播的断线重连与标题分段会归入同一场次
+            </div>
+            <div className="mt-3 max-h-[420px] space-y-2 overflow-auto">
+              {sessions.length === 0 ? (
+                <div className="text-xs text-[var(--muted)]">暂无录制场次</div>
+              ) : (
+                sessions.map((session) => (
+                  <div
+                    key={session.id}
+                    className="rounded-lg border border-black/10 bg-white/60 px-3 py-2 text-xs"
+                  >
+                    <div className="flex items-center gap-2">
+                      <input
+                        type="checkbox"
+                        checked={selectedSessionIds.includes(session.id)}
+                        disabled={session.status === "RECORDING"}
+                        onChange={() => toggleSessionSelected(session.id)}
+                      />
+                      <div className="flex-1 font-semibold">{session.title || "-"}</div>
+                      <span>{session.status === "RECORDING" ? "录制中" : "已结束"}</span>
+                      <button
+                        className="rounded-full border border-black/10 bg-white px-2 py-0.5 text-xs"
+                        disabled={sessionBusy || session.status === "RECORDING"}
+                        onClick={() => handleExportSession(session)}
+                      >
+                        导出
+                      </button>
+                    </div>
+                    <div className="mt-1 text-[var(--muted)]">
+                      {formatDateTime(session.startTime)} ~ {session.endTime ? formatDateTime(session.endTime) : "-"}
+                      ，{session.segmentCount} 个分段，时长 {formatDuration(session.durationSeconds)}，
+                      {(session.totalSize / 1024 / 1024).toFixed(1)} MB
+                      {session.danmakuFiles?.length ? `，弹幕 ${session.danmakuFiles.length} 个` : ""}
+                    </div>
+                    {session.titles?.length > 1 ? (
+                      <div className="mt-1 text-[var(--muted)]">标题：{session.titles.join(" / ")}</div>
+                    ) : null}
+                  </div>
+                ))
+              )}
+            </div>
+            {sessionMessage ? (
+              <div className="mt-3 text-xs text-amber-600">{sessionMessage}</div>
+            ) : null}
+            <div className="mt-4 flex justify-end gap-2">
+              <button className="h-9 rounded-lg px-4" onClick={handleCloseSessions}>
+                关闭
+              </button>
+              <button
+                className="h-9 rounded-lg px-4"
+                onClick={handleMergeSessions}
+                disabled={sessionBusy || selectedSessionIds.length < 2}
+              >
+                合并所选
+              </button>
+            </div>
+          </div>
+        </div>
+      ) : null}
       {syncPickerOpen ? (
         <div className="fixed inset-0 z-[60] flex items-center justify-center bg-black/50">
           <div className="w-[520px] rounded-2xl bg-[var(--block-color)] p-5 text-sm text-[var(--content-color)] shadow-xl">
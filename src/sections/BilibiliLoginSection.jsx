@@ -262,12 +262,11 @@ export default function BilibiliLoginSection({
     setMessage("");
     try {
       await invokeCommand("auth_logout");
-      setIsLogin(false);
-      setUser(defaultUser);
-      if (onStatusChange) {
-        onStatusChange({ loggedIn: false });
+      const loggedIn = await refreshStatus();
+      if (!loggedIn) {
+        setUser(defaultUser);
+        initScan();
       }
-      initScan();
     } catch (error) {
       setMessage(error.message);
     }
@@ -4,6 +4,11 @@ import { showErrorDialog } from "../lib/dialog";
 import { invokeCommand } from "../lib/tauri";
 import { formatDateTime, formatDuration, formatNumber, parseVideoInput } from "../lib/format";
 import BaiduSyncPathPicker from "../components/BaiduSyncPathPicker";
+import BiliAccountSelect from "../components/BiliAccountSelect";
+import SegmentModeFields, {
+  buildSegmentModeConfig,
+  defaultSegmentModeConfig,
+} from "../components/SegmentModeFields";
 
 const recordTabs = [
   { key: "pending", label: "待下载", status: 0 },
@@ -19,13 +24,96 @@ const defaultDownloadConfig = {
   codec: "",
   format: "dash",
   content: "audio_video",
+  accountUserId: null,
+  sidecar: {
+    subtitle: false,
+    subtitleFormat: "srt",
+    danmaku: false,
+    danmakuFormat: "xml",
+    cover: false,
+    metadata: false,
+    metadataFormat: "json",
+  },
+};
+
+const sidecarItems = [
+  { key: "subtitle", label: "CC字幕", formatKey: "subtitleFormat", formats: ["srt", "ass"] },
+  { key: "danmaku", label: "历史弹幕", formatKey: "danmakuFormat", formats: ["xml", "ass"] },
+  { key: "cover", label: "封面" },
+  { key: "metadata", label: "元数据", formatKey: "metadataFormat", formats: ["json", "nfo"] },
+];
+
+const formatSidecarSummary = (files) => {
+  const items = [];
+  if (files.subtitles?.length) {
+    items.push(`字幕${files.subtitles.length}`);
+  }
+  if (files.danmaku) {
+    items.push(`弹幕${files.danmakuCount || 0}`);
+  }
+  if (files.cover) {
+    items.push("封面");
+  }
+  if (files.metadata) {
+    items.push("元数据");
+  }
+  if (files.errors?.length) {
+    items.push(`失败${files.errors.length}`);
+  }
+  return items.length ? items.join(" / ") : "-";
+};
+
+const batchSourceTypes = [
+  { value: "uploader", label: "UP 主空间", idLabel: "UP 主 UID" },
+  { value: "favorite", label: "收藏夹", idLabel: "收藏夹 ID" },
+  { value: "season", label: "合集", idLabel: "合集 season_id" },
+  { value: "series", label: "视频列表", idLabel: "列表 series_id" },
+  { value: "watch_later", label: "稍后再看", idLabel: "" },
+];
+
+const defaultBatchQuery = {
+  sourceType: "uploader",
+  sourceId: "",
+  mid: "",
+  startDate: "",
+  endDate: "",
+  keyword: "",
+  maxCount: 20,
+};
+
+const subscriptionSourceTypes = batchSourceTypes.filter((item) => item.value !== "watch_later");
+
+const defaultSubscriptionForm = {
+  id: null,
+  sourceType: "uploader",
+  sourceId: "",
+  mid: "",
+  name: "",
+  keyword: "",
+  checkIntervalMinutes: 30,
+  enabled: true,
+  autoSubmitEnabled: false,
+  titleTemplate: "{{ title }}",
+  descriptionTemplate: "",
+  tagsTemplate: "",
+  partitionId: "",
+  collectionId: "",
+  accountUserId: null,
+};
+
+const subscriptionItemStatusLabel = {
+  QUEUED: "已加入队列",
+  SKIPPED: "已存在",
+  FAILED: "失败",
 };
 
 const defaultWorkflowConfig = {
   segmentationConfig: {
     segmentDurationSeconds: 133,
     preserveOriginal: true,
+    ...defaultSegmentModeConfig,
   },
+  burnDanmaku: false,
 };
 
 const fallbackResolutions = [
@@ -169,6 +257,16 @@ export default function DownloadSection() {
   const [quickFillTotal, setQuickFillTotal] = useState(0);
   const quickFillPageSize = 10;
   const [syncPickerOpen, setSyncPickerOpen] = useState(false);
+  const [batchQuery, setBatchQuery] = useState(defaultBatchQuery);
+  const [batchVideos, setBatchVideos] = useState([]);
+  const [batchLoading, setBatchLoading] = useState(false);
+  const [batchMessage, setBatchMessage] = useState("");
+  const [subscriptions, setSubscriptions] = useState([]);
+  const [subscriptionForm, setSubscriptionForm] = useState(defaultSubscriptionForm);
+  const [subscriptionItems, setSubscriptionItems] = useState([]);
+  const [subscriptionItemsId, setSubscriptionItemsId] = useState(null);
+  const [subscriptionLoading, setSubscriptionLoading] = useState(false);
+  const [subscriptionMessage, setSubscriptionMessage] = useState("");
   const [deleteConfirmRecord, setDeleteConfirmRecord] = useState(null);
   const [deleteConfirmDeleteFile, setDeleteConfirmDeleteFile] = useState(false);
 
@@ -324,6 +422,19 @@ export default function DownloadSection() {
     }
   }, [mainTab]);
 
+  useEffect(() => {
+    if (mainTab !== "subscription") {
+      return;
+    }
+    loadSubscriptions();
+    if (!partitions.length) {
+      loadPartitions();
+    }
+    if (!collections.length) {
+      loadCollections();
+    }
+  }, [mainTab]);
+
   useEffect(() => {
     if (!integrationEnabled) {
       return;
@@ -392,9 +503,9 @@ export default function DownloadSection() {
     });
   }, [downloadConfig.downloadPath, downloadConfig.downloadName, defaultDownloadPath]);
 
-  const loadPartitions = async () => {
+  const loadPartitions = async (accountUserId = downloadConfig.accountUserId) => {
     try {
-      const data = await invokeCommand("bilibili_partitions");
+      const data = await invokeCommand("bilibili_partitions", { accountUserId });
       setPartitions(data || []);
       if ((data || []).length) {
         setSubmissionConfig((prev) => {
@@ -409,9 +520,12 @@ export default function DownloadSection() {
     }
   };
 
-  const loadCollections = async () => {
+  const loadCollections = async (accountUserId = downloadConfig.accountUserId) => {
     try {
-      const data = await invokeCommand("bilibili_collections", { mid: 0 });
+      const data = await invokeCommand("bilibili_collections", {
+        mid: accountUserId || 0,
+        accountUserId,
+      });
       const mapped = (data || []).map((item) => ({
         ...item,
         seasonId: item.season_id ?? item.seasonId,
@@ -430,6 +544,7 @@ export default function DownloadSection() {
       const data = await invokeCommand("video_playurl", {
         bvid: info.bvid,
         cid: String(part.cid),
+        accountUserId: downloadConfig.accountUserId,
       });
       const videos = data?.dash?.video || [];
       const resolutionMap = new Map();
@@ -543,7 +658,11 @@ export default function DownloadSection() {
       const errorMessages = [];
       for (const input of parsedInputs) {
         try {
-          const data = await invokeCommand("video_detail", { bvid: input.bvid, aid: input.aid });
+          const data = await invokeCommand("video_detail", {
+            bvid: input.bvid,
+            aid: input.aid,
+            accountUserId: downloadConfig.accountUserId,
+          });
           const pages = Array.isArray(data?.pages) ? data.pages : [];
           const coverUrl = await fetchProxyImage(data?.pic);
           const avatarUrl = await fetchProxyImage(data?.owner?.face);
@@ -766,6 +885,7 @@ export default function DownloadSection() {
     try {
       const data = await invokeCommand("bilibili_topics", {
         partitionId: partitionId ? Number(partitionId) : null,
+        accountUserId: downloadConfig.accountUserId,
       });
       const mapped = normalizeActivityOptions(data);
       setActivityOptions(mapped);
@@ -915,6 +1035,8 @@ export default function DownloadSection() {
         codec: downloadConfig.codec || null,
         format: downloadConfig.format || null,
         content: downloadConfig.content || null,
+        sidecar: downloadConfig.sidecar,
+        accountUserId: downloadConfig.accountUserId,
       };
       const downloadName = isMultiVideo ? null : downloadConfig.downloadName || null;
       const requests = selectedVideoItems.map((item) => ({
@@ -945,6 +1067,205 @@ export default function DownloadSection() {
     }
   };
 
+  const buildBatchPayload = () => {
+    const toTimestamp = (value, endOfDay) => {
+      if (!value) {
+        return null;
+      }
+      const date = new Date(`${value}T${endOfDay ? "23:59:59" : "00:00:00"}`);
+      return Number.isNaN(date.getTime()) ? null : Math.floor(date.getTime() / 1000);
+    };
+    return {
+      sourceType: batchQuery.sourceType,
+      sourceId: batchQuery.sourceId.trim() || null,
+      mid: batchQuery.mid.trim() || null,
+      startTime: toTimestamp(batchQuery.startDate, false),
+      endTime: toTimestamp(batchQuery.endDate, true),
+      keyword: batchQuery.keyword.trim() || null,
+      maxCount: Number(batchQuery.maxCount) || null,
+      accountUserId: downloadConfig.accountUserId,
+    };
+  };
+
+  const handleBatchPreview = async () => {
+    setBatchLoading(true);
+    setBatchMessage("");
+    try {
+      const data = await invokeCommand("download_source_preview", {
+        payload: buildBatchPayload(),
+      });
+      setBatchVideos(Array.isArray(data) ? data : []);
+      setBatchMessage(`匹配 ${Array.isArray(data) ? data.length : 0} 个视频`);
+    } catch (error) {
+      setBatchVideos([]);
+      setBatchMessage(error?.message || String(error) || "获取失败");
+    } finally {
+      setBatchLoading(false);
+    }
+  };
+
+  const handleBatchEnqueue = async () => {
+    if (!(await ensureDownloadPathReady())) {
+      return;
+    }
+    setBatchLoading(true);
+    setBatchMessage("");
+    try {
+      const data = await invokeCommand("download_source_enqueue", {
+        payload: {
+          ...buildBatchPayload(),
+          config: {
+            downloadPath: downloadConfig.downloadPath || null,
+            resolution: downloadConfig.resolution || null,
+            codec: downloadConfig.codec || null,
+            format: downloadConfig.format || null,
+            content: downloadConfig.content || null,
+            sidecar: downloadConfig.sidecar,
+            accountUserId: downloadConfig.accountUserId,
+          },
+        },
+      });
+      const failed = data?.failed?.length || 0;
+      setBatchMessage(
+        `匹配 ${data?.matched || 0} 个，加入队列 ${data?.queued || 0} 个，已存在跳过 ${
+          data?.skipped || 0
+        } 个${failed ? `，失败 ${failed} 个` : ""}`,
+      );
+    } catch (error) {
+      setBatchMessage(error?.message || String(error) || "加入队列失败");
+    } finally {
+      setBatchLoading(false);
+    }
+  };
+
+  const loadSubscriptions = async () => {
+    try {
+      const data = await invokeCommand("vod_subscription_list");
+      setSubscriptions(Array.isArray(data) ? data : []);
+    } catch (error) {
+      setSubscriptionMessage(error?.message || String(error) || "读取订阅失败");
+    }
+  };
+
+  const loadSubscriptionItems = async (subscriptionId) => {
+    setSubscriptionItemsId(subscriptionId);
+    try {
+      const data = await invokeCommand("vod_subscription_items", { subscriptionId });
+      setSubscriptionItems(Array.isArray(data) ? data : []);
+    } catch (error) {
+      setSubscriptionItems([]);
+      setSubscriptionMessage(error?.message || String(error) || "读取订阅记录失败");
+    }
+  };
+
+  const handleSubscriptionEdit = (subscription) => {
+    const template = subscription.autoSubmitTemplate || {};
+    setSubscriptionForm({
+      id: subscription.id,
+      sourceType: subscription.sourceType,
+      sourceId: subscription.sourceId,
+      mid: subscription.mid || "",
+      name: subscription.name || "",
+      keyword: subscription.keyword || "",
+      checkIntervalMinutes: subscription.checkIntervalMinutes,
+      enabled: subscription.enabled,
+      autoSubmitEnabled: subscription.autoSubmitEnabled,
+      titleTemplate: template.titleTemplate || defaultSubscriptionForm.titleTemplate,
+      descriptionTemplate: template.descriptionTemplate || "",
+      tagsTemplate: template.tagsTemplate || "",
+      partitionId: template.partitionId ? String(template.partitionId) : "",
+      collectionId: template.collectionId ? String(template.collectionId) : "",
+      accountUserId:
+        subscription.downloadConfig?.accountUserId || template.accountUserId || null,
+    });
+  };
+
+  const handleSubscriptionSave = async () => {
+    if (!(await ensureDownloadPathReady())) {
+      return;
+    }
+    const form = subscriptionForm;
+    setSubscriptionLoading(true);
+    setSubscriptionMessage("");
+    try {
+      await invokeCommand("vod_subscription_save", {
+        payload: {
+          id: form.id,
+          sourceType: form.sourceType,
+          sourceId: form.sourceId.trim(),
+          mid: form.mid.trim() || null,
+          name: form.name.trim() || null,
+          keyword: form.keyword.trim() || null,
+          checkIntervalMinutes: Number(form.checkIntervalMinutes) || null,
+          enabled: form.enabled,
+          downloadConfig: {
+            downloadPath: downloadConfig.downloadPath || null,
+            resolution: downloadConfig.resolution || null,
+            codec: downloadConfig.codec || null,
+            format: downloadConfig.format || null,
+            content: downloadConfig.content || null,
+            sidecar: downloadConfig.sidecar,
+            accountUserId: form.accountUserId,
+          },
+          autoSubmitEnabled: form.autoSubmitEnabled,
+          autoSubmitTemplate: form.autoSubmitEnabled
+            ? {
+                titleTemplate: form.titleTemplate,
+                descriptionTemplate: form.descriptionTemplate,
+                tagsTemplate: form.tagsTemplate,
+                partitionId: Number(form.partitionId) || 0,
+                collectionId: Number(form.collectionId) || null,
+                videoType: "ORIGINAL",
+                segmentationEnabled: false,
+                accountUserId: form.accountUserId,
+              }
+            : null,
+        },
+      });
+      setSubscriptionForm(defaultSubscriptionForm);
+      setSubscriptionMessage("订阅已保存");
+      await loadSubscriptions();
+    } catch (error) {
+      setSubscriptionMessage(error?.message || String(error) || "保存订阅失败");
+    } finally {
+      setSubscriptionLoading(false);
+    }
+  };
+
+  const handleSubscriptionDelete = async (id) => {
+    try {
+      await invokeCommand("vod_subscription_delete", { id });
+      if (subscriptionItemsId === id) {
+        setSubscriptionItemsId(null);
+        setSubscriptionItems([]);
+      }
+      await loadSubscriptions();
+    } catch (error) {
+      setSubscriptionMessage(error?.message || String(error) || "删除订阅失败");
+    }
+  };
+
+  const handleSubscriptionCheck = async (id) => {
+    setSubscriptionLoading(true);
+    setSubscriptionMessage("");
+    try {
+      const data = await invokeCommand("vod_subscription_check", { id: id ?? null });
+      setSubscriptionMessage(
+        `检查 ${data?.checked || 0} 个订阅，新视频 ${data?.newVideos || 0} 个，加入队列 ${
+          data?.queued || 0
+        } 个${data?.failed ? `，失败 ${data.failed} 个` : ""}`,
+      );
+      await loadSubscriptions();
+      if (id && subscriptionItemsId === id) {
+        await loadSubscriptionItems(id);
+      }
+    } catch (error) {
+      setSubscriptionMessage(error?.message || String(error) || "检查订阅失败");
+    } finally {
+      setSubscriptionLoading(false);
+    }
+  };
+
   const buildWorkflowConfig = () => {
     return {
       enableSegmentation: segmentationEnabled,
@@ -952,7 +1273,9 @@ export default function DownloadSection() {
         enabled: segmentationEnabled,
         segmentDurationSeconds: workflowConfig.segmentationConfig.segmentDurationSeconds,
         preserveOriginal: workflowConfig.segmentationConfig.preserveOriginal,
+        ...buildSegmentModeConfig(workflowConfig.segmentationConfig),
       },
+      burnDanmaku: Boolean(workflowConfig.burnDanmaku),
     };
   };
 
@@ -1076,6 +1399,8 @@ export default function DownloadSection() {
         codec: downloadConfig.codec || null,
         format: downloadConfig.format || null,
         content: downloadConfig.content || null,
+        sidecar: downloadConfig.sidecar,
+        accountUserId: downloadConfig.accountUserId,
       };
       const downloadName = isMultiVideo ? null : downloadConfig.downloadName || null;
       const downloadRequests = selectedVideoItems.map((item) => ({
@@ -1115,6 +1440,7 @@ export default function DownloadSection() {
           baiduSyncEnabled: Boolean(submissionConfig.baiduSyncEnabled),
           baiduSyncPath: submissionConfig.baiduSyncPath || null,
           baiduSyncFilename: submissionConfig.baiduSyncFilename || null,
+          accountUserId: downloadConfig.accountUserId,
           videoParts: selectedPartsConfig.map((part) => ({
             originalTitle: part.title,
             filePath: part.filePath,
@@ -1342,9 +1668,403 @@ export default function DownloadSection() {
           >
             下载队列
           </button>
+          <button
+            className={`h-8 px-3 rounded-lg text-sm ${
+              mainTab === "batch"
+                ? "bg-[var(--button-color)] text-[var(--primary-color)]"
+                : "text-[var(--desc-color)]"
+            }`}
+            onClick={() => setMainTab("batch")}
+          >
+            批量下载
+          </button>
+          <button
+            className={`h-8 px-3 rounded-lg text-sm ${
+              mainTab === "subscription"
+                ? "bg-[var(--button-color)] text-[var(--primary-color)]"
+                : "text-[var(--desc-color)]"
+            }`}
+            onClick={() => setMainTab("subscription")}
+          >
+            订阅
+          </button>
         </div>
       </div>
 
+      {mainTab === "batch" ? (
+        <div className="panel p-4 space-y-3">
+          <div>
+            <p className="text-xs uppercase tracking-[0.2em] text-[var(--desc-color)]">批量下载</p>
+            <h2 className="text-lg font-semibold text-[var(--content-color)]">
+              UP 主 / 收藏夹 / 合集 / 稍后再看
+            </h2>
+          </div>
+          <div className="grid gap-2 lg:grid-cols-3">
+            <select
+              value={batchQuery.sourceType}
+              onChange={(event) =>
+                setBatchQuery((prev) => ({ ...prev, sourceType: event.target.value }))
+              }
+            >
+              {batchSourceTypes.map((item) => (
+                <option key={item.value} value={item.value}>
+                  {item.label}
+                </option>
+              ))}
+            </select>
+            {batchQuery.sourceType !== "watch_later" ? (
+              <input
+                value={batchQuery.sourceId}
+                onChange={(event) =>
+                  setBatchQuery((prev) => ({ ...prev, sourceId: event.target.value }))
+                }
+                placeholder={
+                  batchSourceTypes.find((item) => item.value === batchQuery.sourceType)?.idLabel
+                }
+              />
+            ) : null}
+            {batchQuery.sourceType === "season" || batchQuery.sourceType === "series" ? (
+              <input
+                value={batchQuery.mid}
+                onChange={(event) => setBatchQuery((prev) => ({ ...prev, mid: event.target.value }))}
+                placeholder="UP 主 UID"
+              />
+            ) : null}
+            <input
+              type="date"
+              value={batchQuery.startDate}
+              onChange={(event) =>
+                setBatchQuery((prev) => ({ ...prev, startDate: event.target.value }))
+              }
+              title="发布开始日期"
+            />
+            <input
+              type="date"
+              value={batchQuery.endDate}
+              onChange={(event) =>
+                setBatchQuery((prev) => ({ ...prev, endDate: event.target.value }))
+              }
+              title="发布结束日期"
+            />
+            <input
+              value={batchQuery.keyword}
+              onChange={(event) =>
+                setBatchQuery((prev) => ({ ...prev, keyword: event.target.value }))
+              }
+              placeholder="标题关键词"
+            />
+            <input
+              type="number"
+              min="1"
+              value={batchQuery.maxCount}
+              onChange={(event) =>
+                setBatchQuery((prev) => ({ ...prev, maxCount: event.target.value }))
+              }
+              placeholder="最大数量"
+            />
+          </div>
+          <div className="text-xs text-[var(--desc-color)]">
+            使用“视频下载”中的下载路径、清晰度与附件配置；已在下载记录中的分P会自动跳过。
+          </div>
+          <div className="flex flex-wrap items-center gap-2">
+            <button
+              className="h-9 px-4 rounded-lg bg-[var(--solid-button-color)] text-[var(--content-color)]"
+              onClick={handleBatchPreview}
+              disabled={batchLoading}
+            >
+              预览
+            </button>
+            <button
+              className="h-9 px-4 rounded-lg bg-[var(--primary-color)] text-[var(--primary-text)]"
+              onClick={handleBatchEnqueue}
+              disabled={batchLoading}
+            >
+              {batchLoading ? "处理中..." : "加入下载队列"}
+            </button>
+            {batchMessage ? (
+              <span className="text-xs text-[var(--desc-color)]">{batchMessage}</span>
+            ) : null}
+          </div>
+          {batchVideos.length ? (
+            <div className="max-h-80 space-y-1 overflow-y-auto text-xs">
+              {batchVideos.map((video) => (
+                <div
+                  key={video.bvid}
+                  className="flex items-center gap-3 rounded-lg bg-[var(--block-color)] px-3 py-2"
+                >
+                  <span className="w-28 shrink-0 text-[var(--desc-color)]">{video.bvid}</span>
+                  <span className="flex-1 truncate text-[var(--content-color)]">{video.title}</span>
+                  <span className="text-[var(--desc-color)]">
+                    {video.pubdate ? formatDateTime(video.pubdate * 1000) : "-"}
+                  </span>
+                </div>
+              ))}
+            </div>
+          ) : null}
+        </div>
+      ) : null}
+
+      {mainTab === "subscription" ? (
+        <div className="panel p-4 space-y-3">
+          <div>
+            <p className="text-xs uppercase tracking-[0.2em] text-[var(--desc-color)]">订阅</p>
+            <h2 className="text-lg font-semibold text-[var(--content-color)]">
+              自动下载 UP 主 / 合集的新投稿
+            </h2>
+          </div>
+          <div className="grid gap-2 lg:grid-cols-3">
+            <select
+              value={subscriptionForm.sourceType}
+              onChange={(event) =>
+                setSubscriptionForm((prev) => ({ ...prev, sourceType: event.target.value }))
+              }
+            >
+              {subscriptionSourceTypes.map((item) => (
+                <option key={item.value} value={item.value}>
+                  {item.label}
+                </option>
+              ))}
+            </select>
+            <input
+              value={subscriptionForm.sourceId}
+              onChange={(event) =>
+                setSubscriptionForm((prev) => ({ ...prev, sourceId: event.target.value }))
+              }
+              placeholder={
+                subscriptionSourceTypes.find((item) => item.value === subscriptionForm.sourceType)
+                  ?.idLabel
+              }
+            />
+            {subscriptionForm.sourceType === "season" ||
+            subscriptionForm.sourceType === "series" ? (
+              <input
+                value={subscriptionForm.mid}
+                onChange={(event) =>
+                  setSubscriptionForm((prev) => ({ ...prev, mid: event.target.value }))
+                }
+                placeholder="UP 主 UID"
+              />
+            ) : null}
+            <input
+              value={subscriptionForm.name}
+              onChange={(event) =>
+                setSubscriptionForm((prev) => ({ ...prev, name: event.target.value }))
+              }
+              placeholder="备注名称"
+            />
+            <input
+              value={subscriptionForm.keyword}
+              onChange={(event) =>
+                setSubscriptionForm((prev) => ({ ...prev, keyword: event.target.value }))
+              }
+              placeholder="标题关键词（可选）"
+            />
+            <input
+              type="number"
+              min="5"
+              value={subscriptionForm.checkIntervalMinutes}
+              onChange={(event) =>
+                setSubscriptionForm((prev) => ({
+                  ...prev,
+                  checkIntervalMinutes: event.target.value,
+                }))
+              }
+              placeholder="检查间隔（分钟）"
+            />
+            <BiliAccountSelect
+              value={subscriptionForm.accountUserId}
+              onChange={(accountUserId) =>
+                setSubscriptionForm((prev) => ({ ...prev, accountUserId }))
+              }
+              className="w-full"
+            />
+          </div>
+          <div className="flex flex-wrap items-center gap-4 text-sm text-[var(--content-color)]">
+            <label className="flex items-center gap-2">
+              <input
+                type="checkbox"
+                checked={subscriptionForm.enabled}
+                onChange={(event) =>
+                  setSubscriptionForm((prev) => ({ ...prev, enabled: event.target.checked }))
+                }
+              />
+              启用
+            </label>
+            <label className="flex items-center gap-2">
+              <input
+                type="checkbox"
+                checked={subscriptionForm.autoSubmitEnabled}
+                onChange={(event) =>
+                  setSubscriptionForm((prev) => ({
+                    ...prev,
+                    autoSubmitEnabled: event.target.checked,
+                  }))
+                }
+              />
+              下载后自动投稿
+            </label>
+          </div>
+          {subscriptionForm.autoSubmitEnabled ? (
+            <div className="grid gap-2 lg:grid-cols-2">
+              <input
+                value={subscriptionForm.titleTemplate}
+                onChange={(event) =>
+                  setSubscriptionForm((prev) => ({ ...prev, titleTemplate: event.target.value }))
+                }
+                placeholder="标题模板"
+              />
+              <input
+                value={subscriptionForm.tagsTemplate}
+                onChange={(event) =>
+                  setSubscriptionForm((prev) => ({ ...prev, tagsTemplate: event.target.value }))
+                }
+                placeholder="标签（逗号分隔）"
+              />
+              <select
+                value={subscriptionForm.partitionId}
+                onChange={(event) =>
+                  setSubscriptionForm((prev) => ({ ...prev, partitionId: event.target.value }))
+                }
+              >
+                <option value="">B站分区</option>
+                {partitions.map((partition) => (
+                  <option key={partition.tid} value={partition.tid}>
+                    {partition.name}
+                  </option>
+                ))}
+              </select>
+              <select
+                value={subscriptionForm.collectionId}
+                onChange={(event) =>
+                  setSubscriptionForm((prev) => ({ ...prev, collectionId: event.target.value }))
+                }
+              >
+                <option value="">合集（可选）</option>
+                {collections.map((collection) => (
+                  <option key={collection.seasonId} value={collection.seasonId}>
+                    {collection.name}
+                  </option>
+                ))}
+              </select>
+              <textarea
+                className="lg:col-span-2"
+                rows={3}
+                value={subscriptionForm.descriptionTemplate}
+                onChange={(event) =>
+                  setSubscriptionForm((prev) => ({
+                    ...prev,
+                    descriptionTemplate: event.target.value,
+                  }))
+                }
+                placeholder="简介模板"
+              />
+              <div className="text-xs text-[var(--desc-color)] lg:col-span-2">
+                可用变量：{"{{ title }}"} {"{{ name }}"} {"{{ bvid }}"} {"{{ date }}"} {"{{ time }}"}
+              </div>
+            </div>
+          ) : null}
+          <div className="text-xs text-[var(--desc-color)]">
+            保存时使用“视频下载”中的下载路径、清晰度与附件配置；只下载订阅创建之后发布的视频。
+          </div>
+          <div className="flex flex-wrap items-center gap-2">
+            <button
+              className="h-9 px-4 rounded-lg bg-[var(--primary-color)] text-[var(--primary-text)]"
+              onClick={handleSubscriptionSave}
+              disabled={subscriptionLoading}
+            >
+              {subscriptionForm.id ? "更新订阅" : "添加订阅"}
+            </button>
+            {subscriptionForm.id ? (
+              <button
+                className="h-9 px-4 rounded-lg bg-[var(--solid-button-color)] text-[var(--content-color)]"
+                onClick={() => setSubscriptionForm(defaultSubscriptionForm)}
+              >
+                取消编辑
+              </button>
+            ) : null}
+            <button
+              className="h-9 px-4 rounded-lg bg-[var(--solid-button-color)] text-[var(--content-color)]"
+              onClick={() => handleSubscriptionCheck(null)}
+              disabled={subscriptionLoading}
+            >
+              {subscriptionLoading ? "处理中..." : "立即检查全部"}
+            </button>
+            {subscriptionMessage ? (
+              <span className="text-xs text-[var(--desc-color)]">{subscriptionMessage}</span>
+            ) : null}
+          </div>
+          <div className="space-y-1 text-xs">
+            {subscriptions.map((subscription) => (
+              <div key={subscription.id} className="rounded-lg bg-[var(--block-color)] px-3 py-2">
+                <div className="flex flex-wrap items-center gap-3">
+                  <span className="flex-1 truncate text-[var(--content-color)]">
+                    {subscription.name || subscription.sourceId}
+                    <span className="ml-2 text-[var(--desc-color)]">
+                      {subscriptionSourceTypes.find((item) => item.value === subscription.sourceType)
+                        ?.label || subscription.sourceType}
+                      {subscription.enabled ? "" : " · 已停用"}
+                      {subscription.autoSubmitEnabled ? " · 自动投稿" : ""}
+                    </span>
+                  </span>
+                  <span className="text-[var(--desc-color)]">
+                    上次检查 {subscription.lastCheckTime ? formatDateTime(subscription.lastCheckTime) : "-"}
+                  </span>
+                  <button
+                    className="text-[var(--primary-color)]"
+                    onClick={() => handleSubscriptionCheck(subscription.id)}
+                    disabled={subscriptionLoading}
+                  >
+                    检查
+                  </button>
+                  <button
+                    className="text-[var(--primary-color)]"
+                    onClick={() => loadSubscriptionItems(subscription.id)}
+                  >
+                    记录
+                  </button>
+                  <button
+                    className="text-[var(--primary-color)]"
+                    onClick={() => handleSubscriptionEdit(subscription)}
+                  >
+                    编辑
+                  </button>
+                  <button
+                    className="text-red-500"
+                    onClick={() => handleSubscriptionDelete(subscription.id)}
+                  >
+                    删除
+                  </button>
+                </div>
+                {subscription.lastError ? (
+                  <div className="mt-1 text-red-500">{subscription.lastError}</div>
+                ) : null}
+                {subscriptionItemsId === subscription.id ? (
+                  <div className="mt-2 max-h-60 space-y-1 overflow-y-auto">
+                    {subscriptionItems.length ? (
+                      subscriptionItems.map((item) => (
+                        <div key={item.id} className="flex items-center gap-3">
+                          <span className="w-28 shrink-0 text-[var(--desc-color)]">{item.bvid}</span>
+                          <span className="flex-1 truncate text-[var(--content-color)]">
+                            {item.title}
+                          </span>
+                          <span className="text-[var(--desc-color)]">
+                            {subscriptionItemStatusLabel[item.status] || item.status}
+                            {item.error ? `：${item.error}` : ""}
+                            {item.retryCount ? `（已重试 ${item.retryCount} 次）` : ""}
+                          </span>
+                        </div>
+                      ))
+                    ) : (
+                      <div className="text-[var(--desc-color)]">暂无记录</div>
+                    )}
+                  </div>
+                ) : null}
+              </div>
+            ))}
+          </div>
+        </div>
+      ) : null}
+
       {mainTab === "download" ? (
         <div className="space-y-4">
           {downloadStep === "select" ? (
@@ -1358,7 +2078,7 @@ export default function DownloadSection() {
                     <h2 className="text-lg font-semibold text-[var(--content-color)]">视频下载</h2>
                   </div>
                 </div>
-                <div className="mt-3 grid gap-3 lg:grid-cols-[1fr_auto]">
+                <div className="mt-3 grid gap-3 lg:grid-cols-[1fr_auto_auto]">
                   <input
                     value={searchInput}
                     onChange={(event) => setSearchInput(event.target.value)}
@@ -1377,6 +2097,16 @@ export default function DownloadSection() {
                   >
                     {searching ? "搜索中..." : "搜索"}
                   </button>
+                  <BiliAccountSelect
+                    value={downloadConfig.accountUserId}
+                    disabled={searching}
+                    onChange={(accountUserId) => {
+                      setDownloadConfig((prev) => ({ ...prev, accountUserId }));
+                      setSubmissionConfig((prev) => ({ ...prev, collectionId: "" }));
+                      loadCollections(accountUserId);
+                    }}
+                    className="h-8"
+                  />
                 </div>
                 {message ? (
                   <div className="mt-3 rounded-lg border border-amber-200 bg-amber-50 px-3 py-2 text-sm text-amber-700">
@@ -1670,6 +2400,44 @@ export default function DownloadSection() {
                             <option value="audio_only">仅音频</option>
                           </select>
                         </div>
+                        <div className="grid gap-2 text-xs">
+                          {sidecarItems.map((item) => (
+                            <div key={item.key} className="flex items-center justify-between gap-2">
+                              <label className="flex items-center gap-2">
+                                <input
+                                  type="checkbox"
+                                  checked={Boolean(downloadConfig.sidecar[item.key])}
+                                  onChange={(event) =>
+                                    setDownloadConfig((prev) => ({
+                                      ...prev,
+                                      sidecar: { ...prev.sidecar, [item.key]: event.target.checked },
+                                    }))
+                                  }
+                                />
+                                同时保存{item.label}
+                              </label>
+                              {item.formatKey ? (
+                                <select
+                                  value={downloadConfig.sidecar[item.formatKey]}
+                                  disabled={!downloadConfig.sidecar[item.key]}
+                                  onChange={(event) =>
+                                    setDownloadConfig((prev) => ({
+                                      ...prev,
+                                      sidecar: { ...prev.sidecar, [item.formatKey]: event.target.value },
+                                    }))
+                                  }
+                                  className="w-24"
+                                >
+                                  {item.formats.map((format) => (
+                                    <option key={format} value={format}>
+                                      {format.toUpperCase()}
+                                    </option>
+                                  ))}
+                                </select>
+                              ) : null}
+                            </div>
+                          ))}
+                        </div>
                         {playOptionsEmpty ? (
                           <div className="rounded-lg border border-dashed border-[var(--split-color)] px-3 py-2 text-xs text-[var(--desc-color)]">
                             搜索视频后加载可选分辨率与编码。
@@ -1985,6 +2753,19 @@ export default function DownloadSection() {
                           <input type="checkbox" checked={segmentationEnabled} disabled />
                           启用分段
                         </label>
+                        <label className="flex items-center gap-2">
+                          <input
+                            type="checkbox"
+                            checked={Boolean(workflowConfig.burnDanmaku)}
+                            onChange={(event) =>
+                              setWorkflowConfig((prev) => ({
+                                ...prev,
+                                burnDanmaku: event.target.checked,
+                              }))
+                            }
+                          />
+                          压制弹幕
+                        </label>
                       </div>
                       {segmentationEnabled ? (
                         <div className="mt-2 grid gap-2">
@@ -2019,6 +2800,17 @@ export default function DownloadSection() {
                             />
                             保留合并视频
                           </label>
+                          <SegmentModeFields
+                            value={workflowConfig.segmentationConfig}
+                            onChange={(patch) =>
+                              setWorkflowConfig((prev) => ({
+                                ...prev,
+                                segmentationConfig: { ...prev.segmentationConfig, ...patch },
+                              }))
+                            }
+                            inputClassName="w-full"
+                            labelClassName="space-y-1 text-xs text-[var(--desc-color)]"
+                          />
                         </div>
                       ) : null}
                       <div className="text-xs text-[var(--desc-color)]">
@@ -2166,7 +2958,7 @@ export default function DownloadSection() {
             </div>
           ) : null}
         </div>
-      ) : (
+      ) : mainTab === "records" ? (
         <div className="flex w-full h-full gap-3 min-h-0">
           <div className="flex-1 min-h-0">
             <div className="panel flex flex-col gap-2 p-3 min-h-0">
@@ -2214,6 +3006,11 @@ export default function DownloadSection() {
                           <span>编码：{record.codec || "-"}</span>
                           <span>格式：{record.format || "-"}</span>
                           <span>来源：{sourceLabel}</span>
+                          {record.sidecarFiles ? (
+                            <span title={(record.sidecarFiles.errors || []).join("\n")}>
+                              附件：{formatSidecarSummary(record.sidecarFiles)}
+                            </span>
+                          ) : null}
                         </div>
                         <div className="flex items-center gap-3">
                           <div className="flex-1">
@@ -2281,7 +3078,7 @@ export default function DownloadSection() {
             ))}
           </div>
         </div>
-      )}
+      ) : null}
       <BaiduSyncPathPicker
         open={syncPickerOpen}
         value={submissionConfig.baiduSyncPath || defaultBaiduSyncPath || "/录播"}
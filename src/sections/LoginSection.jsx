@@ -53,6 +53,8 @@ export default function LoginSection({
   const [baiduLoading, setBaiduLoading] = useState(false);
   const [biliMessage, setBiliMessage] = useState("");
   const [biliLoading, setBiliLoading] = useState(false);
+  const [biliAccounts, setBiliAccounts] = useState([]);
+  const [biliAccountBusy, setBiliAccountBusy] = useState(false);
 
   const getErrorMessage = (error, fallback) => {
     if (!error) {
@@ -80,6 +82,12 @@ export default function LoginSection({
     }
   }, [activeTab, onRefreshBaidu]);
 
+  useEffect(() => {
+    if (activeTab === "bilibili") {
+      loadBiliAccounts();
+    }
+  }, [activeTab, authStatus?.loggedIn, biliProfile.uid]);
+
   useEffect(() => {
     if (activeTab === "baidu" && baiduLoginTab === "account") {
       loadBaiduAccountStatus();
@@ -300,6 +308,31 @@ export default function LoginSection({
     }
   };
 
+  const loadBiliAccounts = async () => {
+    try {
+      const data = await invokeCommand("auth_account_list");
+      setBiliAccounts(Array.isArray(data) ? data : []);
+    } catch (error) {
+      setBiliMessage(getErrorMessage(error, "读取账号列表失败"));
+    }
+  };
+
+  const handleBiliAccountAction = async (command, userId, successText) => {
+    setBiliMessage("");
+    setBiliAccountBusy(true);
+    try {
+      const data = await invokeCommand(command, { userId });
+      setBiliAccounts(Array.isArray(data) ? data : []);
+      const status = await invokeCommand("auth_status");
+      onAuthChange?.(status || { loggedIn: false });
+      setBiliMessage(successText);
+    } catch (error) {
+      setBiliMessage(getErrorMessage(error, "操作失败"));
+    } finally {
+      setBiliAccountBusy(false);
+    }
+  };
+
   const handleBiliRefresh = async () => {
     setBiliMessage("");
     setBiliLoading(true);
@@ -370,6 +403,62 @@ export default function LoginSection({
               <div className="mt-2 text-xs text-[var(--desc-color)]">{biliMessage}</div>
             ) : null}
           </div>
+          {biliAccounts.length > 0 ? (
+            <div className="panel p-4">
+              <div className="text-sm font-semibold text-[var(--content-color)]">已登录账号</div>
+              <div className="mt-3 space-y-2 text-sm text-[var(--content-color)]">
+                {biliAccounts.map((account) => (
+                  <div
+                    key={account.userId}
+                    className="flex flex-wrap items-center justify-between gap-2 rounded-lg bg-white/70 px-3 py-2"
+                  >
+                    <div>
+                      <div className="font-semibold">
+                        {account.nickname || account.username || account.userId}
+                        {account.isDefault ? (
+                          <span className="ml-2 rounded-full bg-emerald-500/10 px-2 py-0.5 text-xs text-emerald-600">
+                            默认
+                          </span>
+                        ) : null}
+                      </div>
+                      <div className="text-xs text-[var(--desc-color)]">
+                        UID {account.userId}
+                        {account.expireTime
+                          ? ` · 过期 ${formatDateTimeBeijing(account.expireTime)}`
+                          : ""}
+                      </div>
+                    </div>
+                    <div className="flex gap-2">
+                      {!account.isDefault ? (
+                        <button
+                          className="rounded-full border border-black/10 bg-white px-3 py-1 text-xs font-semibold text-[var(--ink)]"
+                          onClick={() =>
+                            handleBiliAccountAction(
+                              "auth_account_set_default",
+                              account.userId,
+                              "已切换默认账号",
+                            )
+                          }
+                          disabled={biliAccountBusy}
+                        >
+                          设为默认
+                        </button>
+                      ) : null}
+                      <button
+                        className="rounded-full border border-black/10 bg-white px-3 py-1 text-xs font-semibold text-[var(--ink)]"
+                        onClick={() =>
+                          handleBiliAccountAction("auth_account_remove", account.userId, "账号已移除")
+                        }
+                        disabled={biliAccountBusy}
+                      >
+                        移除
+                      </button>
+                    </div>
+                  </div>
+                ))}
+              </div>
+            </div>
+          ) : null}
           <BilibiliLoginSection
             onStatusChange={onAuthChange}
             embedded
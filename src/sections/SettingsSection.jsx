@@ -3,6 +3,52 @@ import { open } from "@tauri-apps/plugin-dialog";
 import { invokeCommand } from "../lib/tauri";
 import BaiduSyncPathPicker from "../components/BaiduSyncPathPicker";
 
+const videoEncoderOptions = [
+  { value: "libx264", label: "H.264 软件编码 (libx264)" },
+  { value: "libx265", label: "H.265 软件编码 (libx265)" },
+  { value: "h264_nvenc", label: "H.264 NVIDIA (h264_nvenc)" },
+  { value: "hevc_nvenc", label: "H.265 NVIDIA (hevc_nvenc)" },
+  { value: "h264_qsv", label: "H.264 Intel QSV (h264_qsv)" },
+  { value: "hevc_qsv", label: "H.265 Intel QSV (hevc_qsv)" },
+  { value: "h264_vaapi", label: "H.264 VAAPI (h264_vaapi)" },
+  { value: "hevc_vaapi", label: "H.265 VAAPI (hevc_vaapi)" },
+  { value: "h264_videotoolbox", label: "H.264 VideoToolbox (h264_videotoolbox)" },
+  { value: "hevc_videotoolbox", label: "H.265 VideoToolbox (hevc_videotoolbox)" },
+];
+
+const webhookProviders = [
+  { value: "GENERIC", label: "通用 Webhook" },
+  { value: "DISCORD", label: "Discord" },
+  { value: "FEISHU", label: "飞书" },
+  { value: "DINGTALK", label: "钉钉" },
+  { value: "TELEGRAM", label: "Telegram" },
+];
+
+const webhookEventLabels = {
+  auto_record_start: "自动开始录制",
+  record_stop: "录制结束",
+  live_remux_done: "录制转封装完成",
+  download_completed: "下载完成",
+  download_failed: "下载失败",
+  submission_status_changed: "投稿状态变化",
+  submission_rejected: "投稿被退回",
+  baidu_sync_failed: "网盘同步失败",
+  vod_new_upload: "订阅发现新视频",
+};
+
+const defaultWebhookForm = {
+  id: null,
+  name: "",
+  provider: "GENERIC",
+  url: "",
+  secret: "",
+  chatId: "",
+  events: [],
+  enabled: true,
+  maxRetries: 3,
+  retryBackoffSeconds: 30,
+};
+
 export default function SettingsSection() {
   const [threads, setThreads] = useState(3);
   const [queueSize, setQueueSize] = useState(10);
@@ -11,14 +57,64 @@ export default function SettingsSection() {
   const [uploadConcurrency, setUploadConcurrency] = useState(3);
   const [submissionRemoteRefreshMinutes, setSubmissionRemoteRefreshMinutes] = useState(10);
   const [blockPcdn, setBlockPcdn] = useState(true);
-  const [aria2cConnections, setAria2cConnections] = useState(4);
-  const [aria2cSplit, setAria2cSplit] = useState(4);
+  const [downloadConnections, setDownloadConnections] = useState(4);
+  const [downloadSplit, setDownloadSplit] = useState(4);
+  const [downloadSpeedLimit, setDownloadSpeedLimit] = useState(0);
   const [baiduMaxParallel, setBaiduMaxParallel] = useState(3);
   const [message, setMessage] = useState("");
   const [syncConcurrency, setSyncConcurrency] = useState(3);
   const [syncTargetPath, setSyncTargetPath] = useState("/录播");
   const [syncConfigMessage, setSyncConfigMessage] = useState("");
   const [syncPickerOpen, setSyncPickerOpen] = useState(false);
+  const [syncBackend, setSyncBackend] = useState({
+    backend: "baidu",
+    localRoot: "",
+    webdavUrl: "",
+    webdavUsername: "",
+    webdavPassword: "",
+    s3Endpoint: "",
+    s3Region: "us-east-1",
+    s3Bucket: "",
+    s3AccessKey: "",
+    s3SecretKey: "",
+    s3PathStyle: true,
+    alistUrl: "",
+    alistToken: "",
+  });
+  const [storageSettings, setStorageSettings] = useState({
+    minFreeSpaceMb: 5120,
+    retentionEnabled: false,
+    retentionBaiduSyncDays: 0,
+    retentionSubmissionDays: 0,
+  });
+  const [storageMessage, setStorageMessage] = useState("");
+  const [bandwidthSettings, setBandwidthSettings] = useState({
+    totalLimitKbps: 0,
+    downloadLimitKbps: 0,
+    uploadLimitKbps: 0,
+    syncLimitKbps: 0,
+    downloadPriority: 5,
+    uploadPriority: 3,
+    syncPriority: 2,
+    timeRules: [],
+  });
+  const [bandwidthMessage, setBandwidthMessage] = useState("");
+  const [encoderSettings, setEncoderSettings] = useState({
+    videoEncoder: "h264_videotoolbox",
+    rateControl: "BITRATE",
+    crf: 23,
+    videoBitrateKbps: 5000,
+    preset: "",
+    fpsMode: "FIXED",
+    fps: 60,
+    audioBitrateKbps: 128,
+  });
+  const [encoderCapabilities, setEncoderCapabilities] = useState(null);
+  const [encoderMessage, setEncoderMessage] = useState("");
+  const [webhooks, setWebhooks] = useState([]);
+  const [webhookEvents, setWebhookEvents] = useState([]);
+  const [webhookForm, setWebhookForm] = useState(defaultWebhookForm);
+  const [webhookMessage, setWebhookMessage] = useState("");
   const [liveMessage, setLiveMessage] = useState("");
   const [liveSettings, setLiveSettings] = useState({
     fileNameTemplate: "live/{{ roomId }}/{{ liveDate }}/录制-{{ roomId }}-{{ now }}-{{ title }}.flv",
@@ -41,6 +137,11 @@ export default function SettingsSection() {
     streamRetryNoQnSec: 90,
     streamConnectTimeoutMs: 5000,
     streamReadTimeoutMs: 15000,
+    streamProtocolOrder: "http_stream,http_hls",
+    streamFormatOrder: "flv,ts,fmp4",
+    streamCodecOrder: "avc,hevc",
+    streamCdnOrder: "",
+    streamCdnFailover: true,
     checkIntervalSec: 180,
     flvFixSplitOnMissing: false,
     flvFixAdjustTimestampJump: true,
@@ -79,10 +180,11 @@ export default function SettingsSection() {
         );
         setSubmissionRemoteRefreshMinutes(refreshMinutes);
         setBlockPcdn(Boolean(data.blockPcdn));
-        const connections = Math.min(32, Math.max(1, Number(data.aria2cConnections || 4)));
-        const split = Math.min(32, Math.max(1, Number(data.aria2cSplit || 4)));
-        setAria2cConnections(connections);
-        setAria2cSplit(split);
+        const connections = Math.min(32, Math.max(1, Number(data.downloadConnections || 4)));
+        const split = Math.min(32, Math.max(1, Number(data.downloadSplit || 4)));
+        setDownloadConnections(connections);
+        setDownloadSplit(split);
+        setDownloadSpeedLimit(Math.max(0, Number(data.downloadSpeedLimitKbps || 0)));
         const maxParallel = Math.min(100, Math.max(1, Number(data.baiduMaxParallel || 3)));
         setBaiduMaxParallel(maxParallel);
         await logClient(`settings_load:ok:${data.downloadPath || ""}`);
@@ -100,6 +202,8 @@ export default function SettingsSection() {
       const concurrency = Math.max(1, Number(data?.concurrency || 3));
       setSyncConcurrency(concurrency);
       setSyncTargetPath(data?.targetPath || "/录播");
+      const backend = await invokeCommand("sync_backend_settings");
+      setSyncBackend((prev) => ({ ...prev, ...(backend || {}) }));
     } catch (error) {
       setSyncConfigMessage(error?.message || "加载同步配置失败");
     }
@@ -114,6 +218,7 @@ export default function SettingsSection() {
           targetPath: syncTargetPath,
         },
       });
+      await invokeCommand("sync_backend_update_settings", { payload: syncBackend });
       setSyncConfigMessage("同步配置已保存");
       await loadBaiduSyncSettings();
     } catch (error) {
@@ -121,6 +226,282 @@ export default function SettingsSection() {
     }
   };
 
+  const loadEncoderCapabilities = async () => {
+    try {
+      const data = await invokeCommand("get_encoder_capabilities");
+      setEncoderCapabilities(data || null);
+    } catch (error) {
+      setEncoderMessage(error?.message || "检测编码器失败");
+    }
+  };
+
+  const loadEncoderSettings = async () => {
+    setEncoderMessage("");
+    try {
+      const data = await invokeCommand("get_encoder_settings");
+      setEncoderSettings((prev) => ({ ...prev, ...(data || {}) }));
+    } catch (error) {
+      setEncoderMessage(error?.message || "加载编码设置失败");
+    }
+    await loadEncoderCapabilities();
+  };
+
+  const handleSaveEncoderSettings = async () => {
+    setEncoderMessage("");
+    try {
+      const data = await invokeCommand("update_encoder_settings", {
+        payload: {
+          videoEncoder: encoderSettings.videoEncoder,
+          rateControl: encoderSettings.rateControl,
+          crf: Number(encoderSettings.crf || 0),
+          videoBitrateKbps: Number(encoderSettings.videoBitrateKbps || 0),
+          preset: encoderSettings.preset.trim(),
+          fpsMode: encoderSettings.fpsMode,
+          fps: Number(encoderSettings.fps || 0),
+          audioBitrateKbps: Number(encoderSettings.audioBitrateKbps || 0),
+        },
+      });
+      setEncoderSettings((prev) => ({ ...prev, ...(data || {}) }));
+      await loadEncoderCapabilities();
+      setEncoderMessage("编码设置已保存");
+    } catch (error) {
+      setEncoderMessage(error?.message || "保存编码设置失败");
+    }
+  };
+
+  const loadStorageSettings = async () => {
+    setStorageMessage("");
+    try {
+      const data = await invokeCommand("get_storage_settings");
+      setStorageSettings((prev) => ({ ...prev, ...(data || {}) }));
+    } catch (error) {
+      setStorageMessage(error?.message || "加载存储设置失败");
+    }
+  };
+
+  const handleSaveStorageSettings = async () => {
+    setStorageMessage("");
+    try {
+      const data = await invokeCommand("update_storage_settings", {
+        payload: {
+          minFreeSpaceMb: Number(storageSettings.minFreeSpaceMb || 0),
+          retentionEnabled: Boolean(storageSettings.retentionEnabled),
+          retentionBaiduSyncDays: Number(storageSettings.retentionBaiduSyncDays || 0),
+          retentionSubmissionDays: Number(storageSettings.retentionSubmissionDays || 0),
+        },
+      });
+      setStorageSettings((prev) => ({ ...prev, ...(data || {}) }));
+      setStorageMessage("存储设置已保存");
+    } catch (error) {
+      setStorageMessage(error?.message || "保存存储设置失败");
+    }
+  };
+
+  const loadBandwidthSettings = async () => {
+    setBandwidthMessage("");
+    try {
+      const data = await invokeCommand("get_bandwidth_settings");
+      setBandwidthSettings((prev) => ({ ...prev, ...(data || {}) }));
+    } catch (error) {
+      setBandwidthMessage(error?.message || "加载带宽设置失败");
+    }
+  };
+
+  const handleSaveBandwidthSettings = async () => {
+    setBandwidthMessage("");
+    try {
+      const data = await invokeCommand("update_bandwidth_settings", {
+        payload: {
+          totalLimitKbps: Number(bandwidthSettings.totalLimitKbps || 0),
+          downloadLimitKbps: Number(bandwidthSettings.downloadLimitKbps || 0),
+          uploadLimitKbps: Number(bandwidthSettings.uploadLimitKbps || 0),
+          syncLimitKbps: Number(bandwidthSettings.syncLimitKbps || 0),
+          downloadPriority: Number(bandwidthSettings.downloadPriority || 1),
+          uploadPriority: Number(bandwidthSettings.uploadPriority || 1),
+          syncPriority: Number(bandwidthSettings.syncPriority || 1),
+          timeRules: (bandwidthSettings.timeRules || []).map((rule) => ({
+            timeWindow: String(rule.timeWindow || "").trim(),
+            totalLimitKbps: Number(rule.totalLimitKbps || 0),
+            downloadLimitKbps: Number(rule.downloadLimitKbps || 0),
+            uploadLimitKbps: Number(rule.uploadLimitKbps || 0),
+            syncLimitKbps: Number(rule.syncLimitKbps || 0),
+          })),
+        },
+      });
+      setBandwidthSettings((prev) => ({ ...prev, ...(data || {}) }));
+      setDownloadSpeedLimit(Math.max(0, Number(data?.downloadLimitKbps || 0)));
+      setBandwidthMessage("带宽设置已保存");
+    } catch (error) {
+      setBandwidthMessage(error?.message || "保存带宽设置失败");
+    }
+  };
+
+  const loadWebhooks = async () => {
+    setWebhookMessage("");
+    try {
+      const [list, events] = await Promise.all([
+        invokeCommand("webhook_list"),
+        invokeCommand("webhook_events"),
+      ]);
+      setWebhooks(Array.isArray(list) ? list : []);
+      setWebhookEvents(Array.isArray(events) ? events : []);
+    } catch (error) {
+      setWebhookMessage(error?.message || "加载推送配置失败");
+    }
+  };
+
+  const toggleWebhookEvent = (event) => {
+    setWebhookForm((prev) => ({
+      ...prev,
+      events: prev.events.includes(event)
+        ? prev.events.filter((item) => item !== event)
+        : [...prev.events, event],
+    }));
+  };
+
+  const handleSaveWebhook = async () => {
+    setWebhookMessage("");
+    try {
+      await invokeCommand("webhook_save", {
+        payload: {
+          id: webhookForm.id,
+          name: webhookForm.name.trim(),
+          provider: webhookForm.provider,
+          url: webhookForm.url.trim(),
+          secret: webhookForm.secret.trim() || null,
+          chatId: webhookForm.chatId.trim() || null,
+          events: webhookForm.events,
+          enabled: Boolean(webhookForm.enabled),
+          maxRetries: Number(webhookForm.maxRetries || 0),
+          retryBackoffSeconds: Number(webhookForm.retryBackoffSeconds || 0),
+        },
+      });
+      setWebhookForm(defaultWebhookForm);
+      await loadWebhooks();
+      setWebhookMessage("推送配置已保存");
+    } catch (error) {
+      setWebhookMessage(error?.message || "保存推送配置失败");
+    }
+  };
+
+  const handleEditWebhook = (webhook) => {
+    setWebhookMessage("");
+    setWebhookForm({
+      id: webhook.id,
+      name: webhook.name || "",
+      provider: webhook.provider || "GENERIC",
+      url: webhook.url || "",
+      secret: webhook.secret || "",
+      chatId: webhook.chatId || "",
+      events: Array.isArray(webhook.events) ? webhook.events : [],
+      enabled: Boolean(webhook.enabled),
+      maxRetries: webhook.maxRetries ?? 3,
+      retryBackoffSeconds: webhook.retryBackoffSeconds ?? 30,
+    });
+  };
+
+  const handleDeleteWebhook = async (id) => {
+    setWebhookMessage("");
+    try {
+      await invokeCommand("webhook_delete", { id });
+      if (webhookForm.id === id) {
+        setWebhookForm(defaultWebhookForm);
+      }
+      await loadWebhooks();
+    } catch (error) {
+      setWebhookMessage(error?.message || "删除推送配置失败");
+    }
+  };
+
+  const handleTestWebhook = async (id) => {
+    setWebhookMessage("");
+    try {
+      await invokeCommand("webhook_test", { id });
+      setWebhookMessage("测试消息已发送");
+    } catch (error) {
+      setWebhookMessage(error?.message || "测试推送失败");
+    }
+  };
+
+  const updateBandwidthRule = (index, field, value) => {
+    setBandwidthSettings((prev) => ({
+      ...prev,
+      timeRules: (prev.timeRules || []).map((rule, ruleIndex) =>
+        ruleIndex === index ? { ...rule, [field]: value } : rule,
+      ),
+    }));
+  };
+
+  const handleAddBandwidthRule = () => {
+    setBandwidthSettings((prev) => ({
+      ...prev,
+      timeRules: [
+        ...(prev.timeRules || []),
+        {
+          timeWindow: "",
+          totalLimitKbps: 0,
+          downloadLimitKbps: 0,
+          uploadLimitKbps: 0,
+          syncLimitKbps: 0,
+        },
+      ],
+    }));
+  };
+
+  const handleRemoveBandwidthRule = (index) => {
+    setBandwidthSettings((prev) => ({
+      ...prev,
+      timeRules: (prev.timeRules || []).filter((_, ruleIndex) => ruleIndex !== index),
+    }));
+  };
+
+  const handleRunRetention = async () => {
+    setStorageMessage("");
+    try {
+      const data = await invokeCommand("storage_retention_run");
+      const files = data?.deletedFiles?.length || 0;
+      const tasks = data?.cleanedTaskIds?.length || 0;
+      const freedMb = Math.round((data?.freedBytes || 0) / 1024 / 1024);
+      setStorageMessage(`清理完成：删除 ${files} 个文件，清理 ${tasks} 个任务目录，释放 ${freedMb} MB`);
+    } catch (error) {
+      setStorageMessage(error?.message || "清理失败");
+    }
+  };
+
+  const handleTestSyncBackend = async () => {
+    setSyncConfigMessage("");
+    try {
+      const count = await invokeCommand("sync_backend_test", { payload: syncBackend });
+      setSyncConfigMessage(`连接成功，根目录下共 ${count || 0} 个文件夹`);
+    } catch (error) {
+      setSyncConfigMessage(error?.message || "连接测试失败");
+    }
+  };
+
+  const updateSyncBackend = (key, value) => {
+    setSyncBackend((prev) => ({ ...prev, [key]: value }));
+  };
+
+  const syncBackendFields = {
+    local: [{ key: "localRoot", label: "本地/NAS 目录", placeholder: "/mnt/nas/录播" }],
+    webdav: [
+      { key: "webdavUrl", label: "WebDAV 地址", placeholder: "https://nas.example.com/dav" },
+      { key: "webdavUsername", label: "用户名" },
+      { key: "webdavPassword", label: "密码", type: "password" },
+    ],
+    s3: [
+      { key: "s3Endpoint", label: "Endpoint", placeholder: "http://127.0.0.1:9000" },
+      { key: "s3Region", label: "Region", placeholder: "us-east-1" },
+      { key: "s3Bucket", label: "Bucket" },
+      { key: "s3AccessKey", label: "Access Key" },
+      { key: "s3SecretKey", label: "Secret Key", type: "password" },
+    ],
+    alist: [
+      { key: "alistUrl", label: "Alist 地址", placeholder: "http://127.0.0.1:5244" },
+      { key: "alistToken", label: "令牌", type: "password" },
+    ],
+  };
+
   const handleOpenSyncPicker = () => {
     setSyncPickerOpen(true);
   };
@@ -142,6 +523,10 @@ export default function SettingsSection() {
     loadSettings();
     loadLiveSettings();
     loadBaiduSyncSettings();
+    loadEncoderSettings();
+    loadStorageSettings();
+    loadBandwidthSettings();
+    loadWebhooks();
   }, []);
 
   const loadLiveSettings = async () => {
@@ -170,6 +555,11 @@ export default function SettingsSection() {
           streamRetryNoQnSec: Number(data.streamRetryNoQnSec || 0),
           streamConnectTimeoutMs: Number(data.streamConnectTimeoutMs || 0),
           streamReadTimeoutMs: Number(data.streamReadTimeoutMs || 0),
+          streamProtocolOrder: data.streamProtocolOrder || "",
+          streamFormatOrder: data.streamFormatOrder || "",
+          streamCodecOrder: data.streamCodecOrder || "",
+          streamCdnOrder: data.streamCdnOrder || "",
+          streamCdnFailover: Boolean(data.streamCdnFailover),
           checkIntervalSec: Number(data.checkIntervalSec || 0),
           flvFixSplitOnMissing: Boolean(data.flvFixSplitOnMissing),
           flvFixAdjustTimestampJump: Boolean(data.flvFixAdjustTimestampJump),
@@ -228,17 +618,18 @@ export default function SettingsSection() {
         1,
         Number(submissionRemoteRefreshMinutes) || 1,
       );
-      const normalizedAria2cConnections = Math.min(
+      const normalizedDownloadConnections = Math.min(
         32,
-        Math.max(1, Number(aria2cConnections) || 1),
+        Math.max(1, Number(downloadConnections) || 1),
       );
-      const normalizedAria2cSplit = Math.min(32, Math.max(1, Number(aria2cSplit) || 1));
+      const normalizedDownloadSplit = Math.min(32, Math.max(1, Number(downloadSplit) || 1));
+      const normalizedSpeedLimit = Math.max(0, Math.floor(Number(downloadSpeedLimit) || 0));
       const normalizedBaiduMaxParallel = Math.min(
         100,
         Math.max(1, Number(baiduMaxParallel) || 3),
       );
       await logClient(
-        `settings_save:start path=${downloadPath} logDir=${logDir} threads=${String(threads)} queue=${String(queueSize)} uploadConcurrency=${String(normalizedUploadConcurrency)} remoteRefreshMinutes=${String(normalizedRefreshMinutes)} blockPcdn=${String(blockPcdn)} aria2cConnections=${String(normalizedAria2cConnections)} aria2cSplit=${String(normalizedAria2cSplit)} baiduMaxParallel=${String(normalizedBaiduMaxParallel)}`,
+        `settings_save:start path=${downloadPath} logDir=${logDir} threads=${String(threads)} queue=${String(queueSize)} uploadConcurrency=${String(normalizedUploadConcurrency)} remoteRefreshMinutes=${String(normalizedRefreshMinutes)} blockPcdn=${String(blockPcdn)} downloadConnections=${String(normalizedDownloadConnections)} downloadSplit=${String(normalizedDownloadSplit)} speedLimitKbps=${String(normalizedSpeedLimit)} baiduMaxParallel=${String(normalizedBaiduMaxParallel)}`,
       );
       await logClient("settings_save:invoke_start");
       const data = await invokeCommand("update_download_settings", {
@@ -249,10 +640,10 @@ export default function SettingsSection() {
         uploadConcurrency: normalizedUploadConcurrency,
         submissionRemoteRefreshMinutes: normalizedRefreshMinutes,
         blockPcdn: Boolean(blockPcdn),
-        aria2cConnections: normalizedAria2cConnections,
-        aria2cSplit: normalizedAria2cSplit,
+        downloadConnections: normalizedDownloadConnections,
+        downloadSplit: normalizedDownloadSplit,
         baiduMaxParallel: normalizedBaiduMaxParallel,
-        enableAria2c: true,
+        downloadSpeedLimitKbps: normalizedSpeedLimit,
       });
       await logClient("settings_save:invoke_end");
       if (data) {
@@ -265,10 +656,15 @@ export default function SettingsSection() {
           Math.max(1, Number(data.submissionRemoteRefreshMinutes || 10)),
         );
         setBlockPcdn(Boolean(data.blockPcdn));
-        setAria2cConnections(
-          Math.min(32, Math.max(1, Number(data.aria2cConnections || 4))),
+        setDownloadConnections(
+          Math.min(32, Math.max(1, Number(data.downloadConnections || 4))),
         );
-        setAria2cSplit(Math.min(32, Math.max(1, Number(data.aria2cSplit || 4))));
+        setDownloadSplit(Math.min(32, Math.max(1, Number(data.downloadSplit || 4))));
+        setDownloadSpeedLimit(Math.max(0, Number(data.downloadSpeedLimitKbps || 0)));
+        setBandwidthSettings((prev) => ({
+          ...prev,
+          downloadLimitKbps: Math.max(0, Number(data.downloadSpeedLimitKbps || 0)),
+        }));
         setBaiduMaxParallel(
           Math.min(100, Math.max(1, Number(data.baiduMaxParallel || 3))),
         );
@@ -306,6 +702,11 @@ export default function SettingsSection() {
           streamRetryNoQnSec: Number(liveSettings.streamRetryNoQnSec || 0),
           streamConnectTimeoutMs: Number(liveSettings.streamConnectTimeoutMs || 0),
           streamReadTimeoutMs: Number(liveSettings.streamReadTimeoutMs || 0),
+          streamProtocolOrder: liveSettings.streamProtocolOrder,
+          streamFormatOrder: liveSettings.streamFormatOrder,
+          streamCodecOrder: liveSettings.streamCodecOrder,
+          streamCdnOrder: liveSettings.streamCdnOrder,
+          streamCdnFailover: liveSettings.streamCdnFailover,
           checkIntervalSec: Number(liveSettings.checkIntervalSec || 0),
           flvFixSplitOnMissing: liveSettings.flvFixSplitOnMissing,
           flvFixAdjustTimestampJump: liveSettings.flvFixAdjustTimestampJump,
@@ -361,12 +762,12 @@ export default function SettingsSection() {
           </div>
           <div>
             <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
-              aria2c 连接数
+              下载连接数
             </div>
             <input
               type="number"
-              value={aria2cConnections}
-              onChange={(event) => setAria2cConnections(event.target.value)}
+              value={downloadConnections}
+              onChange={(event) => setDownloadConnections(event.target.value)}
               min={1}
               max={32}
               className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
@@ -374,17 +775,35 @@ export default function SettingsSection() {
           </div>
           <div>
             <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
-              aria2c 分片数
+              下载分片数
             </div>
             <input
               type="number"
-              value={aria2cSplit}
-              onChange={(event) => setAria2cSplit(event.target.value)}
+              value={downloadSplit}
+              onChange={(event) => setDownloadSplit(event.target.value)}
               min={1}
               max={32}
               className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
             />
           </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              下载限速 KB/s
+              <span className="group relative ml-2 inline-flex h-4 w-4 items-center justify-center rounded-full border border-black/20 text-[10px] text-[var(--muted)]">
+                ?
+                <span className="pointer-events-none absolute left-1/2 top-full z-10 mt-2 w-64 -translate-x-1/2 rounded-md bg-black/80 px-2 py-1 text-[10px] text-white opacity-0 shadow transition group-hover:opacity-100">
+                  所有视频下载任务共享的总带宽上限，0 表示不限速。
+                </span>
+              </span>
+            </div>
+            <input
+              type="number"
+              value={downloadSpeedLimit}
+              onChange={(event) => setDownloadSpeedLimit(event.target.value)}
+              min={0}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
           <div>
             <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
               网盘 max_parallel
@@ -519,6 +938,46 @@ export default function SettingsSection() {
           <h2 className="text-2xl font-semibold text-[var(--ink)]">同步配置</h2>
         </div>
         <div className="mt-4 grid gap-3 lg:grid-cols-2">
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              存储后端
+            </div>
+            <select
+              value={syncBackend.backend}
+              onChange={(event) => updateSyncBackend("backend", event.target.value)}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            >
+              <option value="baidu">百度网盘（BaiduPCS-Go）</option>
+              <option value="local">本地/NAS 目录</option>
+              <option value="webdav">WebDAV</option>
+              <option value="s3">S3 兼容存储</option>
+              <option value="alist">Alist</option>
+            </select>
+          </div>
+          {(syncBackendFields[syncBackend.backend] || []).map((field) => (
+            <div key={field.key}>
+              <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+                {field.label}
+              </div>
+              <input
+                type={field.type || "text"}
+                value={syncBackend[field.key] || ""}
+                onChange={(event) => updateSyncBackend(field.key, event.target.value)}
+                placeholder={field.placeholder || ""}
+                className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+              />
+            </div>
+          ))}
+          {syncBackend.backend === "s3" ? (
+            <label className="flex items-center gap-2 text-sm text-[var(--muted)]">
+              <input
+                type="checkbox"
+                checked={syncBackend.s3PathStyle}
+                onChange={(event) => updateSyncBackend("s3PathStyle", event.target.checked)}
+              />
+              使用 Path-Style 访问（MinIO 等自建服务）
+            </label>
+          ) : null}
           <div>
             <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
               最大同时同步数
@@ -533,7 +992,7 @@ export default function SettingsSection() {
           </div>
           <div>
             <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
-              默认网盘上传目录
+              默认上传目录
             </div>
             <div className="mt-2 flex flex-wrap items-center gap-2 text-xs">
               <div className="flex-1 rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-[var(--content-color)]">
@@ -555,6 +1014,12 @@ export default function SettingsSection() {
           >
             保存
           </button>
+          <button
+            className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20"
+            onClick={handleTestSyncBackend}
+          >
+            测试连接
+          </button>
           <button
             className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20"
             onClick={loadBaiduSyncSettings}
@@ -569,6 +1034,536 @@ export default function SettingsSection() {
         ) : null}
       </div>
 
+      <div className="rounded-2xl bg-[var(--surface)]/90 p-6 shadow-sm ring-1 ring-black/5">
+        <div>
+          <p className="text-sm uppercase tracking-[0.2em] text-[var(--muted)]">转码</p>
+          <h2 className="text-2xl font-semibold text-[var(--ink)]">编码设置</h2>
+        </div>
+        <div className="mt-4 grid gap-3 lg:grid-cols-2">
+          <div className="lg:col-span-2">
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">视频编码器</div>
+            <select
+              value={encoderSettings.videoEncoder}
+              onChange={(event) =>
+                setEncoderSettings((prev) => ({ ...prev, videoEncoder: event.target.value }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            >
+              {videoEncoderOptions.map((option) => {
+                const unavailable =
+                  encoderCapabilities &&
+                  !encoderCapabilities.availableEncoders?.includes(option.value);
+                return (
+                  <option key={option.value} value={option.value}>
+                    {unavailable ? `${option.label}（不可用）` : option.label}
+                  </option>
+                );
+              })}
+            </select>
+            {encoderCapabilities ? (
+              <div className="mt-1 text-xs text-[var(--muted)]">
+                实际使用：{encoderCapabilities.resolvedEncoder}
+                {encoderCapabilities.fallback ? "（所选编码器不可用，已回退）" : ""}
+              </div>
+            ) : null}
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">码率控制</div>
+            <select
+              value={encoderSettings.rateControl}
+              onChange={(event) =>
+                setEncoderSettings((prev) => ({ ...prev, rateControl: event.target.value }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            >
+              <option value="BITRATE">固定码率</option>
+              <option value="CRF">恒定质量 (CRF)</option>
+            </select>
+          </div>
+          {encoderSettings.rateControl === "CRF" ? (
+            <div>
+              <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">CRF（0-51）</div>
+              <input
+                type="number"
+                min={0}
+                max={51}
+                value={encoderSettings.crf}
+                onChange={(event) =>
+                  setEncoderSettings((prev) => ({ ...prev, crf: event.target.value }))
+                }
+                className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+              />
+            </div>
+          ) : (
+            <div>
+              <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">视频码率（kbps）</div>
+              <input
+                type="number"
+                min={100}
+                max={200000}
+                value={encoderSettings.videoBitrateKbps}
+                onChange={(event) =>
+                  setEncoderSettings((prev) => ({ ...prev, videoBitrateKbps: event.target.value }))
+                }
+                className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+              />
+            </div>
+          )}
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">编码预设（可选）</div>
+            <input
+              value={encoderSettings.preset}
+              onChange={(event) =>
+                setEncoderSettings((prev) => ({ ...prev, preset: event.target.value }))
+              }
+              placeholder="如 veryfast、p4"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">音频码率（kbps）</div>
+            <input
+              type="number"
+              min={32}
+              max={512}
+              value={encoderSettings.audioBitrateKbps}
+              onChange={(event) =>
+                setEncoderSettings((prev) => ({ ...prev, audioBitrateKbps: event.target.value }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">帧率模式</div>
+            <select
+              value={encoderSettings.fpsMode}
+              onChange={(event) =>
+                setEncoderSettings((prev) => ({ ...prev, fpsMode: event.target.value }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            >
+              <option value="FIXED">固定帧率</option>
+              <option value="MAX">限制最高帧率</option>
+              <option value="SOURCE">保持源帧率</option>
+            </select>
+          </div>
+          {encoderSettings.fpsMode !== "SOURCE" ? (
+            <div>
+              <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">帧率</div>
+              <input
+                type="number"
+                min={1}
+                max={240}
+                value={encoderSettings.fps}
+                onChange={(event) =>
+                  setEncoderSettings((prev) => ({ ...prev, fps: event.target.value }))
+                }
+                className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+              />
+            </div>
+          ) : null}
+        </div>
+        <div className="mt-4 flex flex-wrap gap-2">
+          <button className="rounded-full bg-[var(--accent)] px-4 py-2 text-sm font-semibold text-white shadow-sm transition hover:brightness-110" onClick={handleSaveEncoderSettings}>
+            保存编码设置
+          </button>
+          <button className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20" onClick={loadEncoderCapabilities}>
+            重新检测编码器
+          </button>
+        </div>
+        {encoderMessage ? (
+          <div className="mt-3 rounded-lg border border-amber-200 bg-amber-50 px-3 py-2 text-sm text-amber-700">
+            {encoderMessage}
+          </div>
+        ) : null}
+      </div>
+
+      <div className="rounded-2xl bg-[var(--surface)]/90 p-6 shadow-sm ring-1 ring-black/5">
+        <div>
+          <p className="text-sm uppercase tracking-[0.2em] text-[var(--muted)]">存储</p>
+          <h2 className="text-2xl font-semibold text-[var(--ink)]">空间与清理</h2>
+        </div>
+        <div className="mt-4 grid gap-3 lg:grid-cols-2">
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              最低剩余空间（MB，0 为不检查）
+            </div>
+            <input
+              type="number"
+              value={storageSettings.minFreeSpaceMb}
+              onChange={(event) =>
+                setStorageSettings((prev) => ({
+                  ...prev,
+                  minFreeSpaceMb: event.target.value,
+                }))
+              }
+              min={0}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <label className="flex items-center gap-2 text-sm text-[var(--muted)]">
+            <input
+              type="checkbox"
+              checked={storageSettings.retentionEnabled}
+              onChange={(event) =>
+                setStorageSettings((prev) => ({
+                  ...prev,
+                  retentionEnabled: event.target.checked,
+                }))
+              }
+            />
+            启用自动清理
+          </label>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              网盘同步完成后保留天数（0 为不清理）
+            </div>
+            <input
+              type="number"
+              value={storageSettings.retentionBaiduSyncDays}
+              onChange={(event) =>
+                setStorageSettings((prev) => ({
+                  ...prev,
+                  retentionBaiduSyncDays: event.target.value,
+                }))
+              }
+              min={0}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              投稿完成后保留天数（0 为不清理）
+            </div>
+            <input
+              type="number"
+              value={storageSettings.retentionSubmissionDays}
+              onChange={(event) =>
+                setStorageSettings((prev) => ({
+                  ...prev,
+                  retentionSubmissionDays: event.target.value,
+                }))
+              }
+              min={0}
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+        </div>
+        <div className="mt-4 flex flex-wrap gap-2">
+          <button
+            className="rounded-full bg-[var(--accent)] px-4 py-2 text-sm font-semibold text-white shadow-sm transition hover:brightness-110"
+            onClick={handleSaveStorageSettings}
+          >
+            保存
+          </button>
+          <button
+            className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20"
+            onClick={handleRunRetention}
+          >
+            立即清理
+          </button>
+          <button
+            className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20"
+            onClick={loadStorageSettings}
+          >
+            刷新
+          </button>
+        </div>
+        {storageMessage ? (
+          <div className="mt-3 rounded-lg border border-amber-200 bg-amber-50 px-3 py-2 text-sm text-amber-700">
+            {storageMessage}
+          </div>
+        ) : null}
+      </div>
+
+      <div className="rounded-2xl bg-[var(--surface)]/90 p-6 shadow-sm ring-1 ring-black/5">
+        <div>
+          <p className="text-sm uppercase tracking-[0.2em] text-[var(--muted)]">带宽</p>
+          <h2 className="text-2xl font-semibold text-[var(--ink)]">全局带宽调度</h2>
+        </div>
+        <div className="mt-2 text-xs text-[var(--muted)]">
+          直播录制始终优先占用带宽，剩余带宽按优先级分配给下载、投稿上传与网盘同步；限速单位为 KB/s，0 为不限。
+        </div>
+        <div className="mt-1 text-xs text-[var(--muted)]">
+          百度网盘（BaiduPCS）的限速在每次传输开始时按当前额度下发并由同时进行的传输均分，传输过程中不随录制占用或时间段规则调整；ffmpeg
+          直接拉流的兜底下载不受带宽调度限制。
+        </div>
+        <div className="mt-4 grid gap-3 lg:grid-cols-2">
+          {[
+            ["totalLimitKbps", "总带宽上限（KB/s）", 0],
+            ["downloadLimitKbps", "下载限速（KB/s）", 0],
+            ["uploadLimitKbps", "投稿上传限速（KB/s）", 0],
+            ["syncLimitKbps", "网盘同步限速（KB/s）", 0],
+            ["downloadPriority", "下载优先级（1-10）", 1],
+            ["uploadPriority", "投稿上传优先级（1-10）", 1],
+            ["syncPriority", "网盘同步优先级（1-10）", 1],
+          ].map(([field, label, min]) => (
+            <div key={field}>
+              <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">{label}</div>
+              <input
+                type="number"
+                value={bandwidthSettings[field]}
+                onChange={(event) =>
+                  setBandwidthSettings((prev) => ({
+                    ...prev,
+                    [field]: event.target.value,
+                  }))
+                }
+                min={min}
+                max={min ? 10 : undefined}
+                className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+              />
+            </div>
+          ))}
+        </div>
+        <div className="mt-4">
+          <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+            时段规则（如 01:00-07:00，命中时段时替换以上限速）
+          </div>
+          {(bandwidthSettings.timeRules || []).map((rule, index) => (
+            <div key={index} className="mt-2 grid gap-2 lg:grid-cols-6">
+              <input
+                value={rule.timeWindow}
+                onChange={(event) => updateBandwidthRule(index, "timeWindow", event.target.value)}
+                placeholder="01:00-07:00"
+                className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+              />
+              {[
+                ["totalLimitKbps", "总上限"],
+                ["downloadLimitKbps", "下载"],
+                ["uploadLimitKbps", "上传"],
+                ["syncLimitKbps", "同步"],
+              ].map(([field, label]) => (
+                <input
+                  key={field}
+                  type="number"
+                  value={rule[field]}
+                  onChange={(event) => updateBandwidthRule(index, field, event.target.value)}
+                  placeholder={label}
+                  title={label}
+                  min={0}
+                  className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+                />
+              ))}
+              <button
+                className="mt-2 rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20"
+                onClick={() => handleRemoveBandwidthRule(index)}
+              >
+                删除
+              </button>
+            </div>
+          ))}
+        </div>
+        <div className="mt-4 flex flex-wrap gap-2">
+          <button
+            className="rounded-full bg-[var(--accent)] px-4 py-2 text-sm font-semibold text-white shadow-sm transition hover:brightness-110"
+            onClick={handleSaveBandwidthSettings}
+          >
+            保存
+          </button>
+          <button
+            className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20"
+            onClick={handleAddBandwidthRule}
+          >
+            添加时段规则
+          </button>
+          <button
+            className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20"
+            onClick={loadBandwidthSettings}
+          >
+            刷新
+          </button>
+        </div>
+        {bandwidthMessage ? (
+          <div className="mt-3 rounded-lg border border-amber-200 bg-amber-50 px-3 py-2 text-sm text-amber-700">
+            {bandwidthMessage}
+          </div>
+        ) : null}
+      </div>
+
+      <div className="rounded-2xl bg-[var(--surface)]/90 p-6 shadow-sm ring-1 ring-black/5">
+        <div>
+          <p className="text-sm uppercase tracking-[0.2em] text-[var(--muted)]">通知</p>
+          <h2 className="text-2xl font-semibold text-[var(--ink)]">消息推送</h2>
+        </div>
+        <div className="mt-4 space-y-2">
+          {webhooks.length === 0 ? (
+            <div className="text-sm text-[var(--muted)]">暂无推送配置</div>
+          ) : null}
+          {webhooks.map((webhook) => (
+            <div
+              key={webhook.id}
+              className="flex flex-wrap items-center justify-between gap-2 rounded-lg bg-white/70 px-3 py-2 text-sm text-[var(--ink)]"
+            >
+              <div>
+                <div className="font-semibold">
+                  {webhook.name}
+                  <span className="ml-2 text-xs text-[var(--muted)]">
+                    {webhookProviders.find((item) => item.value === webhook.provider)?.label ||
+                      webhook.provider}
+                    {webhook.enabled ? "" : " · 已停用"}
+                  </span>
+                </div>
+                <div className="text-xs text-[var(--muted)]">
+                  {webhook.events?.length
+                    ? webhook.events.map((event) => webhookEventLabels[event] || event).join("、")
+                    : "全部事件"}
+                </div>
+              </div>
+              <div className="flex gap-2">
+                <button className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20" onClick={() => handleTestWebhook(webhook.id)}>
+                  测试
+                </button>
+                <button className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20" onClick={() => handleEditWebhook(webhook)}>
+                  编辑
+                </button>
+                <button className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20" onClick={() => handleDeleteWebhook(webhook.id)}>
+                  删除
+                </button>
+              </div>
+            </div>
+          ))}
+        </div>
+        <div className="mt-4 grid gap-3 lg:grid-cols-2">
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">名称</div>
+            <input
+              value={webhookForm.name}
+              onChange={(event) =>
+                setWebhookForm((prev) => ({ ...prev, name: event.target.value }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">类型</div>
+            <select
+              value={webhookForm.provider}
+              onChange={(event) =>
+                setWebhookForm((prev) => ({ ...prev, provider: event.target.value }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            >
+              {webhookProviders.map((provider) => (
+                <option key={provider.value} value={provider.value}>
+                  {provider.label}
+                </option>
+              ))}
+            </select>
+          </div>
+          <div className="lg:col-span-2">
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              {webhookForm.provider === "TELEGRAM" ? "Bot Token" : "推送地址"}
+            </div>
+            <input
+              value={webhookForm.url}
+              onChange={(event) =>
+                setWebhookForm((prev) => ({ ...prev, url: event.target.value }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              签名密钥（可选）
+            </div>
+            <input
+              type="password"
+              value={webhookForm.secret}
+              onChange={(event) =>
+                setWebhookForm((prev) => ({ ...prev, secret: event.target.value }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          {webhookForm.provider === "TELEGRAM" ? (
+            <div>
+              <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">Chat ID</div>
+              <input
+                value={webhookForm.chatId}
+                onChange={(event) =>
+                  setWebhookForm((prev) => ({ ...prev, chatId: event.target.value }))
+                }
+                className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+              />
+            </div>
+          ) : null}
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">失败重试次数</div>
+            <input
+              type="number"
+              min={0}
+              value={webhookForm.maxRetries}
+              onChange={(event) =>
+                setWebhookForm((prev) => ({ ...prev, maxRetries: event.target.value }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              重试间隔（秒）
+            </div>
+            <input
+              type="number"
+              min={0}
+              value={webhookForm.retryBackoffSeconds}
+              onChange={(event) =>
+                setWebhookForm((prev) => ({ ...prev, retryBackoffSeconds: event.target.value }))
+              }
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+        </div>
+        <div className="mt-4">
+          <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+            推送事件（不选则推送全部事件）
+          </div>
+          <div className="mt-2 flex flex-wrap gap-3 text-sm text-[var(--muted)]">
+            {webhookEvents
+              .filter((event) => webhookEventLabels[event])
+              .map((event) => (
+                <label key={event} className="flex items-center gap-2">
+                  <input
+                    type="checkbox"
+                    checked={webhookForm.events.includes(event)}
+                    onChange={() => toggleWebhookEvent(event)}
+                  />
+                  {webhookEventLabels[event]}
+                </label>
+              ))}
+          </div>
+          <label className="mt-3 flex items-center gap-2 text-sm text-[var(--muted)]">
+            <input
+              type="checkbox"
+              checked={webhookForm.enabled}
+              onChange={(event) =>
+                setWebhookForm((prev) => ({ ...prev, enabled: event.target.checked }))
+              }
+            />
+            启用
+          </label>
+        </div>
+        <div className="mt-4 flex flex-wrap gap-2">
+          <button className="rounded-full bg-[var(--accent)] px-4 py-2 text-sm font-semibold text-white shadow-sm transition hover:brightness-110" onClick={handleSaveWebhook}>
+            {webhookForm.id ? "保存修改" : "添加推送"}
+          </button>
+          {webhookForm.id ? (
+            <button className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20" onClick={() => setWebhookForm(defaultWebhookForm)}>
+              取消编辑
+            </button>
+          ) : null}
+          <button className="rounded-full border border-black/10 bg-white px-4 py-2 text-sm font-semibold text-[var(--ink)] transition hover:border-black/20" onClick={loadWebhooks}>
+            刷新
+          </button>
+        </div>
+        {webhookMessage ? (
+          <div className="mt-3 rounded-lg border border-amber-200 bg-amber-50 px-3 py-2 text-sm text-amber-700">
+            {webhookMessage}
+          </div>
+        ) : null}
+      </div>
+
       <div className="rounded-2xl bg-[var(--surface)]/90 p-6 shadow-sm ring-1 ring-black/5">
         <div>
           <p className="text-sm uppercase tracking-[0.2em] text-[var(--muted)]">直播录制</p>
@@ -1014,6 +2009,83 @@ export default function SettingsSection() {
               className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
             />
           </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              直播流协议优先级
+            </div>
+            <input
+              value={liveSettings.streamProtocolOrder}
+              onChange={(event) =>
+                setLiveSettings((prev) => ({
+                  ...prev,
+                  streamProtocolOrder: event.target.value,
+                }))
+              }
+              placeholder="http_stream,http_hls"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              直播流格式优先级
+            </div>
+            <input
+              value={liveSettings.streamFormatOrder}
+              onChange={(event) =>
+                setLiveSettings((prev) => ({
+                  ...prev,
+                  streamFormatOrder: event.target.value,
+                }))
+              }
+              placeholder="flv,ts,fmp4"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              直播流编码优先级
+            </div>
+            <input
+              value={liveSettings.streamCodecOrder}
+              onChange={(event) =>
+                setLiveSettings((prev) => ({
+                  ...prev,
+                  streamCodecOrder: event.target.value,
+                }))
+              }
+              placeholder="avc,hevc"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <div>
+            <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
+              CDN 优先级（域名关键字）
+            </div>
+            <input
+              value={liveSettings.streamCdnOrder}
+              onChange={(event) =>
+                setLiveSettings((prev) => ({
+                  ...prev,
+                  streamCdnOrder: event.target.value,
+                }))
+              }
+              placeholder="例如 gotcha,ov-gotcha"
+              className="mt-2 w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          <label className="flex items-center gap-2 text-sm text-[var(--muted)]">
+            <input
+              type="checkbox"
+              checked={liveSettings.streamCdnFailover}
+              onChange={(event) =>
+                setLiveSettings((prev) => ({
+                  ...prev,
+                  streamCdnFailover: event.target.checked,
+                }))
+              }
+            />
+            连接失败时切换备用 CDN
+          </label>
         </div>
         <div className="mt-4 flex flex-wrap gap-2">
           <button
@@ -10,6 +10,13 @@ import { showErrorDialog } from "../lib/dialog";
 import { invokeCommand } from "../lib/tauri";
 import { formatDateTime } from "../lib/format";
 import BaiduSyncPathPicker from "../components/BaiduSyncPathPicker";
+import BiliAccountSelect from "../components/BiliAccountSelect";
+import SegmentModeFields, {
+  buildSegmentModeConfig,
+  defaultSegmentModeConfig,
+  readSegmentModeConfig,
+  segmentModeLabel,
+} from "../components/SegmentModeFields";
 
 const statusFilters = [
   { value: "ALL", label: "全部" },
@@ -20,11 +27,39 @@ const statusFilters = [
   { value: "RUNNING", label: "处理中" },
   { value: "WAITING_UPLOAD", label: "投稿队列中" },
   { value: "UPLOADING", label: "投稿中" },
+  { value: "SCHEDULED", label: "定时发布中" },
   { value: "COMPLETED", label: "已完成" },
   { value: "FAILED", label: "失败" },
   { value: "CANCELLED", label: "已取消" },
 ];
 
+const publishModeOptions = [
+  { value: "LOCAL", label: "本地定时（到点后投稿）" },
+  { value: "REMOTE", label: "B站定时（2小时至15天内）" },
+];
+
+const toDateTimeLocalValue = (timestamp) => {
+  if (!timestamp) {
+    return "";
+  }
+  const date = new Date(Number(timestamp) * 1000);
+  if (Number.isNaN(date.getTime())) {
+    return "";
+  }
+  const pad = (value) => String(value).padStart(2, "0");
+  return `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}T${pad(
+    date.getHours(),
+  )}:${pad(date.getMinutes())}`;
+};
+
+const fromDateTimeLocalValue = (value) => {
+  if (!value) {
+    return null;
+  }
+  const time = new Date(value).getTime();
+  return Number.isNaN(time) ? null : Math.floor(time / 1000);
+};
+
 const emptySource = (index) => ({
   sourceFilePath: "",
   sortOrder: index + 1,
@@ -37,7 +72,9 @@ const defaultWorkflowConfig = {
   segmentationConfig: {
     segmentDurationSeconds: 133,
     preserveOriginal: true,
+    ...defaultSegmentModeConfig,
   },
+  burnDanmaku: false,
 };
 
 export default function SubmissionSection() {
@@ -55,6 +92,9 @@ export default function SubmissionSection() {
     baiduSyncEnabled: false,
     baiduSyncPath: "",
     baiduSyncFilename: "",
+    accountUserId: null,
+    scheduledPublishTime: "",
+    scheduledPublishMode: "LOCAL",
   });
   const [tagInput, setTagInput] = useState("");
   const [tags, setTags] = useState([]);
@@ -105,6 +145,7 @@ export default function SubmissionSection() {
   const [resegmentSubmitting, setResegmentSubmitting] = useState(false);
   const [resegmentVideoSeconds, setResegmentVideoSeconds] = useState(0);
   const [resegmentMode, setResegmentMode] = useState("SPECIFIED");
+  const [resegmentSegmentConfig, setResegmentSegmentConfig] = useState(defaultSegmentModeConfig);
   const [resegmentMergedVideos, setResegmentMergedVideos] = useState([]);
   const [resegmentMergedId, setResegmentMergedId] = useState("");
   const [resegmentIntegrateCurrent, setResegmentIntegrateCurrent] = useState(false);
@@ -134,6 +175,8 @@ export default function SubmissionSection() {
     path: "",
     filename: "",
   });
+  const [updateAccountUserId, setUpdateAccountUserId] = useState(null);
+  const [updateSchedule, setUpdateSchedule] = useState({ time: "", mode: "LOCAL" });
   const [syncPickerOpen, setSyncPickerOpen] = useState(false);
   const [syncTarget, setSyncTarget] = useState("");
   const [updateSubmitting, setUpdateSubmitting] = useState(false);
@@ -264,6 +307,9 @@ export default function SubmissionSection() {
       baiduSyncEnabled: false,
       baiduSyncPath: "",
       baiduSyncFilename: "",
+      accountUserId: null,
+      scheduledPublishTime: "",
+      scheduledPublishMode: "LOCAL",
     });
     setTagInput("");
     setTags([]);
@@ -279,6 +325,8 @@ export default function SubmissionSection() {
     setUpdateWorkflowConfig(defaultWorkflowConfig);
     setUpdateSegmentPrefix("");
     setUpdateBaiduSync({ enabled: false, path: "", filename: "" });
+    setUpdateAccountUserId(null);
+    setUpdateSchedule({ time: "", mode: "LOCAL" });
     setUpdateSubmitting(false);
   };
 
@@ -290,8 +338,8 @@ export default function SubmissionSection() {
     setActivityOptions([]);
     setActivityMessage("");
     resetFormState();
-    await loadPartitions();
-    await loadCollections();
+    await loadPartitions(null);
+    await loadCollections(null);
     await loadBaiduSyncSettings();
   };
 
@@ -305,6 +353,11 @@ export default function SubmissionSection() {
       path: task?.baiduSyncPath || "",
       filename: task?.baiduSyncFilename || "",
     });
+    setUpdateAccountUserId(task?.accountUserId || null);
+    setUpdateSchedule({
+      time: toDateTimeLocalValue(task?.scheduledPublishTime),
+      mode: task?.scheduledPublishMode || "LOCAL",
+    });
     setUpdateSourceVideos([emptySource(0)]);
     setUpdateSegmentationEnabled(true);
     setUpdateWorkflowConfig(defaultWorkflowConfig);
@@ -348,6 +401,7 @@ export default function SubmissionSection() {
     setResegmentSubmitting(false);
     setResegmentVideoSeconds(0);
     setResegmentMode("SPECIFIED");
+    setResegmentSegmentConfig(defaultSegmentModeConfig);
     setResegmentMergedVideos([]);
     setResegmentMergedId("");
     setResegmentIntegrateCurrent(false);
@@ -363,6 +417,9 @@ export default function SubmissionSection() {
       const resolvedSeconds = Number.isFinite(seconds) ? seconds : 0;
       setResegmentDefaultSeconds(resolvedSeconds);
       setResegmentSeconds(resolvedSeconds ? String(resolvedSeconds) : "");
+      setResegmentSegmentConfig(
+        readSegmentModeConfig(detail?.workflowConfig?.segmentationConfig),
+      );
       const mergedVideos = Array.isArray(detail?.mergedVideos)
         ? detail.mergedVideos
         : [];
@@ -385,6 +442,7 @@ export default function SubmissionSection() {
     setResegmentSubmitting(false);
     setResegmentVideoSeconds(0);
     setResegmentMode("SPECIFIED");
+    setResegmentSegmentConfig(defaultSegmentModeConfig);
     setResegmentMergedVideos([]);
     setResegmentMergedId("");
     setResegmentIntegrateCurrent(false);
@@ -471,9 +529,9 @@ export default function SubmissionSection() {
     setQuickFillOpen(false);
   };
 
-  const loadPartitions = async () => {
+  const loadPartitions = async (accountUserId = taskForm.accountUserId) => {
     try {
-      const data = await invokeCommand("bilibili_partitions");
+      const data = await invokeCommand("bilibili_partitions", { accountUserId });
       setPartitions(data || []);
       if ((data || []).length) {
         setTaskForm((prev) => {
@@ -488,7 +546,7 @@ export default function SubmissionSection() {
     }
   };
 
-  const loadCollections = async () => {
+  const loadCollections = async (accountUserId = taskForm.accountUserId) => {
     try {
       await invokeCommand("auth_client_log", {
         message: "collections_load_start",
@@ -504,11 +562,14 @@ export default function SubmissionSection() {
       const userInfo = auth?.userInfo || {};
       const level1 = userInfo?.data || userInfo;
       const level2 = level1?.data || level1;
-      const mid = level2?.mid || level1?.mid || userInfo?.mid || 0;
+      const mid = accountUserId || level2?.mid || level1?.mid || userInfo?.mid || 0;
       await invokeCommand("auth_client_log", {
         message: `collections_mid=${mid || 0}`,
       });
-      const data = await invokeCommand("bilibili_collections", { mid: mid || 0 });
+      const data = await invokeCommand("bilibili_collections", {
+        mid: mid || 0,
+        accountUserId,
+      });
       const mapped = (data || []).map((item) => ({
         ...item,
         seasonId: item.season_id ?? item.seasonId,
@@ -585,6 +646,7 @@ export default function SubmissionSection() {
     try {
       const data = await invokeCommand("bilibili_topics", {
         partitionId: partitionId ? Number(partitionId) : null,
+        accountUserId: taskForm.accountUserId,
       });
       const mapped = normalizeActivityOptions(data);
       setActivityOptions(mapped);
@@ -964,7 +1026,9 @@ export default function SubmissionSection() {
         enabled: segmentationEnabled,
         segmentDurationSeconds: workflowConfig.segmentationConfig.segmentDurationSeconds,
         preserveOriginal: workflowConfig.segmentationConfig.preserveOriginal,
+        ...buildSegmentModeConfig(workflowConfig.segmentationConfig),
       },
+      burnDanmaku: Boolean(workflowConfig.burnDanmaku),
     };
   };
 
@@ -976,8 +1040,10 @@ export default function SubmissionSection() {
         enabled: updateSegmentationEnabled,
         segmentDurationSeconds: updateWorkflowConfig.segmentationConfig.segmentDurationSeconds,
         preserveOriginal: updateWorkflowConfig.segmentationConfig.preserveOriginal,
+        ...buildSegmentModeConfig(updateWorkflowConfig.segmentationConfig),
       },
       segmentPrefix: prefix ? prefix : null,
+      burnDanmaku: Boolean(updateWorkflowConfig.burnDanmaku),
     };
   };
 
@@ -1104,6 +1170,11 @@ export default function SubmissionSection() {
             baiduSyncEnabled: Boolean(taskForm.baiduSyncEnabled),
             baiduSyncPath: taskForm.baiduSyncPath || null,
             baiduSyncFilename: taskForm.baiduSyncFilename || null,
+            accountUserId: taskForm.accountUserId,
+            scheduledPublishTime: fromDateTimeLocalValue(taskForm.scheduledPublishTime),
+            scheduledPublishMode: taskForm.scheduledPublishTime
+              ? taskForm.scheduledPublishMode
+              : null,
           },
           sourceVideos: validSources.map((item, index) => ({
             sourceFilePath: item.sourceFilePath,
@@ -1181,6 +1252,7 @@ export default function SubmissionSection() {
           baiduSyncEnabled: Boolean(updateBaiduSync.enabled),
           baiduSyncPath: updateBaiduSync.path || null,
           baiduSyncFilename: updateBaiduSync.filename || null,
+          accountUserId: updateAccountUserId || 0,
           sourceVideos: validSources.map((item, index) => ({
             sourceFilePath: item.sourceFilePath,
             sortOrder: index + 1,
@@ -1191,6 +1263,11 @@ export default function SubmissionSection() {
         },
       };
       await invokeCommand("submission_update", payload);
+      await invokeCommand("submission_schedule_update", {
+        taskId,
+        scheduledPublishTime: fromDateTimeLocalValue(updateSchedule.time),
+        scheduledPublishMode: updateSchedule.time ? updateSchedule.mode : null,
+      });
       closeUpdateModal();
       await loadTasks(statusFilter, currentPage, pageSize);
     } catch (error) {
@@ -1330,6 +1407,7 @@ export default function SubmissionSection() {
       baiduSyncEnabled: Boolean(task.baiduSyncEnabled),
       baiduSyncPath: task.baiduSyncPath || "",
       baiduSyncFilename: task.baiduSyncFilename || "",
+      accountUserId: task.accountUserId || null,
     }));
     setTags(tagList);
     setTagInput("");
@@ -1375,6 +1453,9 @@ export default function SubmissionSection() {
       baiduSyncEnabled: Boolean(task.baiduSyncEnabled),
       baiduSyncPath: task.baiduSyncPath || "",
       baiduSyncFilename: task.baiduSyncFilename || "",
+      accountUserId: task.accountUserId || null,
+      scheduledPublishTime: toDateTimeLocalValue(task.scheduledPublishTime),
+      scheduledPublishMode: task.scheduledPublishMode || "LOCAL",
     });
     setTags(tagList);
     setTagInput("");
@@ -1392,7 +1473,9 @@ export default function SubmissionSection() {
           typeof segmentation.preserveOriginal === "boolean"
             ? segmentation.preserveOriginal
             : true,
+        ...readSegmentModeConfig(segmentation),
       },
+      burnDanmaku: Boolean(config?.burnDanmaku),
     });
     const sources = (detail?.sourceVideos || []).map((item, index) => ({
       sourceFilePath: item.sourceFilePath || "",
@@ -1494,8 +1577,12 @@ export default function SubmissionSection() {
     try {
       setSubmissionView("detail");
       setSelectedTask(null);
-      const loadPromises = Promise.all([loadPartitions(), loadCollections()]);
       const detail = await fetchTaskDetail(taskId, { log: true });
+      const accountUserId = detail?.task?.accountUserId || null;
+      const loadPromises = Promise.all([
+        loadPartitions(accountUserId),
+        loadCollections(accountUserId),
+      ]);
       setSelectedTask(detail);
       setDetailTab("basic");
       try {
@@ -1555,11 +1642,15 @@ export default function SubmissionSection() {
       setSubmissionView("edit");
       setSelectedTask(null);
       setEditSegments([]);
-      const loadPromises = Promise.all([loadPartitions(), loadCollections()]);
       const detail = await invokeCommand("submission_edit_prepare", { taskId });
       if (!detail?.task) {
         throw new Error("未读取到投稿任务详情");
       }
+      const accountUserId = detail.task.accountUserId || null;
+      const loadPromises = Promise.all([
+        loadPartitions(accountUserId),
+        loadCollections(accountUserId),
+      ]);
       setSelectedTask(detail);
       setDetailTab("basic");
       applyDetailToForm(detail);
@@ -1609,11 +1700,15 @@ export default function SubmissionSection() {
     }
     setMessage("");
     setResegmentSubmitting(true);
+    const { mode: segmentMode, ...segmentTuning } =
+      buildSegmentModeConfig(resegmentSegmentConfig);
     try {
       await invokeCommand("submission_resegment", {
         request: {
           taskId: resegmentTaskId,
           segmentDurationSeconds: Math.floor(nextSeconds),
+          segmentMode,
+          ...segmentTuning,
           mode: resegmentMode,
           mergedVideoId:
             resegmentMode === "SPECIFIED" && resegmentMergedId
@@ -2368,6 +2463,8 @@ export default function SubmissionSection() {
         return "投稿队列中";
       case "UPLOADING":
         return "投稿中";
+      case "SCHEDULED":
+        return "定时发布中";
       case "FAILED":
         return "失败";
       case "CANCELLED":
@@ -2405,6 +2502,7 @@ export default function SubmissionSection() {
     if (["UPLOADING", "WAITING_UPLOAD", "RUNNING"].includes(status)) {
       return "bg-amber-500/10 text-amber-600";
     }
+    if (status === "SCHEDULED") return "bg-sky-500/10 text-sky-600";
     if (["CLIPPING", "MERGING", "SEGMENTING", "PENDING"].includes(status)) {
       return "bg-amber-500/10 text-amber-600";
     }
@@ -2815,6 +2913,50 @@ export default function SubmissionSection() {
             />
             优先投稿（进入投稿队列时置顶）
           </label>
+          <div className="space-y-1">
+            <div className="text-xs text-[var(--muted)]">投稿账号</div>
+            <BiliAccountSelect
+              value={taskForm.accountUserId}
+              disabled={isReadOnly}
+              onChange={(accountUserId) => {
+                setTaskForm((prev) => ({ ...prev, accountUserId, collectionId: "" }));
+                loadCollections(accountUserId);
+              }}
+              className="w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+            />
+          </div>
+          {submissionView === "create" ? (
+            <div className="grid gap-2 lg:grid-cols-2">
+              <div className="space-y-1">
+                <div className="text-xs text-[var(--muted)]">定时发布（可选）</div>
+                <input
+                  type="datetime-local"
+                  value={taskForm.scheduledPublishTime}
+                  onChange={(event) =>
+                    setTaskForm((prev) => ({ ...prev, scheduledPublishTime: event.target.value }))
+                  }
+                  className="w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+                />
+              </div>
+              <div className="space-y-1">
+                <div className="text-xs text-[var(--muted)]">定时方式</div>
+                <select
+                  value={taskForm.scheduledPublishMode}
+                  disabled={!taskForm.scheduledPublishTime}
+                  onChange={(event) =>
+                    setTaskForm((prev) => ({ ...prev, scheduledPublishMode: event.target.value }))
+                  }
+                  className="w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+                >
+                  {publishModeOptions.map((option) => (
+                    <option key={option.value} value={option.value}>
+                      {option.label}
+                    </option>
+                  ))}
+                </select>
+              </div>
+            </div>
+          ) : null}
           <div className="text-xs text-[var(--muted)]">
             分段前缀会作为分段文件名的前缀（可选）
           </div>
@@ -2937,9 +3079,32 @@ export default function SubmissionSection() {
                   </label>
                 </div>
               ) : null}
+              {segmentationEnabled ? (
+                <SegmentModeFields
+                  value={workflowConfig.segmentationConfig}
+                  disabled={isReadOnly}
+                  onChange={(patch) =>
+                    setWorkflowConfig((prev) => ({
+                      ...prev,
+                      segmentationConfig: { ...prev.segmentationConfig, ...patch },
+                    }))
+                  }
+                />
+              ) : null}
               <div className="text-xs text-[var(--muted)]">
                 预计分段数：{segmentationEnabled ? estimatedSegments : "不分段"}
               </div>
+              <label className="flex items-center gap-2 text-xs text-[var(--muted)]">
+                <input
+                  type="checkbox"
+                  checked={Boolean(workflowConfig.burnDanmaku)}
+                  onChange={(event) =>
+                    setWorkflowConfig((prev) => ({ ...prev, burnDanmaku: event.target.checked }))
+                  }
+                  disabled={isReadOnly}
+                />
+                剪辑时压制弹幕（需源视频旁有弹幕文件，无法使用流复制）
+              </label>
             </div>
           </div>
         </div>
@@ -3155,6 +3320,42 @@ export default function SubmissionSection() {
                         className="w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
                       />
                     </div>
+                    <div className="space-y-1">
+                      <div className="text-xs text-[var(--muted)]">投稿账号</div>
+                      <BiliAccountSelect
+                        value={updateAccountUserId}
+                        onChange={setUpdateAccountUserId}
+                        className="w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+                      />
+                    </div>
+                    <div className="space-y-1">
+                      <div className="text-xs text-[var(--muted)]">定时发布（可选）</div>
+                      <input
+                        type="datetime-local"
+                        value={updateSchedule.time}
+                        onChange={(event) =>
+                          setUpdateSchedule((prev) => ({ ...prev, time: event.target.value }))
+                        }
+                        className="w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+                      />
+                    </div>
+                    <div className="space-y-1">
+                      <div className="text-xs text-[var(--muted)]">定时方式</div>
+                      <select
+                        value={updateSchedule.mode}
+                        disabled={!updateSchedule.time}
+                        onChange={(event) =>
+                          setUpdateSchedule((prev) => ({ ...prev, mode: event.target.value }))
+                        }
+                        className="w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
+                      >
+                        {publishModeOptions.map((option) => (
+                          <option key={option.value} value={option.value}>
+                            {option.label}
+                          </option>
+                        ))}
+                      </select>
+                    </div>
                   </div>
                   <div className="rounded-lg border border-black/5 bg-white/80 p-3">
                     <div className="text-xs uppercase tracking-[0.2em] text-[var(--muted)]">
@@ -3262,10 +3463,34 @@ export default function SubmissionSection() {
                       </label>
                     </div>
                   ) : null}
+                  {updateSegmentationEnabled ? (
+                    <SegmentModeFields
+                      value={updateWorkflowConfig.segmentationConfig}
+                      onChange={(patch) =>
+                        setUpdateWorkflowConfig((prev) => ({
+                          ...prev,
+                          segmentationConfig: { ...prev.segmentationConfig, ...patch },
+                        }))
+                      }
+                    />
+                  ) : null}
                   <div className="text-xs text-[var(--muted)]">
                     预计分段数：
                     {updateSegmentationEnabled ? updateEstimatedSegments : "不分段"}
                   </div>
+                  <label className="flex items-center gap-2 text-xs text-[var(--muted)]">
+                    <input
+                      type="checkbox"
+                      checked={Boolean(updateWorkflowConfig.burnDanmaku)}
+                      onChange={(event) =>
+                        setUpdateWorkflowConfig((prev) => ({
+                          ...prev,
+                          burnDanmaku: event.target.checked,
+                        }))
+                      }
+                    />
+                    剪辑时压制弹幕（需源视频旁有弹幕文件，无法使用流复制）
+                  </label>
                 </div>
               </div>
               <div className="rounded-xl border border-black/5 bg-white/80 p-3">
@@ -3767,6 +3992,12 @@ export default function SubmissionSection() {
                       className="w-full rounded-lg border border-black/10 bg-white/80 px-3 py-2 text-sm focus:border-[var(--accent)] focus:outline-none"
                     />
                   </div>
+                  <SegmentModeFields
+                    value={resegmentSegmentConfig}
+                    onChange={(patch) =>
+                      setResegmentSegmentConfig((prev) => ({ ...prev, ...patch }))
+                    }
+                  />
                   <div className="space-y-1">
                     <div className="text-xs text-[var(--muted)]">预计分段数</div>
                     <div className="rounded-lg border border-black/10 bg-black/5 px-3 py-2">
@@ -4426,6 +4657,9 @@ export default function SubmissionSection() {
                   <div>是否分段：{segmentationEnabled ? "需要分段" : "不需要分段"}</div>
                   {segmentationEnabled ? (
                     <>
+                      <div>
+                        分段方式：{segmentModeLabel(workflowConfig.segmentationConfig.mode)}
+                      </div>
                       <div>
                         分段时长：
                         {workflowConfig.segmentationConfig.segmentDurationSeconds || "-"} 秒
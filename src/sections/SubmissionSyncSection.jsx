@@ -157,11 +157,21 @@ export default function SubmissionSyncSection() {
                         <span className="rounded-full bg-black/5 px-2 py-0.5 text-xs">
                           {statusLabel}
                         </span>
+                        {task.verifyStatus ? (
+                          <span className="rounded-full bg-black/5 px-2 py-0.5 text-xs">
+                            {task.verifyStatus === "VERIFIED"
+                              ? "已校验"
+                              : task.verifyStatus === "SIZE_ONLY"
+                                ? "大小一致，MD5未通过"
+                                : "校验不一致"}
+                          </span>
+                        ) : null}
                       </div>
                       <div className="flex flex-wrap gap-3 text-xs text-[var(--desc-color)]">
                         <span className="truncate">本地路径：{task.localPath || "-"}</span>
                         <span className="truncate">远程路径：{remotePath}</span>
                         <span>发起时间：{formatDateTime(task.createdAt)}</span>
+                        {task.error ? <span className="truncate">错误：{task.error}</span> : null}
                       </div>
                       <div className="flex items-center gap-3">
                         <div className="flex-1">
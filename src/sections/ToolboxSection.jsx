@@ -2,7 +2,10 @@ import { useMemo, useState } from "react";
 import { open, save } from "@tauri-apps/plugin-dialog";
 import { invokeCommand } from "../lib/tauri";
 
-const toolboxTabs = [{ key: "remux", label: "格式转码" }];
+const toolboxTabs = [
+  { key: "remux", label: "格式转码" },
+  { key: "flvFix", label: "FLV 修复" },
+];
 
 const normalizePath = (path) => String(path || "").replace(/\\/g, "/");
 
@@ -18,6 +21,14 @@ const buildDefaultTarget = (sourcePath) => {
   return `${dir}${baseName}.mp4`;
 };
 
+const buildFixedTarget = (sourcePath) => {
+  const normalized = normalizePath(sourcePath);
+  if (!normalized) {
+    return "";
+  }
+  return normalized.replace(/(\.[^./]+)?$/, "_fixed.flv");
+};
+
 const ensureMp4Extension = (path) => {
   if (!path) {
     return "";
@@ -31,6 +42,13 @@ export default function ToolboxSection() {
   const [targetPath, setTargetPath] = useState("");
   const [message, setMessage] = useState("");
   const [running, setRunning] = useState(false);
+  const [fixSourcePath, setFixSourcePath] = useState("");
+  const [fixOptions, setFixOptions] = useState({
+    fixTimestamp: true,
+    splitOnTimestampJump: false,
+    splitOnHeaderChange: false,
+  });
+  const [fixReport, setFixReport] = useState(null);
 
   const defaultTarget = useMemo(() => buildDefaultTarget(sourcePath), [sourcePath]);
 
@@ -92,60 +110,170 @@ export default function ToolboxSection() {
     }
   };
 
+  const handlePickFixSource = async () => {
+    setMessage("");
+    const selected = await open({
+      multiple: false,
+      directory: false,
+      title: "选择 FLV 文件",
+      filters: [{ name: "FLV", extensions: ["flv"] }],
+    });
+    if (typeof selected === "string") {
+      setFixSourcePath(selected);
+      setFixReport(null);
+    }
+  };
+
+  const handleFlvFix = async () => {
+    setMessage("");
+    if (!fixSourcePath.trim()) {
+      setMessage("请选择 FLV 文件");
+      return;
+    }
+    setRunning(true);
+    setFixReport(null);
+    try {
+      const report = await invokeCommand("toolbox_flv_fix", {
+        payload: {
+          sourcePath: fixSourcePath,
+          targetPath: buildFixedTarget(fixSourcePath),
+          options: fixOptions,
+        },
+      });
+      setFixReport(report);
+      setMessage("修复完成");
+    } catch (error) {
+      setMessage(error?.message || "修复失败");
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  const fixOptionItems = [
+    { key: "fixTimestamp", label: "修复时间戳跳变" },
+    { key: "splitOnTimestampJump", label: "时间戳跳变时分段" },
+    { key: "splitOnHeaderChange", label: "视频参数变化时分段" },
+  ];
+
   return (
     <div className="flex gap-4">
-      <div className="flex-1 min-w-0 space-y-4">
-        <div className="panel p-4 space-y-3">
-          <div className="space-y-1">
-            <div className="text-lg font-semibold">格式转码</div>
-            <div className="desc">
-              基于 FFmpeg 转封装，仅支持 FLV 转 MP4，不进行重新编码。
+      {activeTab === "flvFix" ? (
+        <div className="flex-1 min-w-0 space-y-4">
+          <div className="panel p-4 space-y-3">
+            <div className="space-y-1">
+              <div className="text-lg font-semibold">FLV 修复</div>
+              <div className="desc">
+                修复时间戳跳变、补全缺失的 AVC/AAC 序列头，可按断点拆分，输出文件旁会生成修复报告。
+              </div>
+            </div>
+            <div className="space-y-2">
+              <div className="flex items-center gap-2">
+                <input
+                  className="flex-1 min-w-0"
+                  value={fixSourcePath}
+                  readOnly
+                  placeholder="请选择 FLV 文件"
+                />
+                <button className="h-8 px-3 rounded-lg" onClick={handlePickFixSource}>
+                  选择文件
+                </button>
+              </div>
+              <div className="flex flex-wrap items-center gap-4">
+                {fixOptionItems.map((item) => (
+                  <label key={item.key} className="flex items-center gap-2 text-sm">
+                    <input
+                      type="checkbox"
+                      checked={fixOptions[item.key]}
+                      onChange={(event) =>
+                        setFixOptions((prev) => ({ ...prev, [item.key]: event.target.checked }))
+                      }
+                    />
+                    {item.label}
+                  </label>
+                ))}
+              </div>
+              <div className="flex flex-wrap items-center gap-3">
+                <button
+                  className="h-8 px-3 rounded-lg"
+                  onClick={handleFlvFix}
+                  disabled={running}
+                >
+                  {running ? "修复中..." : "开始修复"}
+                </button>
+                {message ? <span className="text-xs text-[var(--desc-color)]">{message}</span> : null}
+              </div>
             </div>
           </div>
-          <div className="space-y-2">
-            <div className="flex items-center gap-2">
-              <input
-                className="flex-1 min-w-0"
-                value={sourcePath}
-                readOnly
-                placeholder="请选择 FLV 文件"
-              />
-              <button className="h-8 px-3 rounded-lg" onClick={handlePickSource}>
-                选择文件
-              </button>
+
+          {fixReport ? (
+            <div className="panel p-4 space-y-1 text-xs text-[var(--desc-color)]">
+              <div>输出文件：{fixReport.outputPaths.join("，")}</div>
+              <div>修复报告：{fixReport.reportPath}</div>
+              <div>
+                标签总数 {fixReport.totalTags}，时间戳跳变 {fixReport.timestampJumpCount}，
+                补全序列头 {fixReport.injectedHeaders}，参数变化 {fixReport.headerChanges}
+              </div>
+              <div>
+                丢弃标签 {fixReport.droppedTags}，修正标签长度 {fixReport.fixedTagSizes}，
+                截断字节 {fixReport.truncatedBytes}，拆分次数 {fixReport.splitCount}
+              </div>
             </div>
-            <div className="flex items-center gap-2">
-              <input
-                className="flex-1 min-w-0"
-                value={targetPath}
-                readOnly
-                placeholder="请选择输出 MP4 路径"
-              />
-              <button className="h-8 px-3 rounded-lg" onClick={handlePickTarget}>
-                保存到
-              </button>
+          ) : null}
+        </div>
+      ) : (
+        <div className="flex-1 min-w-0 space-y-4">
+          <div className="panel p-4 space-y-3">
+            <div className="space-y-1">
+              <div className="text-lg font-semibold">格式转码</div>
+              <div className="desc">
+                基于 FFmpeg 转封装，仅支持 FLV 转 MP4，不进行重新编码。
+              </div>
             </div>
-            <div className="flex flex-wrap items-center gap-3">
-              <button
-                className="h-8 px-3 rounded-lg"
-                onClick={handleRemux}
-                disabled={running}
-              >
-                {running ? "转封装中..." : "开始转封装"}
-              </button>
-              {message ? <span className="text-xs text-[var(--desc-color)]">{message}</span> : null}
+            <div className="space-y-2">
+              <div className="flex items-center gap-2">
+                <input
+                  className="flex-1 min-w-0"
+                  value={sourcePath}
+                  readOnly
+                  placeholder="请选择 FLV 文件"
+                />
+                <button className="h-8 px-3 rounded-lg" onClick={handlePickSource}>
+                  选择文件
+                </button>
+              </div>
+              <div className="flex items-center gap-2">
+                <input
+                  className="flex-1 min-w-0"
+                  value={targetPath}
+                  readOnly
+                  placeholder="请选择输出 MP4 路径"
+                />
+                <button className="h-8 px-3 rounded-lg" onClick={handlePickTarget}>
+                  保存到
+                </button>
+              </div>
+              <div className="flex flex-wrap items-center gap-3">
+                <button
+                  className="h-8 px-3 rounded-lg"
+                  onClick={handleRemux}
+                  disabled={running}
+                >
+                  {running ? "转封装中..." : "开始转封装"}
+                </button>
+                {message ? <span className="text-xs text-[var(--desc-color)]">{message}</span> : null}
+              </div>
             </div>
           </div>
-        </div>
 
-        <div className="panel p-4 space-y-1 text-xs text-[var(--desc-color)]">
-          <div>1. 选择需要转封装的 FLV 文件。</div>
-          <div>2. 选择 MP4 保存位置。</div>
-          <div>3. 转封装会占用磁盘 IO，可能影响正在进行的录制。</div>
-          <div>4. 如果录制文件存在问题，请先修复后再转封装。</div>
-          <div>5. 转封装后无法再进行修复，请确认文件正常。</div>
+          <div className="panel p-4 space-y-1 text-xs text-[var(--desc-color)]">
+            <div>1. 选择需要转封装的 FLV 文件。</div>
+            <div>2. 选择 MP4 保存位置。</div>
+            <div>3. 转封装会占用磁盘 IO，可能影响正在进行的录制。</div>
+            <div>4. 如果录制文件存在问题，请先修复后再转封装。</div>
+            <div>5. 转封装后无法再进行修复，请确认文件正常。</div>
+          </div>
         </div>
-      </div>
+      )}
 
       <div className="tab">
         {toolboxTabs.map((tab) => (